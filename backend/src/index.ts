@@ -34,6 +34,12 @@ import { setupRouter } from './routes/setup.js';
 import { PhoenixdService } from './services/phoenixd.js';
 import { startRecurringPaymentScheduler } from './services/recurring-scheduler.js';
 import { cleanupExpiredSessions, validateSessionFromCookie } from './middleware/auth.js';
+import {
+  desktopRouter,
+  hasDesktopAccess,
+  requireDesktopAccess,
+  requireWritable,
+} from './middleware/desktop.js';
 import { dispatchPaymentReceived, cleanupOldWebhookLogs } from './services/app-webhooks.js';
 import { AppDockerService } from './services/app-docker.js';
 import crypto from 'crypto';
@@ -58,6 +64,11 @@ const allowedOrigins = [
   process.env.FRONTEND_URL || 'http://localhost:3000',
   'http://localhost:3000',
   'http://localhost:4001',
+  // Set by the desktop app from the URLs it exposes the dashboard under
+  ...(process.env.ALLOWED_ORIGINS || '')
+    .split(',')
+    .map((origin) => origin.trim())
+    .filter(Boolean),
 ];
 
 // Extract root domain from a hostname (e.g., "api.example.com" -> "example.com")
@@ -166,9 +177,17 @@ app.get('/health', (_, res) => {
     status: 'ok',
     timestamp: Date.now(),
     desktopMode: process.env.DESKTOP_MODE === 'true',
+    nodePaused: process.env.NODE_PAUSED === 'true',
   });
 });
 
+// Pairing, watch-only mode, then the desktop app's access check for
+// everything below. Apps authenticate with their own API keys.
+app.use('/api/desktop', desktopRouter);
+app.use(requireWritable);
+app.use('/api/apps-gateway', appsApiRouter); // API gateway for apps to call backend
+app.use(requireDesktopAccess);
+
 // Routes
 app.use('/api/auth', authRouter);
 app.use('/api/phoenixd', phoenixdRouter);
@@ -187,7 +206,6 @@ app.use('/api/recurring-payments', recurringPaymentsRouter);
 app.use('/api/phoenixd', phoenixdConfigRouter); // Mount phoenixd config routes (they have their own paths like /config)
 app.use('/api/phoenixd-connections', phoenixdConnectionsRouter);
 app.use('/api/apps', appsRouter); // Apps management
-app.use('/api/apps-gateway', appsApiRouter); // API gateway for apps to call backend
 app.use('/api/setup', setupRouter); // Setup wizard
 
 // WebSocket clients
@@ -257,6 +275,12 @@ server.on('upgrade', async (request: IncomingMessage, socket, head) => {
   const url = new URL(request.url || '', `http://${request.headers.host}`);
   const pathname = url.pathname;
 
+  if (!hasDesktopAccess(request.headers, request.socket.remoteAddress)) {
+    socket.write('HTTP/1.1 401 Unauthorized\r\n\r\n');
+    socket.destroy();
+    return;
+  }
+
   // Main WebSocket for payment notifications (no auth required for now)
   if (pathname === '/ws') {
     wss.handleUpgrade(request, socket, head, (ws) => {
@@ -677,10 +701,12 @@ async function installDefaultApps() {
 }
 
 // Start server
-const PORT = process.env.PORT || 4000;
+const PORT = Number(process.env.PORT) || 4000;
+// Listen address, e.g. 127.0.0.1 or ::1; unset listens on every interface
+const HOST = process.env.HOST || undefined;
 
-server.listen(PORT, async () => {
-  console.log(`Backend server running on port ${PORT}`);
+server.listen({ port: PORT, host: HOST }, async () => {
+  console.log(`Backend server running on ${HOST ?? '*'}:${PORT}`);
 
   // Initialize phoenixd connections and connect to WebSocket
   await initializeDockerConnection();
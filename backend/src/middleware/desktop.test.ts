@@ -0,0 +1,241 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import express from 'express';
+import request from 'supertest';
+import {
+  desktopRouter,
+  hasDesktopAccess,
+  requireDesktopAccess,
+  requireWritable,
+} from './desktop';
+
+const PAIRING = 'a'.repeat(32);
+const API = 'b'.repeat(32);
+const LAN_CLIENT = '192.168.1.23';
+
+describe('Desktop access', () => {
+  let app: express.Express;
+  // Address requests appear to come from; supertest always connects locally
+  let remoteAddress: string | undefined;
+
+  beforeEach(() => {
+    remoteAddress = undefined;
+    app = express();
+    app.use(express.json());
+    app.use((req, _res, next) => {
+      if (remoteAddress) {
+        Object.defineProperty(req.socket, 'remoteAddress', {
+          value: remoteAddress,
+          configurable: true,
+        });
+      }
+      next();
+    });
+    app.use('/api/desktop', desktopRouter);
+    app.use(requireDesktopAccess);
+    app.get('/api/node/info', (_req, res) => res.json({ ok: true }));
+  });
+
+  afterEach(() => {
+    delete process.env.API_TOKEN;
+    delete process.env.PAIRING_TOKEN;
+    delete process.env.FRONTEND_URL;
+    delete process.env.ALLOWED_ORIGINS;
+    delete process.env.READ_ONLY;
+  });
+
+  describe('without a pairing token', () => {
+    it('should let every request through', async () => {
+      expect(hasDesktopAccess({}, LAN_CLIENT)).toBe(true);
+
+      const response = await request(app).get('/api/node/info');
+
+      expect(response.status).toBe(200);
+    });
+  });
+
+  describe('with a pairing token', () => {
+    beforeEach(() => {
+      process.env.PAIRING_TOKEN = PAIRING;
+    });
+
+    it('should let this machine through', async () => {
+      const response = await request(app).get('/api/node/info');
+
+      expect(response.status).toBe(200);
+      expect(hasDesktopAccess({}, '::ffff:127.0.0.1')).toBe(true);
+    });
+
+    it('should reject LAN clients that have not paired', async () => {
+      remoteAddress = LAN_CLIENT;
+
+      const response = await request(app).get('/api/node/info');
+      const wrongCookie = await request(app)
+        .get('/api/node/info')
+        .set('Cookie', 'pd_token=wrong');
+
+      expect(response.status).toBe(401);
+      expect(wrongCookie.status).toBe(401);
+    });
+
+    it('should accept LAN clients with the pairing cookie', async () => {
+      remoteAddress = LAN_CLIENT;
+
+      const response = await request(app)
+        .get('/api/node/info')
+        .set('Cookie', `session=x; pd_token=${PAIRING}`);
+
+      expect(response.status).toBe(200);
+    });
+
+    it('should trade the token for a cookie and redirect to the dashboard', async () => {
+      process.env.FRONTEND_URL = 'http://localhost:3000';
+      process.env.ALLOWED_ORIGINS = 'http://192.168.1.10:3000';
+
+      const response = await request(app).get(
+        `/api/desktop/pair?token=${PAIRING}&next=${encodeURIComponent('http://192.168.1.10:3000/')}`
+      );
+
+      expect(response.status).toBe(302);
+      expect(response.headers.location).toBe('http://192.168.1.10:3000/');
+      expect(response.headers['set-cookie'][0]).toContain(`pd_token=${PAIRING}`);
+      expect(response.headers['set-cookie'][0]).toContain('HttpOnly');
+    });
+
+    it('should not redirect to foreign origins', async () => {
+      process.env.FRONTEND_URL = 'http://localhost:3000';
+
+      const response = await request(app).get(
+        `/api/desktop/pair?token=${PAIRING}&next=${encodeURIComponent('https://evil.example/')}`
+      );
+
+      expect(response.status).toBe(302);
+      expect(response.headers.location).toBe('http://localhost:3000');
+    });
+
+    it('should refuse a wrong pairing token', async () => {
+      const response = await request(app).get('/api/desktop/pair?token=nope');
+
+      expect(response.status).toBe(401);
+      expect(response.headers['set-cookie']).toBeUndefined();
+    });
+  });
+
+  describe('with an API token', () => {
+    beforeEach(() => {
+      process.env.API_TOKEN = API;
+    });
+
+    it('should reject requests without it, even from this machine', async () => {
+      const response = await request(app).get('/api/node/info');
+
+      expect(response.status).toBe(401);
+    });
+
+    it('should reject a wrong bearer token', async () => {
+      const response = await request(app)
+        .get('/api/node/info')
+        .set('Authorization', `Bearer ${'c'.repeat(32)}`);
+
+      expect(response.status).toBe(401);
+    });
+
+    it('should accept the bearer token', async () => {
+      const response = await request(app)
+        .get('/api/node/info')
+        .set('Authorization', `Bearer ${API}`);
+
+      expect(response.status).toBe(200);
+    });
+
+    it('should accept the cookie', async () => {
+      const response = await request(app).get('/api/node/info').set('Cookie', `pd_token=${API}`);
+
+      expect(response.status).toBe(200);
+    });
+
+    it('should still accept paired LAN devices', async () => {
+      process.env.PAIRING_TOKEN = PAIRING;
+      remoteAddress = LAN_CLIENT;
+
+      const response = await request(app)
+        .get('/api/node/info')
+        .set('Cookie', `pd_token=${PAIRING}`);
+
+      expect(response.status).toBe(200);
+    });
+
+    it('should only issue tickets for the bearer token', async () => {
+      const response = await request(app).post('/api/desktop/tickets');
+
+      expect(response.status).toBe(401);
+    });
+
+    it('should trade a ticket for the cookie once', async () => {
+      const issued = await request(app)
+        .post('/api/desktop/tickets')
+        .set('Authorization', `Bearer ${API}`);
+      const { ticket } = issued.body;
+
+      const first = await request(app).get(`/api/desktop/pair?ticket=${ticket}`);
+      const second = await request(app).get(`/api/desktop/pair?ticket=${ticket}`);
+
+      expect(issued.status).toBe(200);
+      expect(first.status).toBe(302);
+      expect(first.headers['set-cookie'][0]).toContain(`pd_token=${API}`);
+      expect(second.status).toBe(401);
+    });
+  });
+});
+
+describe('Watch-only mode', () => {
+  let app: express.Express;
+
+  beforeEach(() => {
+    app = express();
+    app.use(express.json());
+    app.use(requireWritable);
+    app.get('/api/node/info', (_req, res) => res.json({ ok: true }));
+    app.post('/api/auth/login', (_req, res) => res.json({ ok: true }));
+    app.post('/api/phoenixd/decodeinvoice', (_req, res) => res.json({ ok: true }));
+    app.post('/api/phoenixd/payinvoice', (_req, res) => res.json({ ok: true }));
+    app.post('/api/lnurl/withdraw', (_req, res) => res.json({ ok: true }));
+    app.put('/api/auth/settings', (_req, res) => res.json({ ok: true }));
+    app.post('/api/apps-gateway/pay/invoice', (_req, res) => res.json({ ok: true }));
+  });
+
+  afterEach(() => {
+    delete process.env.READ_ONLY;
+  });
+
+  it('should allow writes when off', async () => {
+    const response = await request(app).post('/api/phoenixd/payinvoice');
+
+    expect(response.status).toBe(200);
+  });
+
+  it('should reject paying, withdrawing and settings when on', async () => {
+    process.env.READ_ONLY = 'true';
+
+    const pay = await request(app).post('/api/phoenixd/payinvoice');
+    const withdraw = await request(app).post('/api/lnurl/withdraw');
+    const settings = await request(app).put('/api/auth/settings');
+    const appPay = await request(app).post('/api/apps-gateway/pay/invoice');
+
+    expect(pay.status).toBe(403);
+    expect(withdraw.status).toBe(403);
+    expect(settings.status).toBe(403);
+    expect(appPay.status).toBe(403);
+  });
+
+  it('should still allow reading, decoding and logging in when on', async () => {
+    process.env.READ_ONLY = 'true';
+
+    const read = await request(app).get('/api/node/info');
+    const decode = await request(app).post('/api/phoenixd/decodeinvoice');
+    const login = await request(app).post('/api/auth/login/');
+
+    expect(read.status).toBe(200);
+    expect(decode.status).toBe(200);
+    expect(login.status).toBe(200);
+  });
+});
@@ -0,0 +1,193 @@
+import { Request, Response, NextFunction, Router } from 'express';
+import { IncomingHttpHeaders } from 'http';
+import crypto from 'crypto';
+
+/**
+ * Access control for the desktop app. The shell sets these variables; when
+ * they are unset (Docker, development) every request is let through.
+ *
+ * - API_TOKEN: every request needs `Authorization: Bearer <token>` or the
+ *   cookie below. The shell gets browsers that cookie by asking for a
+ *   one-time ticket and opening /api/desktop/pair?ticket=... with it.
+ * - PAIRING_TOKEN: LAN access is on. Devices on the network pair once by
+ *   opening /api/desktop/pair?token=..., which trades the token for an
+ *   HttpOnly cookie. Without API_TOKEN, this machine needs no pairing.
+ * - READ_ONLY: watch-only mode. Nothing may pay, invoice or change settings.
+ */
+
+const TOKEN_COOKIE = 'pd_token';
+const TOKEN_MAX_AGE_MS = 30 * 24 * 60 * 60 * 1000; // 30 days
+const TICKET_TTL_MS = 60 * 1000;
+
+/** What watch-only mode still allows besides GET/HEAD/OPTIONS */
+const READ_ONLY_POSTS = [
+  '/api/auth/login',
+  '/api/auth/logout',
+  '/api/phoenixd/decodeinvoice',
+  '/api/phoenixd/decodeoffer',
+  '/api/apps-gateway/decode/invoice',
+  '/api/apps-gateway/decode/offer',
+];
+
+/** One-time tickets for the pair page and when they expire */
+const tickets = new Map<string, number>();
+
+function apiToken(): string | undefined {
+  return process.env.API_TOKEN || undefined;
+}
+
+function pairingToken(): string | undefined {
+  return process.env.PAIRING_TOKEN || undefined;
+}
+
+function sameToken(given: string | undefined, expected: string | undefined): boolean {
+  if (!given || !expected) return false;
+  const a = Buffer.from(given);
+  const b = Buffer.from(expected);
+  return a.length === b.length && crypto.timingSafeEqual(a, b);
+}
+
+function isLoopback(address: string | undefined): boolean {
+  return address === '127.0.0.1' || address === '::1' || address === '::ffff:127.0.0.1';
+}
+
+function bearerToken(headers: IncomingHttpHeaders): string | undefined {
+  const authorization = headers.authorization || '';
+  return authorization.startsWith('Bearer ') ? authorization.slice(7).trim() : undefined;
+}
+
+function cookieValue(headers: IncomingHttpHeaders, name: string): string | undefined {
+  for (const cookie of (headers.cookie || '').split(';')) {
+    const [key, ...rest] = cookie.trim().split('=');
+    if (key === name) return decodeURIComponent(rest.join('='));
+  }
+  return undefined;
+}
+
+/**
+ * Whether a request may reach the API, from its headers and the address it
+ * came from. Shared by the HTTP middleware and WebSocket upgrades.
+ */
+export function hasDesktopAccess(
+  headers: IncomingHttpHeaders,
+  remoteAddress: string | undefined
+): boolean {
+  const api = apiToken();
+  const pairing = pairingToken();
+  if (sameToken(bearerToken(headers), api)) return true;
+
+  const cookie = cookieValue(headers, TOKEN_COOKIE);
+  if (sameToken(cookie, api) || sameToken(cookie, pairing)) return true;
+  if (api) return false;
+  return !pairing || isLoopback(remoteAddress);
+}
+
+/**
+ * Middleware rejecting requests without desktop access
+ */
+export function requireDesktopAccess(req: Request, res: Response, next: NextFunction) {
+  if (hasDesktopAccess(req.headers, req.socket.remoteAddress)) {
+    return next();
+  }
+  res.status(401).json({
+    error: apiToken()
+      ? 'API token required, open the dashboard from the desktop app'
+      : 'This device is not paired with the dashboard',
+  });
+}
+
+export function isReadOnly(): boolean {
+  return process.env.READ_ONLY === 'true';
+}
+
+/**
+ * Middleware rejecting writes in watch-only mode: reading, decoding and
+ * logging in and out still work; paying, invoicing and settings do not
+ */
+export function requireWritable(req: Request, res: Response, next: NextFunction) {
+  const path = req.path.replace(/\/+$/, '');
+  if (
+    !isReadOnly() ||
+    ['GET', 'HEAD', 'OPTIONS'].includes(req.method) ||
+    (req.method === 'POST' && READ_ONLY_POSTS.includes(path))
+  ) {
+    return next();
+  }
+  res.status(403).json({ error: 'The dashboard is in watch-only mode' });
+}
+
+/**
+ * Where to send the browser once paired: `next` if it is one of the
+ * dashboard's own origins, the frontend otherwise
+ */
+function redirectTarget(next: unknown): string {
+  const frontend = process.env.FRONTEND_URL || 'http://localhost:3000';
+  if (typeof next !== 'string') return frontend;
+  try {
+    const url = new URL(next);
+    const origins = [frontend, ...(process.env.ALLOWED_ORIGINS || '').split(',')]
+      .map((origin) => origin.trim())
+      .filter(Boolean)
+      .map((origin) => new URL(origin).origin);
+    return origins.includes(url.origin) ? url.toString() : frontend;
+  } catch {
+    return frontend;
+  }
+}
+
+export const desktopRouter = Router();
+
+/**
+ * POST /api/desktop/tickets
+ * One-time ticket for the pair page, so the API token itself never ends up
+ * in a browser's address bar or history. Needs the bearer token.
+ */
+desktopRouter.post('/tickets', (req: Request, res: Response) => {
+  if (!sameToken(bearerToken(req.headers), apiToken())) {
+    return res.status(401).json({ error: 'API token required' });
+  }
+
+  const now = Date.now();
+  for (const [ticket, expires] of tickets) {
+    if (expires <= now) tickets.delete(ticket);
+  }
+  const ticket = crypto.randomBytes(24).toString('hex');
+  tickets.set(ticket, now + TICKET_TTL_MS);
+  res.json({ ticket });
+});
+
+/**
+ * Redeem a one-time ticket
+ */
+function takeTicket(ticket: unknown): boolean {
+  if (typeof ticket !== 'string') return false;
+  const expires = tickets.get(ticket);
+  tickets.delete(ticket);
+  return expires !== undefined && expires > Date.now();
+}
+
+/**
+ * GET /api/desktop/pair?token=...&next=... or ?ticket=...&next=...
+ * Trade the pairing token or a ticket for a cookie and continue to the
+ * dashboard
+ */
+desktopRouter.get('/pair', (req: Request, res: Response) => {
+  const pairing = typeof req.query.token === 'string' ? req.query.token : undefined;
+  let token: string | undefined;
+  if (sameToken(pairing, pairingToken())) {
+    token = pairing;
+  } else if (apiToken() && takeTicket(req.query.ticket)) {
+    token = apiToken();
+  } else {
+    return res.status(401).json({ error: 'Invalid pairing token' });
+  }
+
+  res.cookie(TOKEN_COOKIE, token, {
+    httpOnly: true,
+    secure: req.secure,
+    sameSite: 'strict',
+    maxAge: TOKEN_MAX_AGE_MS,
+    path: '/',
+  });
+  res.redirect(redirectTarget(req.query.next));
+});
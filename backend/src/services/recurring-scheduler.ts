@@ -1,5 +1,6 @@
 import { prisma, phoenixd, broadcastPayment } from '../index.js';
 import { calculateNextRunAt } from '../routes/recurring-payments.js';
+import { isReadOnly } from '../middleware/desktop.js';
 
 type RecurringPaymentFrequency =
   | 'every_minute'
@@ -392,6 +393,11 @@ export async function executeRecurringPayment(
  * Only processes payments tied to the currently active connection
  */
 export async function processDuePayments(): Promise<void> {
+  if (isReadOnly()) {
+    console.log('[Recurring] Watch-only mode, skipping payment processing');
+    return;
+  }
+
   const now = new Date();
 
   try {
@@ -0,0 +1,94 @@
+#!/bin/bash
+# Download cloudflared binaries for all platforms
+
+set -e
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+DESKTOP_DIR="$(dirname "$SCRIPT_DIR")"
+BINARIES_DIR="$DESKTOP_DIR/binaries"
+
+# cloudflared release version - update this to get newer versions
+CLOUDFLARED_VERSION="${CLOUDFLARED_VERSION:-2024.12.2}"
+CLOUDFLARED_REPO="cloudflare/cloudflared"
+
+echo "Downloading cloudflared ${CLOUDFLARED_VERSION} binaries..."
+echo "Target directory: $BINARIES_DIR"
+
+mkdir -p "$BINARIES_DIR"
+
+# Function to download (and extract, for macOS archives) cloudflared
+download_cloudflared() {
+    local platform=$1
+    local asset_name=$2
+    local binary_name=$3
+    
+    echo ""
+    echo "Downloading cloudflared for $platform..."
+    
+    local url="https://github.com/${CLOUDFLARED_REPO}/releases/download/${CLOUDFLARED_VERSION}/${asset_name}"
+    local temp_dir=$(mktemp -d)
+    
+    echo "URL: $url"
+    
+    # Download
+    if command -v curl &> /dev/null; then
+        curl -L -o "$temp_dir/$asset_name" "$url" || {
+            echo "Failed to download $platform binary"
+            rm -rf "$temp_dir"
+            return 1
+        }
+    elif command -v wget &> /dev/null; then
+        wget -O "$temp_dir/$asset_name" "$url" || {
+            echo "Failed to download $platform binary"
+            rm -rf "$temp_dir"
+            return 1
+        }
+    else
+        echo "Error: curl or wget is required"
+        rm -rf "$temp_dir"
+        return 1
+    fi
+    
+    # macOS releases are tarballs, the others are plain binaries
+    if [[ "$asset_name" == *.tgz ]]; then
+        tar -xzf "$temp_dir/$asset_name" -C "$temp_dir"
+        cp "$temp_dir/cloudflared" "$BINARIES_DIR/$binary_name"
+    else
+        cp "$temp_dir/$asset_name" "$BINARIES_DIR/$binary_name"
+    fi
+    chmod +x "$BINARIES_DIR/$binary_name"
+    echo "Saved: $BINARIES_DIR/$binary_name"
+    
+    # Cleanup
+    rm -rf "$temp_dir"
+}
+
+# macOS ARM64 (Apple Silicon)
+download_cloudflared \
+    "macOS-arm64" \
+    "cloudflared-darwin-arm64.tgz" \
+    "cloudflared-aarch64-apple-darwin"
+
+# macOS x86_64 (Intel)
+download_cloudflared \
+    "macOS-x64" \
+    "cloudflared-darwin-amd64.tgz" \
+    "cloudflared-x86_64-apple-darwin"
+
+# Linux x86_64
+download_cloudflared \
+    "Linux-x64" \
+    "cloudflared-linux-amd64" \
+    "cloudflared-x86_64-unknown-linux-gnu"
+
+# Windows x86_64
+download_cloudflared \
+    "Windows-x64" \
+    "cloudflared-windows-amd64.exe" \
+    "cloudflared-x86_64-pc-windows-msvc.exe"
+
+echo ""
+echo "Download complete!"
+echo ""
+echo "Downloaded binaries:"
+ls -la "$BINARIES_DIR"
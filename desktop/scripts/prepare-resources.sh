@@ -59,6 +59,20 @@ else
     echo "Run 'scripts/download-phoenixd.sh' first"
 fi
 
+# Copy cloudflared binary (optional, enables Cloudflare tunnels without Docker)
+echo ""
+echo "=== Copying cloudflared binary ==="
+CLOUDFLARED_BINARY=$(detect_platform | sed 's/^phoenixd-/cloudflared-/')
+
+if [ -f "$DESKTOP_DIR/binaries/$CLOUDFLARED_BINARY" ]; then
+    cp "$DESKTOP_DIR/binaries/$CLOUDFLARED_BINARY" "$RESOURCES_DIR/binaries/cloudflared"
+    chmod +x "$RESOURCES_DIR/binaries/cloudflared"
+    echo "Copied: $CLOUDFLARED_BINARY -> binaries/cloudflared"
+else
+    echo "Note: cloudflared binary not found: $DESKTOP_DIR/binaries/$CLOUDFLARED_BINARY"
+    echo "Run 'scripts/download-cloudflared.sh' to bundle Cloudflare tunnel support"
+fi
+
 # Build and copy backend
 echo ""
 echo "=== Building backend ==="
@@ -146,6 +160,16 @@ else
     exit 1
 fi
 
+# Optional exported build the shell can serve without Node.js
+if [ "${STATIC_FRONTEND:-0}" = "1" ]; then
+    echo ""
+    echo "=== Exporting static frontend ==="
+    NEXT_OUTPUT=export npm run build
+    rm -rf "$RESOURCES_DIR/frontend-static"
+    cp -R out "$RESOURCES_DIR/frontend-static"
+    echo "Static frontend exported ✓"
+fi
+
 echo ""
 echo "=== Resources prepared successfully! ==="
 echo ""
@@ -0,0 +1,25 @@
+use crate::i18n::t;
+use tauri::AppHandle;
+
+/// Native window with a QR code for every URL the dashboard is reachable at,
+/// so phones can connect by scanning instead of typing onion hostnames.
+/// Labels are i18n keys.
+pub fn show_access_window(app: &AppHandle, urls: &[(&str, String)]) -> Result<(), String> {
+    let mut body = String::new();
+    for (label, url) in urls {
+        body.push_str(&format!(
+            r#"<section><h2>{}</h2><div class="qr">{}</div>{}</section>"#,
+            crate::windows::escape(t(label)),
+            crate::qr::to_svg(url)?,
+            crate::windows::copyable(url)
+        ));
+    }
+
+    crate::windows::show(
+        app,
+        "access-urls",
+        t("window.access_urls"),
+        &body,
+        (380.0, 640.0),
+    )
+}
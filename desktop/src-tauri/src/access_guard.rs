@@ -0,0 +1,163 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+const FAILURE_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+#[cfg(test)]
+mod tests;
+
+/// Outcome of checking a request against the guard
+pub enum Verdict {
+    Allow,
+    RateLimited,
+    Blocked,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockedClient {
+    pub ip: String,
+    pub remaining_secs: u64,
+}
+
+struct Window {
+    started: Instant,
+    count: u32,
+}
+
+/// Per-IP rate limiting and fail2ban-style lockout for the reverse proxy.
+/// Lives in ProcessManager so lockouts survive proxy restarts.
+pub struct AccessGuard {
+    limits: Mutex<Limits>,
+    requests: Mutex<HashMap<IpAddr, Window>>,
+    failures: Mutex<HashMap<IpAddr, Window>>,
+    blocked: Mutex<HashMap<IpAddr, Instant>>,
+}
+
+#[derive(Clone, Copy)]
+struct Limits {
+    requests_per_minute: u32,
+    max_failed_auth: u32,
+    lockout: Duration,
+}
+
+impl AccessGuard {
+    pub fn new(requests_per_minute: u32, max_failed_auth: u32, lockout_minutes: u64) -> Self {
+        Self {
+            limits: Mutex::new(Limits {
+                requests_per_minute,
+                max_failed_auth,
+                lockout: Duration::from_secs(lockout_minutes * 60),
+            }),
+            requests: Mutex::new(HashMap::new()),
+            failures: Mutex::new(HashMap::new()),
+            blocked: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_limits(&self, requests_per_minute: u32, max_failed_auth: u32, lockout_minutes: u64) {
+        *self.limits.lock().unwrap() = Limits {
+            requests_per_minute,
+            max_failed_auth,
+            lockout: Duration::from_secs(lockout_minutes * 60),
+        };
+    }
+
+    pub fn check(&self, ip: IpAddr) -> Verdict {
+        self.check_at(ip, Instant::now())
+    }
+
+    fn check_at(&self, ip: IpAddr, now: Instant) -> Verdict {
+        let limits = *self.limits.lock().unwrap();
+
+        {
+            let mut blocked = self.blocked.lock().unwrap();
+            match blocked.get(&ip) {
+                Some(until) if *until > now => return Verdict::Blocked,
+                Some(_) => {
+                    blocked.remove(&ip);
+                }
+                None => {}
+            }
+        }
+
+        if limits.requests_per_minute > 0
+            && bump(&mut self.requests.lock().unwrap(), ip, now, RATE_WINDOW)
+                > limits.requests_per_minute
+        {
+            return Verdict::RateLimited;
+        }
+
+        Verdict::Allow
+    }
+
+    /// Record a failed login; returns true when this failure locked the client out
+    pub fn record_failure(&self, ip: IpAddr) -> bool {
+        self.record_failure_at(ip, Instant::now())
+    }
+
+    fn record_failure_at(&self, ip: IpAddr, now: Instant) -> bool {
+        let limits = *self.limits.lock().unwrap();
+        if limits.max_failed_auth == 0 {
+            return false;
+        }
+
+        let failures = bump(&mut self.failures.lock().unwrap(), ip, now, FAILURE_WINDOW);
+        if failures < limits.max_failed_auth {
+            return false;
+        }
+
+        self.failures.lock().unwrap().remove(&ip);
+        self.blocked
+            .lock()
+            .unwrap()
+            .insert(ip, now + limits.lockout);
+        true
+    }
+
+    pub fn record_success(&self, ip: IpAddr) {
+        self.failures.lock().unwrap().remove(&ip);
+    }
+
+    pub fn blocked_clients(&self) -> Vec<BlockedClient> {
+        self.blocked_clients_at(Instant::now())
+    }
+
+    fn blocked_clients_at(&self, now: Instant) -> Vec<BlockedClient> {
+        let mut blocked = self.blocked.lock().unwrap();
+        blocked.retain(|_, until| *until > now);
+
+        blocked
+            .iter()
+            .map(|(ip, until)| BlockedClient {
+                ip: ip.to_string(),
+                remaining_secs: until.duration_since(now).as_secs(),
+            })
+            .collect()
+    }
+
+    pub fn unblock(&self, ip: IpAddr) -> bool {
+        self.failures.lock().unwrap().remove(&ip);
+        self.blocked.lock().unwrap().remove(&ip).is_some()
+    }
+
+    pub fn unblock_all(&self) {
+        self.failures.lock().unwrap().clear();
+        self.blocked.lock().unwrap().clear();
+    }
+}
+
+/// Count an event in the client's current window, starting a new window when it expired
+fn bump(windows: &mut HashMap<IpAddr, Window>, ip: IpAddr, now: Instant, length: Duration) -> u32 {
+    windows.retain(|_, w| now.duration_since(w.started) < length);
+
+    let window = windows.entry(ip).or_insert(Window {
+        started: now,
+        count: 0,
+    });
+    window.count += 1;
+    window.count
+}
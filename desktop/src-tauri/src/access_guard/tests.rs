@@ -0,0 +1,125 @@
+use super::*;
+
+const CLIENT: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 1, 23));
+const OTHER: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 1, 24));
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+fn allowed(verdict: Verdict) -> bool {
+    matches!(verdict, Verdict::Allow)
+}
+
+#[test]
+fn requests_are_limited_per_minute() {
+    let guard = AccessGuard::new(3, 5, 15);
+    let start = Instant::now();
+
+    for i in 0..3 {
+        assert!(allowed(guard.check_at(CLIENT, start + secs(i))));
+    }
+    assert!(matches!(
+        guard.check_at(CLIENT, start + secs(10)),
+        Verdict::RateLimited
+    ));
+    // Other clients have their own window
+    assert!(allowed(guard.check_at(OTHER, start + secs(10))));
+    // A new window starts once the old one is over
+    assert!(allowed(guard.check_at(CLIENT, start + RATE_WINDOW)));
+}
+
+#[test]
+fn zero_requests_per_minute_means_unlimited() {
+    let guard = AccessGuard::new(0, 5, 15);
+    let now = Instant::now();
+
+    assert!((0..1000).all(|_| allowed(guard.check_at(CLIENT, now))));
+}
+
+#[test]
+fn failures_lock_the_client_out() {
+    let guard = AccessGuard::new(0, 3, 15);
+    let start = Instant::now();
+
+    assert!(!guard.record_failure_at(CLIENT, start));
+    assert!(!guard.record_failure_at(CLIENT, start + secs(1)));
+    assert!(guard.record_failure_at(CLIENT, start + secs(2)));
+
+    assert!(matches!(
+        guard.check_at(CLIENT, start + secs(3)),
+        Verdict::Blocked
+    ));
+    assert!(allowed(guard.check_at(OTHER, start + secs(3))));
+}
+
+#[test]
+fn lockout_expires() {
+    let guard = AccessGuard::new(0, 1, 15);
+    let start = Instant::now();
+    guard.record_failure_at(CLIENT, start);
+
+    assert!(matches!(
+        guard.check_at(CLIENT, start + secs(15 * 60 - 1)),
+        Verdict::Blocked
+    ));
+    assert!(allowed(guard.check_at(CLIENT, start + secs(15 * 60))));
+    assert!(guard.blocked_clients_at(start + secs(15 * 60)).is_empty());
+}
+
+#[test]
+fn blocked_clients_report_the_time_left() {
+    let guard = AccessGuard::new(0, 1, 15);
+    let start = Instant::now();
+    guard.record_failure_at(CLIENT, start);
+
+    let blocked = guard.blocked_clients_at(start + secs(60));
+
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0].ip, CLIENT.to_string());
+    assert_eq!(blocked[0].remaining_secs, 14 * 60);
+}
+
+#[test]
+fn old_failures_fall_out_of_the_window() {
+    let guard = AccessGuard::new(0, 2, 15);
+    let start = Instant::now();
+
+    assert!(!guard.record_failure_at(CLIENT, start));
+    assert!(!guard.record_failure_at(CLIENT, start + FAILURE_WINDOW));
+    assert!(guard.record_failure_at(CLIENT, start + FAILURE_WINDOW + secs(1)));
+}
+
+#[test]
+fn success_resets_the_failures() {
+    let guard = AccessGuard::new(0, 2, 15);
+    let start = Instant::now();
+
+    assert!(!guard.record_failure_at(CLIENT, start));
+    guard.record_success(CLIENT);
+    assert!(!guard.record_failure_at(CLIENT, start + secs(1)));
+}
+
+#[test]
+fn zero_failures_allowed_never_locks_out() {
+    let guard = AccessGuard::new(0, 0, 15);
+    let now = Instant::now();
+
+    assert!((0..100).all(|_| !guard.record_failure_at(CLIENT, now)));
+    assert!(allowed(guard.check_at(CLIENT, now)));
+}
+
+#[test]
+fn unblocking_lifts_the_lockout() {
+    let guard = AccessGuard::new(0, 1, 15);
+    let now = Instant::now();
+    guard.record_failure_at(CLIENT, now);
+    guard.record_failure_at(OTHER, now);
+
+    assert!(guard.unblock(CLIENT));
+    assert!(!guard.unblock(CLIENT));
+    assert!(allowed(guard.check_at(CLIENT, now)));
+
+    guard.unblock_all();
+    assert!(allowed(guard.check_at(OTHER, now)));
+}
@@ -0,0 +1,428 @@
+use crate::i18n::t;
+use crate::proxy::Access;
+use crate::windows::escape;
+use crate::{audit, secrets, token, AppState};
+use data_encoding::{BASE64URL_NOPAD, HEXLOWER};
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Url};
+use tracing::{info, warn};
+
+const LINKS_FILE: &str = "access_links.json";
+/// Keychain entry of the key links are signed with
+const SIGNING_KEY: &str = "access-link-key";
+const WINDOW_LABEL: &str = "access-links";
+/// Query parameter a link carries its token in
+pub const PARAM: &str = "access";
+/// Cookie the proxy swaps the token for, so it leaves the address bar
+pub const COOKIE: &str = "pd_access";
+/// Durations offered in the window, in hours
+const HOURS: [u64; 4] = [1, 4, 24, 72];
+const MAX_HOURS: u64 = 7 * 24;
+
+#[cfg(test)]
+mod tests;
+
+/// A temporary access link; the token itself is never stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessLink {
+    pub id: String,
+    /// Who it was shared with, if given
+    pub label: String,
+    /// RFC 3339
+    pub created_at: String,
+    /// RFC 3339
+    pub expires_at: String,
+    /// Links only read unless made with full access
+    #[serde(default)]
+    pub access: Access,
+    /// RFC 3339, since the app started
+    #[serde(skip_deserializing)]
+    pub last_used: Option<String>,
+}
+
+impl AccessLink {
+    fn expires(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::parse_from_rfc3339(&self.expires_at)
+            .ok()
+            .map(|expires| expires.to_utc())
+    }
+
+    fn is_live(&self) -> bool {
+        self.expires()
+            .is_some_and(|expires| expires > chrono::Utc::now())
+    }
+}
+
+/// Links as last loaded, so the proxy doesn't read the file per request
+static LINKS: Mutex<Option<Vec<AccessLink>>> = Mutex::new(None);
+/// The signing key once looked up; `Some(None)` when there is none yet
+static KEY: Mutex<Option<Option<hmac::Key>>> = Mutex::new(None);
+/// A link with the URL for each exposure, labelled with i18n keys
+type SharedLink = (AccessLink, Vec<(&'static str, String)>);
+/// The link created last, shown in the window until the next one
+static CREATED: Mutex<Option<SharedLink>> = Mutex::new(None);
+
+fn with_links<T>(data_dir: &Path, f: impl FnOnce(&mut Vec<AccessLink>) -> T) -> T {
+    let mut links = LINKS.lock().unwrap();
+    let links = links.get_or_insert_with(|| {
+        std::fs::read_to_string(data_dir.join(LINKS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    });
+    f(links)
+}
+
+fn save(data_dir: &Path, links: &[AccessLink]) -> Result<(), String> {
+    let path = data_dir.join(LINKS_FILE);
+    let content = serde_json::to_string_pretty(links)
+        .map_err(|e| format!("Failed to serialize access links: {}", e))?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+fn decode_key(secret: &str) -> Result<hmac::Key, String> {
+    let bytes = HEXLOWER
+        .decode(secret.as_bytes())
+        .map_err(|e| format!("Invalid access link key: {}", e))?;
+    Ok(hmac::Key::new(hmac::HMAC_SHA256, &bytes))
+}
+
+/// The signing key, if a link was ever made. Only reads, so a made-up
+/// token can't get the keychain written to. Blocks on the keychain the
+/// first time.
+fn load_key() -> Option<hmac::Key> {
+    if let Some(key) = KEY.lock().unwrap().as_ref() {
+        return key.clone();
+    }
+    let key = secrets::get(SIGNING_KEY).and_then(|secret| match decode_key(&secret) {
+        Ok(key) => Some(key),
+        Err(e) => {
+            warn!("{}", e);
+            None
+        }
+    });
+    *KEY.lock().unwrap() = Some(key.clone());
+    key
+}
+
+/// The signing key, created if there is none yet
+fn create_key() -> Result<hmac::Key, String> {
+    if let Some(key) = load_key() {
+        return Ok(key);
+    }
+    let secret = format!("{}{}", token::generate()?, token::generate()?);
+    secrets::set(SIGNING_KEY, &secret)?;
+    let key = decode_key(&secret)?;
+    *KEY.lock().unwrap() = Some(Some(key.clone()));
+    Ok(key)
+}
+
+/// `<id>.<expiry>.<signature>`, the expiry in Unix seconds
+fn sign(key: &hmac::Key, id: &str, expires: i64) -> String {
+    let payload = format!("{}.{}", id, expires);
+    let tag = hmac::sign(key, payload.as_bytes());
+    format!("{}.{}", payload, BASE64URL_NOPAD.encode(tag.as_ref()))
+}
+
+/// Issue a link valid for `hours`, returning it with its token
+pub fn create(
+    data_dir: &Path,
+    hours: u64,
+    label: &str,
+    access: Access,
+) -> Result<(AccessLink, String), String> {
+    if !(1..=MAX_HOURS).contains(&hours) {
+        return Err(format!("Access links last 1 to {} hours", MAX_HOURS));
+    }
+    let key = create_key()?;
+    let now = chrono::Utc::now();
+    let expires = now + chrono::Duration::hours(hours as i64);
+    let link = AccessLink {
+        id: token::generate()?[..12].to_string(),
+        label: label.trim().to_string(),
+        created_at: now.to_rfc3339(),
+        expires_at: expires.to_rfc3339(),
+        access,
+        last_used: None,
+    };
+    let token = sign(&key, &link.id, expires.timestamp());
+    with_links(data_dir, |links| {
+        links.retain(AccessLink::is_live);
+        links.push(link.clone());
+        save(data_dir, links)
+    })?;
+    audit::record(
+        data_dir,
+        "access_link.create",
+        format!("{} {}h {:?} {}", link.id, hours, access, link.label),
+    );
+    info!(
+        "🎟️ Temporary {:?} access link {} for {} hours",
+        access, link.id, hours
+    );
+    Ok((link, token))
+}
+
+/// The id in `token` if `key` signed it and it hasn't expired
+fn check(key: &hmac::Key, token: &str) -> Option<String> {
+    let mut parts = token.splitn(3, '.');
+    let (id, expires, signature) = (parts.next()?, parts.next()?, parts.next()?);
+    let signature = BASE64URL_NOPAD.decode(signature.as_bytes()).ok()?;
+    hmac::verify(key, format!("{}.{}", id, expires).as_bytes(), &signature).ok()?;
+    if expires.parse::<i64>().ok()? <= chrono::Utc::now().timestamp() {
+        return None;
+    }
+    Some(id.to_string())
+}
+
+/// The live link `token` belongs to, if it is genuine, unexpired and not
+/// revoked. Marks it used. Blocks on the keychain the first time.
+pub fn verify(data_dir: &Path, token: &str) -> Option<AccessLink> {
+    let id = check(&load_key()?, token)?;
+    with_links(data_dir, |links| use_link(links, &id))
+}
+
+/// The live link with `id`, marked used; `None` once revoked or expired
+fn use_link(links: &mut [AccessLink], id: &str) -> Option<AccessLink> {
+    let link = links
+        .iter_mut()
+        .find(|link| link.id == id)
+        .filter(|link| link.is_live())?;
+    link.last_used = Some(chrono::Utc::now().to_rfc3339());
+    Some(link.clone())
+}
+
+/// Links still live, soonest to expire first
+pub fn list(data_dir: &Path) -> Vec<AccessLink> {
+    let mut live = with_links(data_dir, |links| {
+        links.retain(AccessLink::is_live);
+        links.clone()
+    });
+    live.sort_by(|a, b| a.expires_at.cmp(&b.expires_at));
+    live
+}
+
+pub fn revoke(data_dir: &Path, id: &str) -> Result<bool, String> {
+    let revoked = with_links(data_dir, |links| {
+        let before = links.len();
+        links.retain(|link| link.id != id);
+        (links.len() != before)
+            .then(|| save(data_dir, links))
+            .transpose()
+            .map(|saved| saved.is_some())
+    })?;
+    if revoked {
+        audit::record(data_dir, "access_link.revoke", id);
+        info!("🎟️ Revoked access link {}", id);
+        let mut created = CREATED.lock().unwrap();
+        if created.as_ref().is_some_and(|(link, _)| link.id == id) {
+            *created = None;
+        }
+    }
+    Ok(revoked)
+}
+
+/// Revoke every link and replace the signing key, for credential rotation
+pub fn reset(data_dir: &Path) -> Result<(), String> {
+    with_links(data_dir, |links| {
+        links.clear();
+        save(data_dir, links)
+    })?;
+    secrets::delete(SIGNING_KEY)?;
+    *KEY.lock().unwrap() = None;
+    *CREATED.lock().unwrap() = None;
+    Ok(())
+}
+
+/// Where `token` opens the dashboard: every exposure that goes through the
+/// reverse proxy
+pub fn urls(app: &AppHandle, token: &str) -> Result<Vec<(&'static str, String)>, String> {
+    let state = app.state::<AppState>();
+    if !state.config.lock().unwrap().proxy.enabled {
+        return Err("Temporary access links need the reverse proxy, enable it first".to_string());
+    }
+    let exposures = state.process_manager.lock().unwrap().access_urls();
+    let urls: Vec<(&'static str, String)> = exposures
+        .into_iter()
+        .filter(|(label, _)| matches!(*label, "access.lan" | "access.tor" | "access.cloudflare"))
+        .filter_map(|(label, url)| {
+            let mut url: Url = url.parse().ok()?;
+            url.set_query(None);
+            url.query_pairs_mut().append_pair(PARAM, token);
+            Some((label, url.to_string()))
+        })
+        .collect();
+    if urls.is_empty() {
+        return Err(
+            "The dashboard isn't exposed yet: turn on LAN access, Tor or a tunnel".to_string(),
+        );
+    }
+    Ok(urls)
+}
+
+/// Create a link and keep it for the window
+fn create_for_window(
+    app: &AppHandle,
+    hours: u64,
+    label: &str,
+    access: Access,
+) -> Result<(), String> {
+    let data_dir = app.state::<AppState>().data_dir.clone();
+    // Fail before issuing a link nobody can use
+    urls(app, "")?;
+    let (link, token) = create(&data_dir, hours, label, access)?;
+    let urls = urls(app, &token)?;
+    *CREATED.lock().unwrap() = Some((link, urls));
+    Ok(())
+}
+
+fn local_time(rfc3339: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(rfc3339)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn render(app: &AppHandle) -> String {
+    let state = app.state::<AppState>();
+    let mut body = String::new();
+    if !state.config.lock().unwrap().proxy.enabled {
+        body.push_str(&format!(
+            r#"<p role="status">{}</p>"#,
+            escape(t("access_links.needs_proxy"))
+        ));
+    }
+    if let Some((link, urls)) = CREATED.lock().unwrap().as_ref() {
+        body.push_str(&format!(
+            "<p>{}</p>",
+            escape(&t("access_links.created").replace("{expires}", &local_time(&link.expires_at)))
+        ));
+        for (label, url) in urls {
+            let qr = crate::qr::to_svg(url).unwrap_or_default();
+            body.push_str(&format!(
+                r#"<section><h2>{}</h2><div class="qr">{}</div>{}</section>"#,
+                escape(t(label)),
+                qr,
+                crate::windows::copyable(url)
+            ));
+        }
+    }
+
+    body.push_str(&format!(
+        r#"<form action="submit"><fieldset><legend>{}</legend><input name="label" aria-label="{}" placeholder="{}">"#,
+        escape(t("access_links.create")),
+        escape(t("access_links.label")),
+        escape(t("access_links.label"))
+    ));
+    body.push_str(&format!(
+        r#"<label><input type="checkbox" name="full" value="1"> {}</label> "#,
+        escape(t("access_links.full"))
+    ));
+    for hours in HOURS {
+        body.push_str(&format!(
+            r#"<button type="submit" name="hours" value="{}">{}</button> "#,
+            hours,
+            escape(&t("access_links.hours").replace("{hours}", &hours.to_string()))
+        ));
+    }
+    body.push_str("</fieldset></form>");
+
+    let links = list(&state.data_dir);
+    body.push_str(&format!(
+        r#"<form action="submit"><table><caption>{}</caption>"#,
+        escape(t("access_links.active"))
+    ));
+    if links.is_empty() {
+        body.push_str(&format!(
+            "<tr><td>{}</td></tr>",
+            escape(t("access_links.none"))
+        ));
+    }
+    for link in &links {
+        let last_used = link
+            .last_used
+            .as_deref()
+            .map_or_else(|| t("access_links.never").to_string(), local_time);
+        let name = if link.label.is_empty() {
+            &link.id
+        } else {
+            &link.label
+        };
+        let name = match link.access {
+            Access::Full => format!("{} ({})", name, t("access_links.full")),
+            Access::ReadOnly => name.clone(),
+        };
+        body.push_str(&format!(
+            r#"<tr><th scope="row">{}</th><td>{} {}</td><td>{} {}</td><td><button type="submit" name="revoke" value="{}">{}</button></td></tr>"#,
+            escape(&name),
+            escape(t("access_links.expires")),
+            local_time(&link.expires_at),
+            escape(t("access_links.last_used")),
+            escape(&last_used),
+            escape(&link.id),
+            escape(t("access_links.revoke"))
+        ));
+    }
+    body.push_str("</table></form>");
+    body
+}
+
+/// Live window to share new temporary links and revoke the active ones
+pub fn show_window(app: &AppHandle) -> Result<(), String> {
+    let handle = app.clone();
+    crate::windows::show_live_form(
+        app,
+        WINDOW_LABEL,
+        t("window.access_links"),
+        (420.0, 680.0),
+        30,
+        |app, _| render(app),
+        move |url| {
+            let param = |name: &str| {
+                url.query_pairs()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.into_owned())
+            };
+            let (revoke_id, hours, label) = (param("revoke"), param("hours"), param("label"));
+            let access = if param("full").is_some() {
+                Access::Full
+            } else {
+                Access::ReadOnly
+            };
+            let app = handle.clone();
+            // Reading the keychain and the tunnels can block
+            std::thread::spawn(move || {
+                let data_dir = app.state::<AppState>().data_dir.clone();
+                let result = match (revoke_id, hours) {
+                    (Some(id), _) => revoke(&data_dir, &id).map(|_| ()),
+                    (None, Some(hours)) => hours
+                        .parse()
+                        .map_err(|_| format!("Invalid duration: {}", hours))
+                        .and_then(|hours| {
+                            create_for_window(
+                                &app,
+                                hours,
+                                label.as_deref().unwrap_or_default(),
+                                access,
+                            )
+                        }),
+                    (None, None) => Ok(()),
+                };
+                if let Err(e) = result {
+                    warn!("{}", e);
+                    crate::notify::native(app.clone())(t("window.access_links"), &e);
+                }
+                if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+                    let _ = window.eval("location.reload()");
+                }
+            });
+        },
+    )
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+fn test_key() -> hmac::Key {
+    hmac::Key::new(hmac::HMAC_SHA256, b"access link test key")
+}
+
+fn in_an_hour() -> i64 {
+    chrono::Utc::now().timestamp() + 3600
+}
+
+fn link(id: &str, expires: chrono::DateTime<chrono::Utc>) -> AccessLink {
+    AccessLink {
+        id: id.to_string(),
+        label: String::new(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        expires_at: expires.to_rfc3339(),
+        access: Access::default(),
+        last_used: None,
+    }
+}
+
+#[test]
+fn signed_token_round_trips() {
+    let token = sign(&test_key(), "abc123", in_an_hour());
+
+    assert_eq!(check(&test_key(), &token).as_deref(), Some("abc123"));
+}
+
+#[test]
+fn another_key_is_rejected() {
+    let token = sign(&test_key(), "abc123", in_an_hour());
+    let other = hmac::Key::new(hmac::HMAC_SHA256, b"some other key");
+
+    assert_eq!(check(&other, &token), None);
+}
+
+#[test]
+fn tampered_id_or_expiry_is_rejected() {
+    let expires = in_an_hour();
+    let token = sign(&test_key(), "abc123", expires);
+    let signature = token.rsplit('.').next().unwrap();
+
+    let other_id = format!("abc124.{}.{}", expires, signature);
+    let later = format!("abc123.{}.{}", expires + 3600, signature);
+
+    assert_eq!(check(&test_key(), &other_id), None);
+    assert_eq!(check(&test_key(), &later), None);
+}
+
+#[test]
+fn expired_token_is_rejected() {
+    let token = sign(&test_key(), "abc123", chrono::Utc::now().timestamp() - 1);
+
+    assert_eq!(check(&test_key(), &token), None);
+}
+
+#[test]
+fn malformed_tokens_are_rejected() {
+    let key = test_key();
+    let expires = in_an_hour();
+    for token in [
+        "",
+        "a",
+        "a.b",
+        "a.b.c",
+        "...",
+        &format!("abc123.{}.not base64!", expires),
+        &format!("abc123.{}.", expires),
+        &format!("abc123.soon.{}", BASE64URL_NOPAD.encode(b"x")),
+    ] {
+        assert_eq!(check(&key, token), None, "{:?}", token);
+    }
+}
+
+#[test]
+fn live_link_is_found_and_marked_used() {
+    let mut links = vec![link(
+        "abc123",
+        chrono::Utc::now() + chrono::Duration::hours(1),
+    )];
+
+    let found = use_link(&mut links, "abc123").unwrap();
+
+    assert_eq!(found.id, "abc123");
+    assert!(found.last_used.is_some());
+    assert!(links[0].last_used.is_some());
+}
+
+#[test]
+fn revoked_link_is_rejected() {
+    let mut links = vec![link(
+        "abc123",
+        chrono::Utc::now() + chrono::Duration::hours(1),
+    )];
+
+    assert!(use_link(&mut links, "revoked").is_none());
+}
+
+#[test]
+fn expired_link_is_rejected() {
+    let mut links = vec![link(
+        "abc123",
+        chrono::Utc::now() - chrono::Duration::seconds(1),
+    )];
+
+    assert!(use_link(&mut links, "abc123").is_none());
+}
+
+#[test]
+fn links_without_an_access_level_are_read_only() {
+    let json = r#"{"id":"abc123","label":"","createdAt":"","expiresAt":""}"#;
+
+    let link: AccessLink = serde_json::from_str(json).unwrap();
+
+    assert_eq!(link.access, Access::ReadOnly);
+}
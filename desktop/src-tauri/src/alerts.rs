@@ -0,0 +1,116 @@
+use crate::config::AlertsConfig;
+use crate::secrets;
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    ServiceDown,
+    ServiceRestarted,
+    NodeUnreachable,
+    /// phoenixd down for longer than `node_offline_minutes`
+    NodeOffline,
+    ServiceUnhealthy,
+    LowDisk,
+    /// tor and cloudflared traffic past `bandwidth_daily_mb` / `bandwidth_weekly_mb`
+    BandwidthExceeded,
+    /// Outgoing payment pending past `stuck_payment_minutes`
+    PaymentStuck,
+    /// Invoice of at least `expiring_invoice_sat` about to expire unpaid
+    InvoiceExpiring,
+}
+
+impl AlertKind {
+    /// Worth waking someone up for; these also go to Telegram
+    fn is_critical(self) -> bool {
+        matches!(
+            self,
+            AlertKind::NodeOffline | AlertKind::ServiceUnhealthy | AlertKind::LowDisk
+        )
+    }
+}
+
+/// Event raised by the watchdog, posted as-is to the webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub event: AlertKind,
+    pub service: Option<String>,
+    pub detail: String,
+    /// Unix seconds
+    pub timestamp: u64,
+}
+
+impl Alert {
+    pub fn new(event: AlertKind, service: Option<&str>, detail: impl Into<String>) -> Self {
+        Self {
+            event,
+            service: service.map(String::from),
+            detail: detail.into(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Log the alert and deliver it in the background: every alert to the
+/// webhook if one is configured, critical ones to Telegram if enabled
+pub fn dispatch(config: &AlertsConfig, alert: Alert) {
+    info!("🚨 {:?}: {}", alert.event, alert.detail);
+
+    let webhook = (!config.webhook_url.is_empty()).then(|| config.webhook_url.clone());
+    let telegram = (config.telegram_enabled && alert.event.is_critical())
+        .then(|| config.telegram_chat_id.clone());
+    if webhook.is_none() && telegram.is_none() {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        if let Some(url) = webhook {
+            if let Err(e) = send_webhook(&url, &alert) {
+                warn!("Failed to deliver webhook alert: {}", e);
+            }
+        }
+        if let Some(chat_id) = telegram {
+            if let Err(e) = send_telegram(
+                &chat_id,
+                &format!("⚡ Phoenixd Dashboard\n{}", alert.detail),
+            ) {
+                warn!("{}", e);
+            }
+        }
+    });
+}
+
+/// POST `payload` as JSON to `url`
+pub fn send_webhook(url: &str, payload: &impl Serialize) -> Result<(), String> {
+    ureq::post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .send_json(payload)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Send a message through the bot whose token is in the keychain
+pub fn send_telegram(chat_id: &str, text: &str) -> Result<(), String> {
+    let token = secrets::get(secrets::TELEGRAM_BOT_TOKEN)
+        .ok_or_else(|| "No Telegram bot token configured".to_string())?;
+
+    ureq::post(&format!(
+        "https://api.telegram.org/bot{}/sendMessage",
+        token
+    ))
+    .timeout(WEBHOOK_TIMEOUT)
+    .send_json(serde_json::json!({ "chat_id": chat_id, "text": text }))
+    .map(|_| ())
+    // ureq errors include the URL, which contains the token
+    .map_err(|e| match e {
+        ureq::Error::Status(code, _) => format!("Telegram rejected the alert ({})", code),
+        ureq::Error::Transport(_) => "Failed to reach Telegram".to_string(),
+    })
+}
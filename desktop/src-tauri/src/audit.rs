@@ -0,0 +1,60 @@
+use crate::journal;
+use crate::redact;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use tracing::warn;
+
+/// One JSON object per line, only ever appended to
+const AUDIT_FILE: &str = "audit.log";
+
+/// A privileged action the shell took on the user's behalf
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// RFC 3339 timestamp
+    pub at: String,
+    /// Short machine-readable name, e.g. `service.restart` or `seed.reveal`
+    pub action: String,
+    pub detail: String,
+}
+
+/// Append an entry. Failing to audit never blocks the action itself.
+/// Mode switches also go to the event journal.
+pub fn record(data_dir: &Path, action: &str, detail: impl Into<String>) {
+    let entry = AuditEntry {
+        at: chrono::Local::now().to_rfc3339(),
+        action: action.to_string(),
+        detail: redact::scrub(&detail.into()),
+    };
+    if action.starts_with("mode.") {
+        journal::record(data_dir, action, None, &entry.detail);
+    }
+    let result = serde_json::to_string(&entry)
+        .map_err(|e| e.to_string())
+        .and_then(|line| {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(data_dir.join(AUDIT_FILE))
+                .and_then(|mut file| writeln!(file, "{}", line))
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        warn!("Failed to write audit log: {}", e);
+    }
+}
+
+/// The last `limit` entries, newest first
+pub fn read(data_dir: &Path, limit: usize) -> Result<Vec<AuditEntry>, String> {
+    let content = match std::fs::read_to_string(data_dir.join(AUDIT_FILE)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read audit log: {}", e)),
+    };
+    Ok(content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(limit)
+        .collect())
+}
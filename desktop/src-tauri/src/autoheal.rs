@@ -0,0 +1,92 @@
+use crate::audit;
+use crate::config::AutoHealConfig;
+use crate::i18n::t;
+use crate::logs;
+use crate::notify;
+use crate::process_manager::{ServiceState, ServiceStatus};
+use crate::AppState;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tracing::{error, warn};
+
+/// Log lines shown when a service gets restarted
+const LOG_CONTEXT_LINES: usize = 20;
+const ATTEMPT_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Restarts services that stay unhealthy (or exited on their own) for
+/// several watchdog ticks, capped per hour
+#[derive(Default)]
+pub struct AutoHeal {
+    /// Consecutive failing checks per service
+    streaks: HashMap<&'static str, u32>,
+    /// Restarts within the last hour
+    attempts: HashMap<&'static str, Vec<Instant>>,
+    /// Services we stopped restarting; cleared once they recover
+    gave_up: HashSet<&'static str>,
+}
+
+impl AutoHeal {
+    pub fn tick(
+        &mut self,
+        app: &AppHandle,
+        config: &AutoHealConfig,
+        services: &[ServiceStatus],
+        crashed: &[&'static str],
+    ) {
+        if !config.enabled {
+            return;
+        }
+
+        for status in services {
+            let name = status.name;
+            if config.restart_policy(name) == "no" {
+                continue;
+            }
+            let failing = status.state == ServiceState::Unhealthy || crashed.contains(&name);
+            if !failing {
+                self.streaks.remove(name);
+                self.gave_up.remove(name);
+                continue;
+            }
+
+            let streak = self.streaks.entry(name).or_default();
+            *streak += 1;
+            if *streak < config.unhealthy_checks {
+                continue;
+            }
+
+            let attempts = self.attempts.entry(name).or_default();
+            attempts.retain(|at| at.elapsed() < ATTEMPT_WINDOW);
+            if attempts.len() >= config.max_restarts_per_hour as usize {
+                if self.gave_up.insert(name) {
+                    error!(
+                        "🩺 Giving up on {} after {} restarts in the last hour",
+                        name,
+                        attempts.len()
+                    );
+                    let body = t("notify.autoheal_body")
+                        .replace("{service}", name)
+                        .replace("{attempts}", &attempts.len().to_string());
+                    notify::native(app.clone())(t("notify.autoheal_title"), &body);
+                }
+                continue;
+            }
+
+            let state = app.state::<AppState>();
+            let mut pm = state.process_manager.lock().unwrap();
+            warn!(
+                "🩺 {} failing for {} checks, restarting. Last log lines:\n{}",
+                name,
+                streak,
+                logs::tail(&pm.log_path(name), LOG_CONTEXT_LINES).join("\n")
+            );
+            *streak = 0;
+            attempts.push(Instant::now());
+            audit::record(&state.data_dir, "service.auto_restart", name);
+            if let Err(e) = pm.restart_service(name) {
+                error!("Auto-heal restart of {} failed: {}", name, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use serde::Serialize;
+use std::path::Path;
+
+/// Argument the OS service manager starts us with
+#[cfg(any(windows, target_os = "macos"))]
+pub const SERVICE_ARG: &str = "--service";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackgroundServiceStatus {
+    pub supported: bool,
+    pub installed: bool,
+    pub running: bool,
+}
+
+/// Register the headless supervisor with the OS so phoenixd and the backend
+/// keep running without anyone logged in
+pub fn install(data_dir: &Path) -> Result<(), String> {
+    platform::install(data_dir)
+}
+
+pub fn uninstall() -> Result<(), String> {
+    platform::uninstall()
+}
+
+pub fn status() -> BackgroundServiceStatus {
+    platform::status()
+}
+
+/// Entry point when started by the service manager
+#[cfg(any(windows, target_os = "macos"))]
+pub fn run_service() -> Result<(), String> {
+    platform::run()
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::{BackgroundServiceStatus, SERVICE_ARG};
+    use crate::supervisor;
+    use std::ffi::{OsStr, OsString};
+    use std::path::Path;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tracing::error;
+    use windows_service::service::{
+        ServiceAccess, ServiceControl, ServiceControlAccept, ServiceErrorControl, ServiceExitCode,
+        ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+    };
+    use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
+    use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+    use windows_service::{define_windows_service, service_dispatcher};
+
+    const SERVICE_NAME: &str = "PhoenixdDashboard";
+
+    pub fn install(data_dir: &Path) -> Result<(), String> {
+        let manager = ServiceManager::local_computer(
+            None::<&str>,
+            ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+        )
+        .map_err(|e| {
+            format!(
+                "Failed to open the service manager (run as administrator): {}",
+                e
+            )
+        })?;
+
+        let executable_path = std::env::current_exe()
+            .map_err(|e| format!("Failed to locate the app executable: {}", e))?;
+        let info = ServiceInfo {
+            name: OsString::from(SERVICE_NAME),
+            display_name: OsString::from("Phoenixd Dashboard"),
+            service_type: ServiceType::OWN_PROCESS,
+            start_type: ServiceStartType::AutoStart,
+            error_control: ServiceErrorControl::Normal,
+            executable_path,
+            // The service runs as LocalSystem, whose app data dir isn't the user's
+            launch_arguments: vec![
+                OsString::from(SERVICE_ARG),
+                OsString::from("--data-dir"),
+                data_dir.as_os_str().to_owned(),
+            ],
+            dependencies: vec![],
+            account_name: None,
+            account_password: None,
+        };
+
+        let service = manager
+            .create_service(&info, ServiceAccess::CHANGE_CONFIG | ServiceAccess::START)
+            .map_err(|e| format!("Failed to install the service: {}", e))?;
+        let _ =
+            service.set_description("Runs phoenixd and the dashboard backend in the background");
+        service
+            .start::<&OsStr>(&[])
+            .map_err(|e| format!("Failed to start the service: {}", e))
+    }
+
+    pub fn uninstall() -> Result<(), String> {
+        let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+            .map_err(|e| format!("Failed to open the service manager: {}", e))?;
+        let service = manager
+            .open_service(
+                SERVICE_NAME,
+                ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE,
+            )
+            .map_err(|e| format!("Failed to open the service (run as administrator): {}", e))?;
+
+        if service
+            .query_status()
+            .is_ok_and(|status| status.current_state != ServiceState::Stopped)
+        {
+            let _ = service.stop();
+        }
+        service
+            .delete()
+            .map_err(|e| format!("Failed to remove the service: {}", e))
+    }
+
+    pub fn status() -> BackgroundServiceStatus {
+        let state = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+            .and_then(|manager| manager.open_service(SERVICE_NAME, ServiceAccess::QUERY_STATUS))
+            .and_then(|service| service.query_status())
+            .map(|status| status.current_state);
+
+        BackgroundServiceStatus {
+            supported: true,
+            installed: state.is_ok(),
+            running: matches!(state, Ok(ServiceState::Running)),
+        }
+    }
+
+    define_windows_service!(ffi_service_main, service_main);
+
+    pub fn run() -> Result<(), String> {
+        service_dispatcher::start(SERVICE_NAME, ffi_service_main)
+            .map_err(|e| format!("Failed to start the service dispatcher: {}", e))
+    }
+
+    fn service_main(_arguments: Vec<OsString>) {
+        let stop = Arc::new(AtomicBool::new(false));
+        let handler_stop = Arc::clone(&stop);
+        let handle =
+            service_control_handler::register(SERVICE_NAME, move |control| match control {
+                ServiceControl::Stop | ServiceControl::Shutdown => {
+                    handler_stop.store(true, Ordering::SeqCst);
+                    ServiceControlHandlerResult::NoError
+                }
+                ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+                _ => ServiceControlHandlerResult::NotImplemented,
+            });
+        let Ok(handle) = handle else {
+            return;
+        };
+
+        let report = |state: ServiceState, accepted: ServiceControlAccept, code: u32| {
+            let _ = handle.set_service_status(ServiceStatus {
+                service_type: ServiceType::OWN_PROCESS,
+                current_state: state,
+                controls_accepted: accepted,
+                exit_code: ServiceExitCode::Win32(code),
+                checkpoint: 0,
+                wait_hint: Duration::default(),
+                process_id: None,
+            });
+        };
+
+        report(
+            ServiceState::Running,
+            ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+            0,
+        );
+        let code = match supervisor::run(supervisor::data_dir_from_args(), stop) {
+            Ok(()) => 0,
+            Err(e) => {
+                error!("Background service failed: {}", e);
+                1
+            }
+        };
+        report(ServiceState::Stopped, ServiceControlAccept::empty(), code);
+    }
+}
+
+/// A LaunchAgent in the user's session, started at login and kept alive
+/// by launchd
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{BackgroundServiceStatus, SERVICE_ARG};
+    use crate::supervisor;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use tokio::signal::unix::{signal, SignalKind};
+
+    const LABEL: &str = "com.phoenixd.dashboard.agent";
+
+    fn plist_path() -> Result<PathBuf, String> {
+        dirs::home_dir()
+            .map(|home| {
+                home.join("Library")
+                    .join("LaunchAgents")
+                    .join(format!("{}.plist", LABEL))
+            })
+            .ok_or_else(|| "Failed to locate the home directory".to_string())
+    }
+
+    fn launchctl(args: &[&str]) -> Result<String, String> {
+        let output = Command::new("launchctl")
+            .args(args)
+            .output()
+            .map_err(|e| format!("Failed to run launchctl: {}", e))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(format!(
+                "launchctl {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    pub fn install(data_dir: &Path) -> Result<(), String> {
+        let executable = std::env::current_exe()
+            .map_err(|e| format!("Failed to locate the app executable: {}", e))?;
+        let arguments = [
+            executable.to_string_lossy().into_owned(),
+            SERVICE_ARG.to_string(),
+            "--data-dir".to_string(),
+            data_dir.to_string_lossy().into_owned(),
+        ]
+        .iter()
+        .map(|arg| format!("        <string>{}</string>\n", crate::windows::escape(arg)))
+        .collect::<String>();
+
+        let plist = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+{}    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <true/>
+    <key>ProcessType</key>
+    <string>Background</string>
+</dict>
+</plist>
+"#,
+            LABEL, arguments
+        );
+
+        let path = plist_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create LaunchAgents directory: {}", e))?;
+        }
+        std::fs::write(&path, plist).map_err(|e| format!("Failed to write launch agent: {}", e))?;
+        launchctl(&["load", "-w", &path.to_string_lossy()]).map(|_| ())
+    }
+
+    pub fn uninstall() -> Result<(), String> {
+        let path = plist_path()?;
+        if !path.exists() {
+            return Ok(());
+        }
+        // Unloading stops the supervisor, which stops its services
+        let _ = launchctl(&["unload", "-w", &path.to_string_lossy()]);
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to remove launch agent: {}", e))
+    }
+
+    pub fn status() -> BackgroundServiceStatus {
+        let installed = plist_path().is_ok_and(|path| path.exists());
+        // `launchctl list <label>` only shows a PID while the agent runs
+        let running = installed
+            && launchctl(&["list", LABEL]).is_ok_and(|listing| listing.contains("\"PID\""));
+
+        BackgroundServiceStatus {
+            supported: true,
+            installed,
+            running,
+        }
+    }
+
+    /// launchd stops the agent with SIGTERM
+    pub fn run() -> Result<(), String> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        std::thread::spawn(move || {
+            tauri::async_runtime::block_on(async {
+                if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+                    terminate.recv().await;
+                }
+            });
+            flag.store(true, Ordering::SeqCst);
+        });
+        supervisor::run(supervisor::data_dir_from_args(), stop)
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+    use super::BackgroundServiceStatus;
+    use std::path::Path;
+
+    pub fn install(_data_dir: &Path) -> Result<(), String> {
+        Err("Running as a background service isn't supported on this platform".to_string())
+    }
+
+    pub fn uninstall() -> Result<(), String> {
+        install(Path::new(""))
+    }
+
+    pub fn status() -> BackgroundServiceStatus {
+        BackgroundServiceStatus {
+            supported: false,
+            installed: false,
+            running: false,
+        }
+    }
+}
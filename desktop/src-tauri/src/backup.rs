@@ -0,0 +1,426 @@
+use crate::audit;
+use crate::config::BackupConfig;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::scheduler;
+use crate::AppState;
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone};
+use croner::Cron;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{error, info, warn};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Archive names are this followed by a local timestamp
+const PREFIX: &str = "phoenixd-backup-";
+const TIMESTAMP: &str = "%Y%m%d-%H%M%S";
+/// Written first in each archive, describes what it holds
+const META_FILE: &str = "backup.json";
+/// phoenixd's home: seed, phoenix.conf and the channel database
+const PHOENIX_DIR: &str = ".phoenix";
+/// The backend's SQLite database and its write-ahead log
+const DASHBOARD_DB: [&str; 3] = ["dashboard.db", "dashboard.db-wal", "dashboard.db-shm"];
+/// Delay between attempts while a payment or channel operation is in flight
+const RETRY: Duration = Duration::from_secs(60);
+/// Give up on a scheduled backup that stays blocked this long
+const WINDOW: Duration = Duration::from_secs(60 * 60);
+
+pub fn backup_dir(data_dir: &Path, config: &BackupConfig) -> PathBuf {
+    if config.dir.is_empty() {
+        data_dir.join("backups")
+    } else {
+        PathBuf::from(&config.dir)
+    }
+}
+
+/// Fresh archive path in the backup directory
+pub fn next_archive_path(data_dir: &Path, config: &BackupConfig) -> PathBuf {
+    backup_dir(data_dir, config).join(format!("{}{}.zip", PREFIX, Local::now().format(TIMESTAMP)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupMeta {
+    /// RFC 3339 timestamp
+    created_at: String,
+    chain: String,
+    app_version: String,
+}
+
+/// An archive in the backup directory
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    pub name: String,
+    pub path: PathBuf,
+    /// RFC 3339 timestamp
+    pub created_at: String,
+    pub size: u64,
+    /// Unknown for archives made before the metadata was added
+    pub chain: Option<String>,
+    pub app_version: Option<String>,
+    #[serde(skip)]
+    time: DateTime<Local>,
+}
+
+fn read_meta(archive: &Path) -> Option<BackupMeta> {
+    let file = File::open(archive).ok()?;
+    let mut zip = ZipArchive::new(file).ok()?;
+    let entry = zip.by_name(META_FILE).ok()?;
+    serde_json::from_reader(entry).ok()
+}
+
+/// Archives in the backup directory, newest first
+pub fn list(data_dir: &Path, config: &BackupConfig) -> Result<Vec<BackupInfo>, String> {
+    let dir = backup_dir(data_dir, config);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries =
+        std::fs::read_dir(&dir).map_err(|e| format!("Failed to read {:?}: {}", dir, e))?;
+    let mut backups: Vec<BackupInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let stamp = name.strip_prefix(PREFIX)?.strip_suffix(".zip")?;
+            let named = NaiveDateTime::parse_from_str(stamp, TIMESTAMP)
+                .ok()
+                .and_then(|time| Local.from_local_datetime(&time).earliest());
+            let path = entry.path();
+            let meta = read_meta(&path);
+            let time = meta
+                .as_ref()
+                .and_then(|meta| DateTime::parse_from_rfc3339(&meta.created_at).ok())
+                .map(|time| time.with_timezone(&Local))
+                .or(named)?;
+            Some(BackupInfo {
+                name,
+                size: entry.metadata().map(|m| m.len()).unwrap_or_default(),
+                path,
+                created_at: time.to_rfc3339(),
+                chain: meta.as_ref().map(|meta| meta.chain.clone()),
+                app_version: meta.map(|meta| meta.app_version),
+                time,
+            })
+        })
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.time));
+    Ok(backups)
+}
+
+/// An archive of the backup directory by file name
+pub fn find(data_dir: &Path, config: &BackupConfig, name: &str) -> Result<PathBuf, String> {
+    list(data_dir, config)?
+        .into_iter()
+        .find(|backup| backup.name == name)
+        .map(|backup| backup.path)
+        .ok_or_else(|| format!("No backup named {}", name))
+}
+
+/// Calendar day, ISO week or month a backup falls in
+type Period = fn(&DateTime<Local>) -> (i32, u32, u32);
+
+/// Delete the archives no retention tier keeps. Each tier keeps the newest
+/// archive of its `keep_*` most recent days, weeks or months.
+pub fn prune(data_dir: &Path, config: &BackupConfig) -> Result<usize, String> {
+    if config.keep_daily == 0 && config.keep_weekly == 0 && config.keep_monthly == 0 {
+        return Ok(0);
+    }
+    let backups = list(data_dir, config)?;
+    let mut keep: HashSet<usize> = HashSet::new();
+    // The latest archive always stays
+    keep.insert(0);
+    let tiers: [(u32, Period); 3] = [
+        (config.keep_daily, |t| (t.year(), t.month(), t.day())),
+        (config.keep_weekly, |t| {
+            let week = t.iso_week();
+            (week.year(), week.week(), 0)
+        }),
+        (config.keep_monthly, |t| (t.year(), t.month(), 0)),
+    ];
+    for (count, period) in tiers {
+        let mut seen = HashSet::new();
+        for (i, backup) in backups.iter().enumerate() {
+            if seen.len() >= count as usize {
+                break;
+            }
+            if seen.insert(period(&backup.time)) {
+                keep.insert(i);
+            }
+        }
+    }
+
+    let mut removed = 0;
+    for (i, backup) in backups.iter().enumerate() {
+        if keep.contains(&i) {
+            continue;
+        }
+        match std::fs::remove_file(&backup.path) {
+            Ok(()) => removed += 1,
+            Err(e) => warn!("Failed to delete old backup {:?}: {}", backup.path, e),
+        }
+    }
+    if removed > 0 {
+        info!("🧹 Deleted {} backups past retention", removed);
+    }
+    Ok(removed)
+}
+
+/// Next scheduled backup, if enabled and the schedule parses
+pub fn next_run(config: &BackupConfig) -> Option<DateTime<Local>> {
+    if !config.enabled {
+        return None;
+    }
+    let cron = Cron::new(&config.schedule).parse().ok()?;
+    cron.find_next_occurrence(&Local::now(), false).ok()
+}
+
+/// Zip phoenixd's home and the dashboard database into `dest`. The archive
+/// holds the seed, so it is only readable by the user.
+fn write_archive(data_dir: &Path, dest: &Path, meta: &BackupMeta) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options
+        .open(dest)
+        .map_err(|e| format!("Failed to create {:?}: {}", dest, e))?;
+
+    let mut zip = ZipWriter::new(file);
+    let mut files = Vec::new();
+    collect_files(&data_dir.join(PHOENIX_DIR), &mut files)?;
+    let entries =
+        std::fs::read_dir(data_dir).map_err(|e| format!("Failed to read data dir: {}", e))?;
+    files.extend(entries.flatten().map(|entry| entry.path()).filter(|path| {
+        path.is_file()
+            && path
+                .file_name()
+                .is_some_and(|name| DASHBOARD_DB.iter().any(|db| name == *db))
+    }));
+
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .unix_permissions(0o600);
+    let meta = serde_json::to_vec_pretty(meta).map_err(|e| e.to_string())?;
+    zip.start_file(META_FILE, options)
+        .and_then(|_| zip.write_all(&meta).map_err(Into::into))
+        .map_err(|e| format!("Failed to write backup: {}", e))?;
+    for path in files {
+        let Ok(relative) = path.strip_prefix(data_dir) else {
+            continue;
+        };
+        let name = relative.to_string_lossy().replace('\\', "/");
+        let content =
+            std::fs::read(&path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        zip.start_file(name, options)
+            .and_then(|_| zip.write_all(&content).map_err(Into::into))
+            .map_err(|e| format!("Failed to write backup: {}", e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to write backup: {}", e))?;
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read {:?}: {}", dir, e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Unpack `archive` over the data dir. What it replaces is renamed to
+/// `<name>.before-restore-<time>`, never deleted.
+fn extract_archive(data_dir: &Path, archive: &Path) -> Result<(), String> {
+    let file = File::open(archive).map_err(|e| format!("Failed to open {:?}: {}", archive, e))?;
+    let mut zip = ZipArchive::new(file).map_err(|e| format!("Not a backup archive: {}", e))?;
+    if zip
+        .index_for_name(&format!("{}/seed.dat", PHOENIX_DIR))
+        .is_none()
+    {
+        return Err("The archive doesn't contain a phoenixd seed".to_string());
+    }
+
+    // Unpack next to the data first, so a broken archive changes nothing
+    let staging = data_dir.join(".restore");
+    let _ = std::fs::remove_dir_all(&staging);
+    for i in 0..zip.len() {
+        let mut entry = zip
+            .by_index(i)
+            .map_err(|e| format!("Failed to read backup: {}", e))?;
+        let Some(relative) = entry.enclosed_name() else {
+            continue;
+        };
+        let allowed = relative.starts_with(PHOENIX_DIR)
+            || DASHBOARD_DB.iter().any(|db| relative == Path::new(db));
+        if !allowed || entry.is_dir() {
+            continue;
+        }
+        let target = staging.join(&relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        // The seed among them; staging starts empty, so every file is new
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut out = options
+            .open(&target)
+            .map_err(|e| format!("Failed to create {:?}: {}", target, e))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("Failed to extract {:?}: {}", relative, e))?;
+    }
+
+    let suffix = format!(".before-restore-{}", Local::now().format(TIMESTAMP));
+    let staged =
+        std::fs::read_dir(&staging).map_err(|e| format!("Failed to read {:?}: {}", staging, e))?;
+    let mut replaced: Vec<PathBuf> = staged
+        .flatten()
+        .map(|entry| entry.file_name().into())
+        .collect();
+    // A stale WAL of the current database must not be replayed into the restored one
+    for db in DASHBOARD_DB.map(PathBuf::from) {
+        if !replaced.contains(&db) {
+            replaced.push(db);
+        }
+    }
+    for name in &replaced {
+        let current = data_dir.join(name);
+        if current.exists() {
+            let mut aside = current.clone().into_os_string();
+            aside.push(&suffix);
+            std::fs::rename(&current, &aside)
+                .map_err(|e| format!("Failed to move {:?} aside: {}", current, e))?;
+        }
+        let staged = staging.join(name);
+        if staged.exists() {
+            std::fs::rename(&staged, &current)
+                .map_err(|e| format!("Failed to restore {:?}: {}", name, e))?;
+        }
+    }
+    let _ = std::fs::remove_dir_all(&staging);
+    Ok(())
+}
+
+/// Back up while phoenixd and the backend are briefly stopped, so their
+/// databases are consistent on disk. Refuses while payments are in flight.
+pub fn create(app: &AppHandle, dest: &Path) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let client = PhoenixdClient::new(&state.data_dir);
+    if tauri::async_runtime::block_on(client.has_pending_operations()).unwrap_or(false) {
+        return Err("A payment or channel operation is in flight, try again shortly".to_string());
+    }
+
+    info!("💾 Backing up to {:?}", dest);
+    let meta = BackupMeta {
+        created_at: Local::now().to_rfc3339(),
+        chain: state.config.lock().unwrap().phoenixd.chain.clone(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_service("backend")?;
+    pm.stop_service("phoenixd")?;
+    let result = write_archive(&state.data_dir, dest, &meta);
+    if result.is_err() {
+        let _ = std::fs::remove_file(dest);
+    }
+    for name in ["phoenixd", "backend"] {
+        if let Err(e) = pm.start_service(name) {
+            error!("Failed to start {} after backup: {}", name, e);
+        }
+    }
+    drop(pm);
+
+    audit::record(&state.data_dir, "backup.create", dest.to_string_lossy());
+    result
+}
+
+/// Archives made before the metadata was added can't be checked and pass
+fn check_chain(archive: &Path, chain: &str) -> Result<(), String> {
+    match read_meta(archive) {
+        Some(meta) if meta.chain != chain => Err(format!(
+            "This backup is from {}, but the node runs on {}",
+            meta.chain, chain
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Unpack an archive into a data dir whose services are stopped, e.g.
+/// before phoenixd's first start
+pub fn restore_offline(data_dir: &Path, chain: &str, archive: &Path) -> Result<(), String> {
+    check_chain(archive, chain)?;
+    extract_archive(data_dir, archive)
+}
+
+/// Stop everything, swap in the archive's data and start again
+pub fn restore(app: &AppHandle, archive: &Path) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let chain = state.config.lock().unwrap().phoenixd.chain.clone();
+    check_chain(archive, &chain)?;
+    info!("♻️ Restoring backup {:?}", archive);
+    audit::record(&state.data_dir, "backup.restore", archive.to_string_lossy());
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_all();
+    let result = extract_archive(&state.data_dir, archive);
+    pm.start_all()?;
+    result
+}
+
+/// Take backups on the configured schedule
+pub fn spawn(app: AppHandle) {
+    let read_config = {
+        let app = app.clone();
+        move || {
+            app.state::<AppState>()
+                .config
+                .lock()
+                .unwrap()
+                .backup
+                .clone()
+        }
+    };
+    let config = read_config();
+    if config.enabled && next_run(&config).is_none() {
+        warn!("Invalid backup schedule {:?}", config.schedule);
+    }
+
+    scheduler::run_on_cron(read_config, next_run, move |config, _| {
+        let data_dir = app.state::<AppState>().data_dir.clone();
+        let started = std::time::Instant::now();
+        let client = PhoenixdClient::new(&data_dir);
+        while tauri::async_runtime::block_on(client.has_pending_operations()).unwrap_or(false) {
+            if started.elapsed() + RETRY > WINDOW {
+                warn!("Skipped scheduled backup: operations still in flight");
+                return;
+            }
+            info!("⏳ Payment or channel operation in flight, postponing backup");
+            std::thread::sleep(RETRY);
+        }
+        if let Err(e) = create(&app, &next_archive_path(&data_dir, &config)) {
+            error!("Scheduled backup failed: {}", e);
+            return;
+        }
+        if let Err(e) = prune(&data_dir, &config) {
+            warn!("{}", e);
+        }
+    });
+}
@@ -0,0 +1,220 @@
+use crate::alerts::{self, Alert, AlertKind};
+use crate::cloudflare;
+use crate::i18n::t;
+use crate::notify;
+use crate::process_manager::ServiceState;
+use crate::scheduler;
+use crate::tor::TorManager;
+use crate::AppState;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tracing::{debug, warn};
+
+const STATS_FILE: &str = "bandwidth.json";
+/// Days of per-day totals kept
+const HISTORY_DAYS: usize = 35;
+/// Services that carry traffic to and from the outside
+const EXPOSURE: [&str; 2] = ["tor", "cloudflared"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Traffic {
+    pub received: u64,
+    pub sent: u64,
+}
+
+impl Traffic {
+    pub fn total(&self) -> u64 {
+        self.received + self.sent
+    }
+
+    fn add(&mut self, other: Traffic) {
+        self.received += other.received;
+        self.sent += other.sent;
+    }
+}
+
+/// Bytes per local date (YYYY-MM-DD) and service, persisted as `bandwidth.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BandwidthStats {
+    pub days: BTreeMap<String, BTreeMap<String, Traffic>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BandwidthSummary {
+    pub today: BTreeMap<String, Traffic>,
+    /// The last seven days, today included
+    pub week: BTreeMap<String, Traffic>,
+    pub days: BTreeMap<String, BTreeMap<String, Traffic>>,
+}
+
+impl BandwidthStats {
+    fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(STATS_FILE)
+    }
+
+    pub fn load(data_dir: &Path) -> Self {
+        let path = Self::path(data_dir);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!(
+                    "Invalid bandwidth stats at {:?}, starting over: {}",
+                    path, e
+                );
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize bandwidth stats: {}", e))?;
+        std::fs::write(Self::path(data_dir), content)
+            .map_err(|e| format!("Failed to write bandwidth stats: {}", e))
+    }
+
+    fn record(&mut self, service: &str, traffic: Traffic) {
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        self.days
+            .entry(today)
+            .or_default()
+            .entry(service.to_string())
+            .or_default()
+            .add(traffic);
+        while self.days.len() > HISTORY_DAYS {
+            self.days.pop_first();
+        }
+    }
+
+    /// Per-service totals over the last `days` days, today included
+    fn totals(&self, days: i64) -> BTreeMap<String, Traffic> {
+        let since = (Local::now() - chrono::Duration::days(days - 1))
+            .format("%Y-%m-%d")
+            .to_string();
+        let mut totals: BTreeMap<String, Traffic> = BTreeMap::new();
+        for (_, services) in self.days.range(since..) {
+            for (service, traffic) in services {
+                totals.entry(service.clone()).or_default().add(*traffic);
+            }
+        }
+        totals
+    }
+
+    pub fn summary(&self) -> BandwidthSummary {
+        BandwidthSummary {
+            today: self.totals(1),
+            week: self.totals(7),
+            days: self.days.clone(),
+        }
+    }
+}
+
+fn total(traffic: &BTreeMap<String, Traffic>) -> u64 {
+    traffic.values().map(Traffic::total).sum()
+}
+
+/// Counters since the service started, as (received, sent)
+fn sample(service: &str, data_dir: &Path) -> Result<(u64, u64), String> {
+    match service {
+        "tor" => TorManager::new(data_dir).traffic(),
+        _ => cloudflare::traffic(),
+    }
+}
+
+/// Sample tor's and cloudflared's byte counters every minute, account the
+/// difference to today and alert once per day / week past the thresholds
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        let state = app.state::<AppState>();
+        let mut stats = BandwidthStats::load(&state.data_dir);
+        let mut counters: HashMap<&str, (u64, u64)> = HashMap::new();
+        let mut warned_day = String::new();
+        let mut warned_week = String::new();
+        loop {
+            scheduler::sleep(&app, "bandwidth");
+            let running: Vec<&str> = {
+                let statuses = state.statuses.lock().unwrap();
+                EXPOSURE
+                    .into_iter()
+                    .filter(|name| {
+                        statuses
+                            .iter()
+                            .any(|s| s.name == *name && s.state != ServiceState::Stopped)
+                    })
+                    .collect()
+            };
+            counters.retain(|name, _| running.contains(name));
+
+            let mut changed = false;
+            for service in running {
+                let (received, sent) = match sample(service, &state.data_dir) {
+                    Ok(counter) => counter,
+                    Err(e) => {
+                        debug!("No traffic counters for {}: {}", service, e);
+                        continue;
+                    }
+                };
+                // Counters start over when the service restarts
+                let (last_received, last_sent) = counters
+                    .insert(service, (received, sent))
+                    .filter(|&(r, s)| r <= received && s <= sent)
+                    .unwrap_or_default();
+                let traffic = Traffic {
+                    received: received - last_received,
+                    sent: sent - last_sent,
+                };
+                if traffic.total() > 0 {
+                    stats.record(service, traffic);
+                    changed = true;
+                }
+            }
+            if !changed {
+                continue;
+            }
+            if let Err(e) = stats.save(&state.data_dir) {
+                warn!("{}", e);
+            }
+
+            let config = state.config.lock().unwrap().alerts.clone();
+            let now = Local::now();
+            let checks = [
+                (
+                    config.bandwidth_daily_mb,
+                    1,
+                    now.format("%Y-%m-%d").to_string(),
+                    &mut warned_day,
+                ),
+                (
+                    config.bandwidth_weekly_mb,
+                    7,
+                    now.format("%G-W%V").to_string(),
+                    &mut warned_week,
+                ),
+            ];
+            for (limit_mb, days, period, warned) in checks {
+                let used_mb = total(&stats.totals(days)) / 1024 / 1024;
+                if limit_mb == 0 || used_mb < limit_mb || *warned == period {
+                    continue;
+                }
+                *warned = period;
+                let detail = format!(
+                    "Tor and Cloudflare used {} MB in the last {} day(s), over the {} MB threshold",
+                    used_mb, days, limit_mb
+                );
+                let body = t("notify.bandwidth_body")
+                    .replace("{used}", &used_mb.to_string())
+                    .replace("{limit}", &limit_mb.to_string());
+                notify::native(app.clone())(t("notify.bandwidth_title"), &body);
+                alerts::dispatch(
+                    &config,
+                    Alert::new(AlertKind::BandwidthExceeded, None, detail),
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,138 @@
+use crate::process_manager::ServiceState;
+use crate::AppState;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+const INTERVAL: Duration = Duration::from_secs(30);
+/// Paused on battery, in stop order; phoenixd and the backend keep running
+/// so payments still arrive
+const NON_ESSENTIAL: [&str; 3] = ["cloudflared", "tor", "frontend"];
+
+static SAVING: AtomicBool = AtomicBool::new(false);
+
+/// Whether non-essential services are paused to save battery
+pub fn saving() -> bool {
+    SAVING.load(Ordering::Relaxed)
+}
+
+/// Pause the non-essential services while the computer runs on battery, if
+/// `general.battery_saver` is on, and bring them back on AC power
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut paused: Vec<&'static str> = Vec::new();
+        loop {
+            std::thread::sleep(INTERVAL);
+            let state = app.state::<AppState>();
+            let (enabled, tor_needed) = {
+                let config = state.config.lock().unwrap();
+                (config.general.battery_saver, config.tor.node_traffic)
+            };
+            let save = enabled && on_battery().unwrap_or(false);
+            if save == saving() {
+                continue;
+            }
+
+            let mut pm = state.process_manager.lock().unwrap();
+            if save {
+                let running: Vec<&'static str> = pm
+                    .service_statuses()
+                    .into_iter()
+                    .filter(|s| s.state != ServiceState::Stopped)
+                    .map(|s| s.name)
+                    .collect();
+                paused = NON_ESSENTIAL
+                    .into_iter()
+                    .filter(|name| running.contains(name))
+                    // phoenixd's connections go through tor
+                    .filter(|name| *name != "tor" || !tor_needed)
+                    .collect();
+                info!("🔋 On battery, pausing {:?}", paused);
+                for name in &paused {
+                    if let Err(e) = pm.stop_service(name) {
+                        warn!("{}", e);
+                    }
+                }
+            } else {
+                info!("🔌 Back on AC power, resuming {:?}", paused);
+                for name in paused.drain(..).rev() {
+                    if let Err(e) = pm.start_service(name) {
+                        warn!("{}", e);
+                    }
+                }
+            }
+            SAVING.store(save, Ordering::Relaxed);
+
+            let services = pm.service_statuses();
+            drop(pm);
+            crate::refresh_tray_menu(&app, &services);
+        }
+    });
+}
+
+/// None when there is no battery or the OS doesn't say
+#[cfg(target_os = "linux")]
+pub fn on_battery() -> Option<bool> {
+    let read = |path: std::path::PathBuf| {
+        std::fs::read_to_string(path)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default()
+    };
+    let supplies: Vec<std::path::PathBuf> = std::fs::read_dir("/sys/class/power_supply")
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+
+    let mains: Vec<&std::path::PathBuf> = supplies
+        .iter()
+        .filter(|supply| read(supply.join("type")) == "Mains")
+        .collect();
+    if !mains.is_empty() {
+        return Some(
+            !mains
+                .iter()
+                .any(|supply| read(supply.join("online")) == "1"),
+        );
+    }
+    supplies
+        .iter()
+        .find(|supply| read(supply.join("type")) == "Battery")
+        .map(|battery| read(battery.join("status")) == "Discharging")
+}
+
+#[cfg(target_os = "macos")]
+pub fn on_battery() -> Option<bool> {
+    let output = std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .ok()?;
+    let output = String::from_utf8_lossy(&output.stdout);
+    if output.contains("'Battery Power'") {
+        Some(true)
+    } else if output.contains("'AC Power'") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(windows)]
+pub fn on_battery() -> Option<bool> {
+    use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+        return None;
+    }
+    match status.ACLineStatus {
+        0 => Some(true),
+        1 => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub fn on_battery() -> Option<bool> {
+    None
+}
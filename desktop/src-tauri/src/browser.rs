@@ -0,0 +1,246 @@
+use crate::config::FrontendConfig;
+use crate::health_checks;
+use crate::i18n::t;
+use crate::notify;
+use crate::process_manager;
+use crate::token;
+use crate::AppState;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Url, WebviewUrl, WebviewWindowBuilder};
+use tracing::{info, warn};
+
+/// How the dashboard and other links are opened
+pub const OPEN_WITH: [&str; 4] = ["default", "browser", "app", "window"];
+/// Label of the embedded dashboard window
+const WINDOW: &str = "dashboard";
+/// Stop waiting for a dashboard that doesn't come up after this long
+const READY_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Set while a deferred open waits for the dashboard
+static WAITING: AtomicBool = AtomicBool::new(false);
+/// Page the deferred open lands on, the last one asked for
+static PENDING_PAGE: Mutex<String> = Mutex::new(String::new());
+
+/// Chromium-based browsers that understand `--app=<url>`, most common first
+fn app_mode_candidates() -> Vec<PathBuf> {
+    if cfg!(target_os = "macos") {
+        [
+            "Google Chrome",
+            "Chromium",
+            "Microsoft Edge",
+            "Brave Browser",
+        ]
+        .iter()
+        .map(|name| {
+            PathBuf::from(format!(
+                "/Applications/{}.app/Contents/MacOS/{}",
+                name, name
+            ))
+        })
+        .collect()
+    } else if cfg!(target_os = "windows") {
+        let program_files = std::env::var("ProgramFiles").unwrap_or_default();
+        let program_files_x86 = std::env::var("ProgramFiles(x86)").unwrap_or_default();
+        [
+            (&program_files, r"Google\Chrome\Application\chrome.exe"),
+            (&program_files_x86, r"Google\Chrome\Application\chrome.exe"),
+            (&program_files_x86, r"Microsoft\Edge\Application\msedge.exe"),
+            (&program_files, r"Microsoft\Edge\Application\msedge.exe"),
+        ]
+        .iter()
+        .filter(|(dir, _)| !dir.is_empty())
+        .map(|(dir, exe)| PathBuf::from(dir).join(exe))
+        .collect()
+    } else {
+        [
+            "google-chrome",
+            "chromium",
+            "chromium-browser",
+            "microsoft-edge",
+            "brave-browser",
+        ]
+        .iter()
+        .filter_map(|name| which::which(name).ok())
+        .collect()
+    }
+}
+
+fn browser_binary(config: &FrontendConfig) -> Result<PathBuf, String> {
+    if !config.browser.is_empty() {
+        return Ok(PathBuf::from(&config.browser));
+    }
+    if config.open_with == "app" {
+        if let Some(found) = app_mode_candidates().into_iter().find(|path| path.exists()) {
+            return Ok(found);
+        }
+        return Err("No Chromium-based browser found for app mode".to_string());
+    }
+    Err("No browser set".to_string())
+}
+
+fn open_with(app: &AppHandle, config: &FrontendConfig, url: &str) -> Result<(), String> {
+    match config.open_with.as_str() {
+        "browser" | "app" => {
+            let binary = browser_binary(config)?;
+            let target = if config.open_with == "app" {
+                format!("--app={}", url)
+            } else {
+                url.to_string()
+            };
+            Command::new(&binary)
+                .args(&config.browser_args)
+                .arg(target)
+                .spawn()
+                .map_err(|e| format!("Failed to start {:?}: {}", binary, e))?;
+            Ok(())
+        }
+        "window" => {
+            let url: Url = url
+                .parse()
+                .map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+            if let Some(window) = app.get_webview_window(WINDOW) {
+                window
+                    .navigate(url)
+                    .map_err(|e| format!("Failed to open {}: {}", WINDOW, e))?;
+                let _ = window.show();
+                let _ = window.set_focus();
+                return Ok(());
+            }
+            WebviewWindowBuilder::new(app, WINDOW, WebviewUrl::External(url))
+                .title("Phoenixd Dashboard")
+                .inner_size(1200.0, 800.0)
+                .build()
+                .map_err(|e| format!("Failed to open {} window: {}", WINDOW, e))?;
+            Ok(())
+        }
+        _ => open::that(url).map_err(|e| format!("Failed to open {}: {}", url, e)),
+    }
+}
+
+/// Whether the local service serving the dashboard answers. An external UI
+/// isn't ours to check.
+fn dashboard_ready(app: &AppHandle) -> bool {
+    let Some(state) = app.try_state::<AppState>() else {
+        return true;
+    };
+    let config = state.config.lock().unwrap().clone();
+    let service = if config.frontend.enabled {
+        "frontend"
+    } else if config.frontend.external_url.is_empty() {
+        "backend"
+    } else {
+        return true;
+    };
+    config
+        .bind
+        .service_addr(service)
+        .is_none_or(process_manager::port_open)
+        && health_checks::verdict(service) != Some(false)
+}
+
+/// Open the dashboard, or, while it is still starting, say so and open it
+/// the moment it answers instead of showing a dead page
+pub fn open_dashboard(app: &AppHandle) {
+    open_page(app, "");
+}
+
+/// Open a dashboard page such as `receive` ("" is the home page) once the
+/// dashboard answers, and tell the webview with an `open-page` event
+pub fn open_page(app: &AppHandle, page: &str) {
+    if app.try_state::<AppState>().is_none() {
+        return;
+    }
+    if dashboard_ready(app) {
+        land_on(app, page);
+        return;
+    }
+    *PENDING_PAGE.lock().unwrap() = page.to_string();
+    if WAITING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    info!("⏳ Dashboard not ready yet, opening it once it answers");
+    let notify = notify::native(app.clone());
+    notify(
+        t("notify.dashboard_starting_title"),
+        t("notify.dashboard_starting_body"),
+    );
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        while !dashboard_ready(&app) && started.elapsed() < READY_TIMEOUT {
+            std::thread::sleep(Duration::from_millis(500));
+        }
+        WAITING.store(false, Ordering::SeqCst);
+        let page = std::mem::take(&mut *PENDING_PAGE.lock().unwrap());
+        if dashboard_ready(&app) {
+            land_on(&app, &page);
+        } else {
+            warn!("Dashboard still not answering after {:?}", READY_TIMEOUT);
+            notify(
+                t("notify.dashboard_starting_title"),
+                t("notify.dashboard_not_ready"),
+            );
+        }
+    });
+}
+
+/// Whether a deferred open is waiting for the dashboard to answer
+pub fn opening() -> bool {
+    WAITING.load(Ordering::SeqCst)
+}
+
+fn land_on(app: &AppHandle, page: &str) {
+    let dashboard_url = app
+        .state::<AppState>()
+        .process_manager
+        .lock()
+        .unwrap()
+        .dashboard_url();
+    if page.is_empty() {
+        open(app, &dashboard_url);
+        return;
+    }
+    let url = format!("{}/{}", dashboard_url.trim_end_matches('/'), page);
+    info!("🧭 Opening the dashboard on /{}", page);
+    let _ = app.emit("open-page", serde_json::json!({ "page": page, "url": url }));
+    open(app, &url);
+}
+
+/// `url` through the backend's pair page when it is on the local dashboard,
+/// which the backend only serves to browsers holding its token cookie
+fn signed_in(app: &AppHandle, url: &str) -> String {
+    let Some(state) = app.try_state::<AppState>() else {
+        return url.to_string();
+    };
+    let backend = state.config.lock().unwrap().bind.backend_addr();
+    let (dashboard, backend_url) = {
+        let pm = state.process_manager.lock().unwrap();
+        (pm.dashboard_url(), pm.backend_url())
+    };
+    if !url.starts_with(&dashboard) {
+        return url.to_string();
+    }
+    token::pair_url(&state.data_dir, backend, &backend_url, url).unwrap_or_else(|e| {
+        warn!("{}, opening the dashboard without signing in", e);
+        url.to_string()
+    })
+}
+
+/// Open a dashboard URL the way `frontend.open_with` says, falling back to
+/// the default browser when that doesn't work
+pub fn open(app: &AppHandle, url: &str) {
+    let config = app
+        .try_state::<AppState>()
+        .map(|state| state.config.lock().unwrap().frontend.clone())
+        .unwrap_or_default();
+    let url = &signed_in(app, url);
+    if let Err(e) = open_with(app, &config, url) {
+        warn!("{}, using the default browser", e);
+        let _ = open::that(url);
+    }
+}
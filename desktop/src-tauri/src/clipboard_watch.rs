@@ -0,0 +1,63 @@
+use crate::deeplink;
+use crate::i18n::t;
+use crate::notify::Notifier;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tracing::info;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Opt-in watcher that offers to pay invoices copied anywhere on the system.
+/// Desktop notifications don't report clicks, so the detected payment is
+/// kept until it's picked from the tray menu.
+pub struct ClipboardWatcher {
+    stop: Arc<AtomicBool>,
+    detected: Arc<Mutex<Option<String>>>,
+}
+
+impl ClipboardWatcher {
+    pub fn start(app: AppHandle, notifier: Notifier) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let detected = Arc::new(Mutex::new(None));
+
+        let thread_stop = Arc::clone(&stop);
+        let thread_detected = Arc::clone(&detected);
+        std::thread::spawn(move || {
+            // Whatever is on the clipboard already was not copied just now
+            let mut last = app.clipboard().read_text().unwrap_or_default();
+
+            while !thread_stop.load(Ordering::Relaxed) {
+                std::thread::sleep(POLL_INTERVAL);
+
+                let Ok(text) = app.clipboard().read_text() else {
+                    continue;
+                };
+                if text == last {
+                    continue;
+                }
+                last = text;
+
+                if let Some(uri) = deeplink::detect_payment(&last) {
+                    info!("⚡ Lightning payment request detected on the clipboard");
+                    *thread_detected.lock().unwrap() = Some(uri);
+                    notifier(t("notify.invoice_title"), t("notify.invoice_body"));
+                }
+            }
+        });
+
+        info!("📋 Watching the clipboard for lightning invoices");
+        Self { stop, detected }
+    }
+
+    /// Most recently detected payment link, cleared once taken
+    pub fn take_detected(&self) -> Option<String> {
+        self.detected.lock().unwrap().take()
+    }
+
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
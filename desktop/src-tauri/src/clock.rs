@@ -0,0 +1,169 @@
+use crate::i18n::t;
+use crate::{network, notify, scheduler, AppState};
+use serde::Serialize;
+use std::net::UdpSocket;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+/// Tried in turn; one answering is enough
+const SERVERS: [&str; 3] = [
+    "time.cloudflare.com:123",
+    "pool.ntp.org:123",
+    "time.google.com:123",
+];
+const TIMEOUT: Duration = Duration::from_secs(3);
+/// Seconds from 1900, where NTP time starts, to 1970
+const NTP_EPOCH_OFFSET: f64 = 2_208_988_800.0;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockSkew {
+    /// How far ahead (positive) or behind the system clock is
+    pub offset_ms: i64,
+    pub server: String,
+    pub checked_at: String,
+}
+
+impl ClockSkew {
+    /// Whether the offset is past `threshold_secs` (0 never is)
+    pub fn exceeds(&self, threshold_secs: u64) -> bool {
+        threshold_secs > 0 && self.offset_ms.unsigned_abs() > threshold_secs * 1000
+    }
+
+    pub fn offset_secs(&self) -> i64 {
+        self.offset_ms / 1000
+    }
+}
+
+static LAST: Mutex<Option<ClockSkew>> = Mutex::new(None);
+
+pub fn last() -> Option<ClockSkew> {
+    LAST.lock().unwrap().clone()
+}
+
+fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+fn to_ntp(unix: f64) -> [u8; 8] {
+    let ntp = unix + NTP_EPOCH_OFFSET;
+    let secs = ntp.trunc() as u32;
+    let fraction = (ntp.fract() * 4_294_967_296.0) as u32;
+    let mut bytes = [0u8; 8];
+    bytes[..4].copy_from_slice(&secs.to_be_bytes());
+    bytes[4..].copy_from_slice(&fraction.to_be_bytes());
+    bytes
+}
+
+fn from_ntp(bytes: &[u8]) -> f64 {
+    let secs = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as f64;
+    secs + fraction / 4_294_967_296.0 - NTP_EPOCH_OFFSET
+}
+
+/// The system clock's offset from `server` in seconds, over SNTP
+fn query(server: &str) -> Result<f64, String> {
+    let socket =
+        UdpSocket::bind("0.0.0.0:0").map_err(|e| format!("Failed to open a UDP socket: {}", e))?;
+    socket
+        .set_read_timeout(Some(TIMEOUT))
+        .map_err(|e| format!("Failed to set a timeout: {}", e))?;
+    socket
+        .connect(server)
+        .map_err(|e| format!("Failed to reach {}: {}", server, e))?;
+
+    // Version 3, client mode
+    let mut request = [0u8; 48];
+    request[0] = 0x1b;
+    let sent = now();
+    let origin = to_ntp(sent);
+    request[40..48].copy_from_slice(&origin);
+    socket
+        .send(&request)
+        .map_err(|e| format!("Failed to query {}: {}", server, e))?;
+    let mut response = [0u8; 48];
+    let read = socket
+        .recv(&mut response)
+        .map_err(|e| format!("{} did not answer: {}", server, e))?;
+    let received = now();
+
+    // Stratum 0 is a "kiss of death", e.g. rate limiting
+    if read < 48 || response[24..32] != origin || response[1] == 0 {
+        return Err(format!("Invalid answer from {}", server));
+    }
+    let server_received = from_ntp(&response[32..40]);
+    let server_sent = from_ntp(&response[40..48]);
+    Ok(((server_received - sent) + (server_sent - received)) / 2.0)
+}
+
+/// Ask the NTP servers in turn how far off the system clock is
+pub fn measure() -> Result<ClockSkew, String> {
+    let mut errors = Vec::new();
+    for server in SERVERS {
+        match query(server) {
+            Ok(offset) => {
+                let skew = ClockSkew {
+                    // The clock is ahead when the servers are behind it
+                    offset_ms: (-offset * 1000.0).round() as i64,
+                    server: server.trim_end_matches(":123").to_string(),
+                    checked_at: chrono::Local::now().to_rfc3339(),
+                };
+                *LAST.lock().unwrap() = Some(skew.clone());
+                return Ok(skew);
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+    Err(format!("No time server answered: {}", errors.join("; ")))
+}
+
+/// Measure, warning and notifying when the clock drifts past
+/// `alerts.clock_skew_secs` and refreshing the tray when that changes
+pub fn check(app: &AppHandle) -> Result<ClockSkew, String> {
+    let state = app.state::<AppState>();
+    let threshold = state.config.lock().unwrap().alerts.clock_skew_secs;
+    let was_skewed = last().is_some_and(|skew| skew.exceeds(threshold));
+    let skew = measure()?;
+    let skewed = skew.exceeds(threshold);
+    if skewed {
+        warn!(
+            "⏰ System clock is off by {} ms ({})",
+            skew.offset_ms, skew.server
+        );
+    }
+    if skewed && !was_skewed {
+        let body = t("notify.clock_skew_body").replace("{secs}", &skew.offset_secs().to_string());
+        notify::native(app.clone())(t("notify.clock_skew_title"), &body);
+    } else if was_skewed && !skewed {
+        info!("⏰ System clock is back in sync");
+    }
+    if skewed != was_skewed {
+        let services = state.statuses.lock().unwrap().clone();
+        crate::refresh_tray_menu(app, &services);
+    }
+    Ok(skew)
+}
+
+/// Compare the system clock with NTP time every so often
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let threshold = app
+            .state::<AppState>()
+            .config
+            .lock()
+            .unwrap()
+            .alerts
+            .clock_skew_secs;
+        if threshold > 0 && network::is_online() {
+            if let Err(e) = check(&app) {
+                warn!("{}", e);
+            }
+        }
+        scheduler::sleep(&app, "clock");
+    });
+}
@@ -0,0 +1,269 @@
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use tracing::info;
+
+/// Where cloudflared serves its Prometheus metrics
+pub const METRICS_PORT: u16 = 9065;
+
+/// Where a named tunnel ended up after the setup wizard
+#[derive(Debug, Clone, Serialize)]
+pub struct TunnelInfo {
+    pub name: String,
+    pub id: String,
+    pub hostname: String,
+}
+
+/// Sets up named tunnels; the running tunnel itself is a ProcessManager child
+pub struct CloudflareManager {
+    resource_dir: PathBuf,
+    cloudflared_home: PathBuf,
+}
+
+impl CloudflareManager {
+    pub fn new(resource_dir: PathBuf, data_dir: &Path) -> Self {
+        Self {
+            resource_dir,
+            cloudflared_home: data_dir.join("cloudflared"),
+        }
+    }
+
+    /// cloudflared keeps its state in `$HOME/.cloudflared`; like phoenixd we
+    /// point HOME at data_dir so nothing leaks into the user's own setup
+    fn state_dir(&self) -> PathBuf {
+        self.cloudflared_home.join(".cloudflared")
+    }
+
+    fn cert_path(&self) -> PathBuf {
+        self.state_dir().join("cert.pem")
+    }
+
+    fn credentials_path(&self) -> PathBuf {
+        self.state_dir().join("tunnel.json")
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.state_dir().join("config.yml")
+    }
+
+    pub fn is_logged_in(&self) -> bool {
+        self.cert_path().exists()
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.config_path().exists() && self.credentials_path().exists()
+    }
+
+    fn find_cloudflared_binary(&self) -> Result<PathBuf, String> {
+        let binary_name = if cfg!(target_os = "windows") {
+            "cloudflared.exe"
+        } else {
+            "cloudflared"
+        };
+
+        let bundled = self.resource_dir.join("binaries").join(binary_name);
+        if bundled.exists() {
+            return Ok(bundled);
+        }
+
+        which::which("cloudflared").map_err(|_| {
+            "cloudflared not found. Please install cloudflared or include it in the app bundle."
+                .to_string()
+        })
+    }
+
+    fn command(&self) -> Result<Command, String> {
+        std::fs::create_dir_all(self.state_dir())
+            .map_err(|e| format!("Failed to create cloudflared dir: {}", e))?;
+
+        let mut cmd = Command::new(self.find_cloudflared_binary()?);
+        cmd.env("HOME", &self.cloudflared_home)
+            .env("USERPROFILE", &self.cloudflared_home);
+        Ok(cmd)
+    }
+
+    /// Runs `cloudflared tunnel login`, which opens the browser and blocks
+    /// until the user has authorized a zone
+    pub fn login(&self) -> Result<(), String> {
+        if self.is_logged_in() {
+            return Ok(());
+        }
+
+        info!("☁️ Waiting for Cloudflare login in the browser...");
+        let output = self
+            .command()?
+            .args(["tunnel", "login"])
+            .output()
+            .map_err(|e| format!("Failed to run cloudflared login: {}", e))?;
+
+        if !output.status.success() || !self.is_logged_in() {
+            return Err(format!(
+                "Cloudflare login failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Create a named tunnel, route `hostname` to it and write the local config
+    pub fn create_tunnel(
+        &self,
+        name: &str,
+        hostname: &str,
+        dashboard: SocketAddr,
+    ) -> Result<TunnelInfo, String> {
+        if !self.is_logged_in() {
+            return Err("Log in to Cloudflare before creating a tunnel".to_string());
+        }
+
+        info!("☁️ Creating tunnel '{}'...", name);
+        let output = self
+            .command()?
+            .args(["tunnel", "create", "--credentials-file"])
+            .arg(self.credentials_path())
+            .arg(name)
+            .output()
+            .map_err(|e| format!("Failed to create tunnel: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to create tunnel: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let id = parse_tunnel_id(&stdout)
+            .or_else(|| parse_tunnel_id(&stderr))
+            .ok_or_else(|| "Could not determine the new tunnel's id".to_string())?;
+
+        info!("☁️ Routing {} to tunnel '{}'...", hostname, name);
+        let output = self
+            .command()?
+            .args(["tunnel", "route", "dns", name, hostname])
+            .output()
+            .map_err(|e| format!("Failed to route DNS: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to route {}: {}",
+                hostname,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        self.write_config(&id, hostname, dashboard)?;
+
+        Ok(TunnelInfo {
+            name: name.to_string(),
+            id,
+            hostname: hostname.to_string(),
+        })
+    }
+
+    /// (Re)write the ingress config, e.g. when the local address the tunnel
+    /// should forward to changes
+    pub fn write_config(
+        &self,
+        id: &str,
+        hostname: &str,
+        dashboard: SocketAddr,
+    ) -> Result<(), String> {
+        let config = format!(
+            "tunnel: {}\ncredentials-file: {}\ningress:\n  - hostname: {}\n    service: http://{}\n  - service: http_status:404\n",
+            id,
+            self.credentials_path().display(),
+            hostname,
+            dashboard
+        );
+        std::fs::write(self.config_path(), config)
+            .map_err(|e| format!("Failed to write cloudflared config: {}", e))
+    }
+
+    /// Command that runs the configured tunnel in the foreground
+    pub fn tunnel_command(&self, name: &str) -> Result<Command, String> {
+        if !self.is_configured() {
+            return Err("Cloudflare tunnel is not set up yet".to_string());
+        }
+
+        let mut cmd = self.command()?;
+        cmd.arg("tunnel")
+            .args(["--metrics", &metrics_addr()])
+            .arg("--config")
+            .arg(self.config_path())
+            .args(["run", name])
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        Ok(cmd)
+    }
+
+    /// Command for an account-less quick tunnel; cloudflared prints the
+    /// assigned trycloudflare.com URL on stderr
+    pub fn quick_tunnel_command(&self, dashboard: SocketAddr) -> Result<Command, String> {
+        let mut cmd = self.command()?;
+        cmd.args([
+            "tunnel",
+            "--metrics",
+            &metrics_addr(),
+            "--no-autoupdate",
+            "--url",
+        ])
+        .arg(format!("http://{}", dashboard))
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+        Ok(cmd)
+    }
+}
+
+fn metrics_addr() -> String {
+    format!("127.0.0.1:{}", METRICS_PORT)
+}
+
+/// Bytes the running tunnel received from and sent to Cloudflare's edge,
+/// summed over its QUIC connections since cloudflared started
+pub fn traffic() -> Result<(u64, u64), String> {
+    let body = ureq::get(&format!("http://{}/metrics", metrics_addr()))
+        .timeout(Duration::from_secs(5))
+        .call()
+        .map_err(|e| format!("cloudflared metrics not reachable: {}", e))?
+        .into_string()
+        .map_err(|e| format!("Invalid cloudflared metrics: {}", e))?;
+
+    let sum = |name: &str| -> u64 {
+        body.lines()
+            .filter(|line| {
+                line.strip_prefix(name)
+                    .is_some_and(|rest| rest.starts_with(['{', ' ']))
+            })
+            .filter_map(|line| line.rsplit(' ').next()?.parse::<f64>().ok())
+            .sum::<f64>() as u64
+    };
+    Ok((
+        sum("quic_client_receive_bytes"),
+        sum("quic_client_sent_bytes"),
+    ))
+}
+
+/// Find the public URL in a line of quick tunnel output
+pub fn parse_quick_tunnel_url(line: &str) -> Option<String> {
+    line.split(|c: char| c.is_whitespace() || c == '|')
+        .find(|word| word.starts_with("https://") && word.ends_with(".trycloudflare.com"))
+        .map(String::from)
+}
+
+/// Pull the tunnel UUID out of `cloudflared tunnel create` output
+fn parse_tunnel_id(output: &str) -> Option<String> {
+    output
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|word| word.trim_matches(|c: char| !c.is_ascii_hexdigit() && c != '-'))
+        .find(|word| {
+            word.len() == 36
+                && word.chars().filter(|&c| c == '-').count() == 4
+                && word.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+        })
+        .map(String::from)
+}
@@ -0,0 +1,2214 @@
+use crate::access;
+use crate::access_guard::BlockedClient;
+use crate::access_links::{self, AccessLink};
+use crate::alerts;
+use crate::audit::{self, AuditEntry};
+use crate::background::{self, BackgroundServiceStatus};
+use crate::backup::{self, BackupInfo};
+use crate::bandwidth::{BandwidthStats, BandwidthSummary};
+use crate::clipboard_watch::ClipboardWatcher;
+use crate::clock::{self, ClockSkew};
+use crate::cloudflare::TunnelInfo;
+use crate::config::{
+    self, AppConfig, BindConfig, DigestConfig, ExtraService, HealthCheck, HealthConfig, NodeConfig,
+    NwcConfig, PollingConfig, QuietHoursConfig,
+};
+use crate::crash;
+use crate::credentials;
+use crate::database::{self, OptimizeReport};
+use crate::dialogs;
+use crate::digest::{self, Digest};
+use crate::docker_import::{self, ComposeProject, ImportReport};
+use crate::footprint::{self, Footprint};
+use crate::health_checks;
+use crate::i18n::{self, t};
+use crate::journal::{self, JournalEvent};
+use crate::lan;
+use crate::liquidity::{self, LiquidityPolicy};
+use crate::logs;
+use crate::metrics::MetricsServer;
+use crate::node_info::{self, NodeInfo};
+use crate::notify;
+use crate::nwc::{self, Pairing};
+use crate::onboarding::{self, Onboarding, Step};
+use crate::operations::{self, Operation};
+use crate::os_auth;
+use crate::payments_export::{self, ExportRange};
+use crate::phoenixd_api::{self, PhoenixdClient};
+use crate::phoenixd_update::{self, UpdateStatus};
+use crate::plan::Plan;
+use crate::process_manager::{NodeStatus, ServiceStatus};
+use crate::profile::StartupProfile;
+use crate::proxy::Access;
+use crate::redact;
+use crate::resources::{self, Component};
+use crate::safe_mode::{self, SafeMode};
+use crate::scheduler;
+use crate::secrets;
+use crate::selftest::{self, SelfTestReport};
+use crate::store_connect::{self, StoreConnection};
+use crate::tailscale;
+use crate::tls::TlsFiles;
+use crate::token;
+use crate::tor::{self, TorClientAuth, TorManager};
+use crate::tor_health::{self, TorHealth};
+use crate::uptime::UptimeStats;
+use crate::AppState;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tracing::{info, warn};
+
+#[tauri::command]
+pub fn get_status(state: State<'_, AppState>) -> serde_json::Value {
+    let mut status = state.process_manager.lock().unwrap().get_status();
+    redact::scrub_json(&mut status);
+    status
+}
+
+#[tauri::command(async)]
+pub fn set_tor_enabled(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.tor.enabled = enabled;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "mode.tor", on_off(enabled));
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    pm.restart_tor()
+}
+
+/// Route phoenixd's peer connections through tor; phoenixd restarts so
+/// the change applies
+#[tauri::command(async)]
+pub fn set_tor_node_traffic(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.tor.node_traffic = enabled;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "mode.tor_node_traffic", on_off(enabled));
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    pm.restart_tor()?;
+    let result = pm.restart_service("phoenixd");
+    crate::update_tray_tooltip(&app, &pm);
+    result
+}
+
+#[tauri::command]
+pub fn tor_list_clients(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    TorManager::new(&state.data_dir).list_clients()
+}
+
+/// Authorize a new client. The private key is only returned here (and optionally
+/// written to `export_path`), so it has to be handed to the device right away.
+#[tauri::command(async)]
+pub fn tor_add_client(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    name: String,
+    export_path: Option<PathBuf>,
+) -> Result<TorClientAuth, String> {
+    os_auth::require(
+        &app,
+        &state.data_dir,
+        "tor.client_add",
+        t("auth.export_key"),
+    )?;
+    let auth = TorManager::new(&state.data_dir).add_client(&name)?;
+    audit::record(&state.data_dir, "tor.client_add", &name);
+
+    if let Some(path) = export_path {
+        tor::export_private_key(&auth, &path)?;
+    }
+
+    state.process_manager.lock().unwrap().restart_tor()?;
+    Ok(auth)
+}
+
+#[tauri::command(async)]
+pub fn tor_remove_client(state: State<'_, AppState>, name: String) -> Result<(), String> {
+    TorManager::new(&state.data_dir).remove_client(&name)?;
+    audit::record(&state.data_dir, "tor.client_remove", &name);
+    state.process_manager.lock().unwrap().restart_tor()
+}
+
+/// Switch tor to fresh circuits without restarting it
+#[tauri::command(async)]
+pub fn tor_new_identity(state: State<'_, AppState>) -> Result<(), String> {
+    TorManager::new(&state.data_dir).new_identity()?;
+    audit::record(&state.data_dir, "tor.new_identity", "");
+    Ok(())
+}
+
+/// Result of the periodic onion service self-check
+#[tauri::command]
+pub fn get_tor_health() -> TorHealth {
+    tor_health::status()
+}
+
+fn emit_cloudflare_progress(app: &AppHandle, step: &str, status: &str, message: &str) {
+    let _ = app.emit(
+        "cloudflare-setup-progress",
+        json!({ "step": step, "status": status, "message": message }),
+    );
+}
+
+#[tauri::command]
+pub fn cloudflare_status(state: State<'_, AppState>) -> serde_json::Value {
+    let config = state.config.lock().unwrap().cloudflare.clone();
+    let running = state
+        .process_manager
+        .lock()
+        .unwrap()
+        .is_cloudflared_running();
+
+    json!({
+        "logged_in": state.cloudflare.is_logged_in(),
+        "configured": state.cloudflare.is_configured(),
+        "running": running,
+        "enabled": config.enabled,
+        "tunnel_name": config.tunnel_name,
+        "hostname": config.hostname,
+    })
+}
+
+/// Step 1: authorize cloudflared against a Cloudflare zone (opens the browser)
+#[tauri::command(async)]
+pub fn cloudflare_login(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    emit_cloudflare_progress(
+        &app,
+        "login",
+        "running",
+        "Waiting for Cloudflare login in the browser",
+    );
+    let result = state.cloudflare.login();
+
+    match &result {
+        Ok(()) => emit_cloudflare_progress(&app, "login", "done", "Logged in to Cloudflare"),
+        Err(e) => emit_cloudflare_progress(&app, "login", "failed", e),
+    }
+    result
+}
+
+/// Step 2: create the named tunnel, route the hostname and persist it in config
+#[tauri::command(async)]
+pub fn cloudflare_create_tunnel(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    name: String,
+    hostname: String,
+) -> Result<TunnelInfo, String> {
+    emit_cloudflare_progress(&app, "create", "running", "Creating tunnel");
+    let dashboard = state.process_manager.lock().unwrap().tunnel_addr();
+    let result = state.cloudflare.create_tunnel(&name, &hostname, dashboard);
+
+    let info = match result {
+        Ok(info) => info,
+        Err(e) => {
+            emit_cloudflare_progress(&app, "create", "failed", &e);
+            return Err(e);
+        }
+    };
+
+    let mut config = state.config.lock().unwrap();
+    config.cloudflare.tunnel_name = info.name.clone();
+    config.cloudflare.tunnel_id = info.id.clone();
+    config.cloudflare.hostname = info.hostname.clone();
+    config.save(&state.data_dir)?;
+
+    emit_cloudflare_progress(
+        &app,
+        "create",
+        "done",
+        &format!("Tunnel routed to {}", info.hostname),
+    );
+    Ok(info)
+}
+
+/// Step 3: start the tunnel and keep it enabled across restarts
+#[tauri::command(async)]
+pub fn cloudflare_start_tunnel(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        if config.cloudflare.tunnel_name.is_empty() {
+            return Err("Cloudflare tunnel is not set up yet".to_string());
+        }
+        config.cloudflare.enabled = true;
+        config.cloudflare.quick = false;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+
+    emit_cloudflare_progress(&app, "start", "running", "Starting tunnel");
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    if let Err(e) = pm.start_cloudflared() {
+        emit_cloudflare_progress(&app, "start", "failed", &e);
+        return Err(e);
+    }
+
+    emit_cloudflare_progress(&app, "start", "done", "Tunnel is running");
+    Ok(())
+}
+
+/// Start a temporary trycloudflare.com tunnel, no Cloudflare account needed
+#[tauri::command(async)]
+pub fn cloudflare_start_quick_tunnel(state: State<'_, AppState>) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.cloudflare.enabled = true;
+        config.cloudflare.quick = true;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_cloudflared();
+    pm.set_config(config);
+    pm.start_cloudflared()
+}
+
+#[tauri::command]
+pub fn get_public_url(state: State<'_, AppState>) -> Option<String> {
+    state.process_manager.lock().unwrap().get_public_url()
+}
+
+#[tauri::command]
+pub fn cloudflare_stop_tunnel(state: State<'_, AppState>) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.cloudflare.enabled = false;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    pm.stop_cloudflared();
+    Ok(())
+}
+
+/// Toggle LAN access; services are restarted so the new bind address applies
+#[tauri::command(async)]
+pub fn set_lan_enabled(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.lan.enabled = enabled;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "mode.lan", on_off(enabled));
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_all();
+    pm.set_config(config);
+    let result = pm.start_all();
+    crate::update_tray_tooltip(&app, &pm);
+    result
+}
+
+#[tauri::command]
+pub fn get_bind_addresses(state: State<'_, AppState>) -> BindConfig {
+    state.config.lock().unwrap().bind.clone()
+}
+
+/// Change the addresses phoenixd, the backend and the frontend listen on;
+/// services are restarted so they apply
+#[tauri::command(async)]
+pub fn set_bind_addresses(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    bind: BindConfig,
+) -> Result<(), String> {
+    bind.validate()?;
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.bind = bind;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(
+        &state.data_dir,
+        "mode.bind",
+        format!(
+            "phoenixd={} backend={} frontend={}",
+            config.bind.phoenixd, config.bind.backend, config.bind.frontend
+        ),
+    );
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_all();
+    pm.set_config(config);
+    let result = pm.start_all();
+    crate::update_tray_tooltip(&app, &pm);
+    result
+}
+
+#[tauri::command]
+pub fn set_mdns_enabled(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<Option<String>, String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.lan.mdns = enabled;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    pm.update_mdns();
+
+    crate::update_tray_tooltip(&app, &pm);
+    Ok(pm.mdns_hostname())
+}
+
+#[tauri::command]
+pub fn get_mdns_hostname(state: State<'_, AppState>) -> Option<String> {
+    state.process_manager.lock().unwrap().mdns_hostname()
+}
+
+#[tauri::command]
+pub fn get_lan_access_url(state: State<'_, AppState>) -> Option<String> {
+    state.process_manager.lock().unwrap().lan_access_url()
+}
+
+#[tauri::command(async)]
+pub fn regenerate_pairing_token(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    os_auth::require(
+        &app,
+        &state.data_dir,
+        "credentials.pairing_token",
+        t("auth.rotate_credentials"),
+    )?;
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.regenerate_pairing_token()?;
+    audit::record(&state.data_dir, "credentials.pairing_token", "regenerated");
+    Ok(pm.lan_access_url())
+}
+
+/// Replace every access credential the app manages in one go; see
+/// `credentials::rotate_all`
+#[tauri::command(async)]
+pub fn rotate_all_credentials(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    os_auth::require(
+        &app,
+        &state.data_dir,
+        "credentials.rotate_all",
+        t("auth.rotate_credentials"),
+    )?;
+    let rotated = credentials::rotate_all(&state)?;
+    let services = state.process_manager.lock().unwrap().service_statuses();
+    crate::refresh_tray_menu(&app, &services);
+    Ok(rotated)
+}
+
+#[tauri::command]
+pub fn show_lan_pairing(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let url = state
+        .process_manager
+        .lock()
+        .unwrap()
+        .lan_access_url()
+        .ok_or_else(|| "LAN access is disabled or no network is available".to_string())?;
+    lan::show_pairing_window(&app, &url)
+}
+
+#[tauri::command]
+pub fn set_https_enabled(state: State<'_, AppState>, enabled: bool) -> Result<String, String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.https.enabled = enabled;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "mode.https", on_off(enabled));
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    if enabled {
+        pm.start_https()?;
+    } else {
+        pm.stop_https();
+    }
+    Ok(pm.dashboard_url())
+}
+
+/// Trust the local CA system-wide (the OS prompts for confirmation)
+#[tauri::command(async)]
+pub fn install_tls_ca(state: State<'_, AppState>) -> Result<(), String> {
+    audit::record(&state.data_dir, "tls.install_ca", "");
+    TlsFiles::new(&state.data_dir).install_ca()
+}
+
+#[tauri::command]
+pub fn get_dashboard_url(state: State<'_, AppState>) -> String {
+    state.process_manager.lock().unwrap().dashboard_url()
+}
+
+/// Local URL of the backend API, for UIs hosted elsewhere
+#[tauri::command]
+pub fn get_backend_url(state: State<'_, AppState>) -> String {
+    state.process_manager.lock().unwrap().backend_url()
+}
+
+/// Toggle API-only mode: without the bundled frontend the tray opens
+/// `external_url` (if any) and the backend accepts requests from it
+#[tauri::command(async)]
+pub fn set_frontend_enabled(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+    external_url: Option<String>,
+) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.frontend.enabled = enabled;
+        if let Some(url) = external_url {
+            config.frontend.external_url = url;
+        }
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "mode.frontend", on_off(enabled));
+
+    // The backend's allowed origin and the TLS routes depend on it too
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_all();
+    pm.set_config(config);
+    pm.start_all()?;
+    let services = pm.service_statuses();
+    drop(pm);
+
+    crate::refresh_tray_menu(&app, &services);
+    Ok(())
+}
+
+/// Origins the backend accepts browser requests from, as passed in
+/// `ALLOWED_ORIGINS`
+#[tauri::command]
+pub fn get_allowed_origins(state: State<'_, AppState>) -> Vec<String> {
+    state.process_manager.lock().unwrap().allowed_origins()
+}
+
+/// Set the origins allowed besides the exposures'; the backend restarts
+/// if that changes its list
+#[tauri::command(async)]
+pub fn set_allowed_origins(
+    state: State<'_, AppState>,
+    origins: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        let mut backend = config.backend.clone();
+        backend.allowed_origins = origins
+            .iter()
+            .map(|origin| origin.trim().trim_end_matches('/').to_string())
+            .collect();
+        backend.validate()?;
+        config.backend = backend;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(
+        &state.data_dir,
+        "backend.allowed_origins",
+        config.backend.allowed_origins.join(","),
+    );
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    pm.sync_backend_origins()?;
+    Ok(pm.allowed_origins())
+}
+
+/// Choose how the dashboard opens: `default`, `browser`, `app` or `window`
+#[tauri::command]
+pub fn set_open_with(
+    state: State<'_, AppState>,
+    open_with: String,
+    browser: String,
+    browser_args: Vec<String>,
+) -> Result<(), String> {
+    let mut config = state.config.lock().unwrap();
+    let mut frontend = config.frontend.clone();
+    frontend.open_with = open_with;
+    frontend.browser = browser;
+    frontend.browser_args = browser_args;
+    frontend.validate()?;
+    config.frontend = frontend;
+    config.save(&state.data_dir)
+}
+
+/// Enable the authenticating reverse proxy. Services are restarted so tor,
+/// cloudflared and LAN access are re-pointed at it.
+#[tauri::command(async)]
+pub fn set_proxy_enabled(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.proxy.enabled = enabled;
+        if enabled && config.proxy.password.is_empty() {
+            config.proxy.password = crate::token::generate()?;
+            audit::record(&state.data_dir, "credentials.proxy_password", "generated");
+        }
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_all();
+    pm.set_config(config);
+    pm.start_all()
+}
+
+#[tauri::command]
+pub fn get_proxy_credentials(state: State<'_, AppState>) -> serde_json::Value {
+    let proxy = state.config.lock().unwrap().proxy.clone();
+    json!({
+        "enabled": proxy.enabled,
+        "username": proxy.username,
+        "password": proxy.password,
+        "readOnlyPassword": proxy.read_only_password,
+        "port": proxy.port,
+    })
+}
+
+/// Issue (or, with `enabled` false, revoke) the proxy's read-only password
+/// for dashboards exposed over Tor or a tunnel. Returns the new password.
+#[tauri::command(async)]
+pub fn set_proxy_read_only(
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<Option<String>, String> {
+    credentials::set_read_only_password(&state, enabled)
+}
+
+#[tauri::command]
+pub fn list_blocked_clients(state: State<'_, AppState>) -> Vec<BlockedClient> {
+    state.process_manager.lock().unwrap().blocked_clients()
+}
+
+/// Unblock a single client, or every blocked client when `ip` is omitted
+#[tauri::command]
+pub fn unblock_client(state: State<'_, AppState>, ip: Option<String>) -> Result<bool, String> {
+    let ip = ip
+        .map(|ip| {
+            ip.parse()
+                .map_err(|_| format!("Invalid IP address: {}", ip))
+        })
+        .transpose()?;
+    audit::record(
+        &state.data_dir,
+        "access.unblock",
+        ip.map_or("all".to_string(), |ip: std::net::IpAddr| ip.to_string()),
+    );
+    Ok(state.process_manager.lock().unwrap().unblock_client(ip))
+}
+
+#[tauri::command(async)]
+pub fn tailscale_status(state: State<'_, AppState>) -> serde_json::Value {
+    let status = tailscale::status();
+    let enabled = state.config.lock().unwrap().tailscale.enabled;
+
+    json!({
+        "installed": status.installed,
+        "running": status.running,
+        "ip": status.ip,
+        "dns_name": status.dns_name,
+        "enabled": enabled,
+        "url": state.process_manager.lock().unwrap().tailscale_url(),
+    })
+}
+
+/// "Share on Tailscale": serve the dashboard on the tailnet via MagicDNS
+#[tauri::command(async)]
+pub fn set_tailscale_enabled(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<Option<String>, String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.tailscale.enabled = enabled;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    let result = if enabled {
+        pm.start_tailscale()
+    } else {
+        pm.stop_tailscale();
+        Ok(())
+    };
+    crate::update_tray_tooltip(&app, &pm);
+    result.map(|_| pm.tailscale_url())
+}
+
+/// Opt in or out of the clipboard invoice watcher
+#[tauri::command]
+pub fn set_clipboard_watch_enabled(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    {
+        let mut config = state.config.lock().unwrap();
+        config.clipboard.watch_invoices = enabled;
+        config.save(&state.data_dir)?;
+    }
+
+    let mut watcher = state.clipboard_watcher.lock().unwrap();
+    if let Some(running) = watcher.take() {
+        running.stop();
+    }
+    if enabled {
+        *watcher = Some(ClipboardWatcher::start(app.clone(), notify::native(app)));
+    }
+    Ok(())
+}
+
+/// Change the global quick receive shortcut; an empty string disables it
+#[tauri::command]
+pub fn set_quick_receive_shortcut(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    shortcut: String,
+) -> Result<(), String> {
+    crate::register_quick_receive_shortcut(&app, &shortcut)?;
+
+    let mut config = state.config.lock().unwrap();
+    config.quick_receive.shortcut = shortcut;
+    config.save(&state.data_dir)
+}
+
+#[tauri::command]
+pub fn list_services(state: State<'_, AppState>) -> Vec<ServiceStatus> {
+    state.statuses.lock().unwrap().clone()
+}
+
+/// Start, stop or restart a single service
+#[tauri::command(async)]
+pub fn control_service(
+    state: State<'_, AppState>,
+    name: String,
+    action: String,
+) -> Result<Vec<ServiceStatus>, String> {
+    audit::record(&state.data_dir, &format!("service.{}", action), &name);
+    let mut pm = state.process_manager.lock().unwrap();
+    match action.as_str() {
+        "start" => pm.start_service(&name)?,
+        "stop" => pm.stop_service(&name)?,
+        "restart" => pm.restart_service(&name)?,
+        _ => return Err(format!("Unknown action: {}", action)),
+    }
+    Ok(pm.service_statuses())
+}
+
+#[tauri::command]
+pub fn show_access_qr_codes(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let urls = state.process_manager.lock().unwrap().access_urls();
+    access::show_access_window(&app, &urls)
+}
+
+/// Issue a temporary access link valid for `hours`, read-only unless
+/// `access` says otherwise; returns it with the URL for each exposure going
+/// through the reverse proxy
+#[tauri::command(async)]
+pub fn create_access_link(
+    app: AppHandle,
+    hours: u64,
+    label: Option<String>,
+    access: Option<Access>,
+) -> Result<serde_json::Value, String> {
+    let state = app.state::<AppState>();
+    // Fail before issuing a link nobody can use
+    access_links::urls(&app, "")?;
+    let (link, token) = access_links::create(
+        &state.data_dir,
+        hours,
+        label.as_deref().unwrap_or_default(),
+        access.unwrap_or_default(),
+    )?;
+    let urls: BTreeMap<&str, String> = access_links::urls(&app, &token)?.into_iter().collect();
+    Ok(json!({ "link": link, "urls": urls }))
+}
+
+#[tauri::command]
+pub fn list_access_links(state: State<'_, AppState>) -> Vec<AccessLink> {
+    access_links::list(&state.data_dir)
+}
+
+#[tauri::command]
+pub fn revoke_access_link(state: State<'_, AppState>, id: String) -> Result<bool, String> {
+    access_links::revoke(&state.data_dir, &id)
+}
+
+#[tauri::command]
+pub fn show_access_links(app: AppHandle) -> Result<(), String> {
+    access_links::show_window(&app)
+}
+
+#[tauri::command]
+pub fn get_liquidity_policy(state: State<'_, AppState>) -> LiquidityPolicy {
+    liquidity::read(&state.data_dir.join(".phoenix").join("phoenix.conf"))
+}
+
+/// Save the policy to phoenix.conf and restart phoenixd once no payment is
+/// in flight
+#[tauri::command(async)]
+pub fn set_liquidity_policy(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    policy: LiquidityPolicy,
+) -> Result<(), String> {
+    let phoenix_conf = state.data_dir.join(".phoenix").join("phoenix.conf");
+    if liquidity::read(&phoenix_conf) == policy {
+        return Ok(());
+    }
+    liquidity::write(&phoenix_conf, &policy)?;
+    audit::record(
+        &state.data_dir,
+        "liquidity.policy",
+        format!(
+            "auto-liquidity={} max-fee-credit={} max-relative-fee-percent={}",
+            policy.auto_liquidity, policy.max_fee_credit, policy.max_relative_fee_percent
+        ),
+    );
+    info!("💧 Liquidity policy updated, restarting phoenixd when idle");
+    operations::run(&app, "liquidity", "Apply liquidity policy", |op| {
+        liquidity::restart_when_idle(&state.process_manager, &state.data_dir, op)
+    })
+}
+
+/// Zip recent logs for a support request, with known secrets scrubbed
+pub fn export_logs_to(app: &AppHandle, dest: &Path) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let proxy = state.config.lock().unwrap().proxy.clone();
+    let secrets: Vec<String> = [
+        token::api_token(&state.data_dir),
+        Some(phoenixd_api::read_password(
+            &state.data_dir.join(".phoenix").join("phoenix.conf"),
+        )),
+        Some(proxy.password),
+        Some(proxy.read_only_password).filter(|password| !password.is_empty()),
+        secrets::get(secrets::TELEGRAM_BOT_TOKEN),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    // Clock skew explains many channel failures
+    let skew = match clock::last() {
+        Some(skew) => Ok(skew),
+        None => clock::measure(),
+    };
+    let clock = match skew {
+        Ok(skew) => serde_json::to_string_pretty(&skew).unwrap_or_default(),
+        Err(e) => json!({ "error": e }).to_string(),
+    };
+
+    audit::record(&state.data_dir, "logs.export", dest.to_string_lossy());
+    let footprint = serde_json::to_string_pretty(&footprint::current()).unwrap_or_default();
+    logs::export_zip(
+        &state.data_dir,
+        dest,
+        &secrets,
+        &[("clock.json", clock), ("footprint.json", footprint)],
+    )?;
+    info!("📦 Exported logs to {:?}", dest);
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn export_logs(app: AppHandle, dest: PathBuf) -> Result<(), String> {
+    operations::run(&app, "export_logs", "Export logs", |_| {
+        export_logs_to(&app, &dest)
+    })
+}
+
+/// Write the payment history straight from phoenixd to `dest` as `csv` or
+/// `json`, so it can be had without the web UI. Returns how many payments
+/// were written.
+#[tauri::command(async)]
+pub fn export_payments(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    range: Option<ExportRange>,
+    format: String,
+    dest: PathBuf,
+) -> Result<usize, String> {
+    audit::record(&state.data_dir, "payments.export", dest.to_string_lossy());
+    let client = PhoenixdClient::active(&state.data_dir);
+    operations::run(&app, "export_payments", "Export payments", |op| {
+        payments_export::export(&client, &range.unwrap_or_default(), &format, &dest, op)
+    })
+}
+
+/// The docker-compose deployment in `dir`, if it has phoenixd data to import
+#[tauri::command]
+pub fn detect_docker_deployment(dir: PathBuf) -> Result<ComposeProject, String> {
+    docker_import::detect(&dir)
+}
+
+/// Move the node of the docker-compose deployment in `dir` into the app,
+/// checking its balance and history survive the move
+#[tauri::command(async)]
+pub fn import_docker_deployment(app: AppHandle, dir: PathBuf) -> Result<ImportReport, String> {
+    let data_dir = app.state::<AppState>().data_dir.clone();
+    os_auth::require(&app, &data_dir, "docker.import", t("auth.import_docker"))?;
+    operations::run(&app, "docker_import", "Import Docker deployment", |op| {
+        docker_import::import(&app, &dir, op)
+    })
+}
+
+/// Open one of the user-facing locations in the file manager or default editor:
+/// `data`, `logs`, `config` (config.toml) or `phoenixd_config` (phoenix.conf)
+pub fn open_data_path(data_dir: &Path, config: &AppConfig, kind: &str) -> Result<(), String> {
+    let path = match kind {
+        "data" => data_dir.to_path_buf(),
+        "logs" => {
+            let dir = logs::log_dir(data_dir);
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create log dir: {}", e))?;
+            dir
+        }
+        "config" => {
+            // Write the defaults out so there is something to edit
+            if !AppConfig::path(data_dir).exists() {
+                config.save(data_dir)?;
+            }
+            AppConfig::path(data_dir)
+        }
+        "phoenixd_config" => data_dir.join(".phoenix").join("phoenix.conf"),
+        _ => return Err(format!("Unknown location: {}", kind)),
+    };
+
+    if !path.exists() {
+        return Err(format!("{:?} does not exist yet", path));
+    }
+    open::that(&path).map_err(|e| format!("Failed to open {:?}: {}", path, e))
+}
+
+#[tauri::command]
+pub fn open_location(state: State<'_, AppState>, kind: String) -> Result<(), String> {
+    let config = state.config.lock().unwrap().clone();
+    open_data_path(&state.data_dir, &config, &kind)
+}
+
+#[tauri::command]
+pub fn get_language(state: State<'_, AppState>) -> serde_json::Value {
+    json!({
+        "configured": state.config.lock().unwrap().general.language,
+        "current": i18n::current(),
+        "available": i18n::LANGUAGES,
+    })
+}
+
+/// Change the shell language; an empty string follows the OS locale again
+#[tauri::command]
+pub fn set_language(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    language: String,
+) -> Result<(), String> {
+    {
+        let mut config = state.config.lock().unwrap();
+        config.general.language = language.clone();
+        config.save(&state.data_dir)?;
+    }
+    i18n::init(&language);
+
+    let services = state.process_manager.lock().unwrap().service_statuses();
+    crate::refresh_tray_menu(&app, &services);
+    Ok(())
+}
+
+/// Phase timings of the last startup, for triaging slow-start reports
+#[tauri::command]
+pub fn get_startup_profile(state: State<'_, AppState>) -> StartupProfile {
+    state.process_manager.lock().unwrap().startup_profile()
+}
+
+/// Consent toggle for sending crash reports. Turning it on also sends the
+/// reports collected while it was off.
+#[tauri::command]
+pub fn set_crash_reporting_enabled(
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    let crash_reports = {
+        let mut config = state.config.lock().unwrap();
+        config.crash_reports.enabled = enabled;
+        config.save(&state.data_dir)?;
+        config.crash_reports.clone()
+    };
+
+    crash::set_consent(&crash_reports);
+    if enabled {
+        let data_dir = state.data_dir.clone();
+        std::thread::spawn(move || crash::upload_pending(&data_dir));
+    }
+    Ok(())
+}
+
+/// Crash reports saved locally and not sent (yet), so users can look at or attach them
+#[tauri::command]
+pub fn list_crash_reports(state: State<'_, AppState>) -> Vec<PathBuf> {
+    crash::pending(&state.data_dir)
+}
+
+#[tauri::command]
+pub fn set_metrics_enabled(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    let metrics = {
+        let mut config = state.config.lock().unwrap();
+        config.metrics.enabled = enabled;
+        config.save(&state.data_dir)?;
+        config.metrics.clone()
+    };
+
+    let mut server = state.metrics.lock().unwrap();
+    if let Some(running) = server.take() {
+        running.stop();
+    }
+    if enabled {
+        *server = Some(MetricsServer::start(app, &metrics.bind, metrics.port)?);
+    }
+    Ok(())
+}
+
+/// Webhook the watchdog posts alerts to; an empty URL turns it off
+#[tauri::command]
+pub fn set_alert_webhook(state: State<'_, AppState>, url: String) -> Result<(), String> {
+    let url = url.trim().to_string();
+    if !url.is_empty() && !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err("Webhook URL must start with http:// or https://".to_string());
+    }
+
+    let mut config = state.config.lock().unwrap();
+    config.alerts.webhook_url = url;
+    config.save(&state.data_dir)
+}
+
+/// Daily and weekly traffic thresholds for tor and cloudflared in MB; 0 turns one off
+#[tauri::command]
+pub fn set_bandwidth_thresholds(
+    state: State<'_, AppState>,
+    daily_mb: u64,
+    weekly_mb: u64,
+) -> Result<(), String> {
+    let mut config = state.config.lock().unwrap();
+    config.alerts.bandwidth_daily_mb = daily_mb;
+    config.alerts.bandwidth_weekly_mb = weekly_mb;
+    config.save(&state.data_dir)
+}
+
+/// Configure Telegram alerts. A new bot token goes to the OS keychain;
+/// `None` keeps the stored one. Enabling sends a test message right away.
+#[tauri::command(async)]
+pub fn set_telegram_alerts(
+    state: State<'_, AppState>,
+    enabled: bool,
+    chat_id: String,
+    bot_token: Option<String>,
+) -> Result<(), String> {
+    match bot_token.as_deref().map(str::trim) {
+        Some("") => {
+            secrets::delete(secrets::TELEGRAM_BOT_TOKEN)?;
+            audit::record(&state.data_dir, "credentials.telegram_token", "removed");
+        }
+        Some(token) => {
+            secrets::set(secrets::TELEGRAM_BOT_TOKEN, token)?;
+            audit::record(&state.data_dir, "credentials.telegram_token", "updated");
+        }
+        None => {}
+    }
+
+    if enabled {
+        alerts::send_telegram(chat_id.trim(), "⚡ Phoenixd Dashboard alerts are on")?;
+    }
+
+    let mut config = state.config.lock().unwrap();
+    config.alerts.telegram_enabled = enabled;
+    config.alerts.telegram_chat_id = chat_id.trim().to_string();
+    config.save(&state.data_dir)
+}
+
+#[tauri::command]
+pub fn get_quiet_hours(state: State<'_, AppState>) -> QuietHoursConfig {
+    state.config.lock().unwrap().quiet_hours.clone()
+}
+
+/// Schedule do-not-disturb for notifications; held ones still go to the journal
+#[tauri::command]
+pub fn set_quiet_hours(
+    state: State<'_, AppState>,
+    quiet_hours: QuietHoursConfig,
+) -> Result<(), String> {
+    quiet_hours.validate()?;
+    let mut config = state.config.lock().unwrap();
+    config.quiet_hours = quiet_hours;
+    config.save(&state.data_dir)
+}
+
+/// Uptime, crash counts and per-day availability of every service
+#[tauri::command]
+pub fn get_uptime_stats(state: State<'_, AppState>) -> UptimeStats {
+    state.uptime.lock().unwrap().clone()
+}
+
+/// Node id, version, chain, channels and liquidity of the node the
+/// dashboard shows, asked from phoenixd directly
+#[tauri::command(async)]
+pub fn get_node_info(state: State<'_, AppState>) -> Result<NodeInfo, String> {
+    let client = PhoenixdClient::active(&state.data_dir);
+    tauri::async_runtime::block_on(node_info::fetch(&client))
+}
+
+#[tauri::command(async)]
+pub fn show_node_info(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let info = get_node_info(state)?;
+    node_info::show_window(&app, &info)
+}
+
+/// Running and latest phoenixd version as of the last check
+#[tauri::command]
+pub fn get_phoenixd_update() -> UpdateStatus {
+    phoenixd_update::status()
+}
+
+#[tauri::command(async)]
+pub fn check_phoenixd_update(app: AppHandle) -> Result<UpdateStatus, String> {
+    phoenixd_update::check(&app)
+}
+
+/// System clock offset from NTP time as of the last check
+#[tauri::command]
+pub fn get_clock_skew() -> Option<ClockSkew> {
+    clock::last()
+}
+
+#[tauri::command(async)]
+pub fn check_clock_skew(app: AppHandle) -> Result<ClockSkew, String> {
+    clock::check(&app)
+}
+
+/// Back up, install the latest phoenixd release and verify the node comes
+/// back on it, rolling back otherwise. Returns the new version.
+#[tauri::command(async)]
+pub fn upgrade_phoenixd(app: AppHandle) -> Result<String, String> {
+    operations::run(&app, "phoenixd_upgrade", "Upgrade phoenixd", |op| {
+        phoenixd_update::upgrade(&app, op)
+    })
+}
+
+/// Why the app is in safe mode, or null when it isn't
+#[tauri::command]
+pub fn get_safe_mode(state: State<'_, AppState>) -> Option<SafeMode> {
+    safe_mode::current(&state.data_dir)
+}
+
+#[tauri::command(async)]
+pub fn exit_safe_mode(app: AppHandle) -> Result<(), String> {
+    safe_mode::exit(&app)
+}
+
+/// Move config.toml aside and start over from the defaults. Returns where
+/// the old file went.
+#[tauri::command(async)]
+pub fn reset_config(app: AppHandle) -> Result<PathBuf, String> {
+    safe_mode::reset_config(&app)
+}
+
+/// Bytes moved by tor and cloudflared today, over the last week and per day
+#[tauri::command]
+pub fn get_bandwidth(state: State<'_, AppState>) -> BandwidthSummary {
+    BandwidthStats::load(&state.data_dir).summary()
+}
+
+#[tauri::command]
+pub fn get_onboarding(state: State<'_, AppState>) -> serde_json::Value {
+    let onboarding = Onboarding::load(&state.data_dir);
+    json!({
+        "step": onboarding.step,
+        "restored": onboarding.restored,
+        "chain": state.config.lock().unwrap().phoenixd.chain,
+        "walletExists": onboarding::seed_path(&state.data_dir).exists(),
+    })
+}
+
+#[tauri::command]
+pub fn onboarding_set_network(state: State<'_, AppState>, chain: String) -> Result<(), String> {
+    let mut onboarding = Onboarding::load(&state.data_dir);
+    onboarding.expect(Step::Network)?;
+    if chain != "mainnet" && chain != "testnet" {
+        return Err(format!("Unsupported network: {}", chain));
+    }
+
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.phoenixd.chain = chain;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    state.process_manager.lock().unwrap().set_config(config);
+    onboarding.advance(&state.data_dir)
+}
+
+/// Start phoenixd for the first time, which creates a new seed
+#[tauri::command(async)]
+pub fn onboarding_create_wallet(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut onboarding = Onboarding::load(&state.data_dir);
+    onboarding.expect(Step::Wallet)?;
+
+    operations::run(&app, "wallet", "Create wallet", |op| {
+        start_phoenixd_for_wallet(&state, op)
+    })?;
+    audit::record(&state.data_dir, "wallet.create", "");
+    onboarding.advance(&state.data_dir)
+}
+
+/// Restore from a recovery phrase. With `expected_node_id`, a wallet that
+/// comes up with another node ID is moved aside and the step can be retried.
+/// Returns the restored node ID.
+#[tauri::command(async)]
+pub fn onboarding_restore_wallet(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    mnemonic: String,
+    expected_node_id: Option<String>,
+) -> Result<String, String> {
+    let mut onboarding = Onboarding::load(&state.data_dir);
+    onboarding.expect(Step::Wallet)?;
+
+    onboarding::restore_seed(&state.data_dir, &mnemonic)?;
+    operations::run(&app, "wallet", "Restore wallet", |op| {
+        finish_wallet_restore(
+            &state,
+            &mut onboarding,
+            expected_node_id,
+            "recovery phrase",
+            op,
+        )
+    })
+}
+
+/// Restore from an archive made by `create_backup`, which also brings back
+/// the channel database and the dashboard's data. Returns the restored node ID.
+#[tauri::command(async)]
+pub fn onboarding_restore_archive(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: PathBuf,
+    expected_node_id: Option<String>,
+) -> Result<String, String> {
+    let mut onboarding = Onboarding::load(&state.data_dir);
+    onboarding.expect(Step::Wallet)?;
+    if onboarding::seed_path(&state.data_dir).exists() {
+        return Err("A wallet already exists in this data directory".to_string());
+    }
+
+    let chain = state.config.lock().unwrap().phoenixd.chain.clone();
+    state
+        .process_manager
+        .lock()
+        .unwrap()
+        .stop_service("backend")?;
+    operations::run(&app, "wallet", "Restore wallet archive", |op| {
+        backup::restore_offline(&state.data_dir, &chain, &path)?;
+        op.check()?;
+        finish_wallet_restore(
+            &state,
+            &mut onboarding,
+            expected_node_id,
+            "backup archive",
+            op,
+        )
+    })
+}
+
+fn finish_wallet_restore(
+    state: &State<'_, AppState>,
+    onboarding: &mut Onboarding,
+    expected_node_id: Option<String>,
+    source: &str,
+    op: &operations::Handle,
+) -> Result<String, String> {
+    start_phoenixd_for_wallet(state, op)?;
+    op.progress("Waiting for the node ID");
+    let node_id = wait_for_node_id(state, op)?;
+    if let Some(expected) = expected_node_id.filter(|id| !id.trim().is_empty()) {
+        if !node_id.eq_ignore_ascii_case(expected.trim()) {
+            let mut pm = state.process_manager.lock().unwrap();
+            pm.stop_service("phoenixd")?;
+            pm.set_defer_phoenixd(true);
+            drop(pm);
+            let aside = onboarding::set_aside_wallet(&state.data_dir)?;
+            warn!(
+                "Restored wallet has node ID {}, moved it to {:?}",
+                node_id, aside
+            );
+            return Err(format!(
+                "The restored wallet's node ID is {}, not the expected {}",
+                node_id,
+                expected.trim()
+            ));
+        }
+    }
+
+    audit::record(
+        &state.data_dir,
+        "wallet.restore",
+        format!("{}, node {}", source, node_id),
+    );
+    onboarding.restored = true;
+    onboarding.advance(&state.data_dir)?;
+    Ok(node_id)
+}
+
+/// phoenixd answers a little after it wrote its config
+fn wait_for_node_id(
+    state: &State<'_, AppState>,
+    op: &operations::Handle,
+) -> Result<String, String> {
+    let client = PhoenixdClient::new(&state.data_dir);
+    let started = std::time::Instant::now();
+    loop {
+        match tauri::async_runtime::block_on(client.node_id()) {
+            Ok(node_id) => return Ok(node_id),
+            Err(e) if started.elapsed() > std::time::Duration::from_secs(30) => {
+                return Err(format!("Failed to read the restored node ID: {}", e))
+            }
+            Err(_) => op.sleep(std::time::Duration::from_millis(500))?,
+        }
+    }
+}
+
+/// Start phoenixd, wait until it wrote its seed and API password, then
+/// restart the backend so it picks the password up
+fn start_phoenixd_for_wallet(
+    state: &State<'_, AppState>,
+    op: &operations::Handle,
+) -> Result<(), String> {
+    let phoenix_conf = state.data_dir.join(".phoenix").join("phoenix.conf");
+    {
+        let mut pm = state.process_manager.lock().unwrap();
+        pm.set_defer_phoenixd(false);
+        pm.start_service("phoenixd")?;
+    }
+
+    let started = std::time::Instant::now();
+    while !(onboarding::seed_path(&state.data_dir).exists() && phoenix_conf.exists()) {
+        if started.elapsed() > std::time::Duration::from_secs(60) {
+            return Err("phoenixd did not create a wallet within a minute".to_string());
+        }
+        op.sleep(std::time::Duration::from_millis(500))?;
+    }
+
+    state
+        .process_manager
+        .lock()
+        .unwrap()
+        .restart_service("backend")
+}
+
+/// Set the dashboard password; `None` leaves the dashboard unprotected
+#[tauri::command(async)]
+pub fn onboarding_set_password(
+    state: State<'_, AppState>,
+    password: Option<String>,
+) -> Result<(), String> {
+    let mut onboarding = Onboarding::load(&state.data_dir);
+    onboarding.expect(Step::Password)?;
+
+    if let Some(password) = password {
+        onboarding::set_dashboard_password(&state.data_dir, &password)?;
+    }
+    onboarding.advance(&state.data_dir)
+}
+
+/// The recovery phrase, only available during the backup step and after
+/// the user confirmed their identity
+#[tauri::command(async)]
+pub fn onboarding_get_seed(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
+    Onboarding::load(&state.data_dir).expect(Step::SeedBackup)?;
+    os_auth::require(&app, &state.data_dir, "seed.reveal", t("auth.reveal_seed"))?;
+    audit::record(&state.data_dir, "seed.reveal", "onboarding backup step");
+    onboarding::read_seed(&state.data_dir)
+}
+
+#[tauri::command]
+pub fn onboarding_confirm_seed_backup(state: State<'_, AppState>) -> Result<(), String> {
+    let mut onboarding = Onboarding::load(&state.data_dir);
+    onboarding.expect(Step::SeedBackup)?;
+    onboarding.advance(&state.data_dir)
+}
+
+/// Last step: optionally expose the dashboard over Tor and/or a quick
+/// Cloudflare tunnel, then finish onboarding
+#[tauri::command(async)]
+pub fn onboarding_set_remote_access(
+    state: State<'_, AppState>,
+    tor: bool,
+    cloudflare: bool,
+) -> Result<(), String> {
+    let mut onboarding = Onboarding::load(&state.data_dir);
+    onboarding.expect(Step::RemoteAccess)?;
+
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.tor.enabled = tor;
+        if cloudflare {
+            config.cloudflare.enabled = true;
+            config.cloudflare.quick = true;
+        }
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    if tor {
+        pm.restart_tor()?;
+    }
+    if cloudflare {
+        pm.start_cloudflared()?;
+    }
+    drop(pm);
+
+    onboarding.advance(&state.data_dir)
+}
+
+#[tauri::command]
+pub fn background_service_status() -> BackgroundServiceStatus {
+    background::status()
+}
+
+/// Hand the services over to a background service that keeps them running
+/// without anyone logged in; the app attaches to it from then on
+#[tauri::command(async)]
+pub fn install_background_service(state: State<'_, AppState>) -> Result<(), String> {
+    audit::record(&state.data_dir, "background_service.install", "");
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_all();
+    if let Err(e) = background::install(&state.data_dir) {
+        pm.start_all()?;
+        return Err(e);
+    }
+    pm.set_attached(true);
+    Ok(())
+}
+
+/// Remove the background service and run the services from the app again
+#[tauri::command(async)]
+pub fn uninstall_background_service(state: State<'_, AppState>) -> Result<(), String> {
+    background::uninstall()?;
+    audit::record(&state.data_dir, "background_service.uninstall", "");
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_attached(false);
+    pm.start_all()
+}
+
+/// Watch-only mode makes the backend refuse every spend. Turning it off
+/// needs a native confirmation, so a page can't do it silently.
+#[tauri::command(async)]
+pub fn set_watch_only(app: AppHandle, enabled: bool) -> Result<(), String> {
+    if !enabled && !dialogs::confirm_spending(&app) {
+        return Err("Leaving watch-only mode was cancelled".to_string());
+    }
+    apply_watch_only(&app, enabled)
+}
+
+/// Re-read a hand-edited config.toml and restart the services on it. An
+/// invalid file is reported and the running services are left alone.
+#[tauri::command(async)]
+pub fn reload_config(app: AppHandle) -> Result<(), String> {
+    apply_config_file(&app)
+}
+
+pub fn apply_config_file(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let config = match AppConfig::check(&state.data_dir) {
+        Ok(config) => config,
+        Err(e) => {
+            warn!("Not reloading the config: {}", e);
+            dialogs::invalid_config(app, &e, state.data_dir.clone());
+            return Err(e);
+        }
+    };
+    audit::record(&state.data_dir, "config.reload", "");
+    redact::register_known(&state.data_dir, &config);
+    *state.config.lock().unwrap() = config.clone();
+
+    let mut pm = state.process_manager.lock().unwrap();
+    info!("🔄 Config reloaded, restarting services...");
+    pm.stop_all();
+    pm.set_config(config);
+    pm.start_all()?;
+    let services = pm.service_statuses();
+    drop(pm);
+    crate::refresh_tray_menu(app, &services);
+    Ok(())
+}
+
+/// Save the flag and restart the backend, which reads it at startup
+pub fn apply_watch_only(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.general.watch_only = enabled;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "mode.watch_only", on_off(enabled));
+
+    let nwc_enabled = config.nwc.enabled;
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    pm.restart_service("backend")?;
+    // The bridge only reads watch-only mode when it starts
+    if nwc_enabled {
+        pm.restart_service("nwc")?;
+    }
+    let services = pm.service_statuses();
+    drop(pm);
+
+    info!("👁️ Watch-only mode {}", if enabled { "on" } else { "off" });
+    crate::refresh_tray_menu(app, &services);
+    Ok(())
+}
+
+/// Stop phoenixd cleanly and keep it stopped, e.g. for an OS upgrade or a
+/// backup; the dashboard stays up with `NODE_PAUSED` set
+#[tauri::command(async)]
+pub fn pause_node(app: AppHandle) -> Result<(), String> {
+    set_node_paused(&app, true)
+}
+
+#[tauri::command(async)]
+pub fn resume_node(app: AppHandle) -> Result<(), String> {
+    set_node_paused(&app, false)
+}
+
+pub fn set_node_paused(app: &AppHandle, paused: bool) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    audit::record(
+        &state.data_dir,
+        if paused { "node.pause" } else { "node.resume" },
+        "",
+    );
+    let mut pm = state.process_manager.lock().unwrap();
+    if paused {
+        pm.pause_node()?;
+    } else {
+        pm.resume_node()?;
+    }
+    let services = pm.service_statuses();
+    crate::update_tray_tooltip(app, &pm);
+    drop(pm);
+
+    let _ = app.emit("node-paused", paused);
+    crate::refresh_tray_menu(app, &services);
+    Ok(())
+}
+
+/// The main phoenixd and every extra node, with their state
+#[tauri::command]
+pub fn list_nodes(state: State<'_, AppState>) -> Vec<NodeStatus> {
+    state.process_manager.lock().unwrap().node_statuses()
+}
+
+/// Add and start an extra phoenixd instance with its own wallet and data
+#[tauri::command(async)]
+pub fn add_node(
+    state: State<'_, AppState>,
+    name: String,
+    chain: String,
+    port: u16,
+) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        let mut nodes = config.nodes.clone();
+        nodes.extra.push(NodeConfig {
+            name: name.clone(),
+            chain,
+            port,
+        });
+        nodes.validate()?;
+        config.nodes = nodes;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "node.add", &name);
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    pm.start_node(&name)
+}
+
+/// Stop an extra node and forget it. Its data directory stays, so adding
+/// it back under the same name brings the wallet back.
+#[tauri::command(async)]
+pub fn remove_node(app: AppHandle, name: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if state.config.lock().unwrap().nodes.active == name {
+        apply_active_node(&app, config::MAIN_NODE)?;
+    }
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.nodes.extra.retain(|node| node.name != name);
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "node.remove", &name);
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_node(&name)?;
+    pm.set_config(config);
+    let services = pm.service_statuses();
+    drop(pm);
+    crate::refresh_tray_menu(&app, &services);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_extra_services(state: State<'_, AppState>) -> Vec<ExtraService> {
+    state.config.lock().unwrap().services.extra.clone()
+}
+
+/// Add a user-defined service, or replace the one with the same name, and
+/// start it if it autostarts
+#[tauri::command(async)]
+pub fn add_extra_service(app: AppHandle, service: ExtraService) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let name = service.name.clone();
+    let autostart = service.autostart;
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        let mut services = config.services.clone();
+        services.extra.retain(|existing| existing.name != name);
+        services.extra.push(service);
+        services.validate()?;
+        config.services = services;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "extra_service.add", &name);
+
+    let mut pm = state.process_manager.lock().unwrap();
+    let _ = pm.stop_service(&name);
+    pm.set_config(config);
+    let result = if autostart {
+        pm.start_service(&name)
+    } else {
+        Ok(())
+    };
+    let services = pm.service_statuses();
+    drop(pm);
+    crate::refresh_tray_menu(&app, &services);
+    result
+}
+
+/// Stop a user-defined service and forget it
+#[tauri::command(async)]
+pub fn remove_extra_service(app: AppHandle, name: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.services.extra.retain(|service| service.name != name);
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "extra_service.remove", &name);
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_service(&name)?;
+    pm.set_config(config);
+    let services = pm.service_statuses();
+    drop(pm);
+    crate::refresh_tray_menu(&app, &services);
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn set_active_node(app: AppHandle, name: String) -> Result<(), String> {
+    apply_active_node(&app, &name)
+}
+
+/// Point the backend, and with it the dashboard, at another node
+pub fn apply_active_node(app: &AppHandle, name: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        let mut nodes = config.nodes.clone();
+        nodes.active = if name == config::MAIN_NODE {
+            String::new()
+        } else {
+            name.to_string()
+        };
+        nodes.validate()?;
+        config.nodes = nodes;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(&state.data_dir, "node.switch", name);
+
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config);
+    pm.restart_service("backend")?;
+    let services = pm.service_statuses();
+    drop(pm);
+
+    info!("⚡ Dashboard now shows node {}", name);
+    crate::refresh_tray_menu(app, &services);
+    Ok(())
+}
+
+/// What the app did on the user's behalf, newest first
+#[tauri::command]
+pub fn get_audit_log(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<AuditEntry>, String> {
+    audit::read(&state.data_dir, limit.unwrap_or(500))
+}
+
+#[tauri::command]
+pub fn get_health_checks(state: State<'_, AppState>) -> Vec<HealthCheck> {
+    state.config.lock().unwrap().health.checks.clone()
+}
+
+/// Poller intervals in seconds, defaults included, and the jitter
+#[tauri::command]
+pub fn get_polling(state: State<'_, AppState>) -> serde_json::Value {
+    let config = state.config.lock().unwrap().polling.clone();
+    serde_json::json!({
+        "intervals": scheduler::intervals(&config),
+        "jitterPercent": config.jitter_percent,
+        "adaptive": config.adaptive,
+        "busyCpuPercent": config.busy_cpu_percent,
+        "throttled": footprint::slowdown() > 1.0,
+    })
+}
+
+/// Activity digest settings, with when the next one goes out
+#[tauri::command]
+pub fn get_digest(state: State<'_, AppState>) -> serde_json::Value {
+    let config = state.config.lock().unwrap().digest.clone();
+    json!({
+        "config": config,
+        "nextRun": digest::next_run(&config).map(|next| next.to_rfc3339()),
+    })
+}
+
+/// Change the digest settings; the scheduler picks them up within a minute
+#[tauri::command]
+pub fn set_digest(state: State<'_, AppState>, digest: DigestConfig) -> Result<(), String> {
+    digest.validate()?;
+    let mut config = state.config.lock().unwrap();
+    config.digest = digest;
+    config.save(&state.data_dir)
+}
+
+/// Send the digest for the period ending now, e.g. to try the channels
+#[tauri::command(async)]
+pub fn send_digest(app: AppHandle) -> Result<Digest, String> {
+    digest::send(&app)
+}
+
+/// The app's own CPU, memory and poller wakeups as of the last sample
+#[tauri::command]
+pub fn get_shell_footprint() -> Footprint {
+    footprint::current()
+}
+
+/// Override poller intervals; pollers pick them up on their next wait
+#[tauri::command]
+pub fn set_polling(state: State<'_, AppState>, polling: PollingConfig) -> Result<(), String> {
+    polling.validate()?;
+    let mut config = state.config.lock().unwrap();
+    config.polling = polling;
+    config.save(&state.data_dir)
+}
+
+#[tauri::command]
+pub fn get_nwc(state: State<'_, AppState>) -> NwcConfig {
+    state.config.lock().unwrap().nwc.clone()
+}
+
+/// Save the NWC settings and restart the bridge on them, or stop it when
+/// it's disabled
+fn save_nwc(app: &AppHandle, nwc: NwcConfig) -> Result<(), String> {
+    nwc.validate()?;
+    let state = app.state::<AppState>();
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.nwc = nwc;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.set_config(config.clone());
+    if config.nwc.enabled {
+        pm.restart_service("nwc")?;
+    } else {
+        pm.stop_service("nwc")?;
+    }
+    let services = pm.service_statuses();
+    drop(pm);
+    crate::refresh_tray_menu(app, &services);
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn set_nwc_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    audit::record(&state.data_dir, "mode.nwc", on_off(enabled));
+    let mut nwc = state.config.lock().unwrap().nwc.clone();
+    nwc.enabled = enabled;
+    save_nwc(&app, nwc)
+}
+
+/// Pair a new Nostr client. Unset fields take the tray's defaults: the
+/// next free name and `default_budget_sat` a month.
+#[tauri::command(async)]
+pub fn create_nwc_connection(
+    app: AppHandle,
+    name: Option<String>,
+    budget_sat: Option<u64>,
+    budget_renewal: Option<String>,
+) -> Result<Pairing, String> {
+    let state = app.state::<AppState>();
+    let mut nwc = state.config.lock().unwrap().nwc.clone();
+    let name = name.unwrap_or_else(|| nwc::next_name(&nwc));
+    let budget_sat = budget_sat.unwrap_or(nwc.default_budget_sat);
+    if budget_sat > 0 {
+        os_auth::require(&app, &state.data_dir, "nwc.pair", t("auth.nwc_pair"))?;
+    }
+    let resource_dir = state
+        .process_manager
+        .lock()
+        .unwrap()
+        .resource_dir()
+        .to_path_buf();
+    let uri = nwc::create_connection(
+        &resource_dir,
+        &mut nwc,
+        &name,
+        budget_sat,
+        budget_renewal.as_deref().unwrap_or("monthly"),
+    )?;
+    audit::record(
+        &state.data_dir,
+        "nwc.pair",
+        format!("{} budget={}", name, budget_sat),
+    );
+    save_nwc(&app, nwc)?;
+    Ok(Pairing { name, uri })
+}
+
+/// Change what a connection may spend; raising it needs the OS login
+#[tauri::command(async)]
+pub fn set_nwc_budget(
+    app: AppHandle,
+    name: String,
+    budget_sat: u64,
+    budget_renewal: String,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut nwc = state.config.lock().unwrap().nwc.clone();
+    let connection = nwc
+        .connections
+        .iter_mut()
+        .find(|connection| connection.name == name)
+        .ok_or_else(|| format!("No NWC connection named '{}'", name))?;
+    if budget_sat > connection.budget_sat {
+        os_auth::require(&app, &state.data_dir, "nwc.budget", t("auth.nwc_pair"))?;
+    }
+    connection.budget_sat = budget_sat;
+    connection.budget_renewal = budget_renewal;
+    audit::record(
+        &state.data_dir,
+        "nwc.budget",
+        format!("{} budget={}", name, budget_sat),
+    );
+    save_nwc(&app, nwc)
+}
+
+/// Revoke a connection; the bridge stops answering its client
+#[tauri::command(async)]
+pub fn remove_nwc_connection(app: AppHandle, name: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut nwc = state.config.lock().unwrap().nwc.clone();
+    let before = nwc.connections.len();
+    nwc.connections.retain(|connection| connection.name != name);
+    if nwc.connections.len() == before {
+        return Err(format!("No NWC connection named '{}'", name));
+    }
+    audit::record(&state.data_dir, "nwc.revoke", &name);
+    save_nwc(&app, nwc)
+}
+
+/// Connection details for a BTCPay Server or LNbits instance: phoenixd's
+/// URL through `exposure` (`local`, `lan` or `tor`) and a receive-only or,
+/// after the OS login, full-access password
+#[tauri::command(async)]
+pub fn store_connection(
+    app: AppHandle,
+    exposure: Option<String>,
+    restricted: bool,
+) -> Result<StoreConnection, String> {
+    let exposure = exposure.unwrap_or_else(|| store_connect::default_exposure(&app).to_string());
+    store_connect::connection(&app, &exposure, restricted)
+}
+
+/// Same as `store_connection`, shown as copyable text and QR codes
+#[tauri::command(async)]
+pub fn show_store_connection(
+    app: AppHandle,
+    exposure: Option<String>,
+    restricted: bool,
+) -> Result<(), String> {
+    let connection = store_connection(app.clone(), exposure, restricted)?;
+    store_connect::show_window(&app, &connection)
+}
+
+/// Restart policy of every service, defaults included
+#[tauri::command]
+pub fn get_restart_policies(state: State<'_, AppState>) -> BTreeMap<String, String> {
+    let config = state.config.lock().unwrap().auto_heal.clone();
+    state
+        .statuses
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|service| {
+            (
+                service.name.to_string(),
+                config.restart_policy(service.name).to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Set whether a service comes back after exiting on its own: `no`,
+/// `on-failure` or `unless-stopped`
+#[tauri::command(async)]
+pub fn set_restart_policy(
+    state: State<'_, AppState>,
+    service: String,
+    policy: String,
+) -> Result<(), String> {
+    let mut auto_heal = state.config.lock().unwrap().auto_heal.clone();
+    auto_heal
+        .restart_policies
+        .insert(service.clone(), policy.clone());
+    auto_heal.validate()?;
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.auto_heal = auto_heal;
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(
+        &state.data_dir,
+        "settings.restart_policy",
+        format!("{}={}", service, policy),
+    );
+    state.process_manager.lock().unwrap().set_config(config);
+    Ok(())
+}
+
+/// Replace the configured health checks; the checker picks them up on its next tick
+#[tauri::command]
+pub fn set_health_checks(
+    state: State<'_, AppState>,
+    checks: Vec<HealthCheck>,
+) -> Result<(), String> {
+    let health = HealthConfig { checks };
+    health.validate()?;
+    let mut config = state.config.lock().unwrap();
+    config.health = health;
+    config.save(&state.data_dir)
+}
+
+/// Run a check once, e.g. before saving it
+#[tauri::command(async)]
+pub fn test_health_check(check: HealthCheck) -> Result<(), String> {
+    health_checks::probe(&check)
+}
+
+/// Lifecycle events after `since` (RFC 3339), optionally only one kind
+/// prefix or service, newest first
+#[tauri::command]
+pub fn get_events(
+    state: State<'_, AppState>,
+    since: Option<String>,
+    filter: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<JournalEvent>, String> {
+    journal::read(
+        &state.data_dir,
+        since.as_deref(),
+        filter.as_deref(),
+        limit.unwrap_or(500),
+    )
+}
+
+/// Back up phoenixd's data and the dashboard database now, to `dest` or the
+/// backup directory; returns the archive path
+#[tauri::command(async)]
+pub fn create_backup(app: AppHandle, dest: Option<PathBuf>) -> Result<PathBuf, String> {
+    let state = app.state::<AppState>();
+    // The archive holds the seed, unencrypted
+    os_auth::require(
+        &app,
+        &state.data_dir,
+        "backup.create",
+        t("auth.create_backup"),
+    )?;
+    let dest = match dest {
+        Some(dest) => dest,
+        None => {
+            let config = state.config.lock().unwrap().backup.clone();
+            backup::next_archive_path(&state.data_dir, &config)
+        }
+    };
+    operations::run(&app, "backup", "Create backup", |_| {
+        backup::create(&app, &dest)
+    })?;
+    Ok(dest)
+}
+
+/// Replace the wallet and dashboard data with a backup's. The current data
+/// is kept next to it, renamed.
+#[tauri::command(async)]
+pub fn restore_backup(app: AppHandle, path: PathBuf) -> Result<(), String> {
+    let data_dir = app.state::<AppState>().data_dir.clone();
+    os_auth::require(&app, &data_dir, "backup.restore", t("auth.restore_backup"))?;
+    operations::run(&app, "restore", "Restore backup", |_| {
+        backup::restore(&app, &path)
+    })
+}
+
+/// Archives in the backup directory, newest first
+#[tauri::command(async)]
+pub fn list_backups(state: State<'_, AppState>) -> Result<Vec<BackupInfo>, String> {
+    let config = state.config.lock().unwrap().backup.clone();
+    backup::list(&state.data_dir, &config)
+}
+
+/// Roll the wallet and dashboard back to one of the backup directory's
+/// archives, by file name as returned by `list_backups`
+#[tauri::command(async)]
+pub fn restore_snapshot(app: AppHandle, name: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().unwrap().backup.clone();
+    let path = backup::find(&state.data_dir, &config, &name)?;
+    os_auth::require(
+        &app,
+        &state.data_dir,
+        "backup.restore",
+        t("auth.restore_backup"),
+    )?;
+    operations::run(&app, "restore", "Restore snapshot", |_| {
+        backup::restore(&app, &path)
+    })
+}
+
+/// Stop all services, keeping their logs and data
+#[tauri::command(async)]
+pub fn services_stop(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    teardown(&app, &state, "stop")
+}
+
+/// Stop all services and clear their logs
+#[tauri::command(async)]
+pub fn services_down(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    teardown(&app, &state, "down")
+}
+
+/// Stop all services, clear their logs and delete the dashboard database.
+/// The wallet stays.
+#[tauri::command(async)]
+pub fn services_purge(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    os_auth::require(
+        &app,
+        &state.data_dir,
+        "services.purge",
+        t("auth.purge_data"),
+    )?;
+    teardown(&app, &state, "purge")
+}
+
+fn teardown(app: &AppHandle, state: &AppState, mode: &str) -> Result<(), String> {
+    audit::record(&state.data_dir, &format!("services.{}", mode), "app");
+    let mut pm = state.process_manager.lock().unwrap();
+    let result = pm.teardown(mode);
+    crate::update_tray_tooltip(app, &pm);
+    result
+}
+
+/// Set what quitting from the tray does: `stop`, `down` or `purge`
+#[tauri::command(async)]
+pub fn set_quit_teardown(app: AppHandle, mode: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if !matches!(mode.as_str(), "stop" | "down" | "purge") {
+        return Err(format!("Unknown teardown mode: {}", mode));
+    }
+    if mode == "purge" {
+        os_auth::require(
+            &app,
+            &state.data_dir,
+            "services.purge",
+            t("auth.purge_data"),
+        )?;
+    }
+    audit::record(&state.data_dir, "settings.quit_teardown", &mode);
+    let mut config = state.config.lock().unwrap();
+    config.general.quit_teardown = mode;
+    config.save(&state.data_dir)
+}
+
+/// Set whether quitting stops phoenixd: `stop`, `keep_node` or `ask`.
+/// Takes effect for phoenixd from its next start.
+#[tauri::command]
+pub fn set_on_quit(state: State<'_, AppState>, mode: String) -> Result<(), String> {
+    if !matches!(mode.as_str(), "stop" | "keep_node" | "ask") {
+        return Err(format!("Unknown quit behavior: {}", mode));
+    }
+    audit::record(&state.data_dir, "settings.on_quit", &mode);
+    let mut config = state.config.lock().unwrap();
+    config.general.on_quit = mode;
+    config.save(&state.data_dir)?;
+    state
+        .process_manager
+        .lock()
+        .unwrap()
+        .set_config(config.clone());
+    Ok(())
+}
+
+/// VACUUM and ANALYZE the dashboard database, briefly stopping the backend
+#[tauri::command(async)]
+pub fn optimize_database(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<OptimizeReport, String> {
+    audit::record(&state.data_dir, "database.optimize", "app");
+    operations::run(&app, "optimize_database", "Optimize database", |_| {
+        let mut pm = state.process_manager.lock().unwrap();
+        database::optimize(&mut pm, &state.data_dir)
+    })
+}
+
+/// Write a compact copy of the dashboard database to `dest`
+#[tauri::command(async)]
+pub fn dump_database(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    dest: PathBuf,
+) -> Result<(), String> {
+    audit::record(&state.data_dir, "database.dump", dest.to_string_lossy());
+    operations::run(&app, "dump_database", "Dump database", |_| {
+        database::dump(&state.data_dir, &dest)
+    })
+}
+
+/// What startup would run for the current config, without running it
+#[tauri::command]
+pub fn get_startup_plan(state: State<'_, AppState>) -> Plan {
+    let config = state.config.lock().unwrap().clone();
+    let pm = state.process_manager.lock().unwrap();
+    Plan::new(&pm, &config, state.data_dir.clone())
+}
+
+/// Bundled components the services need but can't find
+#[tauri::command]
+pub fn check_resources(state: State<'_, AppState>) -> Vec<Component> {
+    let config = state.config.lock().unwrap().clone();
+    let pm = state.process_manager.lock().unwrap();
+    resources::missing(pm.resource_dir(), &state.data_dir, &config)
+}
+
+#[tauri::command(async)]
+pub fn repair_resource(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    component: Component,
+) -> Result<(), String> {
+    operations::run(
+        &app,
+        "repair",
+        format!("Repair {}", component.label()),
+        |_| resources::repair(component, &state.data_dir),
+    )?;
+    journal::record(
+        &state.data_dir,
+        "component.repaired",
+        None,
+        component.label(),
+    );
+    Ok(())
+}
+
+/// Long-running operations in flight, with elapsed time and last step
+#[tauri::command]
+pub fn list_operations() -> Vec<Operation> {
+    operations::list()
+}
+
+/// Stop a running operation at its next safe point
+#[tauri::command]
+pub fn cancel_operation(id: String) -> Result<(), String> {
+    operations::cancel(&id)
+}
+
+/// Run the post-start self-test again
+#[tauri::command(async)]
+pub fn run_self_test(app: AppHandle) -> SelfTestReport {
+    selftest::run_and_report(&app)
+}
+
+/// Result of the last self-test, if one ran
+#[tauri::command]
+pub fn get_self_test() -> Option<SelfTestReport> {
+    selftest::last()
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
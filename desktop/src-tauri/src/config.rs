@@ -0,0 +1,1293 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CONFIG_FILE: &str = "config.toml";
+
+/// User settings persisted as `config.toml` in the app data directory.
+/// Every section defaults so older files keep loading after new keys are added.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub general: GeneralConfig,
+    pub phoenixd: PhoenixdConfig,
+    pub backend: BackendConfig,
+    pub frontend: FrontendConfig,
+    pub tor: TorConfig,
+    pub cloudflare: CloudflareConfig,
+    pub lan: LanConfig,
+    pub https: HttpsConfig,
+    pub proxy: ProxyConfig,
+    pub tailscale: TailscaleConfig,
+    pub clipboard: ClipboardConfig,
+    pub quick_receive: QuickReceiveConfig,
+    pub crash_reports: CrashReportConfig,
+    pub metrics: MetricsConfig,
+    pub alerts: AlertsConfig,
+    pub quiet_hours: QuietHoursConfig,
+    pub maintenance: MaintenanceConfig,
+    pub auto_heal: AutoHealConfig,
+    pub backup: BackupConfig,
+    pub bind: BindConfig,
+    pub nodes: NodesConfig,
+    pub health: HealthConfig,
+    pub services: ServicesConfig,
+    pub operations: OperationsConfig,
+    pub polling: PollingConfig,
+    pub nwc: NwcConfig,
+    pub digest: DigestConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneralConfig {
+    /// Language of the tray and native windows; empty follows the OS locale
+    pub language: String,
+    /// tracing filter for the shell's own log, e.g. `debug` or `info,phoenixd_dashboard::proxy=debug`
+    pub log_level: String,
+    /// Also send lifecycle events to journald / os_log / the Windows Event Log
+    pub os_log: bool,
+    /// Open the dashboard in the browser once startup finishes
+    pub open_dashboard_on_start: bool,
+    /// Tell the backend to refuse every send/spend operation, for monitoring
+    /// from untrusted devices
+    pub watch_only: bool,
+    /// Keep the computer awake while payments or channel operations are in flight
+    pub prevent_sleep: bool,
+    /// On battery, pause the frontend, tor and cloudflared and check on
+    /// services less often
+    pub battery_saver: bool,
+    /// What quitting from the tray does to the services: `stop` keeps logs
+    /// and data, `down` also clears the service logs, `purge` also deletes the
+    /// dashboard database. The wallet is never touched.
+    pub quit_teardown: String,
+    /// Whether quitting stops phoenixd too: `stop`, `keep_node` (it keeps
+    /// receiving payments and the next launch re-attaches) or `ask`
+    pub on_quit: String,
+    /// Take JSON-RPC calls from local scripts on `control.sock` in the data
+    /// dir (a named pipe on Windows)
+    pub control_socket: bool,
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            language: String::new(),
+            log_level: "info".to_string(),
+            os_log: true,
+            open_dashboard_on_start: true,
+            watch_only: false,
+            prevent_sleep: false,
+            battery_saver: false,
+            quit_teardown: "stop".to_string(),
+            on_quit: "stop".to_string(),
+            control_socket: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhoenixdConfig {
+    /// `mainnet` or `testnet`, passed to phoenixd as `--chain`
+    pub chain: String,
+    /// Serve a built-in fake node instead of phoenixd, for demos and UI work
+    pub mock: bool,
+    /// Notify about peer, channel, liquidity and swap-in events in phoenixd's log
+    pub notify_events: bool,
+    /// Check twice a day for a newer phoenixd release
+    pub update_check: bool,
+}
+
+impl Default for PhoenixdConfig {
+    fn default() -> Self {
+        Self {
+            chain: "mainnet".to_string(),
+            mock: false,
+            notify_events: false,
+            update_check: true,
+        }
+    }
+}
+
+/// Name the main node goes by next to the extra ones
+pub const MAIN_NODE: &str = "main";
+
+/// phoenixd instances run next to the main one, e.g. to keep business and
+/// personal funds apart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodesConfig {
+    /// Node the dashboard shows; empty for the main one
+    pub active: String,
+    pub extra: Vec<NodeConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeConfig {
+    /// Lowercase letters, digits and dashes; also its directory under
+    /// `<data dir>/nodes`
+    pub name: String,
+    #[serde(default = "default_chain")]
+    pub chain: String,
+    /// phoenixd's API port, on the same address as the main node's
+    pub port: u16,
+}
+
+fn default_chain() -> String {
+    "mainnet".to_string()
+}
+
+impl NodeConfig {
+    /// phoenixd's HOME; its data ends up in `.phoenix` below
+    pub fn home(&self, data_dir: &Path) -> PathBuf {
+        data_dir.join("nodes").join(&self.name)
+    }
+}
+
+impl NodesConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let mut names = vec![MAIN_NODE];
+        let mut ports = vec![9740, 4000, 3000];
+        for node in &self.extra {
+            let valid_name = !node.name.is_empty()
+                && node
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+            if !valid_name {
+                return Err(format!(
+                    "Invalid node name '{}': use lowercase letters, digits and dashes",
+                    node.name
+                ));
+            }
+            if names.contains(&node.name.as_str()) {
+                return Err(format!("There is already a node named '{}'", node.name));
+            }
+            if node.port == 0 || ports.contains(&node.port) {
+                return Err(format!(
+                    "Port {} is not available for node '{}'",
+                    node.port, node.name
+                ));
+            }
+            if node.chain != "mainnet" && node.chain != "testnet" {
+                return Err(format!("Unsupported network: {}", node.chain));
+            }
+            names.push(&node.name);
+            ports.push(node.port);
+        }
+        if !self.active.is_empty() && !names.contains(&self.active.as_str()) {
+            return Err(format!("No node named '{}'", self.active));
+        }
+        Ok(())
+    }
+
+    /// The extra node the dashboard shows, None for the main one
+    pub fn active(&self) -> Option<&NodeConfig> {
+        self.extra.iter().find(|node| node.name == self.active)
+    }
+
+    pub fn active_name(&self) -> &str {
+        self.active().map_or(MAIN_NODE, |node| &node.name)
+    }
+}
+
+/// User-defined processes supervised next to the built-in services, e.g.
+/// LNbits or a webhook relay
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServicesConfig {
+    /// `[services.backend.env]`, merged over the Node backend's environment
+    pub backend: ServiceOverrides,
+    /// `[services.frontend.env]`, merged over the Next.js server's environment
+    pub frontend: ServiceOverrides,
+    pub extra: Vec<ExtraService>,
+}
+
+/// Variables the shell wires up itself; overriding them would break the
+/// services talking to each other
+const MANAGED_ENV: [&str; 6] = [
+    "PORT",
+    "HOST",
+    "HOSTNAME",
+    "API_TOKEN",
+    "PAIRING_TOKEN",
+    "PHOENIXD_PASSWORD",
+];
+
+/// Settings for one of the built-in services
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServiceOverrides {
+    pub env: BTreeMap<String, String>,
+}
+
+impl ServiceOverrides {
+    /// The overrides that may be applied, leaving out the managed variables
+    pub fn env(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.env
+            .iter()
+            .filter(|(key, _)| !MANAGED_ENV.contains(&key.as_str()))
+    }
+
+    fn validate(&self, service: &str) -> Result<(), String> {
+        for key in self.env.keys() {
+            let valid = key
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(format!(
+                    "Invalid environment variable '{}' for the {}",
+                    key, service
+                ));
+            }
+            if MANAGED_ENV.contains(&key.as_str()) {
+                return Err(format!(
+                    "{} of the {} is set by the app and can't be overridden",
+                    key, service
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtraService {
+    /// Lowercase letters, digits and dashes; also its log file's name
+    pub name: String,
+    /// Program to run, a path or a name on PATH
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Working directory; empty for the data directory
+    #[serde(default)]
+    pub cwd: String,
+    /// Start it together with the built-in services
+    #[serde(default = "default_autostart")]
+    pub autostart: bool,
+}
+
+fn default_autostart() -> bool {
+    true
+}
+
+impl ServicesConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        self.backend.validate("backend")?;
+        self.frontend.validate("frontend")?;
+        let mut names: Vec<&str> = crate::process_manager::SERVICES.to_vec();
+        for service in &self.extra {
+            let valid_name = !service.name.is_empty()
+                && service
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+            if !valid_name {
+                return Err(format!(
+                    "Invalid service name '{}': use lowercase letters, digits and dashes",
+                    service.name
+                ));
+            }
+            if names.contains(&service.name.as_str()) || service.name.starts_with("phoenixd-") {
+                return Err(format!(
+                    "There is already a service named '{}'",
+                    service.name
+                ));
+            }
+            if service.command.trim().is_empty() {
+                return Err(format!("Service '{}' has no command", service.name));
+            }
+            names.push(&service.name);
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ExtraService> {
+        self.extra.iter().find(|service| service.name == name)
+    }
+}
+
+/// Timeout for operations without one of their own
+pub const DEFAULT_OPERATION_TIMEOUT_SECS: u64 = 600;
+
+/// Built-in timeouts for the operations that usually take longer
+const OPERATION_TIMEOUTS: [(&str, u64); 6] = [
+    ("backup", 1800),
+    ("restore", 1800),
+    ("optimize_database", 1800),
+    ("liquidity", 900),
+    ("phoenixd_upgrade", 1800),
+    ("docker_import", 1800),
+];
+
+/// How long long-running operations may take before they give up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OperationsConfig {
+    pub default_timeout_secs: u64,
+    /// Per operation kind (`backup`, `restore`, `wallet`, `export_logs`...), in seconds
+    pub timeouts: BTreeMap<String, u64>,
+}
+
+impl Default for OperationsConfig {
+    fn default() -> Self {
+        Self {
+            default_timeout_secs: DEFAULT_OPERATION_TIMEOUT_SECS,
+            timeouts: BTreeMap::new(),
+        }
+    }
+}
+
+impl OperationsConfig {
+    pub fn timeout(&self, kind: &str) -> Duration {
+        let secs = self.timeouts.get(kind).copied().or_else(|| {
+            OPERATION_TIMEOUTS
+                .iter()
+                .find(|(name, _)| *name == kind)
+                .map(|(_, secs)| *secs)
+        });
+        Duration::from_secs(secs.unwrap_or(self.default_timeout_secs).max(1))
+    }
+}
+
+/// Built-in intervals of the background pollers, in seconds
+pub const POLL_INTERVALS: [(&str, u64); 7] = [
+    ("network", 15),
+    ("recent_payments", 30),
+    ("watchtower", 60),
+    ("bandwidth", 60),
+    ("tor_health", 300),
+    ("phoenixd_update", 12 * 60 * 60),
+    ("clock", 60 * 60),
+];
+
+/// How often a connection's budget starts over
+pub const BUDGET_RENEWALS: [&str; 4] = ["daily", "weekly", "monthly", "never"];
+
+/// Nostr Wallet Connect bridge between the node and Nostr clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NwcConfig {
+    /// Start the bridge together with the other services
+    pub enabled: bool,
+    /// Relay the bridge and its clients talk through
+    pub relay: String,
+    /// Budget of connections paired from the tray, in sat
+    pub default_budget_sat: u64,
+    pub connections: Vec<NwcConnection>,
+}
+
+/// A paired client. Its secret only ever lives in the pairing URI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NwcConnection {
+    pub name: String,
+    /// The client's Nostr public key, hex
+    pub pubkey: String,
+    /// Most it may spend per renewal period; 0 for receive and read only
+    pub budget_sat: u64,
+    /// `daily`, `weekly`, `monthly` or `never`
+    pub budget_renewal: String,
+    pub created_at: String,
+}
+
+impl Default for NwcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            relay: "wss://relay.getalby.com/v1".to_string(),
+            default_budget_sat: 10_000,
+            connections: Vec::new(),
+        }
+    }
+}
+
+impl NwcConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.relay.starts_with("wss://") && !self.relay.starts_with("ws://") {
+            return Err(format!(
+                "Invalid Nostr relay '{}': use a wss:// URL",
+                self.relay
+            ));
+        }
+        for (i, connection) in self.connections.iter().enumerate() {
+            if connection.name.trim().is_empty() {
+                return Err("NWC connections need a name".to_string());
+            }
+            if self.connections[..i]
+                .iter()
+                .any(|other| other.name == connection.name)
+            {
+                return Err(format!(
+                    "There is already an NWC connection named '{}'",
+                    connection.name
+                ));
+            }
+            if !BUDGET_RENEWALS.contains(&connection.budget_renewal.as_str()) {
+                return Err(format!(
+                    "Invalid budget renewal '{}': use {}",
+                    connection.budget_renewal,
+                    BUDGET_RENEWALS.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How often the background pollers run, e.g. less often on a Raspberry Pi
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PollingConfig {
+    /// Per poller (`network`, `recent_payments`, `watchtower`, `bandwidth`,
+    /// `tor_health`, `phoenixd_update`, `clock`), in seconds
+    pub intervals: BTreeMap<String, u64>,
+    /// Each wait is randomly up to this many percent shorter or longer, so
+    /// installs don't poll in lockstep
+    pub jitter_percent: u32,
+    /// Poll less often on battery or while the computer is busy
+    pub adaptive: bool,
+    /// System CPU usage that counts as busy
+    pub busy_cpu_percent: u8,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            intervals: BTreeMap::new(),
+            jitter_percent: 10,
+            adaptive: true,
+            busy_cpu_percent: 80,
+        }
+    }
+}
+
+impl PollingConfig {
+    pub fn interval(&self, poller: &str) -> Duration {
+        let secs = self.intervals.get(poller).copied().or_else(|| {
+            POLL_INTERVALS
+                .iter()
+                .find(|(name, _)| *name == poller)
+                .map(|(_, secs)| *secs)
+        });
+        Duration::from_secs(secs.unwrap_or(60).max(1))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (poller, secs) in &self.intervals {
+            if !POLL_INTERVALS.iter().any(|(name, _)| name == poller) {
+                return Err(format!("Unknown poller: {}", poller));
+            }
+            if *secs == 0 {
+                return Err(format!("The {} interval must be positive", poller));
+            }
+        }
+        if self.jitter_percent > 50 {
+            return Err("Polling jitter can be at most 50%".to_string());
+        }
+        if !(20..=100).contains(&self.busy_cpu_percent) {
+            return Err("polling.busy_cpu_percent must be between 20 and 100".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Addresses the local services listen on, e.g. `::1`, a LAN interface's
+/// address, or `0.0.0.0` / `::` for every interface
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BindConfig {
+    /// phoenixd's HTTP API (`--http-bind-ip`); empty for 127.0.0.1
+    pub phoenixd: String,
+    /// Empty for 127.0.0.1, or every interface while LAN access is on
+    pub backend: String,
+    /// Empty for localhost, or every interface while LAN access is on
+    pub frontend: String,
+}
+
+impl BindConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for (service, value) in [
+            ("phoenixd", &self.phoenixd),
+            ("backend", &self.backend),
+            ("frontend", &self.frontend),
+        ] {
+            if !value.is_empty() && value.parse::<IpAddr>().is_err() {
+                return Err(format!(
+                    "Invalid {} bind address '{}': use an IP such as 127.0.0.1, ::1 or 0.0.0.0",
+                    service, value
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn phoenixd_ip(&self) -> IpAddr {
+        self.phoenixd.parse().unwrap_or(Ipv4Addr::LOCALHOST.into())
+    }
+
+    pub fn backend_ip(&self) -> IpAddr {
+        self.backend.parse().unwrap_or(Ipv4Addr::LOCALHOST.into())
+    }
+
+    /// None leaves the choice to the LAN setting
+    pub fn frontend_ip(&self) -> Option<IpAddr> {
+        self.frontend.parse().ok()
+    }
+
+    /// Where the shell and the backend reach phoenixd
+    pub fn phoenixd_addr(&self) -> SocketAddr {
+        local_addr(self.phoenixd_ip(), 9740)
+    }
+
+    /// Where the shell and the backend reach an extra node
+    pub fn node_addr(&self, node: &NodeConfig) -> SocketAddr {
+        local_addr(self.phoenixd_ip(), node.port)
+    }
+
+    /// Where the shell reaches the backend
+    pub fn backend_addr(&self) -> SocketAddr {
+        local_addr(self.backend_ip(), 4000)
+    }
+
+    /// Where the shell reaches the frontend; loopback serves it in every
+    /// automatic case
+    pub fn frontend_addr(&self) -> SocketAddr {
+        local_addr(
+            self.frontend_ip().unwrap_or(Ipv4Addr::LOCALHOST.into()),
+            3000,
+        )
+    }
+
+    /// Where the shell connects to a core service to check on it
+    pub fn service_addr(&self, name: &str) -> Option<SocketAddr> {
+        match name {
+            "phoenixd" => Some(self.phoenixd_addr()),
+            "backend" => Some(self.backend_addr()),
+            "frontend" => Some(self.frontend_addr()),
+            _ => None,
+        }
+    }
+}
+
+/// Address to connect to for a service listening on `ip`: the loopback of
+/// the same family when it listens on every interface
+pub fn local_addr(ip: IpAddr, port: u16) -> SocketAddr {
+    let ip = match ip {
+        IpAddr::V4(v4) if v4.is_unspecified() => Ipv4Addr::LOCALHOST.into(),
+        IpAddr::V6(v6) if v6.is_unspecified() => Ipv6Addr::LOCALHOST.into(),
+        ip => ip,
+    };
+    SocketAddr::new(ip, port)
+}
+
+/// Host for URLs opened in the browser: `localhost` unless the service
+/// only listens on a specific interface
+pub fn browser_host(ip: IpAddr) -> String {
+    match ip {
+        ip if ip.is_loopback() || ip.is_unspecified() => "localhost".to_string(),
+        IpAddr::V6(v6) => format!("[{}]", v6),
+        IpAddr::V4(v4) => v4.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackendConfig {
+    /// Serve the API from the shell itself instead of the bundled Node.js
+    /// backend. Covers the node, payment and LNURL routes; contacts,
+    /// categories, recurring payments and apps need the Node backend.
+    pub native: bool,
+    /// Browser origins the backend accepts besides the ones the shell works
+    /// out from its exposures, e.g. `https://pay.example.com`
+    pub allowed_origins: Vec<String>,
+}
+
+impl BackendConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for origin in &self.allowed_origins {
+            if origin_of(origin).as_deref() != Some(origin.as_str()) {
+                return Err(format!(
+                    "Invalid origin '{}': use scheme://host[:port], e.g. https://pay.example.com",
+                    origin
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `scheme://host[:port]` of an http(s) URL, as browsers send it in `Origin`
+pub fn origin_of(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    if scheme != "http" && scheme != "https" {
+        return None;
+    }
+    let host = rest.split(['/', '?', '#']).next()?;
+    if host.is_empty() || host.contains('@') {
+        return None;
+    }
+    Some(format!("{}://{}", scheme, host))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrontendConfig {
+    /// Run the bundled web UI; off for API-only setups that host their own
+    pub enabled: bool,
+    /// Externally hosted UI the tray opens instead, e.g. behind nginx
+    pub external_url: String,
+    /// Serve the exported static build from the shell instead of running
+    /// the Next.js server, when the bundle has one
+    pub serve_static: bool,
+    /// How the dashboard is opened: `default` browser, a specific `browser`,
+    /// a Chromium `app` mode window or an embedded `window`
+    pub open_with: String,
+    /// Browser binary for `browser` and `app`; empty finds a Chromium-based one for `app`
+    pub browser: String,
+    /// Extra arguments for the browser, e.g. `--profile-directory=Profile 2`
+    pub browser_args: Vec<String>,
+}
+
+impl Default for FrontendConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            external_url: String::new(),
+            serve_static: false,
+            open_with: "default".to_string(),
+            browser: String::new(),
+            browser_args: Vec::new(),
+        }
+    }
+}
+
+impl FrontendConfig {
+    /// Whether there is any web UI to open
+    pub fn has_ui(&self) -> bool {
+        self.enabled || !self.external_url.is_empty()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !crate::browser::OPEN_WITH.contains(&self.open_with.as_str()) {
+            return Err(format!(
+                "Open with must be one of {}",
+                crate::browser::OPEN_WITH.join(", ")
+            ));
+        }
+        if self.open_with == "browser" && self.browser.is_empty() {
+            return Err("Choose the browser to open the dashboard with".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TorConfig {
+    /// Run a local tor instance exposing the dashboard as an onion service
+    pub enabled: bool,
+    /// Route phoenixd's peer connections through the local tor instance
+    /// with torsocks (not available on Windows)
+    pub node_traffic: bool,
+    /// Restart tor when the onion service stops answering through it
+    pub auto_restart: bool,
+    /// Also publish phoenixd's API on the onion, on port 9740, for a store
+    /// server such as BTCPay or LNbits
+    pub node_api: bool,
+}
+
+impl Default for TorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            node_traffic: false,
+            auto_restart: true,
+            node_api: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CloudflareConfig {
+    /// Start the tunnel together with the other services
+    pub enabled: bool,
+    /// Use a temporary trycloudflare.com tunnel instead of the named one
+    pub quick: bool,
+    pub tunnel_name: String,
+    pub tunnel_id: String,
+    pub hostname: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TailscaleConfig {
+    /// Share the dashboard on the tailnet with `tailscale serve`
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClipboardConfig {
+    /// Offer to pay lightning invoices copied to the clipboard (opt-in)
+    pub watch_invoices: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuickReceiveConfig {
+    /// Global shortcut that pops up a fresh invoice (empty disables it)
+    pub shortcut: String,
+    /// Invoice amount; 0 lets the payer choose
+    pub amount_sat: u64,
+    pub description: String,
+}
+
+impl Default for QuickReceiveConfig {
+    fn default() -> Self {
+        Self {
+            shortcut: "CommandOrControl+Shift+L".to_string(),
+            amount_sat: 0,
+            description: "Phoenixd Dashboard".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrashReportConfig {
+    /// User consent to send panic reports (with the last log lines); off by default
+    pub enabled: bool,
+    /// https endpoint receiving the report as a JSON POST
+    pub endpoint: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// Serve Prometheus metrics at `/metrics` and the aggregate health check at `/healthz`
+    pub enabled: bool,
+    /// Loopback by default; use 0.0.0.0 to scrape from another machine
+    pub bind: String,
+    pub port: u16,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: "127.0.0.1".to_string(),
+            port: 9464,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    /// URL the watchdog POSTs JSON events to (ntfy, Slack, Home Assistant...); empty disables
+    pub webhook_url: String,
+    /// Alert when the data directory's disk has less free space than this (0 disables)
+    pub low_disk_mb: u64,
+    /// Minutes phoenixd may be down before a node_offline alert
+    pub node_offline_minutes: u64,
+    /// Alert when tor and cloudflared move more than this in a day / in
+    /// seven days, for metered connections (0 disables)
+    pub bandwidth_daily_mb: u64,
+    pub bandwidth_weekly_mb: u64,
+    /// Notify when an outgoing payment is still pending after this many
+    /// minutes (0 disables)
+    pub stuck_payment_minutes: u64,
+    /// Notify when an unpaid invoice of at least this many sat expires within
+    /// `expiring_invoice_minutes` (0 disables)
+    pub expiring_invoice_sat: u64,
+    pub expiring_invoice_minutes: u64,
+    /// Send critical alerts to Telegram; the bot token lives in the OS keychain
+    pub telegram_enabled: bool,
+    pub telegram_chat_id: String,
+    /// Warn when the system clock is off from NTP time by more than this
+    /// many seconds; channels can fail on a skewed clock (0 disables)
+    pub clock_skew_secs: u64,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: String::new(),
+            low_disk_mb: 1024,
+            node_offline_minutes: 5,
+            bandwidth_daily_mb: 0,
+            bandwidth_weekly_mb: 0,
+            stuck_payment_minutes: 30,
+            expiring_invoice_sat: 100_000,
+            expiring_invoice_minutes: 10,
+            telegram_enabled: false,
+            telegram_chat_id: String::new(),
+            clock_skew_secs: 30,
+        }
+    }
+}
+
+/// Do not disturb: notifications are held back, and only journaled, during
+/// these hours or while the screen is shared
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuietHoursConfig {
+    pub enabled: bool,
+    /// Local `HH:MM`; a start after the end spans midnight
+    pub start: String,
+    pub end: String,
+    /// Also stay quiet while a screen sharing or recording app is capturing
+    pub while_screen_sharing: bool,
+}
+
+impl Default for QuietHoursConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "22:00".to_string(),
+            end: "07:00".to_string(),
+            while_screen_sharing: true,
+        }
+    }
+}
+
+impl QuietHoursConfig {
+    fn times(&self) -> Result<(chrono::NaiveTime, chrono::NaiveTime), String> {
+        let parse = |time: &str| {
+            chrono::NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .map_err(|_| format!("Quiet hours need HH:MM times, not '{}'", time))
+        };
+        Ok((parse(&self.start)?, parse(&self.end)?))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.times().map(|_| ())
+    }
+
+    /// Whether `now` falls within the scheduled hours
+    pub fn covers(&self, now: chrono::NaiveTime) -> bool {
+        let Ok((start, end)) = self.times() else {
+            return false;
+        };
+        if start <= end {
+            start <= now && now < end
+        } else {
+            now >= start || now < end
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceConfig {
+    /// Restart `services` on `schedule`
+    pub enabled: bool,
+    /// Cron expression in local time (minute hour day month weekday)
+    pub schedule: String,
+    pub services: Vec<String>,
+    /// How long after the scheduled time a restart may still happen while
+    /// waiting for in-flight payments; after that the run is skipped
+    pub window_minutes: u64,
+    /// Also VACUUM and ANALYZE the dashboard database
+    pub optimize_database: bool,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: "0 4 * * 0".to_string(),
+            services: vec![
+                "phoenixd".to_string(),
+                "backend".to_string(),
+                "frontend".to_string(),
+            ],
+            window_minutes: 60,
+            optimize_database: true,
+        }
+    }
+}
+
+/// How often the activity digest goes out
+pub const DIGEST_PERIODS: [&str; 2] = ["daily", "weekly"];
+
+/// Summary of the payments received and sent and the fees paid over the
+/// last day or week
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestConfig {
+    pub enabled: bool,
+    /// `daily`, or `weekly` on Mondays
+    pub period: String,
+    /// Local hour it goes out at
+    pub hour: u32,
+    /// As a native notification
+    pub notify: bool,
+    /// To `alerts.webhook_url`, as JSON with `"event": "digest"`
+    pub webhook: bool,
+    /// To `alerts.telegram_chat_id`
+    pub telegram: bool,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            period: "weekly".to_string(),
+            hour: 9,
+            notify: true,
+            webhook: false,
+            telegram: false,
+        }
+    }
+}
+
+impl DigestConfig {
+    /// Cron expression of the schedule
+    pub fn schedule(&self) -> String {
+        let weekday = if self.period == "daily" { "*" } else { "1" };
+        format!("0 {} * * {}", self.hour, weekday)
+    }
+
+    /// Length of the period the digest covers
+    pub fn span(&self) -> chrono::Duration {
+        if self.period == "daily" {
+            chrono::Duration::days(1)
+        } else {
+            chrono::Duration::weeks(1)
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !DIGEST_PERIODS.contains(&self.period.as_str()) {
+            return Err(format!(
+                "Unknown digest period '{}': use {}",
+                self.period,
+                DIGEST_PERIODS.join(" or ")
+            ));
+        }
+        if self.hour > 23 {
+            return Err("digest.hour must be between 0 and 23".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Archive phoenixd's data and the dashboard database on `schedule`
+    pub enabled: bool,
+    /// Cron expression in local time (minute hour day month weekday)
+    pub schedule: String,
+    /// Where archives are written; empty for `<data dir>/backups`
+    pub dir: String,
+    /// After each scheduled backup, keep the newest archive of this many
+    /// recent days, ISO weeks and months and delete the rest; all zero keeps
+    /// every archive
+    pub keep_daily: u32,
+    pub keep_weekly: u32,
+    pub keep_monthly: u32,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: "0 3 * * *".to_string(),
+            dir: String::new(),
+            keep_daily: 7,
+            keep_weekly: 4,
+            keep_monthly: 12,
+        }
+    }
+}
+
+/// Health checks replacing the built-in port probe of the services they name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthConfig {
+    pub checks: Vec<HealthCheck>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthCheck {
+    /// Service the check reports on
+    pub service: String,
+    /// `http`, `tcp` or `command`
+    pub kind: String,
+    /// URL for `http`, `host:port` for `tcp`, a command line for `command`
+    pub target: String,
+    /// Status an `http` check expects
+    #[serde(default = "default_expected_status")]
+    pub expected_status: u16,
+    #[serde(default = "default_check_interval")]
+    pub interval_secs: u64,
+    #[serde(default = "default_check_timeout")]
+    pub timeout_secs: u64,
+    /// Consecutive failures before the service counts as unhealthy
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+}
+
+fn default_expected_status() -> u16 {
+    200
+}
+
+fn default_check_interval() -> u64 {
+    30
+}
+
+fn default_check_timeout() -> u64 {
+    5
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+impl HealthConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for check in &self.checks {
+            if check.service.is_empty() {
+                return Err("Every health check needs a service".to_string());
+            }
+            if !matches!(check.kind.as_str(), "http" | "tcp" | "command") {
+                return Err(format!(
+                    "Health check kind must be http, tcp or command, not '{}'",
+                    check.kind
+                ));
+            }
+            if check.target.trim().is_empty() {
+                return Err(format!(
+                    "The {} health check of {} has no target",
+                    check.kind, check.service
+                ));
+            }
+            if check.interval_secs == 0 || check.timeout_secs == 0 || check.failure_threshold == 0 {
+                return Err(format!(
+                    "Interval, timeout and failure threshold of {}'s health check must be positive",
+                    check.service
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoHealConfig {
+    /// Restart services that stay unhealthy or exit on their own
+    pub enabled: bool,
+    /// Consecutive failing watchdog checks (5s apart) before a restart
+    pub unhealthy_checks: u32,
+    /// Restarts per service and hour before giving up and notifying
+    pub max_restarts_per_hour: u32,
+    /// Crashes of one service within `crash_loop_window_secs` that stop
+    /// everything and enter safe mode; 0 disables
+    pub crash_loop_crashes: u32,
+    pub crash_loop_window_secs: u64,
+    /// Per service: `no`, `on-failure` or `unless-stopped` (the default).
+    /// The headless supervisor follows them too.
+    pub restart_policies: BTreeMap<String, String>,
+}
+
+impl Default for AutoHealConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            unhealthy_checks: 6,
+            max_restarts_per_hour: 3,
+            crash_loop_crashes: 3,
+            crash_loop_window_secs: 600,
+            restart_policies: BTreeMap::new(),
+        }
+    }
+}
+
+pub const RESTART_POLICIES: [&str; 3] = ["no", "on-failure", "unless-stopped"];
+
+impl AutoHealConfig {
+    pub fn restart_policy(&self, service: &str) -> &str {
+        self.restart_policies
+            .get(service)
+            .map(String::as_str)
+            .unwrap_or("unless-stopped")
+    }
+
+    /// Whether a service that exited on its own should be brought back
+    pub fn restarts_after_exit(&self, service: &str, success: bool) -> bool {
+        match self.restart_policy(service) {
+            "no" => false,
+            "on-failure" => !success,
+            _ => true,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (service, policy) in &self.restart_policies {
+            if !RESTART_POLICIES.contains(&policy.as_str()) {
+                return Err(format!(
+                    "Restart policy of {} must be no, on-failure or unless-stopped, not '{}'",
+                    service, policy
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanConfig {
+    /// Bind the frontend on all interfaces so devices on the LAN can pair
+    pub enabled: bool,
+    /// Advertise the dashboard over mDNS/Bonjour while LAN access is on
+    pub mdns: bool,
+    /// mDNS instance name, advertised as `<mdns_name>.local`
+    pub mdns_name: String,
+}
+
+impl Default for LanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mdns: true,
+            mdns_name: "phoenixd-dashboard".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpsConfig {
+    /// Serve the dashboard over https with a locally generated certificate
+    pub enabled: bool,
+    /// https port in front of the frontend (3000)
+    pub port: u16,
+    /// https port in front of the backend API (4000)
+    pub api_port: u16,
+}
+
+impl Default for HttpsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 3443,
+            api_port: 4443,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyConfig {
+    /// Put the authenticating reverse proxy in front of every remote exposure
+    pub enabled: bool,
+    /// https port for direct (LAN) access
+    pub port: u16,
+    /// Loopback-only plain http port that tor and tailscale forward to
+    pub plain_port: u16,
+    /// Loopback-only plain http port for cloudflared, the one place where the
+    /// client address is taken from `CF-Connecting-IP`
+    pub tunnel_port: u16,
+    pub username: String,
+    /// Generated on first enable; also accepted as a bearer token
+    pub password: String,
+    /// Login for remote viewers, e.g. over Tor or a tunnel: same username,
+    /// but only reads go through. Empty until handed out.
+    pub read_only_password: String,
+    /// Requests per minute and client before answering 429 (0 disables)
+    pub rate_limit_per_minute: u32,
+    /// Failed logins within 15 minutes before a client is locked out (0 disables)
+    pub max_failed_auth: u32,
+    pub lockout_minutes: u64,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8443,
+            plain_port: 8088,
+            tunnel_port: 8089,
+            username: "phoenixd".to_string(),
+            password: String::new(),
+            read_only_password: String::new(),
+            rate_limit_per_minute: 300,
+            max_failed_auth: 5,
+            lockout_minutes: 60,
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(CONFIG_FILE)
+    }
+
+    /// Runs before logging is set up (the log level lives in here), so
+    /// problems go straight to stderr
+    pub fn load(data_dir: &Path) -> Self {
+        let path = Self::path(data_dir);
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).unwrap_or_else(|e| {
+                eprintln!(
+                    "Warning: Invalid config at {:?}, using defaults: {}",
+                    path, e
+                );
+                Self::default()
+            }),
+            Err(e) => {
+                eprintln!("Warning: Could not read config at {:?}: {}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Read config.toml strictly and run every section's checks, so a hand
+    /// edit can be vetted before anything restarts on it. Parse errors name
+    /// the line and column.
+    pub fn check(data_dir: &Path) -> Result<Self, String> {
+        let path = Self::path(data_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        let config: Self =
+            toml::from_str(&content).map_err(|e| format!("Invalid {}: {}", CONFIG_FILE, e))?;
+        config.bind.validate()?;
+        config.backend.validate()?;
+        config.nodes.validate()?;
+        config.services.validate()?;
+        config.frontend.validate()?;
+        config.health.validate()?;
+        config.quiet_hours.validate()?;
+        config.auto_heal.validate()?;
+        config.polling.validate()?;
+        config.nwc.validate()?;
+        config.digest.validate()?;
+        Ok(config)
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<(), String> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        std::fs::write(Self::path(data_dir), content)
+            .map_err(|e| format!("Failed to write config: {}", e))
+    }
+}
@@ -0,0 +1,225 @@
+use crate::phoenixd_api::PhoenixdClient;
+use crate::{audit, commands, AppState};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::{debug, error, info};
+
+#[cfg(windows)]
+const PIPE_NAME: &str = r"\\.\pipe\phoenixd-dashboard";
+#[cfg(unix)]
+const SOCKET_FILE: &str = "control.sock";
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const CALL_FAILED: i64 = -32000;
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct RestartParams {
+    service: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct InvoiceParams {
+    amount_sat: Option<u64>,
+    description: Option<String>,
+}
+
+fn params<T: for<'de> Deserialize<'de> + Default>(params: Value) -> Result<T, (i64, String)> {
+    if params.is_null() {
+        return Ok(T::default());
+    }
+    serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))
+}
+
+/// Run one method; blocking work goes to the blocking pool so a backup
+/// doesn't hold up other connections
+async fn call(app: &AppHandle, method: &str, raw: Value) -> Result<Value, (i64, String)> {
+    let failed = |e: String| (CALL_FAILED, e);
+    let blocking = |work: Box<dyn FnOnce() -> Result<Value, String> + Send>| async move {
+        tauri::async_runtime::spawn_blocking(work)
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result)
+            .map_err(failed)
+    };
+    match method {
+        "status" => {
+            let services = app.state::<AppState>().statuses.lock().unwrap().clone();
+            Ok(json!({
+                "status": commands::get_status(app.state()),
+                "services": services,
+            }))
+        }
+        "restart" => {
+            let RestartParams { service } =
+                serde_json::from_value(raw).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+            let app = app.clone();
+            blocking(Box::new(move || {
+                let state = app.state::<AppState>();
+                audit::record(&state.data_dir, "control.restart", &service);
+                let mut pm = state.process_manager.lock().unwrap();
+                pm.restart_service(&service)?;
+                Ok(json!(pm.service_statuses()))
+            }))
+            .await
+        }
+        "backup" => {
+            // Always into the backup directory: the archive holds the seed,
+            // and a caller must not get to choose where it lands
+            let app = app.clone();
+            blocking(Box::new(move || {
+                audit::record(&app.state::<AppState>().data_dir, "control.backup", "");
+                Ok(json!({ "path": commands::create_backup(app.clone(), None)? }))
+            }))
+            .await
+        }
+        "create_invoice" => {
+            let InvoiceParams {
+                amount_sat,
+                description,
+            } = params(raw)?;
+            let state = app.state::<AppState>();
+            let description = description.unwrap_or_else(|| {
+                state
+                    .config
+                    .lock()
+                    .unwrap()
+                    .quick_receive
+                    .description
+                    .clone()
+            });
+            let invoice = PhoenixdClient::active(&state.data_dir)
+                .create_invoice(amount_sat, &description)
+                .await
+                .map_err(failed)?;
+            info!("⚡ Created invoice {} for a script", invoice.payment_hash);
+            Ok(json!(invoice))
+        }
+        _ => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+    }
+}
+
+/// Answer newline-delimited JSON-RPC requests until the client hangs up
+async fn serve<S: AsyncRead + AsyncWrite + Unpin>(app: AppHandle, stream: S) {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (id, outcome) = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                debug!("Control socket call: {}", request.method);
+                (
+                    request.id,
+                    call(&app, &request.method, request.params).await,
+                )
+            }
+            Err(e) => (Value::Null, Err((PARSE_ERROR, e.to_string()))),
+        };
+        let response = match outcome {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        };
+        let mut bytes = response.to_string().into_bytes();
+        bytes.push(b'\n');
+        if writer.write_all(&bytes).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Where scripts connect
+#[cfg(unix)]
+fn socket_path(data_dir: &std::path::Path) -> PathBuf {
+    data_dir.join(SOCKET_FILE)
+}
+
+/// Listen on `control.sock` in the data dir, readable by the user only
+#[cfg(unix)]
+pub fn spawn(app: AppHandle) {
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::net::UnixListener;
+
+    let path = socket_path(&app.state::<AppState>().data_dir);
+    tauri::async_runtime::spawn(async move {
+        // A socket left by a crashed session refuses connections
+        if std::os::unix::net::UnixStream::connect(&path).is_err() {
+            let _ = std::fs::remove_file(&path);
+        }
+        let listener = match UnixListener::bind(&path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("Failed to listen on {:?}: {}", path, e);
+                return;
+            }
+        };
+        if let Err(e) = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)) {
+            error!("Failed to restrict {:?}: {}", path, e);
+            return;
+        }
+        info!("🔌 Control socket on {:?}", path);
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tauri::async_runtime::spawn(serve(app.clone(), stream));
+                }
+                Err(e) => error!("Control socket accept failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Listen on a named pipe local clients only can open
+#[cfg(windows)]
+pub fn spawn(app: AppHandle) {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    tauri::async_runtime::spawn(async move {
+        let mut options = ServerOptions::new();
+        options.reject_remote_clients(true);
+        let mut server = match options.first_pipe_instance(true).create(PIPE_NAME) {
+            Ok(server) => server,
+            Err(e) => {
+                error!("Failed to create {}: {}", PIPE_NAME, e);
+                return;
+            }
+        };
+        info!("🔌 Control pipe on {}", PIPE_NAME);
+        options.first_pipe_instance(false);
+        loop {
+            if let Err(e) = server.connect().await {
+                error!("Control pipe connect failed: {}", e);
+                continue;
+            }
+            // Open the next instance before serving this client
+            let next = match options.create(PIPE_NAME) {
+                Ok(next) => next,
+                Err(e) => {
+                    error!("Failed to create {}: {}", PIPE_NAME, e);
+                    return;
+                }
+            };
+            tauri::async_runtime::spawn(serve(app.clone(), std::mem::replace(&mut server, next)));
+        }
+    });
+}
@@ -0,0 +1,186 @@
+use crate::config::CrashReportConfig;
+use crate::logs;
+use crate::redact;
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+/// Lines of the shell's own log attached to a report
+const LOG_TAIL_LINES: usize = 200;
+/// Unsent reports kept on disk; older ones are dropped
+const MAX_PENDING: usize = 10;
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Endpoint reports go to, `None` while the user hasn't opted in.
+/// Read from the panic hook, so it can't live in `AppState`.
+static ENDPOINT: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    pub timestamp: u64,
+    pub thread: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+    pub log_tail: Vec<String>,
+}
+
+pub fn crash_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("crashes")
+}
+
+/// Apply the consent toggle. Nothing leaves the machine unless it's enabled
+/// and an endpoint is configured.
+pub fn set_consent(config: &CrashReportConfig) {
+    let endpoint = (config.enabled && !config.endpoint.is_empty()).then(|| config.endpoint.clone());
+    *ENDPOINT.lock().unwrap_or_else(|e| e.into_inner()) = endpoint;
+}
+
+/// Write a report to `crashes/` on every panic and, with consent, send it
+/// right away. Release builds abort on panic, so this all happens synchronously
+/// before the default hook runs.
+pub fn install_hook(data_dir: PathBuf) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let report = build_report(&data_dir, info);
+        let saved = save(&data_dir, &report);
+
+        // try_lock: a panic while the toggle is being changed must not deadlock
+        let endpoint = ENDPOINT.try_lock().ok().and_then(|e| e.clone());
+        if let (Some(endpoint), Some(path)) = (endpoint, saved) {
+            if upload(&endpoint, &report).is_ok() {
+                let _ = fs::remove_file(path);
+            }
+        }
+
+        default_hook(info);
+    }));
+}
+
+/// Send reports left over from earlier crashes (the app may have died before
+/// the upload finished, or consent was given afterwards)
+pub fn upload_pending(data_dir: &Path) {
+    let Some(endpoint) = ENDPOINT.lock().unwrap_or_else(|e| e.into_inner()).clone() else {
+        return;
+    };
+
+    for path in pending(data_dir) {
+        let report = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CrashReport>(&content).ok());
+        let Some(report) = report else {
+            let _ = fs::remove_file(&path);
+            continue;
+        };
+
+        match upload(&endpoint, &report) {
+            Ok(()) => {
+                info!(
+                    "📤 Sent crash report {:?}",
+                    path.file_name().unwrap_or_default()
+                );
+                let _ = fs::remove_file(&path);
+            }
+            Err(e) => {
+                warn!("{}", e);
+                break;
+            }
+        }
+    }
+}
+
+/// Reports waiting to be sent, oldest first
+pub fn pending(data_dir: &Path) -> Vec<PathBuf> {
+    let mut reports: Vec<PathBuf> = fs::read_dir(crash_dir(data_dir))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        })
+        .unwrap_or_default();
+    reports.sort();
+    reports
+}
+
+fn build_report(data_dir: &Path, info: &PanicHookInfo<'_>) -> CrashReport {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string());
+
+    CrashReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        thread: std::thread::current()
+            .name()
+            .unwrap_or("<unnamed>")
+            .to_string(),
+        message: redact::scrub(&message),
+        location: info.location().map(|l| l.to_string()),
+        backtrace: Backtrace::force_capture().to_string(),
+        log_tail: log_tail(data_dir),
+    }
+}
+
+/// Last lines of the newest desktop log file
+fn log_tail(data_dir: &Path) -> Vec<String> {
+    let newest = fs::read_dir(logs::log_dir(data_dir).join("desktop"))
+        .ok()
+        .and_then(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+                .max()
+        });
+    match newest {
+        Some((_, path)) => logs::tail(&path, LOG_TAIL_LINES)
+            .iter()
+            .map(|line| redact::scrub(line))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn save(data_dir: &Path, report: &CrashReport) -> Option<PathBuf> {
+    let dir = crash_dir(data_dir);
+    fs::create_dir_all(&dir).ok()?;
+
+    let existing = pending(data_dir);
+    for old in existing
+        .iter()
+        .take((existing.len() + 1).saturating_sub(MAX_PENDING))
+    {
+        let _ = fs::remove_file(old);
+    }
+
+    let path = dir.join(format!("panic-{}.json", report.timestamp));
+    let content = serde_json::to_string_pretty(report).ok()?;
+    fs::write(&path, content).ok()?;
+    eprintln!("💥 Crash report written to {:?}", path);
+    Some(path)
+}
+
+fn upload(endpoint: &str, report: &CrashReport) -> Result<(), String> {
+    ureq::post(endpoint)
+        .timeout(UPLOAD_TIMEOUT)
+        .send_json(report)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to send crash report: {}", e))
+}
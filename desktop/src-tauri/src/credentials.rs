@@ -0,0 +1,116 @@
+use crate::phoenixd_api::PhoenixdClient;
+use crate::{access_links, audit, config, tls, token, AppState};
+use std::path::Path;
+use tracing::info;
+
+/// phoenixd's API passwords in `phoenix.conf`; the limited one only if set
+const PASSWORD_KEYS: [&str; 2] = ["http-password", "http-password-limited-access"];
+
+/// Give every password key already in `phoenix.conf` a fresh value
+fn rotate_phoenix_conf(phoenix_conf: &Path) -> Result<(), String> {
+    let content = std::fs::read_to_string(phoenix_conf)
+        .map_err(|e| format!("Failed to read {:?}: {}", phoenix_conf, e))?;
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let key = line.split_once('=').map(|(key, _)| key.trim());
+        match key.filter(|key| PASSWORD_KEYS.contains(key)) {
+            Some(key) => lines.push(format!("{}={}", key, token::generate()?)),
+            None => lines.push(line.to_string()),
+        }
+    }
+    tls::write_private(phoenix_conf, &(lines.join("\n") + "\n"))
+}
+
+/// Hand out a fresh read-only proxy password, replacing any earlier one, or
+/// take it back. The proxy restarts on it right away.
+pub fn set_read_only_password(state: &AppState, enabled: bool) -> Result<Option<String>, String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        if enabled && config.proxy.password.is_empty() {
+            return Err("Enable the reverse proxy first".to_string());
+        }
+        config.proxy.read_only_password = if enabled {
+            token::generate()?
+        } else {
+            String::new()
+        };
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    audit::record(
+        &state.data_dir,
+        "credentials.proxy_read_only",
+        if enabled { "generated" } else { "revoked" },
+    );
+    let password = config.proxy.read_only_password.clone();
+    let mut pm = state.process_manager.lock().unwrap();
+    let proxy_enabled = config.proxy.enabled;
+    pm.set_config(config);
+    if proxy_enabled {
+        pm.start_proxy()?;
+    }
+    info!(
+        "🔑 Read-only proxy login {}",
+        if enabled { "issued" } else { "revoked" }
+    );
+    Ok(enabled.then_some(password))
+}
+
+/// Replace phoenixd's API passwords on every node and the reverse proxy's
+/// password, then restart everything in startup order so the backend picks
+/// up the new password and issues a new API token. Refuses while payments
+/// are in flight. Returns what was rotated.
+pub fn rotate_all(state: &AppState) -> Result<Vec<String>, String> {
+    let client = PhoenixdClient::new(&state.data_dir);
+    if tauri::async_runtime::block_on(client.has_pending_operations()).unwrap_or(false) {
+        return Err("Payments are in flight, try again once they settle".to_string());
+    }
+
+    let config = state.config.lock().unwrap().clone();
+    let homes = std::iter::once((config::MAIN_NODE.to_string(), state.data_dir.clone())).chain(
+        config
+            .nodes
+            .extra
+            .iter()
+            .map(|node| (node.name.clone(), node.home(&state.data_dir))),
+    );
+
+    let mut rotated = Vec::new();
+    for (name, home) in homes {
+        let phoenix_conf = home.join(".phoenix").join("phoenix.conf");
+        if phoenix_conf.exists() {
+            rotate_phoenix_conf(&phoenix_conf)?;
+            rotated.push(format!("phoenixd:{}", name));
+        }
+    }
+
+    let config = if config.proxy.password.is_empty() && config.proxy.read_only_password.is_empty() {
+        config
+    } else {
+        let mut config = state.config.lock().unwrap();
+        if !config.proxy.password.is_empty() {
+            config.proxy.password = token::generate()?;
+            rotated.push("proxy".to_string());
+        }
+        if !config.proxy.read_only_password.is_empty() {
+            config.proxy.read_only_password = token::generate()?;
+            rotated.push("proxy_read_only".to_string());
+        }
+        config.save(&state.data_dir)?;
+        config.clone()
+    };
+    access_links::reset(&state.data_dir)?;
+    rotated.push("access_links".to_string());
+    rotated.push("api_token".to_string());
+    if config.lan.enabled {
+        rotated.push("pairing_token".to_string());
+    }
+
+    audit::record(&state.data_dir, "credentials.rotate_all", rotated.join(","));
+    info!("🔑 Rotated {}, restarting services", rotated.join(", "));
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_all();
+    pm.set_config(config);
+    pm.start_all()?;
+    Ok(rotated)
+}
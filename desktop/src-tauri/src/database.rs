@@ -0,0 +1,81 @@
+use crate::process_manager::ProcessManager;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tracing::{error, info};
+
+/// The backend's SQLite database in the data dir
+pub const DB_FILE: &str = "dashboard.db";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizeReport {
+    /// Database plus write-ahead log, in bytes
+    pub before_bytes: u64,
+    pub after_bytes: u64,
+}
+
+impl OptimizeReport {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} → {}",
+            human_size(self.before_bytes),
+            human_size(self.after_bytes)
+        )
+    }
+}
+
+fn db_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(DB_FILE)
+}
+
+fn size_on_disk(data_dir: &Path) -> u64 {
+    ["", "-wal", "-shm"]
+        .iter()
+        .filter_map(|suffix| {
+            std::fs::metadata(data_dir.join(format!("{}{}", DB_FILE, suffix))).ok()
+        })
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn human_size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+/// Fold the WAL back in, rebuild the file without free pages and refresh
+/// the query planner's statistics. The backend is stopped meanwhile, since
+/// VACUUM needs the database to itself.
+pub fn optimize(pm: &mut ProcessManager, data_dir: &Path) -> Result<OptimizeReport, String> {
+    let before_bytes = size_on_disk(data_dir);
+    pm.stop_service("backend")?;
+
+    let result = Connection::open(db_path(data_dir))
+        .and_then(|db| db.execute_batch("PRAGMA wal_checkpoint(TRUNCATE); VACUUM; ANALYZE;"))
+        .map_err(|e| format!("Failed to optimize the database: {}", e));
+    if let Err(e) = pm.start_service("backend") {
+        error!("Failed to start backend after database maintenance: {}", e);
+    }
+    result?;
+
+    let report = OptimizeReport {
+        before_bytes,
+        after_bytes: size_on_disk(data_dir),
+    };
+    info!("🧹 Optimized the dashboard database: {}", report.summary());
+    Ok(report)
+}
+
+/// Compact, consistent copy of the live database at `dest`
+pub fn dump(data_dir: &Path, dest: &Path) -> Result<(), String> {
+    if dest.exists() {
+        return Err(format!("{:?} already exists", dest));
+    }
+    Connection::open(db_path(data_dir))
+        .and_then(|db| db.execute("VACUUM INTO ?1", [dest.to_string_lossy()]))
+        .map(|_| ())
+        .map_err(|e| format!("Failed to dump the database: {}", e))
+}
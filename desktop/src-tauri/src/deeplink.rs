@@ -0,0 +1,149 @@
+use tauri::Url;
+
+/// URI schemes the app registers itself as the OS handler for
+pub const SCHEMES: [&str; 4] = ["lightning", "lnurl", "bitcoin", APP_SCHEME];
+/// The app's own links, e.g. `phoenixd-dashboard://receive`
+pub const APP_SCHEME: &str = "phoenixd-dashboard";
+/// Launch argument opening a page, e.g. `--page=payments`
+pub const PAGE_ARG: &str = "--page";
+/// Dashboard pages links and `--page` can open
+const PAGES: [&str; 12] = [
+    "analytics",
+    "apps",
+    "channels",
+    "contacts",
+    "lnurl",
+    "node",
+    "payments",
+    "receive",
+    "recurring",
+    "send",
+    "settings",
+    "tools",
+];
+
+/// Dashboard page and query parameter that pre-fills a payment link
+#[derive(Debug, PartialEq)]
+pub struct PaymentTarget {
+    pub path: &'static str,
+    pub param: &'static str,
+    pub value: String,
+}
+
+impl PaymentTarget {
+    fn new(path: &'static str, param: &'static str, value: &str) -> Self {
+        Self {
+            path,
+            param,
+            value: value.to_string(),
+        }
+    }
+
+    /// Full dashboard URL for this target under `dashboard_url`
+    pub fn url(&self, dashboard_url: &str) -> Result<Url, String> {
+        let mut url: Url = format!("{}{}", dashboard_url.trim_end_matches('/'), self.path)
+            .parse()
+            .map_err(|e| format!("Invalid dashboard URL: {}", e))?;
+        url.query_pairs_mut().append_pair(self.param, &self.value);
+        Ok(url)
+    }
+}
+
+/// Map a `lightning:`, `lnurl:` or `bitcoin:` link to the page that pays it.
+/// BIP21 links carrying a `lightning=` fallback prefer the lightning invoice.
+pub fn parse(uri: &str) -> Option<PaymentTarget> {
+    let (scheme, rest) = uri.trim().split_once(':')?;
+    let rest = rest.trim_start_matches("//");
+
+    match scheme.to_ascii_lowercase().as_str() {
+        "lightning" => parse_lightning(rest),
+        "lnurl" => Some(PaymentTarget::new("/lnurl", "lnurl", rest)),
+        "bitcoin" => {
+            let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+            let lightning = query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| key.eq_ignore_ascii_case("lightning"))
+                .and_then(|(_, value)| parse_lightning(value));
+
+            lightning.or_else(|| {
+                (!address.is_empty()).then(|| PaymentTarget::new("/send", "btcaddress", address))
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The dashboard page a `phoenixd-dashboard://` link points to, e.g.
+/// `receive` or `settings/backup?tab=cloud`; "" is the home page
+pub fn parse_page(uri: &str) -> Option<String> {
+    let (scheme, rest) = uri.trim().split_once(':')?;
+    if !scheme.eq_ignore_ascii_case(APP_SCHEME) {
+        return None;
+    }
+    page(rest.trim_start_matches('/'))
+}
+
+/// The page a `--page=<page>` launch argument asks for
+pub fn page_arg(arg: &str) -> Option<String> {
+    arg.strip_prefix(PAGE_ARG)?
+        .strip_prefix('=')
+        .and_then(|value| page(value.trim_start_matches('/')))
+}
+
+/// `value` if it is one of `PAGES`, optionally with a sub-page and query
+fn page(value: &str) -> Option<String> {
+    let (path, query) = value.split_once('?').unwrap_or((value, ""));
+    let path = path.trim_end_matches('/').to_ascii_lowercase();
+    let first = path.split('/').next().unwrap_or_default();
+    let valid_path = path
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '-' | '_'));
+    let valid_query = query.chars().all(|c| c.is_ascii_graphic() && c != '#');
+    if !valid_path || !valid_query || !(first.is_empty() || PAGES.contains(&first)) {
+        return None;
+    }
+    Some(if query.is_empty() {
+        path
+    } else {
+        format!("{}?{}", path, query)
+    })
+}
+
+fn parse_lightning(value: &str) -> Option<PaymentTarget> {
+    let lower = value.to_ascii_lowercase();
+
+    if lower.starts_with("lnurl") {
+        Some(PaymentTarget::new("/lnurl", "lnurl", value))
+    } else if lower.starts_with("lno") {
+        Some(PaymentTarget::new("/send", "offer", value))
+    } else if value.contains('@') {
+        Some(PaymentTarget::new("/send", "address", value))
+    } else if lower.starts_with("ln") {
+        Some(PaymentTarget::new("/send", "invoice", value))
+    } else {
+        None
+    }
+}
+
+/// Recognize a bare BOLT11 invoice, BOLT12 offer or LNURL (e.g. copied text)
+/// and return it as a `lightning:` link
+pub fn detect_payment(text: &str) -> Option<String> {
+    let text = text.trim();
+    let value = text
+        .get(..10)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("lightning:"))
+        .map_or(text, |_| &text[10..]);
+
+    if value.len() < 20 || !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let lower = value.to_ascii_lowercase();
+    // bolt11 (mainnet, testnet/signet, regtest), bolt12 offers, lnurl
+    let known = ["lnbc", "lntb", "lno1", "lnurl1"];
+    known
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+        .then(|| format!("lightning:{}", value))
+}
@@ -0,0 +1,194 @@
+use crate::docker_import::ComposeProject;
+use crate::i18n::t;
+use crate::lease::Lease;
+use crate::resources::Component;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_dialog::{
+    DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult,
+};
+use tracing::warn;
+
+/// Native error dialog for a fatal startup problem. Packaged apps have no
+/// visible stdout, so this is the only place users would see it. Offers to
+/// copy the details (for a bug report) or open the logs folder.
+pub fn startup_error(app: &AppHandle, error: &str, log_dir: PathBuf) {
+    let details = format!(
+        "{}\n\nPhoenixd Dashboard {} ({} {})",
+        error,
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let copy = t("dialog.copy_details").to_string();
+    let open_logs = t("dialog.open_logs").to_string();
+
+    let handle = app.clone();
+    app.dialog()
+        .message(format!("{}\n\n{}", error, t("dialog.startup_failed_hint")))
+        .title(t("dialog.startup_failed_title"))
+        .kind(MessageDialogKind::Error)
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            copy.clone(),
+            open_logs.clone(),
+            t("dialog.close").to_string(),
+        ))
+        // Depending on the platform custom buttons come back as Yes/No or by label
+        .show_with_result(move |result| match result {
+            MessageDialogResult::Yes => copy_details(&handle, details),
+            MessageDialogResult::Custom(label) if label == copy => copy_details(&handle, details),
+            MessageDialogResult::No => open_folder(&log_dir),
+            MessageDialogResult::Custom(label) if label == open_logs => open_folder(&log_dir),
+            _ => {}
+        });
+}
+
+/// Point out what is wrong in a hand-edited config.toml and offer to open
+/// its folder
+pub fn invalid_config(app: &AppHandle, error: &str, config_dir: PathBuf) {
+    let open_folder_label = t("dialog.open_folder").to_string();
+    app.dialog()
+        .message(format!("{}\n\n{}", error, t("dialog.invalid_config_hint")))
+        .title(t("dialog.invalid_config_title"))
+        .kind(MessageDialogKind::Error)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            open_folder_label.clone(),
+            t("dialog.close").to_string(),
+        ))
+        .show_with_result(move |result| match result {
+            MessageDialogResult::Ok => open_folder(&config_dir),
+            MessageDialogResult::Custom(label) if label == open_folder_label => {
+                open_folder(&config_dir)
+            }
+            _ => {}
+        });
+}
+
+fn copy_details(app: &AppHandle, details: String) {
+    if let Err(e) = app.clipboard().write_text(details) {
+        warn!("Failed to copy error details: {}", e);
+    }
+}
+
+fn open_folder(dir: &Path) {
+    if let Err(e) = open::that(dir) {
+        warn!("Failed to open {:?}: {}", dir, e);
+    }
+}
+
+/// Tell which bundled components are missing and ask to download the ones
+/// that can be. Blocks until answered, so never call it on the main thread.
+pub fn confirm_repair(app: &AppHandle, missing: &[Component]) -> bool {
+    let labels: Vec<&str> = missing.iter().map(|c| c.label()).collect();
+    let mut body = t("dialog.repair_body").replace("{components}", &labels.join(", "));
+    if missing.iter().any(|c| !c.downloadable()) {
+        body.push_str(&format!("\n\n{}", t("dialog.repair_reinstall")));
+    }
+    if !missing.iter().any(|c| c.downloadable()) {
+        app.dialog()
+            .message(body)
+            .title(t("dialog.repair_title"))
+            .kind(MessageDialogKind::Error)
+            .blocking_show();
+        return false;
+    }
+
+    app.dialog()
+        .message(format!("{}\n\n{}", body, t("dialog.repair_download")))
+        .title(t("dialog.repair_title"))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            t("dialog.download").to_string(),
+            t("dialog.cancel").to_string(),
+        ))
+        .blocking_show()
+}
+
+/// Ask whether phoenixd should keep running after the app quits. Blocks
+/// until answered, so never call it on the main thread.
+pub fn confirm_keep_node(app: &AppHandle) -> bool {
+    app.dialog()
+        .message(t("dialog.keep_node_body"))
+        .title(t("dialog.keep_node_title"))
+        .kind(MessageDialogKind::Info)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            t("dialog.keep_node_running").to_string(),
+            t("dialog.stop_everything").to_string(),
+        ))
+        .blocking_show()
+}
+
+/// Explain what importing a Docker deployment does before doing it. Blocks
+/// until answered, so never call it on the main thread.
+pub fn confirm_docker_import(app: &AppHandle, project: &ComposeProject) -> bool {
+    let body = t("dialog.docker_import_body")
+        .replace("{dir}", &project.dir.display().to_string())
+        .replace("{chain}", &project.chain);
+    app.dialog()
+        .message(body)
+        .title(t("dialog.docker_import_title"))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            t("dialog.import").to_string(),
+            t("dialog.cancel").to_string(),
+        ))
+        .blocking_show()
+}
+
+/// Warn that another machine runs the wallet in this data dir and ask
+/// whether to start anyway. Blocks until answered, so never call it on the
+/// main thread.
+pub fn confirm_lease_override(app: &AppHandle, lease: &Lease) -> bool {
+    let body = t("dialog.lease_body")
+        .replace("{host}", &lease.hostname)
+        .replace("{time}", &lease.heartbeat_local());
+    app.dialog()
+        .message(body)
+        .title(t("dialog.lease_title"))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            t("dialog.start_anyway").to_string(),
+            t("dialog.cancel").to_string(),
+        ))
+        .blocking_show()
+}
+
+/// Warn that `provider` syncs the data dir and ask whether to move it.
+/// Blocks until answered, so never call it on the main thread.
+pub fn confirm_relocation(app: &AppHandle, provider: &str, dir: &Path) -> bool {
+    let body = t("dialog.relocate_body")
+        .replace("{provider}", provider)
+        .replace("{dir}", &dir.display().to_string());
+    app.dialog()
+        .message(body)
+        .title(t("dialog.relocate_title"))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            t("dialog.relocate_move").to_string(),
+            t("dialog.later").to_string(),
+        ))
+        .blocking_show()
+}
+
+pub fn relocation_failed(app: &AppHandle, error: &str) {
+    app.dialog()
+        .message(error)
+        .title(t("dialog.relocate_failed_title"))
+        .kind(MessageDialogKind::Error)
+        .blocking_show();
+}
+
+/// Ask before leaving watch-only mode, which re-enables payments. Blocks
+/// until answered, so never call it on the main thread.
+pub fn confirm_spending(app: &AppHandle) -> bool {
+    app.dialog()
+        .message(t("dialog.watch_only_off_body"))
+        .title(t("dialog.watch_only_off_title"))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            t("dialog.enable_payments").to_string(),
+            t("dialog.cancel").to_string(),
+        ))
+        .blocking_show()
+}
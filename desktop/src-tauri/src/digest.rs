@@ -0,0 +1,138 @@
+use crate::config::DigestConfig;
+use crate::i18n::t;
+use crate::payments_export::{self, ExportRange};
+use crate::phoenixd_api::PhoenixdClient;
+use crate::{alerts, notify, scheduler, AppState};
+use chrono::{DateTime, Local};
+use croner::Cron;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::{error, info, warn};
+
+/// Wallet activity over one period
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Digest {
+    pub period: String,
+    pub from: String,
+    pub to: String,
+    pub received_sat: u64,
+    pub received_count: usize,
+    /// Fees included
+    pub sent_sat: u64,
+    pub sent_count: usize,
+    /// Routing and liquidity fees, both directions
+    pub fees_sat: u64,
+    pub balance_sat: Option<u64>,
+}
+
+impl Digest {
+    fn title(&self) -> &'static str {
+        if self.period == "daily" {
+            t("digest.title_daily")
+        } else {
+            t("digest.title_weekly")
+        }
+    }
+
+    fn text(&self) -> String {
+        let mut text = t("digest.body")
+            .replace("{received}", &self.received_sat.to_string())
+            .replace("{received_count}", &self.received_count.to_string())
+            .replace("{sent}", &self.sent_sat.to_string())
+            .replace("{sent_count}", &self.sent_count.to_string())
+            .replace("{fees}", &self.fees_sat.to_string());
+        if let Some(balance) = self.balance_sat {
+            text.push(' ');
+            text.push_str(&t("digest.balance").replace("{balance}", &balance.to_string()));
+        }
+        text
+    }
+}
+
+/// Next digest, if enabled
+pub fn next_run(config: &DigestConfig) -> Option<DateTime<Local>> {
+    if !config.enabled {
+        return None;
+    }
+    let cron = Cron::new(&config.schedule()).parse().ok()?;
+    cron.find_next_occurrence(&Local::now(), false).ok()
+}
+
+/// Sum up the settled payments of the period ending now
+pub fn compute(app: &AppHandle, config: &DigestConfig) -> Result<Digest, String> {
+    let state = app.state::<AppState>();
+    let client = PhoenixdClient::new(&state.data_dir);
+    let to = Local::now();
+    let from = to - config.span();
+    let range = ExportRange {
+        from: Some(from.timestamp_millis()),
+        to: Some(to.timestamp_millis()),
+    };
+    let records = payments_export::fetch_with(&client, &range, |_, _| Ok(()))?;
+    let paid = || records.iter().filter(|record| record.status == "paid");
+    let (received, sent): (Vec<_>, Vec<_>) =
+        paid().partition(|record| record.direction == "incoming");
+    let balance = tauri::async_runtime::block_on(client.get_balance()).ok();
+    Ok(Digest {
+        period: config.period.clone(),
+        from: from.to_rfc3339(),
+        to: to.to_rfc3339(),
+        received_sat: received.iter().map(|record| record.amount_sat).sum(),
+        received_count: received.len(),
+        sent_sat: sent.iter().map(|record| record.amount_sat).sum(),
+        sent_count: sent.len(),
+        fees_sat: paid().map(|record| record.fees_msat).sum::<u64>() / 1000,
+        balance_sat: balance.map(|balance| balance.balance_sat),
+    })
+}
+
+/// Compute the digest and hand it to every channel `digest` turns on
+pub fn send(app: &AppHandle) -> Result<Digest, String> {
+    let state = app.state::<AppState>();
+    let (config, alerts) = {
+        let config = state.config.lock().unwrap();
+        (config.digest.clone(), config.alerts.clone())
+    };
+    let digest = compute(app, &config)?;
+    info!("📊 {}: {}", digest.title(), digest.text());
+
+    if config.notify {
+        notify::native(app.clone())(digest.title(), &digest.text());
+    }
+    if config.webhook && !alerts.webhook_url.is_empty() {
+        let mut payload = serde_json::to_value(&digest).unwrap_or_default();
+        payload["event"] = "digest".into();
+        if let Err(e) = alerts::send_webhook(&alerts.webhook_url, &payload) {
+            warn!("Failed to deliver the digest webhook: {}", e);
+        }
+    }
+    if config.telegram && !alerts.telegram_chat_id.is_empty() {
+        let text = format!("⚡ {}\n{}", digest.title(), digest.text());
+        if let Err(e) = alerts::send_telegram(&alerts.telegram_chat_id, &text) {
+            warn!("{}", e);
+        }
+    }
+    Ok(digest)
+}
+
+/// Send the digest on its schedule in the background
+pub fn spawn(app: AppHandle) {
+    let read_config = {
+        let app = app.clone();
+        move || {
+            app.state::<AppState>()
+                .config
+                .lock()
+                .unwrap()
+                .digest
+                .clone()
+        }
+    };
+
+    scheduler::run_on_cron(read_config, next_run, move |_, _| {
+        if let Err(e) = send(&app) {
+            error!("Failed to send the activity digest: {}", e);
+        }
+    });
+}
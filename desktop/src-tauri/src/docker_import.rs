@@ -0,0 +1,330 @@
+use crate::operations::Handle;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::{audit, journal, AppState};
+use serde::Serialize;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tracing::{error, info, warn};
+
+const COMPOSE_FILES: [&str; 4] = [
+    "docker-compose.yml",
+    "docker-compose.yaml",
+    "compose.yml",
+    "compose.yaml",
+];
+/// Where the compose file bind-mounts phoenixd's `.phoenix`
+const COMPOSE_PHOENIX_DIR: &str = "data/phoenixd";
+/// Port the compose file publishes phoenixd on
+const COMPOSE_PHOENIXD_PORT: u16 = 9740;
+const PAGE_SIZE: usize = 100;
+/// How long the imported node gets to come up
+const START_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[cfg(test)]
+mod tests;
+
+/// A docker-compose deployment of the dashboard
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposeProject {
+    pub dir: PathBuf,
+    /// phoenixd's data, with `seed.dat` and `phoenix.conf`
+    pub phoenix_dir: PathBuf,
+    /// From the names of phoenixd's databases
+    pub chain: String,
+}
+
+/// What a node holds, compared before and after the move
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeSnapshot {
+    pub node_id: String,
+    pub balance_sat: u64,
+    pub incoming: usize,
+    pub outgoing: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub node: NodeSnapshot,
+    /// False when the Docker node wasn't running, so there was nothing to
+    /// compare the imported one with
+    pub verified: bool,
+    /// Where the app's own phoenixd data was moved, if it had any
+    pub previous_data: Option<PathBuf>,
+}
+
+/// Find the compose project in `dir` and the phoenixd data it mounts
+pub fn detect(dir: &Path) -> Result<ComposeProject, String> {
+    if !COMPOSE_FILES.iter().any(|name| dir.join(name).is_file()) {
+        return Err(format!("No docker-compose file in {:?}", dir));
+    }
+    let phoenix_dir = dir.join(COMPOSE_PHOENIX_DIR);
+    if !phoenix_dir.join("seed.dat").is_file() {
+        return Err(format!("No phoenixd seed in {:?}", phoenix_dir));
+    }
+    let chain = std::fs::read_dir(&phoenix_dir)
+        .map_err(|e| format!("Failed to read {:?}: {}", phoenix_dir, e))?
+        .flatten()
+        .find_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let mut parts = name.strip_suffix(".db")?.split('.');
+            (parts.next()? == "phoenix").then(|| parts.next().map(String::from))?
+        })
+        .unwrap_or_else(|| "mainnet".to_string());
+    Ok(ComposeProject {
+        dir: dir.to_path_buf(),
+        phoenix_dir,
+        chain,
+    })
+}
+
+fn count_payments(client: &PhoenixdClient, direction: &str, op: &Handle) -> Result<usize, String> {
+    let mut count = 0;
+    loop {
+        op.check()?;
+        let page = tauri::async_runtime::block_on(
+            client.payments_page(direction, None, None, PAGE_SIZE, count),
+        )?;
+        count += page.len();
+        if page.len() < PAGE_SIZE {
+            return Ok(count);
+        }
+    }
+}
+
+fn snapshot(client: &PhoenixdClient, op: &Handle) -> Result<NodeSnapshot, String> {
+    let (node_id, balance) = tauri::async_runtime::block_on(async {
+        Ok::<_, String>((client.node_id().await?, client.get_balance().await?))
+    })?;
+    Ok(NodeSnapshot {
+        node_id,
+        balance_sat: balance.balance_sat,
+        incoming: count_payments(client, "incoming", op)?,
+        outgoing: count_payments(client, "outgoing", op)?,
+    })
+}
+
+fn compose_stop(dir: &Path) -> Result<(), String> {
+    let output = Command::new("docker")
+        .args(["compose", "stop"])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to run docker compose: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+pub(crate) fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(to).map_err(|e| format!("Failed to create {:?}: {}", to, e))?;
+    let entries =
+        std::fs::read_dir(from).map_err(|e| format!("Failed to read {:?}: {}", from, e))?;
+    for entry in entries.flatten() {
+        let target = to.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)
+                .map_err(|e| format!("Failed to copy {:?}: {}", entry.path(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy the project's phoenixd data in place of the app's, which is moved
+/// aside rather than deleted
+fn swap_in(data_dir: &Path, phoenix_dir: &Path) -> Result<Option<PathBuf>, String> {
+    let current = data_dir.join(".phoenix");
+    let staging = data_dir.join(".phoenix.importing");
+    let _ = std::fs::remove_dir_all(&staging);
+    if let Err(e) = copy_dir(phoenix_dir, &staging) {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+    let aside = current.exists().then(|| {
+        data_dir.join(format!(
+            ".phoenix.before-import-{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ))
+    });
+    if let Some(aside) = &aside {
+        std::fs::rename(&current, aside)
+            .map_err(|e| format!("Failed to move {:?} aside: {}", current, e))?;
+    }
+    std::fs::rename(&staging, &current)
+        .map_err(|e| format!("Failed to move the imported data in place: {}", e))?;
+    Ok(aside)
+}
+
+/// Put the app's own phoenixd data back after a failed import
+fn swap_back(data_dir: &Path, aside: Option<&Path>) {
+    let current = data_dir.join(".phoenix");
+    let _ = std::fs::remove_dir_all(&current);
+    if let Some(aside) = aside {
+        if let Err(e) = std::fs::rename(aside, &current) {
+            error!("Failed to put {:?} back: {}", aside, e);
+        }
+    }
+}
+
+fn set_chain(app: &AppHandle, chain: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut config = state.config.lock().unwrap();
+    config.phoenixd.chain = chain.to_string();
+    config.save(&state.data_dir)?;
+    state
+        .process_manager
+        .lock()
+        .unwrap()
+        .set_config(config.clone());
+    Ok(())
+}
+
+/// Wait for the imported node to answer and match what the Docker one held
+fn verify(
+    app: &AppHandle,
+    before: Option<&NodeSnapshot>,
+    op: &Handle,
+) -> Result<NodeSnapshot, String> {
+    let client = PhoenixdClient::new(&app.state::<AppState>().data_dir);
+    let started = Instant::now();
+    let after = loop {
+        match snapshot(&client, op) {
+            Ok(after) => break after,
+            Err(e) if started.elapsed() >= START_TIMEOUT => {
+                return Err(format!("The imported node didn't come up: {}", e))
+            }
+            Err(_) => op.sleep(Duration::from_secs(2))?,
+        }
+    };
+    match before {
+        Some(before) if *before != after => Err(format!(
+            "The imported node doesn't match the Docker one: {} sat, {} incoming and {} outgoing payments instead of {} sat, {} and {}",
+            after.balance_sat, after.incoming, after.outgoing, before.balance_sat, before.incoming, before.outgoing
+        )),
+        _ => Ok(after),
+    }
+}
+
+/// Move a docker-compose deployment's node into the app: stop the Docker
+/// stack, copy its phoenixd data over the app's and check the node comes
+/// back with the same balance and payment history. On a mismatch the
+/// app's previous data is put back.
+///
+/// The compose project's postgres volume stays where it is; the app's
+/// backend keeps its own database.
+pub fn import(app: &AppHandle, dir: &Path, op: &Handle) -> Result<ImportReport, String> {
+    let state = app.state::<AppState>();
+    let project = detect(dir)?;
+    info!("🐳 Importing the Docker deployment in {:?}", project.dir);
+    audit::record(
+        &state.data_dir,
+        "docker.import",
+        project.dir.to_string_lossy(),
+    );
+
+    op.progress("Stopping the app's node");
+    {
+        let mut pm = state.process_manager.lock().unwrap();
+        pm.stop_service("backend")?;
+        pm.stop_service("phoenixd")?;
+    }
+    let previous_chain = state.config.lock().unwrap().phoenixd.chain.clone();
+    let restart = |app: &AppHandle| {
+        let state = app.state::<AppState>();
+        let mut pm = state.process_manager.lock().unwrap();
+        for name in ["phoenixd", "backend"] {
+            if let Err(e) = pm.start_service(name) {
+                error!("Failed to start {} after the import: {}", name, e);
+            }
+        }
+    };
+
+    let docker_addr = SocketAddr::from((Ipv4Addr::LOCALHOST, COMPOSE_PHOENIXD_PORT));
+    let docker = PhoenixdClient::with_conf(&project.phoenix_dir.join("phoenix.conf"), docker_addr);
+    op.progress("Reading the Docker node");
+    let before = snapshot(&docker, op).ok();
+    if before.is_none() {
+        warn!("The Docker node isn't answering, the import can't be compared with it");
+    }
+
+    op.progress("Stopping the Docker deployment");
+    if let Err(e) = compose_stop(&project.dir) {
+        // Never run the same seed twice
+        if tauri::async_runtime::block_on(docker.node_id()).is_ok() {
+            restart(app);
+            return Err(format!(
+                "Could not stop the Docker deployment, run `docker compose stop` in {:?} first: {}",
+                project.dir, e
+            ));
+        }
+    }
+
+    op.progress("Copying phoenixd data");
+    let aside = match swap_in(&state.data_dir, &project.phoenix_dir) {
+        Ok(aside) => aside,
+        Err(e) => {
+            restart(app);
+            return Err(e);
+        }
+    };
+    op.progress("Starting the imported node");
+    let verified = set_chain(app, &project.chain)
+        .and_then(|()| {
+            state
+                .process_manager
+                .lock()
+                .unwrap()
+                .start_service("phoenixd")
+        })
+        .and_then(|()| verify(app, before.as_ref(), op));
+    let node = match verified {
+        Ok(node) => node,
+        Err(e) => {
+            error!("🐳 Import failed, putting the previous node back: {}", e);
+            let _ = state
+                .process_manager
+                .lock()
+                .unwrap()
+                .stop_service("phoenixd");
+            swap_back(&state.data_dir, aside.as_deref());
+            if let Err(e) = set_chain(app, &previous_chain) {
+                error!("{}", e);
+            }
+            restart(app);
+            journal::record(
+                &state.data_dir,
+                "docker.import_failed",
+                Some("phoenixd"),
+                &e,
+            );
+            return Err(e);
+        }
+    };
+    restart(app);
+
+    journal::record(
+        &state.data_dir,
+        "docker.imported",
+        Some("phoenixd"),
+        format!("{} from {}", node.node_id, project.dir.display()),
+    );
+    info!(
+        "🐳 Imported node {} with {} sat and {} payments",
+        node.node_id,
+        node.balance_sat,
+        node.incoming + node.outgoing
+    );
+    Ok(ImportReport {
+        node,
+        verified: before.is_some(),
+        previous_data: aside,
+    })
+}
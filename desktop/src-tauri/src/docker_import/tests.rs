@@ -0,0 +1,80 @@
+//! Reads throwaway compose projects. Stopping one needs Docker, so that
+//! test is ignored by default: `cargo test -- --ignored`
+
+use super::*;
+
+/// An empty directory, removed when dropped
+struct Scratch(PathBuf);
+
+impl Scratch {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "phoenixd-dashboard-{}-{}-{}",
+            name,
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        Scratch(dir)
+    }
+
+    fn write(&self, path: &str, content: &str) {
+        let path = self.0.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn detects_the_chain_from_phoenixd_databases() {
+    let project = Scratch::new("compose");
+    project.write("compose.yaml", "services: {}\n");
+    project.write("data/phoenixd/seed.dat", "seed");
+    project.write("data/phoenixd/phoenix.testnet.7f3a.db", "");
+
+    let detected = detect(&project.0).unwrap();
+    assert_eq!(detected.phoenix_dir, project.0.join(COMPOSE_PHOENIX_DIR));
+    assert_eq!(detected.chain, "testnet");
+}
+
+#[test]
+fn needs_a_compose_file_and_a_seed() {
+    let project = Scratch::new("compose");
+    project.write("data/phoenixd/seed.dat", "seed");
+    assert!(detect(&project.0).is_err());
+
+    let project = Scratch::new("compose");
+    project.write("docker-compose.yml", "services: {}\n");
+    assert!(detect(&project.0).is_err());
+}
+
+#[test]
+#[ignore = "needs Docker"]
+fn compose_stop_stops_every_container() {
+    let project = Scratch::new("compose");
+    project.write(
+        "compose.yaml",
+        "services:\n  idle:\n    image: busybox\n    command: sleep 600\n",
+    );
+    let compose = |args: &[&str]| {
+        Command::new("docker")
+            .arg("compose")
+            .args(args)
+            .current_dir(&project.0)
+            .output()
+            .unwrap()
+    };
+    assert!(compose(&["up", "-d"]).status.success());
+
+    let stopped = compose_stop(&project.0);
+    let running = compose(&["ps", "-q", "--status", "running"]);
+    compose(&["down", "--timeout", "0"]);
+    stopped.unwrap();
+    assert!(running.stdout.is_empty());
+}
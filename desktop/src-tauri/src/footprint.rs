@@ -0,0 +1,126 @@
+use crate::{battery, AppState};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tauri::{AppHandle, Manager};
+use tracing::info;
+
+const INTERVAL: Duration = Duration::from_secs(30);
+/// How much longer the pollers wait while throttled
+const SLOWDOWN: f64 = 3.0;
+/// Load has to drop this far below `polling.busy_cpu_percent` before the
+/// pollers speed up again, so they don't flap around the threshold
+const HYSTERESIS: f32 = 10.0;
+
+/// What the app itself costs, next to how busy the computer is
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Footprint {
+    /// Of one core, so above 100 on several
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+    pub system_cpu_percent: f32,
+    pub on_battery: bool,
+    /// Whether the pollers currently wait longer
+    pub throttled: bool,
+    /// Times each poller woke up since the app started
+    pub wakeups: BTreeMap<String, u64>,
+    pub sampled_at: String,
+}
+
+static LAST: Mutex<Option<Footprint>> = Mutex::new(None);
+static WAKEUPS: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+
+/// Count a wakeup of `poller`
+pub fn woke(poller: &str) {
+    *WAKEUPS
+        .lock()
+        .unwrap()
+        .entry(poller.to_string())
+        .or_default() += 1;
+}
+
+/// The latest sample, with up to date wakeup counts
+pub fn current() -> Footprint {
+    let mut footprint = LAST.lock().unwrap().clone().unwrap_or_default();
+    footprint.wakeups = WAKEUPS.lock().unwrap().clone();
+    footprint
+}
+
+/// Factor the pollers stretch their waits by: `SLOWDOWN` while throttled
+pub fn slowdown() -> f64 {
+    if LAST
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|last| last.throttled)
+    {
+        SLOWDOWN
+    } else {
+        1.0
+    }
+}
+
+fn sample(system: &mut System, was_throttled: bool, adaptive: bool, busy_cpu: f32) -> Footprint {
+    let pid = Pid::from_u32(std::process::id());
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system.refresh_cpu_usage();
+    let process = system.process(pid);
+    let system_cpu_percent = system.global_cpu_usage();
+    let on_battery = battery::on_battery().unwrap_or(false);
+    let busy_above = if was_throttled {
+        busy_cpu - HYSTERESIS
+    } else {
+        busy_cpu
+    };
+    Footprint {
+        cpu_percent: process.map_or(0.0, |p| p.cpu_usage()),
+        memory_bytes: process.map_or(0, |p| p.memory()),
+        system_cpu_percent,
+        on_battery,
+        throttled: adaptive && (on_battery || system_cpu_percent > busy_above),
+        wakeups: BTreeMap::new(),
+        sampled_at: chrono::Local::now().to_rfc3339(),
+    }
+}
+
+/// Sample the app's CPU and memory every so often and, with
+/// `polling.adaptive`, throttle the pollers on battery or under load
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        // CPU usage is measured between two refreshes
+        let mut system = System::new();
+        loop {
+            std::thread::sleep(INTERVAL);
+            let polling = app
+                .state::<AppState>()
+                .config
+                .lock()
+                .unwrap()
+                .polling
+                .clone();
+            let was_throttled = slowdown() > 1.0;
+            let footprint = sample(
+                &mut system,
+                was_throttled,
+                polling.adaptive,
+                polling.busy_cpu_percent as f32,
+            );
+            if footprint.throttled != was_throttled {
+                info!(
+                    "🐢 Background polling {} (system CPU {:.0}%, on battery: {})",
+                    if footprint.throttled {
+                        "slowed down"
+                    } else {
+                        "back to normal"
+                    },
+                    footprint.system_cpu_percent,
+                    footprint.on_battery
+                );
+            }
+            *LAST.lock().unwrap() = Some(footprint);
+        }
+    });
+}
@@ -0,0 +1,163 @@
+use crate::config::HealthCheck;
+use crate::{scheduler, AppState};
+use std::collections::HashMap;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+const TICK: Duration = Duration::from_secs(1);
+
+/// Whether each service with configured checks passes them
+static VERDICTS: Mutex<Option<HashMap<String, bool>>> = Mutex::new(None);
+
+/// The configured checks' verdict on a service; None when it has none, so
+/// the built-in port probe applies
+pub fn verdict(service: &str) -> Option<bool> {
+    VERDICTS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|verdicts| verdicts.get(service).copied())
+}
+
+/// Run one check once
+pub fn probe(check: &HealthCheck) -> Result<(), String> {
+    let timeout = Duration::from_secs(check.timeout_secs);
+    match check.kind.as_str() {
+        "http" => {
+            let status = match ureq::get(&check.target).timeout(timeout).call() {
+                Ok(response) => response.status(),
+                Err(ureq::Error::Status(status, _)) => status,
+                Err(e) => return Err(format!("{} is not reachable: {}", check.target, e)),
+            };
+            if status == check.expected_status {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{} answered {} instead of {}",
+                    check.target, status, check.expected_status
+                ))
+            }
+        }
+        "tcp" => {
+            let addr = check
+                .target
+                .to_socket_addrs()
+                .ok()
+                .and_then(|mut addrs| addrs.next())
+                .ok_or_else(|| format!("Can't resolve {}", check.target))?;
+            #[cfg(feature = "test-hooks")]
+            if crate::test_hooks::blackholed(addr) {
+                return Err(format!("{} is blackholed", check.target));
+            }
+            TcpStream::connect_timeout(&addr, timeout)
+                .map(|_| ())
+                .map_err(|e| format!("{} refused the connection: {}", check.target, e))
+        }
+        _ => run_command(&check.target, timeout),
+    }
+}
+
+fn run_command(command_line: &str, timeout: Duration) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c");
+        command
+    };
+    let mut child = command
+        .arg(command_line)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run '{}': {}", command_line, e))?;
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(format!("'{}' exited with {}", command_line, status)),
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("'{}' timed out", command_line));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(format!("Failed to wait for '{}': {}", command_line, e)),
+        }
+    }
+}
+
+/// When a check runs next and how often it failed in a row
+struct Tracker {
+    next_run: Instant,
+    failures: u32,
+}
+
+fn key(check: &HealthCheck) -> String {
+    format!("{}|{}|{}", check.service, check.kind, check.target)
+}
+
+/// Run the configured checks on their own intervals and publish a verdict
+/// per service for the watchdog and tray. A service turns unhealthy once
+/// any of its checks failed `failure_threshold` times in a row.
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut trackers: HashMap<String, Tracker> = HashMap::new();
+        loop {
+            let (checks, jitter) = {
+                let state = app.state::<AppState>();
+                let config = state.config.lock().unwrap();
+                let checks = match config.health.validate() {
+                    Ok(()) => config.health.checks.clone(),
+                    Err(_) => Vec::new(),
+                };
+                (checks, config.polling.jitter_percent)
+            };
+            trackers.retain(|key_, _| checks.iter().any(|check| key(check) == *key_));
+
+            let mut verdicts: HashMap<String, bool> = HashMap::new();
+            for check in &checks {
+                let tracker = trackers.entry(key(check)).or_insert(Tracker {
+                    next_run: Instant::now(),
+                    failures: 0,
+                });
+                if tracker.next_run <= Instant::now() {
+                    match probe(check) {
+                        Ok(()) => {
+                            if tracker.failures >= check.failure_threshold {
+                                info!("🩺 {} passes its {} check again", check.service, check.kind);
+                            }
+                            tracker.failures = 0;
+                        }
+                        Err(e) => {
+                            tracker.failures += 1;
+                            if tracker.failures == check.failure_threshold {
+                                warn!(
+                                    "🩺 {} failed its {} check: {}",
+                                    check.service, check.kind, e
+                                );
+                            }
+                        }
+                    }
+                    tracker.next_run = Instant::now()
+                        + scheduler::jittered(Duration::from_secs(check.interval_secs), jitter);
+                }
+                let healthy = tracker.failures < check.failure_threshold;
+                verdicts
+                    .entry(check.service.clone())
+                    .and_modify(|all| *all &= healthy)
+                    .or_insert(healthy);
+            }
+            *VERDICTS.lock().unwrap() = Some(verdicts);
+            std::thread::sleep(TICK);
+        }
+    });
+}
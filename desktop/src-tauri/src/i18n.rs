@@ -0,0 +1,1080 @@
+use std::sync::RwLock;
+
+/// Languages the shell is translated into, matching the dashboard's locale codes
+pub const LANGUAGES: [&str; 5] = ["en", "pt", "es", "de", "fr"];
+
+static CURRENT: RwLock<&str> = RwLock::new("en");
+
+/// Pick the shell language: the configured one, else the OS locale, else English
+pub fn init(configured: &str) {
+    let language = if configured.is_empty() {
+        sys_locale::get_locale().unwrap_or_default()
+    } else {
+        configured.to_string()
+    };
+    set(&language);
+}
+
+/// Switch language; accepts full locales like `pt-BR`, unknown ones fall back to English
+pub fn set(language: &str) {
+    let code = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    *CURRENT.write().unwrap() = LANGUAGES
+        .iter()
+        .find(|&&l| l == code)
+        .copied()
+        .unwrap_or("en");
+}
+
+pub fn current() -> &'static str {
+    *CURRENT.read().unwrap()
+}
+
+/// Translate `key` into the current language, falling back to English
+pub fn t(key: &str) -> &'static str {
+    lookup(table(current()), key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or("")
+}
+
+fn lookup(table: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+fn table(language: &str) -> &'static [(&'static str, &'static str)] {
+    match language {
+        "pt" => PT,
+        "es" => ES,
+        "de" => DE,
+        "fr" => FR,
+        _ => EN,
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("tray.open_dashboard", "Open Dashboard"),
+    ("tray.copy_node_id", "Copy Node ID"),
+    ("tray.node_info", "Node Info"),
+    ("tray.status_window", "Status Window"),
+    ("tray.phoenixd_update", "phoenixd {version} available – Upgrade"),
+    ("tray.clock_skew", "Clock is off by {secs}s – check time sync"),
+    ("tray.relocate_data_dir", "Data folder synced by {provider} – Move…"),
+    ("tray.phoenixd_upgrading", "Upgrading phoenixd to {version}…"),
+    ("tray.safe_mode", "Safe mode…"),
+    ("tray.new_invoice", "New Invoice (copies to clipboard)"),
+    ("tray.pay_copied", "Pay Copied Invoice"),
+    ("tray.watch_only", "Watch-Only Mode"),
+    ("tray.offline", "Offline — no internet connection"),
+    ("tray.node_over_tor", "Node traffic goes over Tor"),
+    ("tray.tor_new_identity", "New Tor identity"),
+    ("tray.active_node", "Node"),
+    ("tray.recent_payments", "Recent payments"),
+    ("tray.needs_attention", "Needs attention"),
+    ("watchtower.stuck", "Payment pending for {minutes} min"),
+    ("watchtower.expiring", "Invoice of {amount} sat expires in {minutes} min"),
+    ("tray.copy_public_url", "Copy Public URL"),
+    ("tray.copy_tailscale_url", "Copy Tailscale URL"),
+    ("tray.lan_pairing", "Connect Phone (LAN)"),
+    ("tray.access_qr", "Show Access QR Codes"),
+    ("tray.share_access", "Share Temporary Access…"),
+    ("tray.unblock", "Unblock Remote Clients"),
+    ("tray.services", "Services"),
+    ("tray.open", "Open"),
+    ("tray.open_data", "Data Folder"),
+    ("tray.open_logs", "Logs Folder"),
+    ("tray.open_config", "Settings (config.toml)"),
+    ("tray.open_phoenixd_config", "phoenixd Config (phoenix.conf)"),
+    ("tray.next_maintenance", "Next maintenance"),
+    ("selftest.passed", "Self-test: all checks passed"),
+    ("selftest.failed", "Self-test: {count} failed (run again)"),
+    ("selftest.notify_title", "Self-test found problems"),
+    ("node_event.peer_connected", "Connected to the Lightning peer"),
+    ("node_event.peer_disconnected", "Disconnected from the Lightning peer"),
+    ("node_event.channel_opened", "Channel opened"),
+    ("node_event.liquidity_purchased", "Liquidity purchased"),
+    ("node_event.swap_in_confirmed", "Swap-in confirmed"),
+    ("tray.restart", "Restart"),
+    ("tray.reload_config", "Reload Config"),
+    ("tray.optimize_database", "Optimize Database"),
+    ("tray.export_logs", "Export Logs…"),
+    ("tray.export_payments", "Export Payments…"),
+    ("tray.import_docker", "Import Docker Deployment…"),
+    ("tray.nwc", "Nostr Wallet Connect"),
+    ("tray.nwc_pair", "New NWC Connection…"),
+    ("tray.store_connect", "Connect a Store…"),
+    ("tray.pause_node", "Pause Node"),
+    ("tray.resume_node", "Resume Node"),
+    ("tray.node_paused", "Node paused"),
+    ("tray.restarting", "Restarting…"),
+    ("tray.working", "working…"),
+    ("tray.job_done", "Done"),
+    ("tray.job_failed", "Failed"),
+    ("tray.quit", "Quit"),
+    ("service.running", "running"),
+    ("service.unhealthy", "unhealthy"),
+    ("service.stopped", "stopped"),
+    ("service.start", "Start"),
+    ("service.stop", "Stop"),
+    ("service.restart", "Restart"),
+    ("service.logs", "View Logs"),
+    ("window.copy", "Copy"),
+    ("window.copied", "Copied"),
+    ("window.receive", "Receive"),
+    ("window.any_amount", "Any amount"),
+    ("window.access_urls", "Access URLs"),
+    ("window.lan_pairing", "Connect over LAN"),
+    ("window.lan_pairing_hint", "Scan with a device on the same network"),
+    ("window.nwc_pairing", "Nostr Wallet Connect"),
+    ("window.nwc_pairing_hint", "Scan with a Nostr client to pair {name}. This code is shown only once."),
+    ("nwc.connection", "Connection"),
+    ("window.store_connect", "Connect a Store"),
+    ("window.access_links", "Temporary Access"),
+    ("access_links.needs_proxy", "Temporary links go through the reverse proxy. Turn it on first."),
+    ("access_links.created", "New link, valid until {expires}."),
+    ("access_links.create", "New link valid for"),
+    ("access_links.label", "Who is it for? (optional)"),
+    ("access_links.full", "full access"),
+    ("access_links.hours", "{hours} h"),
+    ("access_links.active", "Active links"),
+    ("access_links.none", "No active links"),
+    ("access_links.expires", "Expires"),
+    ("access_links.last_used", "Last used"),
+    ("access_links.never", "never"),
+    ("access_links.revoke", "Revoke"),
+    ("store.btcpay", "BTCPay Server"),
+    ("store.lnbits", "LNbits"),
+    ("store.receive_only", "Receive only: the store can create invoices and read payments, but not send."),
+    ("store.full_access", "Full access: the store can also send payments."),
+    ("window.node_info", "Node Info"),
+    ("window.status", "Status"),
+    ("window.actions", "Actions"),
+    ("window.no_tray", "The system tray isn't available, so the app is controlled from this window. The app's log says how to enable the tray on this desktop."),
+    ("window.crashes", "crashes"),
+    ("window.logs", "{service} log"),
+    ("window.no_log", "Nothing logged yet."),
+    ("node_info.node_id", "Node ID"),
+    ("node_info.version", "Version"),
+    ("node_info.chain", "Chain"),
+    ("node_info.block_height", "Block height"),
+    ("node_info.channels", "Active / total channels"),
+    ("node_info.balance", "Balance"),
+    ("node_info.inbound_liquidity", "Inbound liquidity"),
+    ("node_info.fee_credit", "Fee credit"),
+    ("safe_mode.title", "Safe mode"),
+    ("safe_mode.reason", "{service} crashed {crashes} times in a short time. Only the app is running."),
+    ("safe_mode.hint", "Check the logs, restore the latest backup or reset the settings, then start the services again."),
+    ("safe_mode.view_logs", "View logs"),
+    ("safe_mode.restore_backup", "Restore latest backup"),
+    ("safe_mode.reset_config", "Reset settings"),
+    ("safe_mode.resume", "Start services"),
+    ("startup.title", "Starting Phoenixd Dashboard"),
+    ("startup.phoenixd", "Starting phoenixd…"),
+    ("startup.backend", "Starting backend…"),
+    ("startup.frontend", "Starting dashboard…"),
+    ("startup.remote_access", "Setting up remote access…"),
+    ("startup.repairing", "Downloading missing components…"),
+    ("startup.waiting_backend", "Waiting for backend…"),
+    ("startup.waiting_frontend", "Waiting for dashboard…"),
+    ("access.local", "This computer"),
+    ("access.lan", "Local network"),
+    ("access.tor", "Tor onion service"),
+    ("access.cloudflare", "Cloudflare tunnel"),
+    ("access.tailscale", "Tailscale"),
+    ("notify.invoice_title", "Lightning invoice detected"),
+    ("notify.invoice_body", "Pay with Phoenixd Dashboard? Choose \"Pay Copied Invoice\" in the tray menu."),
+    ("notify.blocked_title", "Remote access blocked"),
+    ("notify.blocked_body", "{ip} was locked out after repeated failed logins"),
+    ("notify.autoheal_title", "Automatic restart paused"),
+    ("notify.autoheal_body", "{service} keeps failing after {attempts} restarts in the last hour. Check its logs."),
+    ("notify.bandwidth_title", "Bandwidth threshold exceeded"),
+    ("notify.bandwidth_body", "Tor and Cloudflare used {used} MB, over your {limit} MB threshold"),
+    ("notify.phoenixd_update_title", "phoenixd update available"),
+    ("notify.phoenixd_update_body", "phoenixd {latest} is out, you run {current}. Upgrade from the tray menu."),
+    ("notify.clock_skew_title", "System clock is off"),
+    ("notify.clock_skew_body", "Your clock is {secs}s off from internet time. Lightning channels can fail on a wrong clock; turn on automatic time sync."),
+    ("digest.title_daily", "Daily summary"),
+    ("digest.title_weekly", "Weekly summary"),
+    ("digest.body", "Received {received} sat ({received_count} payments), sent {sent} sat ({sent_count} payments), {fees} sat in fees."),
+    ("digest.balance", "Balance: {balance} sat."),
+    ("notify.lease_lost_title", "Wallet started on another computer"),
+    ("notify.dashboard_starting_title", "Dashboard is starting"),
+    ("notify.dashboard_starting_body", "It opens as soon as it is ready"),
+    ("notify.dashboard_not_ready", "The dashboard didn't come up. Check the services in the tray menu."),
+    ("notify.payment_stuck_title", "Payment stuck"),
+    ("notify.payment_stuck_body", "An outgoing payment has been pending for {minutes} minutes. Open it from the tray menu."),
+    ("notify.invoice_expiring_title", "Invoice about to expire"),
+    ("notify.invoice_expiring_body", "An unpaid invoice of {amount} sat expires in {minutes} minutes. Open it from the tray menu."),
+    ("notify.safe_mode_title", "Safe mode"),
+    ("notify.safe_mode_body", "{service} kept crashing, so all services were stopped"),
+    ("dialog.startup_failed_title", "Phoenixd Dashboard could not start"),
+    ("dialog.startup_failed_hint", "The dashboard won't work until this is fixed. The logs have more details."),
+    ("dialog.copy_details", "Copy Details"),
+    ("dialog.open_logs", "Open Logs"),
+    ("dialog.close", "Close"),
+    ("dialog.invalid_config_title", "Invalid Configuration"),
+    ("dialog.invalid_config_hint", "Fix config.toml and reload it from the tray. Until then the running services keep their current settings."),
+    ("dialog.open_folder", "Open Folder"),
+    ("dialog.watch_only_off_title", "Leave watch-only mode?"),
+    ("dialog.lease_title", "Wallet in use on another computer"),
+    ("dialog.lease_body", "{host} is running this wallet (last seen at {time}). Running the same wallet on two computers, e.g. through a synced folder, can lose funds.\n\nOnly start it here if {host} is off or no longer uses this data folder."),
+    ("dialog.start_anyway", "Start anyway"),
+    ("dialog.watch_only_off_body", "Anyone with access to the dashboard will be able to send payments again."),
+    ("dialog.enable_payments", "Enable Payments"),
+    ("dialog.keep_node_title", "Quit Phoenixd Dashboard"),
+    ("dialog.keep_node_body", "Keep the Lightning node running after quitting? It keeps receiving payments, and the app reconnects to it the next time it starts."),
+    ("dialog.keep_node_running", "Keep Node Running"),
+    ("dialog.stop_everything", "Stop Everything"),
+    ("dialog.cancel", "Cancel"),
+    ("dialog.docker_import_title", "Import Docker deployment"),
+    ("dialog.docker_import_body", "The Docker deployment in {dir} will be stopped and its {chain} node moved into the app. The app's current node data is kept aside, not deleted. The node's balance and payment history are checked after the move; if they don't match, the previous node comes back.\n\nDashboard data kept in the Docker postgres database (contacts, notes) is not imported."),
+    ("dialog.import", "Import"),
+    ("dialog.repair_title", "Missing components"),
+    ("dialog.repair_body", "These components are missing from the app: {components}."),
+    ("dialog.repair_download", "Download them now?"),
+    ("dialog.repair_reinstall", "They are built into the app; reinstall Phoenixd Dashboard to restore them."),
+    ("dialog.download", "Download"),
+    ("dialog.relocate_title", "Data folder is synced"),
+    ("dialog.relocate_body", "The wallet's data folder {dir} is synced by {provider}. Sync clients corrupt the node's databases and channel state, and can start the same wallet on another computer, which can lose funds.\n\nMove it to a folder on this computer? The services stop while it is copied and the app restarts."),
+    ("dialog.relocate_move", "Move…"),
+    ("dialog.later", "Later"),
+    ("dialog.relocate_pick", "Choose a local folder for the wallet data"),
+    ("dialog.relocate_background", "Turn off the background service first, then move the data folder."),
+    ("dialog.relocate_target_synced", "That folder is synced by {provider} too. Choose one that stays on this computer."),
+    ("dialog.relocate_failed_title", "Could not move the data folder"),
+    ("auth.title", "Confirm it's you"),
+    ("auth.type_phrase", "Type \"{phrase}\" to continue."),
+    ("auth.phrase", "I understand"),
+    ("auth.confirm", "Confirm"),
+    ("auth.reveal_seed", "Phoenixd Dashboard wants to show your recovery phrase."),
+    ("auth.rotate_credentials", "Phoenixd Dashboard wants to replace an access credential."),
+    ("auth.export_key", "Phoenixd Dashboard wants to create and export a private access key."),
+    ("auth.create_backup", "Phoenixd Dashboard wants to save a backup of your wallet, including its seed."),
+    ("auth.restore_backup", "Phoenixd Dashboard wants to replace your wallet with a backup."),
+    ("auth.import_docker", "Phoenixd Dashboard wants to replace your wallet with the node of a Docker deployment."),
+    ("auth.nwc_pair", "Phoenixd Dashboard wants to let a Nostr client spend from your wallet."),
+    ("auth.store_full_access", "Phoenixd Dashboard wants to give a store server full access to your wallet, including sending."),
+    ("auth.purge_data", "Phoenixd Dashboard wants to delete the dashboard database."),
+];
+
+const PT: &[(&str, &str)] = &[
+    ("tray.open_dashboard", "Abrir Dashboard"),
+    ("tray.copy_node_id", "Copiar ID do Nó"),
+    ("tray.node_info", "Informações do nó"),
+    ("tray.status_window", "Janela de status"),
+    ("tray.phoenixd_update", "phoenixd {version} disponível – Atualizar"),
+    ("tray.clock_skew", "Relógio com {secs}s de diferença – verifique a sincronização"),
+    ("tray.relocate_data_dir", "Pasta de dados sincronizada pelo {provider} – Mover…"),
+    ("tray.phoenixd_upgrading", "Atualizando phoenixd para {version}…"),
+    ("tray.safe_mode", "Modo de segurança…"),
+    ("tray.new_invoice", "Nova Fatura (copia para a área de transferência)"),
+    ("tray.pay_copied", "Pagar Fatura Copiada"),
+    ("tray.watch_only", "Modo Somente Leitura"),
+    ("tray.offline", "Offline — sem conexão com a internet"),
+    ("tray.node_over_tor", "Tráfego do nó passa pelo Tor"),
+    ("tray.tor_new_identity", "Nova identidade Tor"),
+    ("tray.active_node", "Nó"),
+    ("tray.recent_payments", "Pagamentos recentes"),
+    ("tray.needs_attention", "Requer atenção"),
+    ("watchtower.stuck", "Pagamento pendente há {minutes} min"),
+    ("watchtower.expiring", "Fatura de {amount} sat expira em {minutes} min"),
+    ("tray.copy_public_url", "Copiar URL Pública"),
+    ("tray.copy_tailscale_url", "Copiar URL do Tailscale"),
+    ("tray.lan_pairing", "Conectar Celular (LAN)"),
+    ("tray.access_qr", "Mostrar QR Codes de Acesso"),
+    ("tray.share_access", "Compartilhar acesso temporário…"),
+    ("tray.unblock", "Desbloquear Clientes Remotos"),
+    ("tray.services", "Serviços"),
+    ("tray.open", "Abrir"),
+    ("tray.open_data", "Pasta de Dados"),
+    ("tray.open_logs", "Pasta de Logs"),
+    ("tray.open_config", "Configurações (config.toml)"),
+    ("tray.open_phoenixd_config", "Configuração do phoenixd (phoenix.conf)"),
+    ("tray.next_maintenance", "Próxima manutenção"),
+    ("selftest.passed", "Autoteste: tudo certo"),
+    ("selftest.failed", "Autoteste: {count} falharam (executar novamente)"),
+    ("selftest.notify_title", "O autoteste encontrou problemas"),
+    ("node_event.peer_connected", "Conectado ao par Lightning"),
+    ("node_event.peer_disconnected", "Desconectado do par Lightning"),
+    ("node_event.channel_opened", "Canal aberto"),
+    ("node_event.liquidity_purchased", "Liquidez comprada"),
+    ("node_event.swap_in_confirmed", "Swap-in confirmado"),
+    ("tray.restart", "Reiniciar"),
+    ("tray.reload_config", "Recarregar configuração"),
+    ("tray.optimize_database", "Otimizar Banco de Dados"),
+    ("tray.export_logs", "Exportar Logs…"),
+    ("tray.export_payments", "Exportar pagamentos…"),
+    ("tray.import_docker", "Importar instalação Docker…"),
+    ("tray.nwc", "Nostr Wallet Connect"),
+    ("tray.nwc_pair", "Nova conexão NWC…"),
+    ("tray.store_connect", "Conectar uma loja…"),
+    ("tray.pause_node", "Pausar Nó"),
+    ("tray.resume_node", "Retomar Nó"),
+    ("tray.node_paused", "Nó pausado"),
+    ("tray.restarting", "Reiniciando…"),
+    ("tray.working", "em andamento…"),
+    ("tray.job_done", "Concluído"),
+    ("tray.job_failed", "Falhou"),
+    ("tray.quit", "Sair"),
+    ("service.running", "rodando"),
+    ("service.unhealthy", "com problemas"),
+    ("service.stopped", "parado"),
+    ("service.start", "Iniciar"),
+    ("service.stop", "Parar"),
+    ("service.restart", "Reiniciar"),
+    ("service.logs", "Ver Logs"),
+    ("window.copy", "Copiar"),
+    ("window.copied", "Copiado"),
+    ("window.receive", "Receber"),
+    ("window.any_amount", "Qualquer valor"),
+    ("window.access_urls", "URLs de Acesso"),
+    ("window.lan_pairing", "Conectar pela LAN"),
+    ("window.lan_pairing_hint", "Escaneie com um dispositivo na mesma rede"),
+    ("window.nwc_pairing", "Nostr Wallet Connect"),
+    ("window.nwc_pairing_hint", "Escaneie com um cliente Nostr para parear {name}. Este código é mostrado só uma vez."),
+    ("nwc.connection", "Conexão"),
+    ("window.store_connect", "Conectar uma loja"),
+    ("window.access_links", "Acesso temporário"),
+    ("access_links.needs_proxy", "Os links temporários passam pelo proxy reverso. Ative-o primeiro."),
+    ("access_links.created", "Novo link, válido até {expires}."),
+    ("access_links.create", "Novo link válido por"),
+    ("access_links.label", "Para quem é? (opcional)"),
+    ("access_links.full", "acesso total"),
+    ("access_links.hours", "{hours} h"),
+    ("access_links.active", "Links ativos"),
+    ("access_links.none", "Nenhum link ativo"),
+    ("access_links.expires", "Expira"),
+    ("access_links.last_used", "Último uso"),
+    ("access_links.never", "nunca"),
+    ("access_links.revoke", "Revogar"),
+    ("store.btcpay", "BTCPay Server"),
+    ("store.lnbits", "LNbits"),
+    ("store.receive_only", "Só recebimento: a loja pode criar faturas e ler pagamentos, mas não enviar."),
+    ("store.full_access", "Acesso total: a loja também pode enviar pagamentos."),
+    ("window.node_info", "Informações do nó"),
+    ("window.status", "Status"),
+    ("window.actions", "Ações"),
+    ("window.no_tray", "A bandeja do sistema não está disponível, então o app é controlado por esta janela. O log do app explica como ativar a bandeja neste desktop."),
+    ("window.crashes", "falhas"),
+    ("window.logs", "Log de {service}"),
+    ("window.no_log", "Nada registrado ainda."),
+    ("node_info.node_id", "ID do nó"),
+    ("node_info.version", "Versão"),
+    ("node_info.chain", "Rede"),
+    ("node_info.block_height", "Altura do bloco"),
+    ("node_info.channels", "Canais ativos / total"),
+    ("node_info.balance", "Saldo"),
+    ("node_info.inbound_liquidity", "Liquidez de entrada"),
+    ("node_info.fee_credit", "Crédito de taxas"),
+    ("safe_mode.title", "Modo de segurança"),
+    ("safe_mode.reason", "{service} travou {crashes} vezes em pouco tempo. Apenas o aplicativo está rodando."),
+    ("safe_mode.hint", "Confira os logs, restaure o backup mais recente ou redefina as configurações e depois inicie os serviços novamente."),
+    ("safe_mode.view_logs", "Ver logs"),
+    ("safe_mode.restore_backup", "Restaurar último backup"),
+    ("safe_mode.reset_config", "Redefinir configurações"),
+    ("safe_mode.resume", "Iniciar serviços"),
+    ("startup.title", "Iniciando o Phoenixd Dashboard"),
+    ("startup.phoenixd", "Iniciando o phoenixd…"),
+    ("startup.backend", "Iniciando o backend…"),
+    ("startup.frontend", "Iniciando o painel…"),
+    ("startup.remote_access", "Configurando o acesso remoto…"),
+    ("startup.repairing", "Baixando componentes ausentes…"),
+    ("startup.waiting_backend", "Aguardando o backend…"),
+    ("startup.waiting_frontend", "Aguardando o painel…"),
+    ("access.local", "Este computador"),
+    ("access.lan", "Rede local"),
+    ("access.tor", "Serviço onion do Tor"),
+    ("access.cloudflare", "Túnel Cloudflare"),
+    ("access.tailscale", "Tailscale"),
+    ("notify.invoice_title", "Fatura Lightning detectada"),
+    ("notify.invoice_body", "Pagar com o Phoenixd Dashboard? Escolha \"Pagar Fatura Copiada\" no menu da bandeja."),
+    ("notify.blocked_title", "Acesso remoto bloqueado"),
+    ("notify.blocked_body", "{ip} foi bloqueado após repetidas falhas de login"),
+    ("notify.autoheal_title", "Reinício automático pausado"),
+    ("notify.autoheal_body", "{service} continua falhando após {attempts} reinícios na última hora. Verifique os logs."),
+    ("notify.bandwidth_title", "Limite de banda excedido"),
+    ("notify.bandwidth_body", "Tor e Cloudflare usaram {used} MB, acima do seu limite de {limit} MB"),
+    ("notify.phoenixd_update_title", "Atualização do phoenixd disponível"),
+    ("notify.phoenixd_update_body", "O phoenixd {latest} foi lançado, você usa o {current}. Atualize pelo menu da bandeja."),
+    ("notify.clock_skew_title", "Relógio do sistema incorreto"),
+    ("notify.clock_skew_body", "Seu relógio está {secs}s diferente da hora da internet. Canais Lightning podem falhar com o relógio errado; ative a sincronização automática."),
+    ("digest.title_daily", "Resumo diário"),
+    ("digest.title_weekly", "Resumo semanal"),
+    ("digest.body", "Recebidos {received} sat ({received_count} pagamentos), enviados {sent} sat ({sent_count} pagamentos), {fees} sat em taxas."),
+    ("digest.balance", "Saldo: {balance} sat."),
+    ("notify.lease_lost_title", "Carteira iniciada em outro computador"),
+    ("notify.dashboard_starting_title", "O painel está iniciando"),
+    ("notify.dashboard_starting_body", "Ele abre assim que estiver pronto"),
+    ("notify.dashboard_not_ready", "O painel não iniciou. Verifique os serviços no menu da bandeja."),
+    ("notify.payment_stuck_title", "Pagamento travado"),
+    ("notify.payment_stuck_body", "Um pagamento enviado está pendente há {minutes} minutos. Abra-o pelo menu da bandeja."),
+    ("notify.invoice_expiring_title", "Fatura prestes a expirar"),
+    ("notify.invoice_expiring_body", "Uma fatura não paga de {amount} sat expira em {minutes} minutos. Abra-a pelo menu da bandeja."),
+    ("notify.safe_mode_title", "Modo de segurança"),
+    ("notify.safe_mode_body", "{service} continuou travando, então todos os serviços foram parados"),
+    ("dialog.startup_failed_title", "O Phoenixd Dashboard não pôde iniciar"),
+    ("dialog.startup_failed_hint", "O painel não funcionará até que isso seja corrigido. Os logs têm mais detalhes."),
+    ("dialog.copy_details", "Copiar Detalhes"),
+    ("dialog.open_logs", "Abrir Logs"),
+    ("dialog.close", "Fechar"),
+    ("dialog.invalid_config_title", "Configuração inválida"),
+    ("dialog.invalid_config_hint", "Corrija o config.toml e recarregue-o pela bandeja. Até lá, os serviços em execução mantêm as configurações atuais."),
+    ("dialog.open_folder", "Abrir pasta"),
+    ("dialog.watch_only_off_title", "Sair do modo somente leitura?"),
+    ("dialog.lease_title", "Carteira em uso em outro computador"),
+    ("dialog.lease_body", "{host} está executando esta carteira (visto pela última vez às {time}). Executar a mesma carteira em dois computadores, por exemplo por uma pasta sincronizada, pode causar perda de fundos.\n\nSó inicie aqui se {host} estiver desligado ou não usar mais esta pasta de dados."),
+    ("dialog.start_anyway", "Iniciar mesmo assim"),
+    ("dialog.watch_only_off_body", "Qualquer pessoa com acesso ao painel poderá enviar pagamentos novamente."),
+    ("dialog.enable_payments", "Ativar Pagamentos"),
+    ("dialog.keep_node_title", "Sair do Phoenixd Dashboard"),
+    ("dialog.keep_node_body", "Manter o nó Lightning rodando após sair? Ele continua recebendo pagamentos e o aplicativo se reconecta a ele na próxima vez que iniciar."),
+    ("dialog.keep_node_running", "Manter nó rodando"),
+    ("dialog.stop_everything", "Parar tudo"),
+    ("dialog.cancel", "Cancelar"),
+    ("dialog.docker_import_title", "Importar instalação Docker"),
+    ("dialog.docker_import_body", "A instalação Docker em {dir} será parada e seu nó {chain} movido para o app. Os dados do nó atual do app são guardados à parte, não apagados. O saldo e o histórico de pagamentos são verificados após a mudança; se não conferirem, o nó anterior volta.\n\nDados do painel guardados no postgres do Docker (contatos, notas) não são importados."),
+    ("dialog.import", "Importar"),
+    ("dialog.repair_title", "Componentes ausentes"),
+    ("dialog.repair_body", "Estes componentes estão faltando no app: {components}."),
+    ("dialog.repair_download", "Baixá-los agora?"),
+    ("dialog.repair_reinstall", "Eles fazem parte do app; reinstale o Phoenixd Dashboard para restaurá-los."),
+    ("dialog.download", "Baixar"),
+    ("dialog.relocate_title", "Pasta de dados sincronizada"),
+    ("dialog.relocate_body", "A pasta de dados da carteira {dir} é sincronizada pelo {provider}. Clientes de sincronização corrompem os bancos de dados e o estado dos canais do nó, e podem iniciar a mesma carteira em outro computador, o que pode causar perda de fundos.\n\nMovê-la para uma pasta neste computador? Os serviços param durante a cópia e o app reinicia."),
+    ("dialog.relocate_move", "Mover…"),
+    ("dialog.later", "Depois"),
+    ("dialog.relocate_pick", "Escolha uma pasta local para os dados da carteira"),
+    ("dialog.relocate_background", "Desative o serviço em segundo plano primeiro e depois mova a pasta de dados."),
+    ("dialog.relocate_target_synced", "Essa pasta também é sincronizada pelo {provider}. Escolha uma que fique neste computador."),
+    ("dialog.relocate_failed_title", "Não foi possível mover a pasta de dados"),
+    ("auth.title", "Confirme que é você"),
+    ("auth.type_phrase", "Digite \"{phrase}\" para continuar."),
+    ("auth.phrase", "Eu entendo"),
+    ("auth.confirm", "Confirmar"),
+    ("auth.reveal_seed", "O Phoenixd Dashboard quer mostrar sua frase de recuperação."),
+    ("auth.rotate_credentials", "O Phoenixd Dashboard quer substituir uma credencial de acesso."),
+    ("auth.export_key", "O Phoenixd Dashboard quer criar e exportar uma chave de acesso privada."),
+    ("auth.create_backup", "O Phoenixd Dashboard quer salvar um backup da sua carteira, incluindo a seed."),
+    ("auth.restore_backup", "O Phoenixd Dashboard quer substituir sua carteira por um backup."),
+    ("auth.import_docker", "O Phoenixd Dashboard quer substituir sua carteira pelo nó de uma instalação Docker."),
+    ("auth.nwc_pair", "O Phoenixd Dashboard quer permitir que um cliente Nostr gaste da sua carteira."),
+    ("auth.store_full_access", "O Phoenixd Dashboard quer dar a um servidor de loja acesso total à sua carteira, incluindo envios."),
+    ("auth.purge_data", "O Phoenixd Dashboard quer apagar o banco de dados do painel."),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("tray.open_dashboard", "Abrir Dashboard"),
+    ("tray.copy_node_id", "Copiar ID del Nodo"),
+    ("tray.node_info", "Información del nodo"),
+    ("tray.status_window", "Ventana de estado"),
+    ("tray.phoenixd_update", "phoenixd {version} disponible – Actualizar"),
+    ("tray.clock_skew", "El reloj tiene {secs}s de desfase – revisa la sincronización"),
+    ("tray.relocate_data_dir", "Carpeta de datos sincronizada por {provider} – Mover…"),
+    ("tray.phoenixd_upgrading", "Actualizando phoenixd a {version}…"),
+    ("tray.safe_mode", "Modo seguro…"),
+    ("tray.new_invoice", "Nueva Factura (copia al portapapeles)"),
+    ("tray.pay_copied", "Pagar Factura Copiada"),
+    ("tray.watch_only", "Modo Solo Lectura"),
+    ("tray.offline", "Sin conexión a internet"),
+    ("tray.node_over_tor", "El tráfico del nodo pasa por Tor"),
+    ("tray.tor_new_identity", "Nueva identidad de Tor"),
+    ("tray.active_node", "Nodo"),
+    ("tray.recent_payments", "Pagos recientes"),
+    ("tray.needs_attention", "Requiere atención"),
+    ("watchtower.stuck", "Pago pendiente desde hace {minutes} min"),
+    ("watchtower.expiring", "Factura de {amount} sat caduca en {minutes} min"),
+    ("tray.copy_public_url", "Copiar URL Pública"),
+    ("tray.copy_tailscale_url", "Copiar URL de Tailscale"),
+    ("tray.lan_pairing", "Conectar Teléfono (LAN)"),
+    ("tray.access_qr", "Mostrar Códigos QR de Acceso"),
+    ("tray.share_access", "Compartir acceso temporal…"),
+    ("tray.unblock", "Desbloquear Clientes Remotos"),
+    ("tray.services", "Servicios"),
+    ("tray.open", "Abrir"),
+    ("tray.open_data", "Carpeta de Datos"),
+    ("tray.open_logs", "Carpeta de Logs"),
+    ("tray.open_config", "Ajustes (config.toml)"),
+    ("tray.open_phoenixd_config", "Configuración de phoenixd (phoenix.conf)"),
+    ("tray.next_maintenance", "Próximo mantenimiento"),
+    ("selftest.passed", "Autoprueba: todo correcto"),
+    ("selftest.failed", "Autoprueba: {count} fallaron (ejecutar de nuevo)"),
+    ("selftest.notify_title", "La autoprueba encontró problemas"),
+    ("node_event.peer_connected", "Conectado al par Lightning"),
+    ("node_event.peer_disconnected", "Desconectado del par Lightning"),
+    ("node_event.channel_opened", "Canal abierto"),
+    ("node_event.liquidity_purchased", "Liquidez comprada"),
+    ("node_event.swap_in_confirmed", "Swap-in confirmado"),
+    ("tray.restart", "Reiniciar"),
+    ("tray.reload_config", "Recargar configuración"),
+    ("tray.optimize_database", "Optimizar Base de Datos"),
+    ("tray.export_logs", "Exportar Registros…"),
+    ("tray.export_payments", "Exportar pagos…"),
+    ("tray.import_docker", "Importar instalación Docker…"),
+    ("tray.nwc", "Nostr Wallet Connect"),
+    ("tray.nwc_pair", "Nueva conexión NWC…"),
+    ("tray.store_connect", "Conectar una tienda…"),
+    ("tray.pause_node", "Pausar Nodo"),
+    ("tray.resume_node", "Reanudar Nodo"),
+    ("tray.node_paused", "Nodo en pausa"),
+    ("tray.restarting", "Reiniciando…"),
+    ("tray.working", "en curso…"),
+    ("tray.job_done", "Listo"),
+    ("tray.job_failed", "Falló"),
+    ("tray.quit", "Salir"),
+    ("service.running", "en ejecución"),
+    ("service.unhealthy", "con problemas"),
+    ("service.stopped", "detenido"),
+    ("service.start", "Iniciar"),
+    ("service.stop", "Detener"),
+    ("service.restart", "Reiniciar"),
+    ("service.logs", "Ver Logs"),
+    ("window.copy", "Copiar"),
+    ("window.copied", "Copiado"),
+    ("window.receive", "Recibir"),
+    ("window.any_amount", "Cualquier monto"),
+    ("window.access_urls", "URLs de Acceso"),
+    ("window.lan_pairing", "Conectar por LAN"),
+    ("window.lan_pairing_hint", "Escanea con un dispositivo en la misma red"),
+    ("window.nwc_pairing", "Nostr Wallet Connect"),
+    ("window.nwc_pairing_hint", "Escanea con un cliente Nostr para vincular {name}. Este código se muestra solo una vez."),
+    ("nwc.connection", "Conexión"),
+    ("window.store_connect", "Conectar una tienda"),
+    ("window.access_links", "Acceso temporal"),
+    ("access_links.needs_proxy", "Los enlaces temporales pasan por el proxy inverso. Actívalo primero."),
+    ("access_links.created", "Nuevo enlace, válido hasta {expires}."),
+    ("access_links.create", "Nuevo enlace válido por"),
+    ("access_links.label", "¿Para quién es? (opcional)"),
+    ("access_links.full", "acceso total"),
+    ("access_links.hours", "{hours} h"),
+    ("access_links.active", "Enlaces activos"),
+    ("access_links.none", "No hay enlaces activos"),
+    ("access_links.expires", "Caduca"),
+    ("access_links.last_used", "Último uso"),
+    ("access_links.never", "nunca"),
+    ("access_links.revoke", "Revocar"),
+    ("store.btcpay", "BTCPay Server"),
+    ("store.lnbits", "LNbits"),
+    ("store.receive_only", "Solo recibir: la tienda puede crear facturas y leer pagos, pero no enviar."),
+    ("store.full_access", "Acceso total: la tienda también puede enviar pagos."),
+    ("window.node_info", "Información del nodo"),
+    ("window.status", "Estado"),
+    ("window.actions", "Acciones"),
+    ("window.no_tray", "La bandeja del sistema no está disponible, así que la app se controla desde esta ventana. El registro de la app explica cómo activar la bandeja en este escritorio."),
+    ("window.crashes", "fallos"),
+    ("window.logs", "Registro de {service}"),
+    ("window.no_log", "Aún no hay nada registrado."),
+    ("node_info.node_id", "ID del nodo"),
+    ("node_info.version", "Versión"),
+    ("node_info.chain", "Red"),
+    ("node_info.block_height", "Altura de bloque"),
+    ("node_info.channels", "Canales activos / total"),
+    ("node_info.balance", "Saldo"),
+    ("node_info.inbound_liquidity", "Liquidez entrante"),
+    ("node_info.fee_credit", "Crédito de comisiones"),
+    ("safe_mode.title", "Modo seguro"),
+    ("safe_mode.reason", "{service} falló {crashes} veces en poco tiempo. Solo la aplicación está en ejecución."),
+    ("safe_mode.hint", "Revisa los registros, restaura la última copia de seguridad o restablece la configuración y luego vuelve a iniciar los servicios."),
+    ("safe_mode.view_logs", "Ver registros"),
+    ("safe_mode.restore_backup", "Restaurar última copia"),
+    ("safe_mode.reset_config", "Restablecer configuración"),
+    ("safe_mode.resume", "Iniciar servicios"),
+    ("startup.title", "Iniciando Phoenixd Dashboard"),
+    ("startup.phoenixd", "Iniciando phoenixd…"),
+    ("startup.backend", "Iniciando el backend…"),
+    ("startup.frontend", "Iniciando el panel…"),
+    ("startup.remote_access", "Configurando el acceso remoto…"),
+    ("startup.repairing", "Descargando componentes faltantes…"),
+    ("startup.waiting_backend", "Esperando al backend…"),
+    ("startup.waiting_frontend", "Esperando al panel…"),
+    ("access.local", "Este equipo"),
+    ("access.lan", "Red local"),
+    ("access.tor", "Servicio onion de Tor"),
+    ("access.cloudflare", "Túnel de Cloudflare"),
+    ("access.tailscale", "Tailscale"),
+    ("notify.invoice_title", "Factura Lightning detectada"),
+    ("notify.invoice_body", "¿Pagar con Phoenixd Dashboard? Elige \"Pagar Factura Copiada\" en el menú de la bandeja."),
+    ("notify.blocked_title", "Acceso remoto bloqueado"),
+    ("notify.blocked_body", "{ip} fue bloqueado tras varios inicios de sesión fallidos"),
+    ("notify.autoheal_title", "Reinicio automático en pausa"),
+    ("notify.autoheal_body", "{service} sigue fallando tras {attempts} reinicios en la última hora. Revisa sus registros."),
+    ("notify.bandwidth_title", "Umbral de ancho de banda superado"),
+    ("notify.bandwidth_body", "Tor y Cloudflare usaron {used} MB, por encima de tu umbral de {limit} MB"),
+    ("notify.phoenixd_update_title", "Actualización de phoenixd disponible"),
+    ("notify.phoenixd_update_body", "Salió phoenixd {latest}, usas {current}. Actualiza desde el menú de la bandeja."),
+    ("notify.clock_skew_title", "El reloj del sistema está desfasado"),
+    ("notify.clock_skew_body", "Tu reloj tiene {secs}s de desfase con la hora de internet. Los canales Lightning pueden fallar con un reloj incorrecto; activa la sincronización automática."),
+    ("digest.title_daily", "Resumen diario"),
+    ("digest.title_weekly", "Resumen semanal"),
+    ("digest.body", "Recibidos {received} sat ({received_count} pagos), enviados {sent} sat ({sent_count} pagos), {fees} sat en comisiones."),
+    ("digest.balance", "Saldo: {balance} sat."),
+    ("notify.lease_lost_title", "Billetera iniciada en otro equipo"),
+    ("notify.dashboard_starting_title", "El panel se está iniciando"),
+    ("notify.dashboard_starting_body", "Se abrirá en cuanto esté listo"),
+    ("notify.dashboard_not_ready", "El panel no se inició. Revisa los servicios en el menú de la bandeja."),
+    ("notify.payment_stuck_title", "Pago atascado"),
+    ("notify.payment_stuck_body", "Un pago saliente lleva {minutes} minutos pendiente. Ábrelo desde el menú de la bandeja."),
+    ("notify.invoice_expiring_title", "Factura a punto de caducar"),
+    ("notify.invoice_expiring_body", "Una factura sin pagar de {amount} sat caduca en {minutes} minutos. Ábrela desde el menú de la bandeja."),
+    ("notify.safe_mode_title", "Modo seguro"),
+    ("notify.safe_mode_body", "{service} seguía fallando, así que se detuvieron todos los servicios"),
+    ("dialog.startup_failed_title", "Phoenixd Dashboard no pudo iniciarse"),
+    ("dialog.startup_failed_hint", "El panel no funcionará hasta que se solucione. Los registros tienen más detalles."),
+    ("dialog.copy_details", "Copiar Detalles"),
+    ("dialog.open_logs", "Abrir Registros"),
+    ("dialog.close", "Cerrar"),
+    ("dialog.invalid_config_title", "Configuración no válida"),
+    ("dialog.invalid_config_hint", "Corrige config.toml y recárgalo desde la bandeja. Mientras tanto, los servicios en ejecución mantienen su configuración actual."),
+    ("dialog.open_folder", "Abrir carpeta"),
+    ("dialog.watch_only_off_title", "¿Salir del modo solo lectura?"),
+    ("dialog.lease_title", "Billetera en uso en otro equipo"),
+    ("dialog.lease_body", "{host} está ejecutando esta billetera (visto por última vez a las {time}). Ejecutar la misma billetera en dos equipos, por ejemplo mediante una carpeta sincronizada, puede hacer perder fondos.\n\nInícialo aquí solo si {host} está apagado o ya no usa esta carpeta de datos."),
+    ("dialog.start_anyway", "Iniciar de todos modos"),
+    ("dialog.watch_only_off_body", "Cualquiera con acceso al panel podrá volver a enviar pagos."),
+    ("dialog.enable_payments", "Activar Pagos"),
+    ("dialog.keep_node_title", "Salir de Phoenixd Dashboard"),
+    ("dialog.keep_node_body", "¿Mantener el nodo Lightning en ejecución después de salir? Seguirá recibiendo pagos y la aplicación se reconectará a él la próxima vez que se inicie."),
+    ("dialog.keep_node_running", "Mantener nodo activo"),
+    ("dialog.stop_everything", "Detener todo"),
+    ("dialog.cancel", "Cancelar"),
+    ("dialog.docker_import_title", "Importar instalación Docker"),
+    ("dialog.docker_import_body", "La instalación Docker en {dir} se detendrá y su nodo {chain} pasará a la app. Los datos del nodo actual de la app se guardan aparte, no se borran. El saldo y el historial de pagos se comprueban después del traslado; si no coinciden, vuelve el nodo anterior.\n\nLos datos del panel guardados en el postgres de Docker (contactos, notas) no se importan."),
+    ("dialog.import", "Importar"),
+    ("dialog.repair_title", "Componentes faltantes"),
+    ("dialog.repair_body", "Faltan estos componentes en la app: {components}."),
+    ("dialog.repair_download", "¿Descargarlos ahora?"),
+    ("dialog.repair_reinstall", "Vienen con la app; reinstala Phoenixd Dashboard para restaurarlos."),
+    ("dialog.download", "Descargar"),
+    ("dialog.relocate_title", "Carpeta de datos sincronizada"),
+    ("dialog.relocate_body", "La carpeta de datos de la billetera {dir} está sincronizada por {provider}. Los clientes de sincronización corrompen las bases de datos y el estado de los canales del nodo, y pueden iniciar la misma billetera en otro equipo, lo que puede hacer perder fondos.\n\n¿Moverla a una carpeta de este equipo? Los servicios se detienen mientras se copia y la app se reinicia."),
+    ("dialog.relocate_move", "Mover…"),
+    ("dialog.later", "Más tarde"),
+    ("dialog.relocate_pick", "Elige una carpeta local para los datos de la billetera"),
+    ("dialog.relocate_background", "Desactiva primero el servicio en segundo plano y luego mueve la carpeta de datos."),
+    ("dialog.relocate_target_synced", "Esa carpeta también está sincronizada por {provider}. Elige una que quede en este equipo."),
+    ("dialog.relocate_failed_title", "No se pudo mover la carpeta de datos"),
+    ("auth.title", "Confirma que eres tú"),
+    ("auth.type_phrase", "Escribe \"{phrase}\" para continuar."),
+    ("auth.phrase", "Entiendo"),
+    ("auth.confirm", "Confirmar"),
+    ("auth.reveal_seed", "Phoenixd Dashboard quiere mostrar tu frase de recuperación."),
+    ("auth.rotate_credentials", "Phoenixd Dashboard quiere reemplazar una credencial de acceso."),
+    ("auth.export_key", "Phoenixd Dashboard quiere crear y exportar una clave de acceso privada."),
+    ("auth.create_backup", "Phoenixd Dashboard quiere guardar una copia de seguridad de tu billetera, incluida la semilla."),
+    ("auth.restore_backup", "Phoenixd Dashboard quiere reemplazar tu billetera con una copia de seguridad."),
+    ("auth.import_docker", "Phoenixd Dashboard quiere reemplazar tu billetera con el nodo de una instalación Docker."),
+    ("auth.nwc_pair", "Phoenixd Dashboard quiere permitir que un cliente Nostr gaste de tu billetera."),
+    ("auth.store_full_access", "Phoenixd Dashboard quiere dar a un servidor de tienda acceso total a tu billetera, incluidos los envíos."),
+    ("auth.purge_data", "Phoenixd Dashboard quiere eliminar la base de datos del panel."),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("tray.open_dashboard", "Dashboard öffnen"),
+    ("tray.copy_node_id", "Node-ID kopieren"),
+    ("tray.node_info", "Knoteninfo"),
+    ("tray.status_window", "Statusfenster"),
+    ("tray.phoenixd_update", "phoenixd {version} verfügbar – Aktualisieren"),
+    ("tray.clock_skew", "Uhr weicht um {secs}s ab – Zeitsynchronisierung prüfen"),
+    ("tray.relocate_data_dir", "Datenordner von {provider} synchronisiert – Verschieben…"),
+    ("tray.phoenixd_upgrading", "phoenixd wird auf {version} aktualisiert…"),
+    ("tray.safe_mode", "Abgesicherter Modus…"),
+    ("tray.new_invoice", "Neue Rechnung (in die Zwischenablage)"),
+    ("tray.pay_copied", "Kopierte Rechnung bezahlen"),
+    ("tray.watch_only", "Nur-Beobachten-Modus"),
+    ("tray.offline", "Offline — keine Internetverbindung"),
+    ("tray.node_over_tor", "Knoten-Traffic läuft über Tor"),
+    ("tray.tor_new_identity", "Neue Tor-Identität"),
+    ("tray.active_node", "Knoten"),
+    ("tray.recent_payments", "Letzte Zahlungen"),
+    ("tray.needs_attention", "Braucht Aufmerksamkeit"),
+    ("watchtower.stuck", "Zahlung seit {minutes} Min. ausstehend"),
+    ("watchtower.expiring", "Rechnung über {amount} sat läuft in {minutes} Min. ab"),
+    ("tray.copy_public_url", "Öffentliche URL kopieren"),
+    ("tray.copy_tailscale_url", "Tailscale-URL kopieren"),
+    ("tray.lan_pairing", "Telefon verbinden (LAN)"),
+    ("tray.access_qr", "Zugangs-QR-Codes anzeigen"),
+    ("tray.share_access", "Temporären Zugang teilen…"),
+    ("tray.unblock", "Gesperrte Clients freigeben"),
+    ("tray.services", "Dienste"),
+    ("tray.open", "Öffnen"),
+    ("tray.open_data", "Datenordner"),
+    ("tray.open_logs", "Log-Ordner"),
+    ("tray.open_config", "Einstellungen (config.toml)"),
+    ("tray.open_phoenixd_config", "phoenixd-Konfiguration (phoenix.conf)"),
+    ("tray.next_maintenance", "Nächste Wartung"),
+    ("selftest.passed", "Selbsttest: alles in Ordnung"),
+    ("selftest.failed", "Selbsttest: {count} fehlgeschlagen (erneut ausführen)"),
+    ("selftest.notify_title", "Der Selbsttest hat Probleme gefunden"),
+    ("node_event.peer_connected", "Mit dem Lightning-Peer verbunden"),
+    ("node_event.peer_disconnected", "Vom Lightning-Peer getrennt"),
+    ("node_event.channel_opened", "Kanal geöffnet"),
+    ("node_event.liquidity_purchased", "Liquidität gekauft"),
+    ("node_event.swap_in_confirmed", "Swap-in bestätigt"),
+    ("tray.restart", "Neu starten"),
+    ("tray.reload_config", "Konfiguration neu laden"),
+    ("tray.optimize_database", "Datenbank optimieren"),
+    ("tray.export_logs", "Protokolle exportieren…"),
+    ("tray.export_payments", "Zahlungen exportieren…"),
+    ("tray.import_docker", "Docker-Installation importieren…"),
+    ("tray.nwc", "Nostr Wallet Connect"),
+    ("tray.nwc_pair", "Neue NWC-Verbindung…"),
+    ("tray.store_connect", "Shop verbinden…"),
+    ("tray.pause_node", "Node pausieren"),
+    ("tray.resume_node", "Node fortsetzen"),
+    ("tray.node_paused", "Node pausiert"),
+    ("tray.restarting", "Neustart läuft…"),
+    ("tray.working", "läuft…"),
+    ("tray.job_done", "Fertig"),
+    ("tray.job_failed", "Fehlgeschlagen"),
+    ("tray.quit", "Beenden"),
+    ("service.running", "läuft"),
+    ("service.unhealthy", "gestört"),
+    ("service.stopped", "gestoppt"),
+    ("service.start", "Starten"),
+    ("service.stop", "Stoppen"),
+    ("service.restart", "Neu starten"),
+    ("service.logs", "Logs anzeigen"),
+    ("window.copy", "Kopieren"),
+    ("window.copied", "Kopiert"),
+    ("window.receive", "Empfangen"),
+    ("window.any_amount", "Beliebiger Betrag"),
+    ("window.access_urls", "Zugangs-URLs"),
+    ("window.lan_pairing", "Über LAN verbinden"),
+    ("window.lan_pairing_hint", "Mit einem Gerät im selben Netzwerk scannen"),
+    ("window.nwc_pairing", "Nostr Wallet Connect"),
+    ("window.nwc_pairing_hint", "Mit einem Nostr-Client scannen, um {name} zu koppeln. Dieser Code wird nur einmal angezeigt."),
+    ("nwc.connection", "Verbindung"),
+    ("window.store_connect", "Shop verbinden"),
+    ("window.access_links", "Temporärer Zugang"),
+    ("access_links.needs_proxy", "Temporäre Links laufen über den Reverse Proxy. Schalte ihn zuerst ein."),
+    ("access_links.created", "Neuer Link, gültig bis {expires}."),
+    ("access_links.create", "Neuer Link gültig für"),
+    ("access_links.label", "Für wen ist er? (optional)"),
+    ("access_links.full", "voller Zugriff"),
+    ("access_links.hours", "{hours} Std."),
+    ("access_links.active", "Aktive Links"),
+    ("access_links.none", "Keine aktiven Links"),
+    ("access_links.expires", "Läuft ab"),
+    ("access_links.last_used", "Zuletzt benutzt"),
+    ("access_links.never", "nie"),
+    ("access_links.revoke", "Widerrufen"),
+    ("store.btcpay", "BTCPay Server"),
+    ("store.lnbits", "LNbits"),
+    ("store.receive_only", "Nur empfangen: Der Shop kann Rechnungen erstellen und Zahlungen lesen, aber nicht senden."),
+    ("store.full_access", "Vollzugriff: Der Shop kann auch Zahlungen senden."),
+    ("window.node_info", "Knoteninfo"),
+    ("window.status", "Status"),
+    ("window.actions", "Aktionen"),
+    ("window.no_tray", "Der System-Tray ist nicht verfügbar, daher wird die App über dieses Fenster gesteuert. Das Log der App erklärt, wie du den Tray auf diesem Desktop aktivierst."),
+    ("window.crashes", "Abstürze"),
+    ("window.logs", "{service}-Log"),
+    ("window.no_log", "Noch nichts protokolliert."),
+    ("node_info.node_id", "Knoten-ID"),
+    ("node_info.version", "Version"),
+    ("node_info.chain", "Chain"),
+    ("node_info.block_height", "Blockhöhe"),
+    ("node_info.channels", "Aktive / alle Kanäle"),
+    ("node_info.balance", "Guthaben"),
+    ("node_info.inbound_liquidity", "Eingehende Liquidität"),
+    ("node_info.fee_credit", "Gebührenguthaben"),
+    ("safe_mode.title", "Abgesicherter Modus"),
+    ("safe_mode.reason", "{service} ist in kurzer Zeit {crashes} Mal abgestürzt. Nur die App läuft."),
+    ("safe_mode.hint", "Prüfe die Logs, stelle die letzte Sicherung wieder her oder setze die Einstellungen zurück und starte dann die Dienste erneut."),
+    ("safe_mode.view_logs", "Logs anzeigen"),
+    ("safe_mode.restore_backup", "Letzte Sicherung wiederherstellen"),
+    ("safe_mode.reset_config", "Einstellungen zurücksetzen"),
+    ("safe_mode.resume", "Dienste starten"),
+    ("startup.title", "Phoenixd Dashboard wird gestartet"),
+    ("startup.phoenixd", "phoenixd wird gestartet…"),
+    ("startup.backend", "Backend wird gestartet…"),
+    ("startup.frontend", "Dashboard wird gestartet…"),
+    ("startup.remote_access", "Fernzugriff wird eingerichtet…"),
+    ("startup.repairing", "Fehlende Komponenten werden heruntergeladen…"),
+    ("startup.waiting_backend", "Warte auf das Backend…"),
+    ("startup.waiting_frontend", "Warte auf das Dashboard…"),
+    ("access.local", "Dieser Computer"),
+    ("access.lan", "Lokales Netzwerk"),
+    ("access.tor", "Tor-Onion-Dienst"),
+    ("access.cloudflare", "Cloudflare-Tunnel"),
+    ("access.tailscale", "Tailscale"),
+    ("notify.invoice_title", "Lightning-Rechnung erkannt"),
+    ("notify.invoice_body", "Mit Phoenixd Dashboard bezahlen? Wähle \"Kopierte Rechnung bezahlen\" im Tray-Menü."),
+    ("notify.blocked_title", "Fernzugriff gesperrt"),
+    ("notify.blocked_body", "{ip} wurde nach wiederholten Fehlanmeldungen gesperrt"),
+    ("notify.autoheal_title", "Automatischer Neustart pausiert"),
+    ("notify.autoheal_body", "{service} fällt nach {attempts} Neustarts in der letzten Stunde weiterhin aus. Bitte die Logs prüfen."),
+    ("notify.bandwidth_title", "Bandbreiten-Schwelle überschritten"),
+    ("notify.bandwidth_body", "Tor und Cloudflare haben {used} MB verbraucht, über deiner Schwelle von {limit} MB"),
+    ("notify.phoenixd_update_title", "phoenixd-Update verfügbar"),
+    ("notify.phoenixd_update_body", "phoenixd {latest} ist erschienen, du nutzt {current}. Aktualisiere über das Tray-Menü."),
+    ("notify.clock_skew_title", "Systemuhr geht falsch"),
+    ("notify.clock_skew_body", "Deine Uhr weicht {secs}s von der Internetzeit ab. Lightning-Kanäle können bei falscher Uhrzeit fehlschlagen; aktiviere die automatische Zeitsynchronisierung."),
+    ("digest.title_daily", "Tägliche Zusammenfassung"),
+    ("digest.title_weekly", "Wöchentliche Zusammenfassung"),
+    ("digest.body", "{received} sat empfangen ({received_count} Zahlungen), {sent} sat gesendet ({sent_count} Zahlungen), {fees} sat Gebühren."),
+    ("digest.balance", "Guthaben: {balance} sat."),
+    ("notify.lease_lost_title", "Wallet auf einem anderen Computer gestartet"),
+    ("notify.dashboard_starting_title", "Dashboard startet"),
+    ("notify.dashboard_starting_body", "Es öffnet sich, sobald es bereit ist"),
+    ("notify.dashboard_not_ready", "Das Dashboard ist nicht gestartet. Prüfe die Dienste im Tray-Menü."),
+    ("notify.payment_stuck_title", "Zahlung hängt"),
+    ("notify.payment_stuck_body", "Eine ausgehende Zahlung ist seit {minutes} Minuten ausstehend. Öffne sie über das Tray-Menü."),
+    ("notify.invoice_expiring_title", "Rechnung läuft bald ab"),
+    ("notify.invoice_expiring_body", "Eine unbezahlte Rechnung über {amount} sat läuft in {minutes} Minuten ab. Öffne sie über das Tray-Menü."),
+    ("notify.safe_mode_title", "Abgesicherter Modus"),
+    ("notify.safe_mode_body", "{service} ist wiederholt abgestürzt, daher wurden alle Dienste gestoppt"),
+    ("dialog.startup_failed_title", "Phoenixd Dashboard konnte nicht starten"),
+    ("dialog.startup_failed_hint", "Das Dashboard funktioniert erst, wenn das behoben ist. Die Logs enthalten mehr Details."),
+    ("dialog.copy_details", "Details kopieren"),
+    ("dialog.open_logs", "Logs öffnen"),
+    ("dialog.close", "Schließen"),
+    ("dialog.invalid_config_title", "Ungültige Konfiguration"),
+    ("dialog.invalid_config_hint", "Korrigiere config.toml und lade sie über das Tray neu. Bis dahin behalten die laufenden Dienste ihre aktuellen Einstellungen."),
+    ("dialog.open_folder", "Ordner öffnen"),
+    ("dialog.watch_only_off_title", "Nur-Beobachten-Modus verlassen?"),
+    ("dialog.lease_title", "Wallet wird auf einem anderen Computer verwendet"),
+    ("dialog.lease_body", "{host} führt diese Wallet aus (zuletzt gesehen um {time}). Dieselbe Wallet auf zwei Computern auszuführen, z. B. über einen synchronisierten Ordner, kann zu Geldverlust führen.\n\nStarte sie hier nur, wenn {host} aus ist oder diesen Datenordner nicht mehr verwendet."),
+    ("dialog.start_anyway", "Trotzdem starten"),
+    ("dialog.watch_only_off_body", "Jeder mit Zugriff auf das Dashboard kann wieder Zahlungen senden."),
+    ("dialog.enable_payments", "Zahlungen aktivieren"),
+    ("dialog.keep_node_title", "Phoenixd Dashboard beenden"),
+    ("dialog.keep_node_body", "Soll der Lightning-Knoten nach dem Beenden weiterlaufen? Er empfängt weiter Zahlungen, und die App verbindet sich beim nächsten Start wieder mit ihm."),
+    ("dialog.keep_node_running", "Knoten weiterlaufen lassen"),
+    ("dialog.stop_everything", "Alles stoppen"),
+    ("dialog.cancel", "Abbrechen"),
+    ("dialog.docker_import_title", "Docker-Installation importieren"),
+    ("dialog.docker_import_body", "Die Docker-Installation in {dir} wird gestoppt und ihr {chain}-Node in die App übernommen. Die Daten des bisherigen Nodes der App werden beiseitegelegt, nicht gelöscht. Guthaben und Zahlungsverlauf werden nach dem Umzug geprüft; stimmen sie nicht überein, kommt der vorherige Node zurück.\n\nDashboard-Daten in der Postgres-Datenbank von Docker (Kontakte, Notizen) werden nicht importiert."),
+    ("dialog.import", "Importieren"),
+    ("dialog.repair_title", "Fehlende Komponenten"),
+    ("dialog.repair_body", "Diese Komponenten fehlen in der App: {components}."),
+    ("dialog.repair_download", "Jetzt herunterladen?"),
+    ("dialog.repair_reinstall", "Sie sind Teil der App; installiere Phoenixd Dashboard neu, um sie wiederherzustellen."),
+    ("dialog.download", "Herunterladen"),
+    ("dialog.relocate_title", "Datenordner wird synchronisiert"),
+    ("dialog.relocate_body", "Der Datenordner der Wallet {dir} wird von {provider} synchronisiert. Sync-Programme beschädigen die Datenbanken und den Kanalzustand des Knotens und können dieselbe Wallet auf einem anderen Computer starten, was zu Geldverlust führen kann.\n\nAuf einen Ordner auf diesem Computer verschieben? Die Dienste halten während des Kopierens an und die App startet neu."),
+    ("dialog.relocate_move", "Verschieben…"),
+    ("dialog.later", "Später"),
+    ("dialog.relocate_pick", "Wähle einen lokalen Ordner für die Wallet-Daten"),
+    ("dialog.relocate_background", "Schalte zuerst den Hintergrunddienst aus und verschiebe dann den Datenordner."),
+    ("dialog.relocate_target_synced", "Dieser Ordner wird ebenfalls von {provider} synchronisiert. Wähle einen, der auf diesem Computer bleibt."),
+    ("dialog.relocate_failed_title", "Der Datenordner konnte nicht verschoben werden"),
+    ("auth.title", "Bestätige, dass du es bist"),
+    ("auth.type_phrase", "Gib „{phrase}“ ein, um fortzufahren."),
+    ("auth.phrase", "Ich verstehe"),
+    ("auth.confirm", "Bestätigen"),
+    ("auth.reveal_seed", "Phoenixd Dashboard möchte deine Wiederherstellungsphrase anzeigen."),
+    ("auth.rotate_credentials", "Phoenixd Dashboard möchte eine Zugangsberechtigung ersetzen."),
+    ("auth.export_key", "Phoenixd Dashboard möchte einen privaten Zugangsschlüssel erstellen und exportieren."),
+    ("auth.create_backup", "Phoenixd Dashboard möchte ein Backup deines Wallets samt Seed speichern."),
+    ("auth.restore_backup", "Phoenixd Dashboard möchte dein Wallet durch ein Backup ersetzen."),
+    ("auth.import_docker", "Phoenixd Dashboard möchte dein Wallet durch den Node einer Docker-Installation ersetzen."),
+    ("auth.nwc_pair", "Phoenixd Dashboard möchte einem Nostr-Client erlauben, aus deiner Wallet auszugeben."),
+    ("auth.store_full_access", "Phoenixd Dashboard möchte einem Shop-Server vollen Zugriff auf deine Wallet geben, einschließlich Senden."),
+    ("auth.purge_data", "Phoenixd Dashboard möchte die Dashboard-Datenbank löschen."),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("tray.open_dashboard", "Ouvrir le Dashboard"),
+    ("tray.copy_node_id", "Copier l'ID du nœud"),
+    ("tray.node_info", "Infos du nœud"),
+    ("tray.status_window", "Fenêtre d'état"),
+    ("tray.phoenixd_update", "phoenixd {version} disponible – Mettre à jour"),
+    ("tray.clock_skew", "Horloge décalée de {secs}s – vérifiez la synchronisation"),
+    ("tray.relocate_data_dir", "Dossier de données synchronisé par {provider} – Déplacer…"),
+    ("tray.phoenixd_upgrading", "Mise à jour de phoenixd vers {version}…"),
+    ("tray.safe_mode", "Mode sans échec…"),
+    ("tray.new_invoice", "Nouvelle facture (copiée dans le presse-papiers)"),
+    ("tray.pay_copied", "Payer la facture copiée"),
+    ("tray.watch_only", "Mode lecture seule"),
+    ("tray.offline", "Hors ligne — pas de connexion Internet"),
+    ("tray.node_over_tor", "Le trafic du nœud passe par Tor"),
+    ("tray.tor_new_identity", "Nouvelle identité Tor"),
+    ("tray.active_node", "Nœud"),
+    ("tray.recent_payments", "Paiements récents"),
+    ("tray.needs_attention", "Requiert votre attention"),
+    ("watchtower.stuck", "Paiement en attente depuis {minutes} min"),
+    ("watchtower.expiring", "Facture de {amount} sat expire dans {minutes} min"),
+    ("tray.copy_public_url", "Copier l'URL publique"),
+    ("tray.copy_tailscale_url", "Copier l'URL Tailscale"),
+    ("tray.lan_pairing", "Connecter un téléphone (LAN)"),
+    ("tray.access_qr", "Afficher les QR codes d'accès"),
+    ("tray.share_access", "Partager un accès temporaire…"),
+    ("tray.unblock", "Débloquer les clients distants"),
+    ("tray.services", "Services"),
+    ("tray.open", "Ouvrir"),
+    ("tray.open_data", "Dossier de données"),
+    ("tray.open_logs", "Dossier des logs"),
+    ("tray.open_config", "Paramètres (config.toml)"),
+    ("tray.open_phoenixd_config", "Configuration phoenixd (phoenix.conf)"),
+    ("tray.next_maintenance", "Prochaine maintenance"),
+    ("selftest.passed", "Autotest : tout est correct"),
+    ("selftest.failed", "Autotest : {count} en échec (relancer)"),
+    ("selftest.notify_title", "L'autotest a trouvé des problèmes"),
+    ("node_event.peer_connected", "Connecté au pair Lightning"),
+    ("node_event.peer_disconnected", "Déconnecté du pair Lightning"),
+    ("node_event.channel_opened", "Canal ouvert"),
+    ("node_event.liquidity_purchased", "Liquidité achetée"),
+    ("node_event.swap_in_confirmed", "Swap-in confirmé"),
+    ("tray.restart", "Redémarrer"),
+    ("tray.reload_config", "Recharger la configuration"),
+    ("tray.optimize_database", "Optimiser la base de données"),
+    ("tray.export_logs", "Exporter les journaux…"),
+    ("tray.export_payments", "Exporter les paiements…"),
+    ("tray.import_docker", "Importer le déploiement Docker…"),
+    ("tray.nwc", "Nostr Wallet Connect"),
+    ("tray.nwc_pair", "Nouvelle connexion NWC…"),
+    ("tray.store_connect", "Connecter une boutique…"),
+    ("tray.pause_node", "Mettre le nœud en pause"),
+    ("tray.resume_node", "Reprendre le nœud"),
+    ("tray.node_paused", "Nœud en pause"),
+    ("tray.restarting", "Redémarrage…"),
+    ("tray.working", "en cours…"),
+    ("tray.job_done", "Terminé"),
+    ("tray.job_failed", "Échec"),
+    ("tray.quit", "Quitter"),
+    ("service.running", "en cours"),
+    ("service.unhealthy", "défaillant"),
+    ("service.stopped", "arrêté"),
+    ("service.start", "Démarrer"),
+    ("service.stop", "Arrêter"),
+    ("service.restart", "Redémarrer"),
+    ("service.logs", "Voir les logs"),
+    ("window.copy", "Copier"),
+    ("window.copied", "Copié"),
+    ("window.receive", "Recevoir"),
+    ("window.any_amount", "Montant libre"),
+    ("window.access_urls", "URLs d'accès"),
+    ("window.lan_pairing", "Connexion en LAN"),
+    ("window.lan_pairing_hint", "Scannez avec un appareil sur le même réseau"),
+    ("window.nwc_pairing", "Nostr Wallet Connect"),
+    ("window.nwc_pairing_hint", "Scannez avec un client Nostr pour associer {name}. Ce code n'est affiché qu'une fois."),
+    ("nwc.connection", "Connexion"),
+    ("window.store_connect", "Connecter une boutique"),
+    ("window.access_links", "Accès temporaire"),
+    ("access_links.needs_proxy", "Les liens temporaires passent par le proxy inverse. Activez-le d'abord."),
+    ("access_links.created", "Nouveau lien, valable jusqu'au {expires}."),
+    ("access_links.create", "Nouveau lien valable"),
+    ("access_links.label", "Pour qui ? (facultatif)"),
+    ("access_links.full", "accès complet"),
+    ("access_links.hours", "{hours} h"),
+    ("access_links.active", "Liens actifs"),
+    ("access_links.none", "Aucun lien actif"),
+    ("access_links.expires", "Expire"),
+    ("access_links.last_used", "Dernière utilisation"),
+    ("access_links.never", "jamais"),
+    ("access_links.revoke", "Révoquer"),
+    ("store.btcpay", "BTCPay Server"),
+    ("store.lnbits", "LNbits"),
+    ("store.receive_only", "Réception seule : la boutique peut créer des factures et lire les paiements, mais pas envoyer."),
+    ("store.full_access", "Accès complet : la boutique peut aussi envoyer des paiements."),
+    ("window.node_info", "Infos du nœud"),
+    ("window.status", "État"),
+    ("window.actions", "Actions"),
+    ("window.no_tray", "La barre système n'est pas disponible, l'application se pilote donc depuis cette fenêtre. Le journal de l'application explique comment activer la barre système sur ce bureau."),
+    ("window.crashes", "plantages"),
+    ("window.logs", "Journal de {service}"),
+    ("window.no_log", "Rien n'a encore été journalisé."),
+    ("node_info.node_id", "ID du nœud"),
+    ("node_info.version", "Version"),
+    ("node_info.chain", "Chaîne"),
+    ("node_info.block_height", "Hauteur de bloc"),
+    ("node_info.channels", "Canaux actifs / total"),
+    ("node_info.balance", "Solde"),
+    ("node_info.inbound_liquidity", "Liquidité entrante"),
+    ("node_info.fee_credit", "Crédit de frais"),
+    ("safe_mode.title", "Mode sans échec"),
+    ("safe_mode.reason", "{service} a planté {crashes} fois en peu de temps. Seule l'application tourne."),
+    ("safe_mode.hint", "Consultez les journaux, restaurez la dernière sauvegarde ou réinitialisez les paramètres, puis redémarrez les services."),
+    ("safe_mode.view_logs", "Voir les journaux"),
+    ("safe_mode.restore_backup", "Restaurer la dernière sauvegarde"),
+    ("safe_mode.reset_config", "Réinitialiser les paramètres"),
+    ("safe_mode.resume", "Démarrer les services"),
+    ("startup.title", "Démarrage de Phoenixd Dashboard"),
+    ("startup.phoenixd", "Démarrage de phoenixd…"),
+    ("startup.backend", "Démarrage du backend…"),
+    ("startup.frontend", "Démarrage du tableau de bord…"),
+    ("startup.remote_access", "Configuration de l'accès distant…"),
+    ("startup.repairing", "Téléchargement des composants manquants…"),
+    ("startup.waiting_backend", "En attente du backend…"),
+    ("startup.waiting_frontend", "En attente du tableau de bord…"),
+    ("access.local", "Cet ordinateur"),
+    ("access.lan", "Réseau local"),
+    ("access.tor", "Service onion Tor"),
+    ("access.cloudflare", "Tunnel Cloudflare"),
+    ("access.tailscale", "Tailscale"),
+    ("notify.invoice_title", "Facture Lightning détectée"),
+    ("notify.invoice_body", "Payer avec Phoenixd Dashboard ? Choisissez \"Payer la facture copiée\" dans le menu."),
+    ("notify.blocked_title", "Accès distant bloqué"),
+    ("notify.blocked_body", "{ip} a été bloqué après plusieurs échecs de connexion"),
+    ("notify.autoheal_title", "Redémarrage automatique suspendu"),
+    ("notify.autoheal_body", "{service} échoue toujours après {attempts} redémarrages au cours de la dernière heure. Consultez ses journaux."),
+    ("notify.bandwidth_title", "Seuil de bande passante dépassé"),
+    ("notify.bandwidth_body", "Tor et Cloudflare ont utilisé {used} Mo, au-delà de votre seuil de {limit} Mo"),
+    ("notify.phoenixd_update_title", "Mise à jour de phoenixd disponible"),
+    ("notify.phoenixd_update_body", "phoenixd {latest} est sorti, vous utilisez {current}. Mettez à jour depuis le menu de la barre système."),
+    ("notify.clock_skew_title", "L'horloge système est décalée"),
+    ("notify.clock_skew_body", "Votre horloge est décalée de {secs}s par rapport à l'heure internet. Les canaux Lightning peuvent échouer avec une mauvaise heure ; activez la synchronisation automatique."),
+    ("digest.title_daily", "Résumé quotidien"),
+    ("digest.title_weekly", "Résumé hebdomadaire"),
+    ("digest.body", "{received} sat reçus ({received_count} paiements), {sent} sat envoyés ({sent_count} paiements), {fees} sat de frais."),
+    ("digest.balance", "Solde : {balance} sat."),
+    ("notify.lease_lost_title", "Portefeuille démarré sur un autre ordinateur"),
+    ("notify.dashboard_starting_title", "Le tableau de bord démarre"),
+    ("notify.dashboard_starting_body", "Il s'ouvrira dès qu'il sera prêt"),
+    ("notify.dashboard_not_ready", "Le tableau de bord n'a pas démarré. Vérifiez les services dans le menu de la barre système."),
+    ("notify.payment_stuck_title", "Paiement bloqué"),
+    ("notify.payment_stuck_body", "Un paiement sortant est en attente depuis {minutes} minutes. Ouvrez-le depuis le menu de la barre système."),
+    ("notify.invoice_expiring_title", "Facture sur le point d'expirer"),
+    ("notify.invoice_expiring_body", "Une facture impayée de {amount} sat expire dans {minutes} minutes. Ouvrez-la depuis le menu de la barre système."),
+    ("notify.safe_mode_title", "Mode sans échec"),
+    ("notify.safe_mode_body", "{service} plantait sans cesse, tous les services ont donc été arrêtés"),
+    ("dialog.startup_failed_title", "Phoenixd Dashboard n'a pas pu démarrer"),
+    ("dialog.startup_failed_hint", "Le tableau de bord ne fonctionnera pas tant que ce problème n'est pas résolu. Les journaux contiennent plus de détails."),
+    ("dialog.copy_details", "Copier les détails"),
+    ("dialog.open_logs", "Ouvrir les journaux"),
+    ("dialog.close", "Fermer"),
+    ("dialog.invalid_config_title", "Configuration invalide"),
+    ("dialog.invalid_config_hint", "Corrigez config.toml puis rechargez-le depuis la barre système. D'ici là, les services en cours gardent leurs paramètres actuels."),
+    ("dialog.open_folder", "Ouvrir le dossier"),
+    ("dialog.watch_only_off_title", "Quitter le mode lecture seule ?"),
+    ("dialog.lease_title", "Portefeuille utilisé sur un autre ordinateur"),
+    ("dialog.lease_body", "{host} exécute ce portefeuille (vu pour la dernière fois à {time}). Exécuter le même portefeuille sur deux ordinateurs, par exemple via un dossier synchronisé, peut faire perdre des fonds.\n\nNe le démarrez ici que si {host} est éteint ou n'utilise plus ce dossier de données."),
+    ("dialog.start_anyway", "Démarrer quand même"),
+    ("dialog.watch_only_off_body", "Toute personne ayant accès au tableau de bord pourra de nouveau envoyer des paiements."),
+    ("dialog.enable_payments", "Activer les paiements"),
+    ("dialog.keep_node_title", "Quitter Phoenixd Dashboard"),
+    ("dialog.keep_node_body", "Laisser le nœud Lightning tourner après avoir quitté ? Il continue de recevoir des paiements et l'application s'y reconnecte au prochain démarrage."),
+    ("dialog.keep_node_running", "Laisser le nœud tourner"),
+    ("dialog.stop_everything", "Tout arrêter"),
+    ("dialog.cancel", "Annuler"),
+    ("dialog.docker_import_title", "Importer le déploiement Docker"),
+    ("dialog.docker_import_body", "Le déploiement Docker dans {dir} sera arrêté et son nœud {chain} transféré dans l'application. Les données du nœud actuel de l'application sont mises de côté, pas supprimées. Le solde et l'historique des paiements sont vérifiés après le transfert ; s'ils ne correspondent pas, le nœud précédent est rétabli.\n\nLes données du tableau de bord conservées dans le postgres de Docker (contacts, notes) ne sont pas importées."),
+    ("dialog.import", "Importer"),
+    ("dialog.repair_title", "Composants manquants"),
+    ("dialog.repair_body", "Ces composants manquent dans l'application : {components}."),
+    ("dialog.repair_download", "Les télécharger maintenant ?"),
+    ("dialog.repair_reinstall", "Ils font partie de l'application ; réinstallez Phoenixd Dashboard pour les restaurer."),
+    ("dialog.download", "Télécharger"),
+    ("dialog.relocate_title", "Dossier de données synchronisé"),
+    ("dialog.relocate_body", "Le dossier de données du portefeuille {dir} est synchronisé par {provider}. Les clients de synchronisation corrompent les bases de données et l'état des canaux du nœud, et peuvent démarrer le même portefeuille sur un autre ordinateur, ce qui peut faire perdre des fonds.\n\nLe déplacer vers un dossier de cet ordinateur ? Les services s'arrêtent pendant la copie et l'app redémarre."),
+    ("dialog.relocate_move", "Déplacer…"),
+    ("dialog.later", "Plus tard"),
+    ("dialog.relocate_pick", "Choisissez un dossier local pour les données du portefeuille"),
+    ("dialog.relocate_background", "Désactivez d'abord le service en arrière-plan, puis déplacez le dossier de données."),
+    ("dialog.relocate_target_synced", "Ce dossier est aussi synchronisé par {provider}. Choisissez-en un qui reste sur cet ordinateur."),
+    ("dialog.relocate_failed_title", "Impossible de déplacer le dossier de données"),
+    ("auth.title", "Confirmez votre identité"),
+    ("auth.type_phrase", "Tapez « {phrase} » pour continuer."),
+    ("auth.phrase", "Je comprends"),
+    ("auth.confirm", "Confirmer"),
+    ("auth.reveal_seed", "Phoenixd Dashboard veut afficher votre phrase de récupération."),
+    ("auth.rotate_credentials", "Phoenixd Dashboard veut remplacer un identifiant d'accès."),
+    ("auth.export_key", "Phoenixd Dashboard veut créer et exporter une clé d'accès privée."),
+    ("auth.create_backup", "Phoenixd Dashboard veut enregistrer une sauvegarde de votre portefeuille, graine comprise."),
+    ("auth.restore_backup", "Phoenixd Dashboard veut remplacer votre portefeuille par une sauvegarde."),
+    ("auth.import_docker", "Phoenixd Dashboard veut remplacer votre portefeuille par le nœud d'un déploiement Docker."),
+    ("auth.nwc_pair", "Phoenixd Dashboard veut autoriser un client Nostr à dépenser depuis votre portefeuille."),
+    ("auth.store_full_access", "Phoenixd Dashboard veut donner à un serveur de boutique un accès complet à votre portefeuille, y compris l'envoi."),
+    ("auth.purge_data", "Phoenixd Dashboard veut supprimer la base de données du tableau de bord."),
+];
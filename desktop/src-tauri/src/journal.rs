@@ -0,0 +1,87 @@
+use crate::redact;
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use tracing::warn;
+
+/// One JSON object per line, like the audit log
+const JOURNAL_FILE: &str = "events.jsonl";
+/// The journal moves to `events.jsonl.1` past this size, replacing the older one
+const MAX_BYTES: u64 = 2 << 20;
+
+/// Something that happened to the services, for the dashboard's timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEvent {
+    /// RFC 3339 timestamp
+    pub at: String,
+    /// e.g. `service.started`, `service.crashed` or `mode.tor`
+    pub kind: String,
+    pub service: Option<String>,
+    pub detail: String,
+}
+
+/// Append an event. Like auditing, failing to journal never blocks anything.
+pub fn record(data_dir: &Path, kind: &str, service: Option<&str>, detail: impl Into<String>) {
+    let event = JournalEvent {
+        at: chrono::Local::now().to_rfc3339(),
+        kind: kind.to_string(),
+        service: service.map(String::from),
+        detail: redact::scrub(&detail.into()),
+    };
+    let path = data_dir.join(JOURNAL_FILE);
+    if std::fs::metadata(&path).is_ok_and(|meta| meta.len() > MAX_BYTES) {
+        let _ = std::fs::rename(&path, data_dir.join(format!("{}.1", JOURNAL_FILE)));
+    }
+    let result = serde_json::to_string(&event)
+        .map_err(|e| e.to_string())
+        .and_then(|line| {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .and_then(|mut file| writeln!(file, "{}", line))
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        warn!("Failed to write event journal: {}", e);
+    }
+}
+
+/// Events after `since`, whose kind starts with or whose service equals
+/// `filter`, newest first
+pub fn read(
+    data_dir: &Path,
+    since: Option<&str>,
+    filter: Option<&str>,
+    limit: usize,
+) -> Result<Vec<JournalEvent>, String> {
+    let since = since
+        .map(DateTime::parse_from_rfc3339)
+        .transpose()
+        .map_err(|e| format!("Invalid since timestamp: {}", e))?;
+    let mut content = String::new();
+    for name in [format!("{}.1", JOURNAL_FILE), JOURNAL_FILE.to_string()] {
+        match std::fs::read_to_string(data_dir.join(name)) {
+            Ok(part) => content.push_str(&part),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to read event journal: {}", e)),
+        }
+    }
+    let after = |event: &JournalEvent| match since {
+        Some(since) => DateTime::parse_from_rfc3339(&event.at)
+            .is_ok_and(|at: DateTime<FixedOffset>| at > since),
+        None => true,
+    };
+    let matches = |event: &JournalEvent| match filter {
+        Some(filter) => event.kind.starts_with(filter) || event.service.as_deref() == Some(filter),
+        None => true,
+    };
+    Ok(content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|event| after(event) && matches(event))
+        .take(limit)
+        .collect())
+}
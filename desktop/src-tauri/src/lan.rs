@@ -0,0 +1,38 @@
+use crate::i18n::t;
+use std::net::{IpAddr, UdpSocket};
+
+/// Address of the interface used for outbound traffic, i.e. the one other
+/// devices on the LAN can reach us at. No packets are actually sent.
+pub fn lan_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+
+    if ip.is_unspecified() || ip.is_loopback() {
+        None
+    } else {
+        Some(ip)
+    }
+}
+
+/// Random token handed to the backend; a device has to present it once to pair
+pub fn generate_pairing_token() -> Result<String, String> {
+    crate::token::generate()
+}
+
+/// Native window with a QR code of the pairing URL for scanning from a phone
+pub fn show_pairing_window(app: &tauri::AppHandle, url: &str) -> Result<(), String> {
+    let body = format!(
+        r#"<div class="qr">{}</div><p>{}</p><code>{}</code>"#,
+        crate::qr::to_svg(url)?,
+        crate::windows::escape(t("window.lan_pairing_hint")),
+        crate::windows::escape(url)
+    );
+    crate::windows::show(
+        app,
+        "lan-pairing",
+        t("window.lan_pairing"),
+        &body,
+        (360.0, 520.0),
+    )
+}
@@ -0,0 +1,145 @@
+use crate::i18n::t;
+use crate::{notify, token, AppState};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{error, info};
+
+const LEASE_FILE: &str = "instance.lease";
+/// How often the running instance renews its lease
+pub const HEARTBEAT: Duration = Duration::from_secs(30);
+/// A lease not renewed for this long is left over from a crash or a
+/// machine that went to sleep
+const STALE_AFTER: Duration = Duration::from_secs(3 * 60);
+
+/// Who runs the wallet in a data dir, renewed while they do. Synced folders
+/// carry it to the other machines sharing the data dir.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lease {
+    pub hostname: String,
+    pub pid: u32,
+    /// Tells this run from an earlier one on the same machine
+    pub instance: String,
+    /// RFC 3339
+    pub heartbeat: String,
+}
+
+impl Lease {
+    /// When it was last renewed, in local time
+    pub fn heartbeat_local(&self) -> String {
+        chrono::DateTime::parse_from_rfc3339(&self.heartbeat)
+            .map(|heartbeat| {
+                heartbeat
+                    .with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|_| self.heartbeat.clone())
+    }
+
+    fn is_fresh(&self) -> bool {
+        chrono::DateTime::parse_from_rfc3339(&self.heartbeat).is_ok_and(|heartbeat| {
+            (chrono::Utc::now() - heartbeat.to_utc())
+                .to_std()
+                .map_or(true, |age| age < STALE_AFTER)
+        })
+    }
+}
+
+fn path(data_dir: &Path) -> PathBuf {
+    data_dir.join(LEASE_FILE)
+}
+
+fn hostname() -> String {
+    sysinfo::System::host_name().unwrap_or_else(|| "unknown".to_string())
+}
+
+fn instance() -> &'static str {
+    static INSTANCE: OnceLock<String> = OnceLock::new();
+    INSTANCE.get_or_init(|| token::generate().unwrap_or_else(|_| std::process::id().to_string()))
+}
+
+fn read(data_dir: &Path) -> Option<Lease> {
+    let content = std::fs::read_to_string(path(data_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write(data_dir: &Path) -> Result<(), String> {
+    let lease = Lease {
+        hostname: hostname(),
+        pid: std::process::id(),
+        instance: instance().to_string(),
+        heartbeat: chrono::Utc::now().to_rfc3339(),
+    };
+    let content = serde_json::to_string_pretty(&lease)
+        .map_err(|e| format!("Failed to serialize lease: {}", e))?;
+    // Written aside and renamed, so a sync never carries half a file
+    let tmp = path(data_dir).with_extension("lease.tmp");
+    std::fs::write(&tmp, content).map_err(|e| format!("Failed to write {:?}: {}", tmp, e))?;
+    std::fs::rename(&tmp, path(data_dir))
+        .map_err(|e| format!("Failed to write {:?}: {}", path(data_dir), e))
+}
+
+/// A live lease from another machine: the wallet is in use there. Runs on
+/// this machine are left to the single instance and background service checks.
+pub fn held_elsewhere(data_dir: &Path) -> Option<Lease> {
+    read(data_dir).filter(|lease| lease.hostname != hostname() && lease.is_fresh())
+}
+
+/// Take the lease for this run, whoever held it
+pub fn acquire(data_dir: &Path) -> Result<(), String> {
+    if let Some(previous) = read(data_dir).filter(|lease| lease.hostname != hostname()) {
+        info!(
+            "🔒 Taking over the data dir lease from {}",
+            previous.hostname
+        );
+    }
+    write(data_dir)
+}
+
+/// Renew the lease, unless another machine took it over meanwhile: then
+/// say so and stop renewing
+pub fn renew(data_dir: &Path) -> Result<(), String> {
+    match read(data_dir) {
+        Some(lease) if lease.instance != instance() && lease.hostname != hostname() => {
+            Err(format!(
+                "{} took over this data dir at {}",
+                lease.hostname, lease.heartbeat
+            ))
+        }
+        _ => write(data_dir),
+    }
+}
+
+/// Give the lease up on quit, if it is still ours
+pub fn release(data_dir: &Path) {
+    if read(data_dir).is_some_and(|lease| lease.instance == instance()) {
+        let _ = std::fs::remove_file(path(data_dir));
+    }
+}
+
+/// Renew the lease every `HEARTBEAT` while the app runs; if another machine
+/// takes it over, stop phoenixd and tell the user
+pub fn spawn(app: AppHandle, data_dir: PathBuf) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(HEARTBEAT);
+        if let Err(e) = renew(&data_dir) {
+            error!("🔒 {}", e);
+            // Two nodes on one seed can lose funds; stop ours before anything else
+            let state = app.state::<AppState>();
+            if let Err(e) = state
+                .process_manager
+                .lock()
+                .unwrap()
+                .stop_service("phoenixd")
+            {
+                error!("🔒 Failed to stop phoenixd: {}", e);
+            }
+            notify::native(app.clone())(t("notify.lease_lost_title"), &e);
+            return;
+        }
+    });
+}
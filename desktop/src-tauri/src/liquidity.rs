@@ -0,0 +1,137 @@
+use crate::operations;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::process_manager::ProcessManager;
+use crate::tls;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+const AUTO_LIQUIDITY: [&str; 4] = ["off", "2m", "5m", "10m"];
+const MAX_FEE_CREDIT: [&str; 3] = ["off", "50k", "100k"];
+/// Longest a policy change waits for in-flight payments before restarting
+const SAFE_RESTART_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const POLL: Duration = Duration::from_secs(5);
+
+/// phoenixd's inbound liquidity settings, kept in `phoenix.conf`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityPolicy {
+    /// Channel size bought automatically when inbound liquidity runs out:
+    /// `off`, `2m`, `5m` or `10m` sat
+    pub auto_liquidity: String,
+    /// Fee credit accepted for payments too small to pay for liquidity:
+    /// `off`, `50k` or `100k` sat
+    pub max_fee_credit: String,
+    /// Highest liquidity fee as a percentage of the incoming amount
+    pub max_relative_fee_percent: u8,
+}
+
+impl Default for LiquidityPolicy {
+    /// phoenixd's own defaults
+    fn default() -> Self {
+        Self {
+            auto_liquidity: "2m".to_string(),
+            max_fee_credit: "100k".to_string(),
+            max_relative_fee_percent: 30,
+        }
+    }
+}
+
+impl LiquidityPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if !AUTO_LIQUIDITY.contains(&self.auto_liquidity.as_str()) {
+            return Err(format!(
+                "Auto-liquidity must be one of {}",
+                AUTO_LIQUIDITY.join(", ")
+            ));
+        }
+        if !MAX_FEE_CREDIT.contains(&self.max_fee_credit.as_str()) {
+            return Err(format!(
+                "Max fee credit must be one of {}",
+                MAX_FEE_CREDIT.join(", ")
+            ));
+        }
+        if !(1..=50).contains(&self.max_relative_fee_percent) {
+            return Err("Max relative fee must be between 1 and 50 percent".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub fn read(phoenix_conf: &Path) -> LiquidityPolicy {
+    let content = std::fs::read_to_string(phoenix_conf).unwrap_or_default();
+    let mut policy = LiquidityPolicy::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "auto-liquidity" => policy.auto_liquidity = value.to_string(),
+            "max-fee-credit" => policy.max_fee_credit = value.to_string(),
+            "max-relative-fee-percent" => {
+                if let Ok(percent) = value.parse() {
+                    policy.max_relative_fee_percent = percent;
+                }
+            }
+            _ => {}
+        }
+    }
+    policy
+}
+
+/// Update the policy lines in `phoenix.conf`, leaving everything else as is
+pub fn write(phoenix_conf: &Path, policy: &LiquidityPolicy) -> Result<(), String> {
+    policy.validate()?;
+    let content = std::fs::read_to_string(phoenix_conf)
+        .map_err(|e| format!("Failed to read phoenix.conf: {}", e))?;
+    let settings = [
+        ("auto-liquidity", policy.auto_liquidity.clone()),
+        ("max-fee-credit", policy.max_fee_credit.clone()),
+        (
+            "max-relative-fee-percent",
+            policy.max_relative_fee_percent.to_string(),
+        ),
+    ];
+
+    let mut lines: Vec<String> = content
+        .lines()
+        .filter(|line| {
+            let key = line.split_once('=').map(|(key, _)| key.trim());
+            !settings.iter().any(|(name, _)| key == Some(*name))
+        })
+        .map(str::to_string)
+        .collect();
+    lines.extend(
+        settings
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value)),
+    );
+    // phoenix.conf holds the HTTP password
+    tls::write_private(phoenix_conf, &(lines.join("\n") + "\n"))
+}
+
+/// Restart phoenixd once no payment or channel operation is in flight, so a
+/// changed policy takes effect without interrupting anything
+pub fn restart_when_idle(
+    pm: &Mutex<ProcessManager>,
+    data_dir: &Path,
+    op: &operations::Handle,
+) -> Result<(), String> {
+    let client = PhoenixdClient::new(data_dir);
+    let started = Instant::now();
+    while tauri::async_runtime::block_on(client.has_pending_operations()).unwrap_or(false) {
+        if started.elapsed() > SAFE_RESTART_TIMEOUT {
+            return Err(
+                "Operations are still in flight; the new policy applies at the next restart"
+                    .to_string(),
+            );
+        }
+        info!("⏳ Waiting for in-flight payments before restarting phoenixd");
+        op.progress("Waiting for in-flight payments");
+        op.sleep(POLL)?;
+    }
+    pm.lock().unwrap().restart_service("phoenixd")
+}
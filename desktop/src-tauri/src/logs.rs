@@ -0,0 +1,252 @@
+use crate::redact::{self, Scrubbing};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+/// Rotated files of the shell's own log kept around
+const MAX_DESKTOP_LOG_FILES: usize = 7;
+/// How much of each log goes into an export, from the end
+const EXPORT_BYTES_PER_LOG: usize = 2 << 20;
+/// Service logs included in an export
+const EXPORTED_SERVICES: [&str; 6] = [
+    "phoenixd",
+    "backend",
+    "frontend",
+    "tor",
+    "cloudflared",
+    "nwc",
+];
+/// Newest desktop log files included in an export
+const EXPORTED_DESKTOP_FILES: usize = 2;
+use tracing::warn;
+
+/// Directory holding one log file per managed service
+pub fn log_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("logs")
+}
+
+pub fn log_path(data_dir: &Path, service: &str) -> PathBuf {
+    log_dir(data_dir).join(format!("{}.log", service))
+}
+
+/// Last `lines` lines of a log file (empty if it can't be read)
+pub fn tail(path: &Path, lines: usize) -> Vec<String> {
+    let content = std::fs::read_to_string(path).unwrap_or_default();
+    let all: Vec<&str> = content.lines().collect();
+    all[all.len().saturating_sub(lines)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Send the shell's own `tracing` output to stdout, a daily rotated file
+/// under `logs/desktop/` and, if `os_log` is set, the OS logging facility. `RUST_LOG` overrides the configured level;
+/// both accept per-module targets like `info,phoenixd_dashboard::proxy=debug`.
+/// The returned guard flushes the file writer and has to be kept alive.
+pub fn init_tracing(data_dir: &Path, level: &str, os_log: bool) -> Option<WorkerGuard> {
+    let filter = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new(level))
+        .unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("desktop")
+        .filename_suffix("log")
+        .max_log_files(MAX_DESKTOP_LOG_FILES)
+        .build(log_dir(data_dir).join("desktop"));
+    let (file_layer, guard) = match appender {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (
+                Some(fmt::layer().with_ansi(false).with_writer(Scrubbing(writer))),
+                Some(guard),
+            )
+        }
+        Err(e) => {
+            eprintln!("Warning: Could not open the desktop log file: {}", e);
+            (None, None)
+        }
+    };
+
+    tracing_subscriber::registry()
+        .with(os_log.then(crate::oslog::layer).flatten())
+        .with(filter)
+        .with(fmt::layer().with_writer(Scrubbing(std::io::stdout)))
+        .with(file_layer)
+        .init();
+    guard
+}
+
+/// Append a child's stdout and stderr to its log file. Draining the pipes
+/// also keeps a chatty child from blocking once the pipe buffer is full.
+pub fn capture(data_dir: &Path, service: &str, child: &mut Child) {
+    capture_with(data_dir, service, child, |_| {});
+}
+
+/// Like `capture`, additionally handing every line to `on_line`
+pub fn capture_with(
+    data_dir: &Path,
+    service: &str,
+    child: &mut Child,
+    on_line: impl Fn(&str) + Send + Sync + 'static,
+) {
+    let file = match open(data_dir, service) {
+        Ok(file) => Some(Arc::new(Mutex::new(file))),
+        Err(e) => {
+            warn!("{}", e);
+            None
+        }
+    };
+    let on_line = Arc::new(on_line);
+
+    let streams: [Option<Box<dyn Read + Send>>; 2] = [
+        child
+            .stdout
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+    ];
+    for stream in streams.into_iter().flatten() {
+        let file = file.clone();
+        let on_line = Arc::clone(&on_line);
+        std::thread::spawn(move || {
+            for line in BufReader::new(stream).lines().map_while(Result::ok) {
+                on_line(&line);
+                if let Some(file) = &file {
+                    let _ = writeln!(file.lock().unwrap(), "{}", redact::scrub(&line));
+                }
+            }
+        });
+    }
+}
+
+/// stdout and stderr writing straight to a service's log file, for a child
+/// that has to outlive the app (a pipe would break when the app quits)
+pub fn detached_output(data_dir: &Path, service: &str) -> Result<(Stdio, Stdio), String> {
+    let file = open(data_dir, service)?;
+    let stderr = file
+        .try_clone()
+        .map_err(|e| format!("Failed to open {} log: {}", service, e))?;
+    Ok((Stdio::from(file), Stdio::from(stderr)))
+}
+
+/// Hand every line appended to a service's log file from now on to
+/// `on_line`, until `alive` turns false
+pub fn follow(
+    data_dir: &Path,
+    service: &str,
+    alive: impl Fn() -> bool + Send + 'static,
+    on_line: impl Fn(&str) + Send + 'static,
+) {
+    let path = log_path(data_dir, service);
+    std::thread::spawn(move || {
+        let Ok(mut file) = File::open(&path) else {
+            return;
+        };
+        let _ = file.seek(SeekFrom::End(0));
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        loop {
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => {
+                    if !alive() {
+                        return;
+                    }
+                    std::thread::sleep(Duration::from_millis(500));
+                }
+                // A partial line is completed by the next read
+                Ok(_) if !line.ends_with('\n') => {}
+                Ok(_) => {
+                    on_line(line.trim_end());
+                    line.clear();
+                }
+            }
+        }
+    });
+}
+
+fn open(data_dir: &Path, service: &str) -> Result<File, String> {
+    std::fs::create_dir_all(log_dir(data_dir))
+        .map_err(|e| format!("Failed to create log dir: {}", e))?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path(data_dir, service))
+        .map_err(|e| format!("Failed to open {} log: {}", service, e))
+}
+
+/// Zip the last few MB of every service log, the newest desktop logs and
+/// `extra` files (name, content) into `dest`, with `secrets` and anything
+/// else `redact` knows replaced by `[redacted]`
+pub fn export_zip(
+    data_dir: &Path,
+    dest: &Path,
+    secrets: &[String],
+    extra: &[(&str, String)],
+) -> Result<(), String> {
+    for secret in secrets {
+        redact::register(secret);
+    }
+    let mut logs: Vec<(String, PathBuf)> = EXPORTED_SERVICES
+        .iter()
+        .map(|service| (format!("{}.log", service), log_path(data_dir, service)))
+        .filter(|(_, path)| path.exists())
+        .collect();
+    let mut desktop: Vec<PathBuf> = std::fs::read_dir(log_dir(data_dir).join("desktop"))
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    // Daily files carry the date in their name
+    desktop.sort();
+    logs.extend(
+        desktop
+            .into_iter()
+            .rev()
+            .take(EXPORTED_DESKTOP_FILES)
+            .map(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                (format!("desktop/{}", name), path.clone())
+            }),
+    );
+
+    let file = File::create(dest).map_err(|e| format!("Failed to create {:?}: {}", dest, e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, path) in logs {
+        let content = redact::scrub(&tail_bytes(&path, EXPORT_BYTES_PER_LOG));
+        zip.start_file(name, options)
+            .and_then(|_| zip.write_all(content.as_bytes()).map_err(Into::into))
+            .map_err(|e| format!("Failed to write log export: {}", e))?;
+    }
+    for (name, content) in extra {
+        zip.start_file(*name, options)
+            .and_then(|_| {
+                zip.write_all(redact::scrub(content).as_bytes())
+                    .map_err(Into::into)
+            })
+            .map_err(|e| format!("Failed to write log export: {}", e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to write log export: {}", e))?;
+    Ok(())
+}
+
+/// The last `max` bytes of a file, starting at a line boundary
+fn tail_bytes(path: &Path, max: usize) -> String {
+    let content = std::fs::read(path).unwrap_or_default();
+    if content.len() <= max {
+        return String::from_utf8_lossy(&content).into_owned();
+    }
+    let tail = &content[content.len() - max..];
+    let start = tail.iter().position(|&b| b == b'\n').map_or(0, |i| i + 1);
+    String::from_utf8_lossy(&tail[start..]).into_owned()
+}
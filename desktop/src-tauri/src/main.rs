@@ -1,125 +1,1434 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod access;
+mod access_guard;
+mod access_links;
+mod alerts;
+mod audit;
+mod autoheal;
+mod background;
+mod backup;
+mod bandwidth;
+mod battery;
+mod browser;
+mod clipboard_watch;
+mod clock;
+mod cloudflare;
+mod commands;
+mod config;
+mod control_socket;
+mod crash;
+mod credentials;
+mod database;
+mod deeplink;
+mod dialogs;
+mod digest;
+mod docker_import;
+mod footprint;
+mod health_checks;
+mod i18n;
+mod journal;
+mod lan;
+mod lease;
+mod liquidity;
+mod logs;
+mod maintenance;
+mod mdns;
+mod metrics;
+mod mock_phoenixd;
+mod native_backend;
+mod network;
+mod node_events;
+mod node_info;
+mod notify;
+mod nwc;
+mod onboarding;
+mod operations;
+mod os_auth;
+mod oslog;
+mod payments_export;
+mod phoenixd_api;
+mod phoenixd_update;
+mod plan;
+mod power;
 mod process_manager;
+mod profile;
+mod proxy;
+mod qr;
+mod receive;
+mod recent_payments;
+mod redact;
+mod resources;
+mod safe_mode;
+mod scheduler;
+mod secrets;
+mod selftest;
+mod startup;
+mod static_frontend;
+mod status_window;
+mod store_connect;
+mod supervisor;
+mod synced_dir;
+mod tailscale;
+#[cfg(feature = "test-hooks")]
+mod test_hooks;
+mod tls;
+mod token;
+mod tor;
+mod tor_health;
+mod tray_icon;
+mod tray_jobs;
+mod uptime;
+mod watchdog;
+mod watchtower;
+mod windows;
 
-use process_manager::ProcessManager;
+use clipboard_watch::ClipboardWatcher;
+use cloudflare::CloudflareManager;
+use config::AppConfig;
+use i18n::t;
+use metrics::MetricsServer;
+use process_manager::{ProcessManager, ServiceState, ServiceStatus, StatusCache};
+use std::path::PathBuf;
 use std::sync::Mutex;
 use tauri::{
-    image::Image,
-    menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem},
+    menu::{
+        CheckMenuItemBuilder, Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem,
+        SubmenuBuilder,
+    },
     tray::TrayIconBuilder,
-    Manager,
+    Emitter, Manager, RunEvent,
 };
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_dialog::DialogExt;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tracing::{error, info, warn};
+use uptime::UptimeStats;
+
+/// Show the advertised mDNS name and Tailscale URL (if any) in the tray tooltip
+pub(crate) fn update_tray_tooltip(app: &tauri::AppHandle, pm: &ProcessManager) {
+    let mut tooltip = "Phoenixd Dashboard".to_string();
+    if let Some(hostname) = pm.mdns_hostname() {
+        tooltip.push_str(&format!(" — {}", hostname));
+    }
+    if let Some(url) = pm.tailscale_url() {
+        tooltip.push_str(&format!("\n{}", url));
+    }
+    if !network::is_online() {
+        tooltip.push_str(&format!("\n{}", t("tray.offline")));
+    }
+    if tor::node_private() {
+        tooltip.push_str(&format!("\n🧅 {}", t("tray.node_over_tor")));
+    }
+    if pm.node_paused() {
+        tooltip.push_str(&format!("\n⏸️ {}", t("tray.node_paused")));
+    }
+
+    if let Some(tray) = app.tray_by_id("main") {
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+}
+
+/// Open the page each `phoenixd-dashboard://` link asks for and the pay
+/// screen for each payment link the OS handed us, and let any listening
+/// window know about it
+fn open_links(app: &tauri::AppHandle, uris: &[String]) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let dashboard_url = state.process_manager.lock().unwrap().dashboard_url();
+
+    for uri in uris {
+        if let Some(page) = deeplink::parse_page(uri) {
+            browser::open_page(app, &page);
+            continue;
+        }
+        let Some(target) = deeplink::parse(uri) else {
+            warn!("Unsupported payment link: {}", uri);
+            continue;
+        };
+
+        match target.url(&dashboard_url) {
+            Ok(url) => {
+                info!("⚡ Opening payment link in the dashboard");
+                let _ = app.emit(
+                    "deep-link",
+                    serde_json::json!({ "uri": uri, "url": url.as_str() }),
+                );
+                browser::open(app, url.as_str());
+            }
+            Err(e) => error!("{}", e),
+        }
+    }
+}
+
+/// Bind the quick receive shortcut, replacing any previous binding
+fn register_quick_receive_shortcut(app: &tauri::AppHandle, shortcut: &str) -> Result<(), String> {
+    let shortcuts = app.global_shortcut();
+    shortcuts
+        .unregister_all()
+        .map_err(|e| format!("Failed to clear shortcuts: {}", e))?;
+
+    if shortcut.is_empty() {
+        return Ok(());
+    }
+    shortcuts
+        .register(shortcut)
+        .map_err(|e| format!("Failed to register shortcut {}: {}", shortcut, e))?;
+    info!("⌨️ Quick receive on {}", shortcut);
+    Ok(())
+}
+
+/// Tray menu, rebuilt whenever a service changes state
+fn build_tray_menu(
+    app: &tauri::AppHandle,
+    services: &[ServiceStatus],
+) -> tauri::Result<Menu<tauri::Wry>> {
+    let open_dashboard = MenuItemBuilder::with_id("open", t("tray.open_dashboard")).build(app)?;
+
+    let copy_node_id =
+        MenuItemBuilder::with_id("copy_node_id", format!("📋 {}", t("tray.copy_node_id")))
+            .build(app)?;
+
+    let node_info =
+        MenuItemBuilder::with_id("node_info", format!("ℹ️ {}", t("tray.node_info"))).build(app)?;
+
+    let status_window =
+        MenuItemBuilder::with_id("status_window", format!("📊 {}", t("tray.status_window")))
+            .build(app)?;
+
+    let clock_skew_secs = app.try_state::<AppState>().map_or(0, |state| {
+        state.config.lock().unwrap().alerts.clock_skew_secs
+    });
+    let clock_skew = clock::last()
+        .filter(|skew| skew.exceeds(clock_skew_secs))
+        .map(|skew| {
+            MenuItemBuilder::with_id(
+                "clock_skew",
+                format!(
+                    "⏰ {}",
+                    t("tray.clock_skew").replace("{secs}", &skew.offset_secs().to_string())
+                ),
+            )
+            .enabled(false)
+            .build(app)
+        })
+        .transpose()?;
+
+    let phoenixd_update = phoenixd_update::available()
+        .map(|version| {
+            let upgrading = tray_jobs::is_running("phoenixd_upgrade");
+            let label = if upgrading {
+                t("tray.phoenixd_upgrading")
+            } else {
+                t("tray.phoenixd_update")
+            };
+            MenuItemBuilder::with_id(
+                "phoenixd_upgrade",
+                format!("⬆️ {}", label.replace("{version}", &version)),
+            )
+            .enabled(!upgrading)
+            .build(app)
+        })
+        .transpose()?;
+
+    let new_invoice =
+        MenuItemBuilder::with_id("new_invoice", format!("⚡ {}", t("tray.new_invoice")))
+            .build(app)?;
+
+    let watch_only = app
+        .try_state::<AppState>()
+        .is_some_and(|state| state.config.lock().unwrap().general.watch_only);
+
+    let pay_copied = MenuItemBuilder::with_id("pay_copied", format!("⚡ {}", t("tray.pay_copied")))
+        .enabled(!watch_only)
+        .build(app)?;
+
+    let watch_only_item =
+        CheckMenuItemBuilder::with_id("watch_only", format!("👁️ {}", t("tray.watch_only")))
+            .checked(watch_only)
+            .build(app)?;
+
+    let copy_public_url = MenuItemBuilder::with_id(
+        "copy_public_url",
+        format!("☁️ {}", t("tray.copy_public_url")),
+    )
+    .build(app)?;
+
+    let lan_pairing =
+        MenuItemBuilder::with_id("lan_pairing", format!("📱 {}", t("tray.lan_pairing")))
+            .build(app)?;
+
+    let copy_tailscale_url = MenuItemBuilder::with_id(
+        "copy_tailscale_url",
+        format!("🔗 {}", t("tray.copy_tailscale_url")),
+    )
+    .build(app)?;
+
+    let access_qr =
+        MenuItemBuilder::with_id("access_qr", format!("🔳 {}", t("tray.access_qr"))).build(app)?;
+
+    let open_folder = SubmenuBuilder::new(app, format!("📂 {}", t("tray.open")))
+        .text("open_location:data", t("tray.open_data"))
+        .text("open_location:logs", t("tray.open_logs"))
+        .text("open_location:config", t("tray.open_config"))
+        .text(
+            "open_location:phoenixd_config",
+            t("tray.open_phoenixd_config"),
+        )
+        .build()?;
+
+    let export_logs =
+        MenuItemBuilder::with_id("export_logs", format!("📦 {}", t("tray.export_logs")))
+            .enabled(!tray_jobs::is_running("export_logs"))
+            .build(app)?;
+
+    let export_payments = MenuItemBuilder::with_id(
+        "export_payments",
+        format!("🧾 {}", t("tray.export_payments")),
+    )
+    .enabled(!tray_jobs::is_running("export_payments"))
+    .build(app)?;
+
+    let nwc_enabled = app
+        .try_state::<AppState>()
+        .is_some_and(|state| state.config.lock().unwrap().nwc.enabled);
+    let nwc_menu = SubmenuBuilder::new(app, format!("🔗 {}", t("tray.nwc")))
+        .item(
+            &CheckMenuItemBuilder::with_id("nwc", t("tray.nwc"))
+                .checked(nwc_enabled)
+                .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::with_id("nwc_pair", t("tray.nwc_pair"))
+                .enabled(nwc_enabled && !watch_only)
+                .build(app)?,
+        )
+        .build()?;
+
+    let node_paused = app
+        .try_state::<AppState>()
+        .is_some_and(|state| state.process_manager.lock().unwrap().node_paused());
+    let pause_node = if node_paused {
+        MenuItemBuilder::with_id("resume_node", format!("▶️ {}", t("tray.resume_node")))
+    } else {
+        MenuItemBuilder::with_id("pause_node", format!("⏸️ {}", t("tray.pause_node")))
+    }
+    .enabled(!tray_jobs::is_running("node_pause"))
+    .build(app)?;
+
+    let share_access =
+        MenuItemBuilder::with_id("share_access", format!("🎟️ {}", t("tray.share_access")))
+            .build(app)?;
+
+    let store_connect =
+        MenuItemBuilder::with_id("store_connect", format!("🏪 {}", t("tray.store_connect")))
+            .build(app)?;
+
+    let import_docker =
+        MenuItemBuilder::with_id("import_docker", format!("🐳 {}", t("tray.import_docker")))
+            .enabled(!tray_jobs::is_running("docker_import"))
+            .build(app)?;
+
+    let unblock =
+        MenuItemBuilder::with_id("unblock", format!("🔓 {}", t("tray.unblock"))).build(app)?;
+
+    let tor_running = services
+        .iter()
+        .any(|service| service.name == "tor" && service.state != ServiceState::Stopped);
+    let tor_new_identity = tor_running
+        .then(|| {
+            MenuItemBuilder::with_id(
+                "tor_new_identity",
+                format!("🧅 {}", t("tray.tor_new_identity")),
+            )
+            .build(app)
+        })
+        .transpose()?;
+
+    let separator1 = PredefinedMenuItem::separator(app)?;
+
+    let payments = recent_payments::recent();
+    let recent_payments_menu = if payments.is_empty() {
+        None
+    } else {
+        let mut submenu = SubmenuBuilder::new(app, format!("🧾 {}", t("tray.recent_payments")));
+        for payment in &payments {
+            submenu = submenu.text(format!("payment:{}", payment.id), payment.label());
+        }
+        Some(submenu.build()?)
+    };
+
+    let flagged = watchtower::flagged();
+    let attention_menu = if flagged.is_empty() {
+        None
+    } else {
+        let mut submenu = SubmenuBuilder::new(app, format!("⚠️ {}", t("tray.needs_attention")));
+        for flag in &flagged {
+            submenu = submenu.text(format!("payment:{}", flag.id), &flag.label);
+        }
+        Some(submenu.build()?)
+    };
+
+    // Only worth a submenu once there is more than the main node
+    let nodes = app
+        .try_state::<AppState>()
+        .map(|state| state.config.lock().unwrap().nodes.clone())
+        .filter(|nodes| !nodes.extra.is_empty());
+    let nodes_menu = match &nodes {
+        Some(nodes) => {
+            let active = nodes.active_name();
+            let names = std::iter::once(config::MAIN_NODE)
+                .chain(nodes.extra.iter().map(|node| node.name.as_str()));
+            let mut submenu =
+                SubmenuBuilder::new(app, format!("⚡ {}: {}", t("tray.active_node"), active));
+            for name in names {
+                submenu = submenu.item(
+                    &CheckMenuItemBuilder::with_id(format!("node:{}", name), name)
+                        .checked(name == active)
+                        .build(app)?,
+                );
+            }
+            Some(submenu.build()?)
+        }
+        None => None,
+    };
+
+    let mut services_menu = SubmenuBuilder::new(app, t("tray.services"));
+    for service in services {
+        let (icon, label) = match service.state {
+            ServiceState::Running => ("🟢", t("service.running")),
+            ServiceState::Unhealthy => ("🟠", t("service.unhealthy")),
+            ServiceState::Stopped => ("🔴", t("service.stopped")),
+        };
+        let stopped = service.state == ServiceState::Stopped;
+        let busy = tray_jobs::is_running(&format!("service:{}", service.name));
+        let label = if busy { t("tray.working") } else { label };
+        let id = |action: &str| format!("service:{}:{}", service.name, action);
+
+        let submenu = SubmenuBuilder::new(app, format!("{} {} — {}", icon, service.name, label))
+            .item(
+                &MenuItemBuilder::with_id(id("start"), t("service.start"))
+                    .enabled(stopped && !busy)
+                    .build(app)?,
+            )
+            .item(
+                &MenuItemBuilder::with_id(id("stop"), t("service.stop"))
+                    .enabled(!stopped && !busy)
+                    .build(app)?,
+            )
+            .item(
+                &MenuItemBuilder::with_id(id("restart"), t("service.restart"))
+                    .enabled(!busy)
+                    .build(app)?,
+            )
+            .item(&MenuItemBuilder::with_id(id("logs"), t("service.logs")).build(app)?)
+            .build()?;
+        services_menu = services_menu.item(&submenu);
+    }
+    let services_menu = services_menu.build()?;
+
+    let next_maintenance = app
+        .try_state::<AppState>()
+        .and_then(|state| maintenance::next_run(&state.config.lock().unwrap().maintenance))
+        .map(|next| {
+            MenuItemBuilder::with_id(
+                "next_maintenance",
+                format!(
+                    "🛠️ {}: {}",
+                    t("tray.next_maintenance"),
+                    next.format("%a %H:%M")
+                ),
+            )
+            .enabled(false)
+            .build(app)
+        })
+        .transpose()?;
+
+    let self_test = selftest::last()
+        .map(|report| {
+            let icon = if report.failed().count() == 0 {
+                "🩺"
+            } else {
+                "⚠️"
+            };
+            MenuItemBuilder::with_id("self_test", format!("{} {}", icon, report.summary()))
+                .build(app)
+        })
+        .transpose()?;
+
+    let separator2 = PredefinedMenuItem::separator(app)?;
+
+    let optimizing = tray_jobs::is_running("optimize_database");
+    let optimize_database = MenuItemBuilder::with_id(
+        "optimize_database",
+        format!("🧹 {}", t("tray.optimize_database")),
+    )
+    .enabled(!optimizing)
+    .build(app)?;
+
+    let restarting = tray_jobs::is_running("restart");
+    let restart = MenuItemBuilder::with_id(
+        "restart",
+        if restarting {
+            t("tray.restarting")
+        } else {
+            t("tray.restart")
+        },
+    )
+    .enabled(!restarting)
+    .build(app)?;
+
+    let reload_config = MenuItemBuilder::with_id("reload_config", t("tray.reload_config"))
+        .enabled(!tray_jobs::is_running("reload_config"))
+        .build(app)?;
+
+    let quit = MenuItemBuilder::with_id("quit", t("tray.quit")).build(app)?;
+
+    // API-only mode without an external UI has no dashboard to open
+    let has_ui = app
+        .try_state::<AppState>()
+        .is_none_or(|state| state.config.lock().unwrap().frontend.has_ui());
+
+    let offline = (!network::is_online())
+        .then(|| {
+            MenuItemBuilder::with_id("offline", format!("🌐 {}", t("tray.offline")))
+                .enabled(false)
+                .build(app)
+        })
+        .transpose()?;
+    let in_safe_mode = app
+        .try_state::<AppState>()
+        .is_some_and(|state| safe_mode::is_active(&state.data_dir));
+    let safe_mode_item = in_safe_mode
+        .then(|| {
+            MenuItemBuilder::with_id("safe_mode", format!("🛟 {}", t("tray.safe_mode"))).build(app)
+        })
+        .transpose()?;
+
+    let synced_by = app
+        .try_state::<AppState>()
+        .and_then(|state| synced_dir::detect(&state.data_dir));
+    let relocate_item = synced_by
+        .map(|provider| {
+            MenuItemBuilder::with_id(
+                "relocate_data_dir",
+                format!(
+                    "☁️ {}",
+                    t("tray.relocate_data_dir").replace("{provider}", provider)
+                ),
+            )
+            .build(app)
+        })
+        .transpose()?;
+
+    let mut menu = MenuBuilder::new(app);
+    if let Some(safe_mode_item) = &safe_mode_item {
+        menu = menu.item(safe_mode_item);
+    }
+    if let Some(relocate_item) = &relocate_item {
+        menu = menu.item(relocate_item);
+    }
+    if let Some(offline) = &offline {
+        menu = menu.item(offline);
+    }
+    if has_ui {
+        menu = menu.item(&open_dashboard);
+    }
+    let mut menu = menu
+        .item(&copy_node_id)
+        .item(&node_info)
+        .item(&status_window);
+    if let Some(phoenixd_update) = &phoenixd_update {
+        menu = menu.item(phoenixd_update);
+    }
+    if let Some(clock_skew) = &clock_skew {
+        menu = menu.item(clock_skew);
+    }
+    let mut menu = menu
+        .item(&new_invoice)
+        .item(&pay_copied)
+        .item(&copy_public_url)
+        .item(&copy_tailscale_url)
+        .item(&lan_pairing)
+        .item(&access_qr)
+        .item(&share_access)
+        .item(&store_connect)
+        .item(&pause_node)
+        .item(&unblock)
+        .item(&watch_only_item)
+        .item(&nwc_menu);
+    if let Some(tor_new_identity) = &tor_new_identity {
+        menu = menu.item(tor_new_identity);
+    }
+    // Only the dashboard can show a payment's details
+    if let Some(attention_menu) = attention_menu.as_ref().filter(|_| has_ui) {
+        menu = menu.item(attention_menu);
+    }
+    if let Some(recent_payments_menu) = recent_payments_menu.as_ref().filter(|_| has_ui) {
+        menu = menu.item(recent_payments_menu);
+    }
+    let mut menu = menu.item(&separator1);
+    if let Some(nodes_menu) = &nodes_menu {
+        menu = menu.item(nodes_menu);
+    }
+    let mut menu = menu
+        .item(&services_menu)
+        .item(&open_folder)
+        .item(&export_logs)
+        .item(&export_payments)
+        .item(&import_docker);
+    if let Some(next_maintenance) = &next_maintenance {
+        menu = menu.item(next_maintenance);
+    }
+    if let Some(self_test) = &self_test {
+        menu = menu.item(self_test);
+    }
+    menu.item(&separator2)
+        .item(&optimize_database)
+        .item(&restart)
+        .item(&reload_config)
+        .item(&quit)
+        .build()
+}
+
+pub(crate) fn refresh_tray_menu(app: &tauri::AppHandle, services: &[ServiceStatus]) {
+    let Some(tray) = app.tray_by_id("main") else {
+        return;
+    };
+    match build_tray_menu(app, services) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => warn!("Could not rebuild tray menu: {}", e),
+    }
+}
+
+/// Tray menu actions by item id; the status window offers the same ones
+fn handle_menu_action(app: &tauri::AppHandle, id: &str) {
+    match id {
+        "open" => browser::open_dashboard(app),
+        "copy_node_id" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = receive::node_id_to_clipboard(app).await {
+                    error!("{}", e);
+                }
+            });
+        }
+        "status_window" => {
+            if let Err(e) = status_window::show(app) {
+                error!("{}", e);
+            }
+        }
+        "node_info" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let data_dir = app.state::<AppState>().data_dir.clone();
+                let client = phoenixd_api::PhoenixdClient::active(&data_dir);
+                let result = match node_info::fetch(&client).await {
+                    Ok(info) => node_info::show_window(&app, &info),
+                    Err(e) => node_info::show_error(&app, &e),
+                };
+                if let Err(e) = result {
+                    error!("{}", e);
+                }
+            });
+        }
+        "new_invoice" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = receive::invoice_to_clipboard(app).await {
+                    error!("{}", e);
+                }
+            });
+        }
+        "pay_copied" => {
+            if let Some(state) = app.try_state::<AppState>() {
+                let detected = state
+                    .clipboard_watcher
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .and_then(|watcher| watcher.take_detected());
+                match detected {
+                    Some(uri) => open_links(app, &[uri]),
+                    None => info!("📋 No invoice detected on the clipboard"),
+                }
+            }
+        }
+        "self_test" => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                selftest::run_and_report(&app);
+            });
+        }
+        "copy_public_url" => {
+            if let Some(state) = app.try_state::<AppState>() {
+                let url = state.process_manager.lock().unwrap().get_public_url();
+                match url {
+                    Some(url) => {
+                        if let Err(e) = app.clipboard().write_text(url.clone()) {
+                            error!("Failed to copy public URL: {}", e);
+                        } else {
+                            info!("📋 Copied public URL: {}", url);
+                        }
+                    }
+                    None => info!("☁️ No tunnel is running"),
+                }
+            }
+        }
+        "copy_tailscale_url" => {
+            if let Some(state) = app.try_state::<AppState>() {
+                let url = state.process_manager.lock().unwrap().tailscale_url();
+                match url {
+                    Some(url) => {
+                        if let Err(e) = app.clipboard().write_text(url.clone()) {
+                            error!("Failed to copy Tailscale URL: {}", e);
+                        } else {
+                            info!("📋 Copied Tailscale URL: {}", url);
+                        }
+                    }
+                    None => info!("🔗 Not shared on Tailscale"),
+                }
+            }
+        }
+        "lan_pairing" => {
+            if let Some(state) = app.try_state::<AppState>() {
+                let url = state.process_manager.lock().unwrap().lan_access_url();
+                match url {
+                    Some(url) => {
+                        if let Err(e) = lan::show_pairing_window(app, &url) {
+                            error!("{}", e);
+                        }
+                    }
+                    None => info!("📱 LAN access is disabled"),
+                }
+            }
+        }
+        "access_qr" => {
+            if let Some(state) = app.try_state::<AppState>() {
+                let urls = state.process_manager.lock().unwrap().access_urls();
+                if let Err(e) = access::show_access_window(app, &urls) {
+                    error!("{}", e);
+                }
+            }
+        }
+        "share_access" => {
+            if let Err(e) = access_links::show_window(app) {
+                error!("{}", e);
+            }
+        }
+        "store_connect" => {
+            let app = app.clone();
+            // Restarting phoenixd for a new password blocks
+            std::thread::spawn(move || {
+                if let Err(e) = commands::show_store_connection(app, None, true) {
+                    error!("{}", e);
+                }
+            });
+        }
+        "relocate_data_dir" => {
+            let app = app.clone();
+            // Dialogs and the copy block
+            std::thread::spawn(move || synced_dir::offer_relocation(&app));
+        }
+        "safe_mode" => safe_mode::show_window(app),
+        "tor_new_identity" => {
+            if let Some(state) = app.try_state::<AppState>() {
+                let data_dir = state.data_dir.clone();
+                std::thread::spawn(
+                    move || match tor::TorManager::new(&data_dir).new_identity() {
+                        Ok(()) => audit::record(&data_dir, "tor.new_identity", "tray"),
+                        Err(e) => error!("{}", e),
+                    },
+                );
+            }
+        }
+        "unblock" => {
+            if let Some(state) = app.try_state::<AppState>() {
+                state.process_manager.lock().unwrap().unblock_client(None);
+                info!("🔓 Unblocked all remote clients");
+            }
+        }
+        "restart" => {
+            let description = format!("{}: {}", t("tray.services"), t("tray.restart"));
+            tray_jobs::spawn(app, "restart", description, |app| {
+                let state = app.state::<AppState>();
+                audit::record(&state.data_dir, "services.restart_all", "tray");
+                let mut pm = state.process_manager.lock().unwrap();
+                info!("🔄 Restarting services...");
+                pm.stop_all();
+                pm.start_all()?;
+                pm.service_statuses();
+                info!("✅ Services restarted!");
+                Ok(None)
+            });
+        }
+        "pause_node" | "resume_node" => {
+            let paused = id == "pause_node";
+            let description = t(if paused {
+                "tray.pause_node"
+            } else {
+                "tray.resume_node"
+            })
+            .to_string();
+            tray_jobs::spawn(app, "node_pause", description, move |app| {
+                commands::set_node_paused(app, paused).map(|()| None)
+            });
+        }
+        "reload_config" => {
+            let description = t("tray.reload_config").to_string();
+            tray_jobs::spawn(app, "reload_config", description, |app| {
+                commands::apply_config_file(app).map(|()| None)
+            });
+        }
+        "export_logs" => {
+            let app = app.clone();
+            // The save dialog blocks, keep it off the main thread
+            std::thread::spawn(move || {
+                let file_name = format!(
+                    "phoenixd-dashboard-logs-{}.zip",
+                    chrono::Local::now().format("%Y%m%d-%H%M%S")
+                );
+                let dest = app
+                    .dialog()
+                    .file()
+                    .add_filter("Zip", &["zip"])
+                    .set_file_name(file_name)
+                    .blocking_save_file()
+                    .and_then(|path| path.into_path().ok());
+                if let Some(dest) = dest {
+                    let description = t("tray.export_logs").to_string();
+                    tray_jobs::spawn(&app, "export_logs", description, move |app| {
+                        commands::export_logs_to(app, &dest)?;
+                        Ok(Some(dest.display().to_string()))
+                    });
+                }
+            });
+        }
+        "export_payments" => {
+            let app = app.clone();
+            // The save dialog blocks, keep it off the main thread
+            std::thread::spawn(move || {
+                let file_name = format!(
+                    "phoenixd-payments-{}.csv",
+                    chrono::Local::now().format("%Y%m%d")
+                );
+                let dest = app
+                    .dialog()
+                    .file()
+                    .add_filter("CSV", &["csv"])
+                    .add_filter("JSON", &["json"])
+                    .set_file_name(file_name)
+                    .blocking_save_file()
+                    .and_then(|path| path.into_path().ok());
+                if let Some(dest) = dest {
+                    let description = t("tray.export_payments").to_string();
+                    tray_jobs::spawn_with(&app, "export_payments", description, move |app, op| {
+                        let data_dir = &app.state::<AppState>().data_dir;
+                        audit::record(data_dir, "payments.export", dest.to_string_lossy());
+                        let format = if dest.extension().is_some_and(|ext| ext == "json") {
+                            "json"
+                        } else {
+                            "csv"
+                        };
+                        let client = phoenixd_api::PhoenixdClient::active(data_dir);
+                        let range = payments_export::ExportRange::default();
+                        let count = payments_export::export(&client, &range, format, &dest, op)?;
+                        Ok(Some(format!("{} → {}", count, dest.display())))
+                    });
+                }
+            });
+        }
+        "import_docker" => {
+            let app = app.clone();
+            // The folder picker and confirmations block, keep them off the main thread
+            std::thread::spawn(move || {
+                let Some(dir) = app
+                    .dialog()
+                    .file()
+                    .blocking_pick_folder()
+                    .and_then(|path| path.into_path().ok())
+                else {
+                    return;
+                };
+                let project = match docker_import::detect(&dir) {
+                    Ok(project) => project,
+                    Err(e) => {
+                        notify::native(app.clone())(t("tray.import_docker"), &e);
+                        return;
+                    }
+                };
+                if !dialogs::confirm_docker_import(&app, &project) {
+                    return;
+                }
+                let data_dir = app.state::<AppState>().data_dir.clone();
+                if let Err(e) =
+                    os_auth::require(&app, &data_dir, "docker.import", t("auth.import_docker"))
+                {
+                    warn!("{}", e);
+                    return;
+                }
+                let description = t("tray.import_docker").to_string();
+                tray_jobs::spawn_with(&app, "docker_import", description, move |app, op| {
+                    let report = docker_import::import(app, &project.dir, op)?;
+                    Ok(Some(format!(
+                        "{} sat, {} payments",
+                        report.node.balance_sat,
+                        report.node.incoming + report.node.outgoing
+                    )))
+                });
+            });
+        }
+        "phoenixd_upgrade" => {
+            let description = t("tray.phoenixd_upgrading").replace(
+                "{version}",
+                &phoenixd_update::available().unwrap_or_default(),
+            );
+            tray_jobs::spawn_with(app, "phoenixd_upgrade", description, |app, op| {
+                phoenixd_update::upgrade(app, op).map(Some)
+            });
+        }
+        "optimize_database" => {
+            let description = t("tray.optimize_database").to_string();
+            tray_jobs::spawn(app, "optimize_database", description, |app| {
+                let state = app.state::<AppState>();
+                audit::record(&state.data_dir, "database.optimize", "tray");
+                let mut pm = state.process_manager.lock().unwrap();
+                let report = database::optimize(&mut pm, &state.data_dir)?;
+                pm.service_statuses();
+                Ok(Some(report.summary()))
+            });
+        }
+        "watch_only" => {
+            let app = app.clone();
+            // The confirmation dialog blocks, keep it off the main thread
+            std::thread::spawn(move || {
+                let state = app.state::<AppState>();
+                let enabled = !state.config.lock().unwrap().general.watch_only;
+                let result = if enabled || dialogs::confirm_spending(&app) {
+                    commands::apply_watch_only(&app, enabled)
+                } else {
+                    Ok(())
+                };
+                if let Err(e) = result {
+                    error!("{}", e);
+                }
+                // A check item flips itself on click, resync it with the config
+                let services = state.process_manager.lock().unwrap().service_statuses();
+                refresh_tray_menu(&app, &services);
+            });
+        }
+        "nwc" => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                let enabled = !app.state::<AppState>().config.lock().unwrap().nwc.enabled;
+                if let Err(e) = commands::set_nwc_enabled(app.clone(), enabled) {
+                    error!("{}", e);
+                    // A check item flips itself on click, resync it with the config
+                    let services = app.state::<AppState>().statuses.lock().unwrap().clone();
+                    refresh_tray_menu(&app, &services);
+                }
+            });
+        }
+        "nwc_pair" => {
+            let app = app.clone();
+            // Pairing asks for the OS login, which blocks
+            std::thread::spawn(move || {
+                let result = commands::create_nwc_connection(app.clone(), None, None, None)
+                    .and_then(|pairing| {
+                        nwc::show_pairing_window(&app, &pairing.name, &pairing.uri)
+                    });
+                if let Err(e) = result {
+                    error!("{}", e);
+                }
+            });
+        }
+        "quit" => {
+            let app = app.clone();
+            // Asking blocks, so not on the main thread
+            std::thread::spawn(move || {
+                if let Some(state) = app.try_state::<AppState>() {
+                    let general = state.config.lock().unwrap().general.clone();
+                    let keep_node = match general.on_quit.as_str() {
+                        "keep_node" => true,
+                        "ask" => dialogs::confirm_keep_node(&app),
+                        _ => false,
+                    };
+                    let mut pm = state.process_manager.lock().unwrap();
+                    info!("👋 Shutting down...");
+                    if let Err(e) = pm.quit(&general.quit_teardown, keep_node) {
+                        error!("{}", e);
+                    }
+                    // A node left running still uses the wallet
+                    if !keep_node {
+                        lease::release(&state.data_dir);
+                    }
+                }
+                app.exit(0);
+            });
+        }
+        id if id.starts_with("service:") => handle_service_action(app, id),
+        id if id.starts_with("payment:") => {
+            if let Some(state) = app.try_state::<AppState>() {
+                let url = state.process_manager.lock().unwrap().dashboard_url();
+                let id = &id["payment:".len()..];
+                browser::open(
+                    app,
+                    &format!("{}/payments?payment={}", url.trim_end_matches('/'), id),
+                );
+            }
+        }
+        id if id.starts_with("node:") => {
+            let app = app.clone();
+            let name = id["node:".len()..].to_string();
+            // Restarting the backend takes a moment
+            std::thread::spawn(move || {
+                if let Err(e) = commands::apply_active_node(&app, &name) {
+                    error!("{}", e);
+                }
+            });
+        }
+        id if id.starts_with("open_location:") => {
+            if let Some(state) = app.try_state::<AppState>() {
+                let config = state.config.lock().unwrap().clone();
+                let kind = &id["open_location:".len()..];
+                if let Err(e) = commands::open_data_path(&state.data_dir, &config, kind) {
+                    error!("{}", e);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Tray "Services" submenu actions, ids look like `service:<name>:<action>`
+fn handle_service_action(app: &tauri::AppHandle, id: &str) {
+    let Some((name, action)) = id
+        .strip_prefix("service:")
+        .and_then(|rest| rest.split_once(':'))
+    else {
+        return;
+    };
+    if action == "logs" {
+        if let Err(e) = status_window::show_logs(app, name) {
+            error!("Failed to open {} log: {}", name, e);
+        }
+        return;
+    }
+
+    let (name, action) = (name.to_string(), action.to_string());
+    let description = format!("{}: {}", name, t(&format!("service.{}", action)));
+    tray_jobs::spawn(app, format!("service:{}", name), description, move |app| {
+        let state = app.state::<AppState>();
+        audit::record(&state.data_dir, &format!("service.{}", action), &name);
+        let mut pm = state.process_manager.lock().unwrap();
+        let result = match action.as_str() {
+            "start" => pm.start_service(&name),
+            "stop" => pm.stop_service(&name),
+            "restart" => pm.restart_service(&name),
+            _ => Ok(()),
+        };
+        pm.service_statuses();
+        result.map(|()| None)
+    });
+}
 
 struct AppState {
+    data_dir: PathBuf,
+    config: Mutex<AppConfig>,
     process_manager: Mutex<ProcessManager>,
+    /// Service states as of the last poll, for callers that mustn't block
+    statuses: StatusCache,
+    cloudflare: CloudflareManager,
+    clipboard_watcher: Mutex<Option<ClipboardWatcher>>,
+    metrics: Mutex<Option<MetricsServer>>,
+    uptime: Mutex<UptimeStats>,
+    /// Flushes the desktop log file on exit
+    _log_guard: Option<tracing_appender::non_blocking::WorkerGuard>,
 }
 
 fn main() {
+    // Started by the Windows service manager or launchd: run headless, no
+    // tray or windows
+    #[cfg(any(windows, target_os = "macos"))]
+    if std::env::args().any(|arg| arg == background::SERVICE_ARG) {
+        if let Err(e) = background::run_service() {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if std::env::args().any(|arg| arg == plan::DRY_RUN_ARG) {
+        if let Err(e) = plan::run_cli() {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     tauri::Builder::default()
+        // Must come first: a second launch (e.g. from a clicked invoice) hands
+        // its arguments to the running instance and exits
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            let is_link = argv.iter().skip(1).any(|arg| {
+                deeplink::SCHEMES.iter().any(|scheme| {
+                    arg.to_ascii_lowercase()
+                        .starts_with(&format!("{}:", scheme))
+                })
+            });
+            let page = argv.iter().skip(1).find_map(|arg| deeplink::page_arg(arg));
+            // Links are delivered through the deep-link plugin; a plain relaunch opens the dashboard
+            if let Some(page) = page {
+                browser::open_page(app, &page);
+            } else if !is_link {
+                browser::open_dashboard(app);
+                if status_window::tray_missing() {
+                    if let Err(e) = status_window::show(app) {
+                        error!("{}", e);
+                    }
+                }
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, _shortcut, event| {
+                    // Quick receive is the only shortcut we register
+                    if event.state() == ShortcutState::Pressed {
+                        let app = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = receive::quick_receive(app).await {
+                                error!("Quick receive failed: {}", e);
+                            }
+                        });
+                    }
+                })
+                .build(),
+        )
+        .manage(windows::WindowPages::default())
+        .register_uri_scheme_protocol(windows::PROTOCOL, |ctx, request| {
+            windows::handle_protocol(ctx, request)
+        })
+        .invoke_handler(tauri::generate_handler![
+            commands::get_status,
+            commands::set_tor_enabled,
+            commands::set_tor_node_traffic,
+            commands::tor_list_clients,
+            commands::tor_add_client,
+            commands::tor_remove_client,
+            commands::tor_new_identity,
+            commands::get_tor_health,
+            commands::cloudflare_status,
+            commands::cloudflare_login,
+            commands::cloudflare_create_tunnel,
+            commands::cloudflare_start_tunnel,
+            commands::cloudflare_start_quick_tunnel,
+            commands::cloudflare_stop_tunnel,
+            commands::get_public_url,
+            commands::set_lan_enabled,
+            commands::get_bind_addresses,
+            commands::set_bind_addresses,
+            commands::set_mdns_enabled,
+            commands::get_mdns_hostname,
+            commands::get_lan_access_url,
+            commands::regenerate_pairing_token,
+            commands::rotate_all_credentials,
+            commands::show_lan_pairing,
+            commands::set_https_enabled,
+            commands::install_tls_ca,
+            commands::get_dashboard_url,
+            commands::get_backend_url,
+            commands::set_frontend_enabled,
+            commands::get_allowed_origins,
+            commands::set_allowed_origins,
+            commands::set_open_with,
+            commands::set_proxy_enabled,
+            commands::get_proxy_credentials,
+            commands::set_proxy_read_only,
+            commands::list_blocked_clients,
+            commands::unblock_client,
+            commands::tailscale_status,
+            commands::set_tailscale_enabled,
+            commands::set_clipboard_watch_enabled,
+            commands::set_quick_receive_shortcut,
+            commands::list_services,
+            commands::show_access_qr_codes,
+            commands::create_access_link,
+            commands::list_access_links,
+            commands::revoke_access_link,
+            commands::show_access_links,
+            commands::open_location,
+            commands::get_language,
+            commands::set_language,
+            commands::get_startup_profile,
+            commands::set_crash_reporting_enabled,
+            commands::list_crash_reports,
+            commands::set_metrics_enabled,
+            commands::set_alert_webhook,
+            commands::set_telegram_alerts,
+            commands::get_uptime_stats,
+            commands::list_operations,
+            commands::cancel_operation,
+            commands::get_node_info,
+            commands::show_node_info,
+            commands::export_payments,
+            commands::detect_docker_deployment,
+            commands::import_docker_deployment,
+            commands::get_phoenixd_update,
+            commands::check_phoenixd_update,
+            commands::get_clock_skew,
+            commands::check_clock_skew,
+            commands::upgrade_phoenixd,
+            commands::get_safe_mode,
+            commands::exit_safe_mode,
+            commands::reset_config,
+            commands::get_bandwidth,
+            commands::set_bandwidth_thresholds,
+            commands::get_quiet_hours,
+            commands::set_quiet_hours,
+            commands::get_onboarding,
+            commands::onboarding_set_network,
+            commands::onboarding_create_wallet,
+            commands::onboarding_restore_wallet,
+            commands::onboarding_restore_archive,
+            commands::onboarding_set_password,
+            commands::onboarding_get_seed,
+            commands::onboarding_confirm_seed_backup,
+            commands::onboarding_set_remote_access,
+            commands::control_service,
+            commands::set_watch_only,
+            commands::get_audit_log,
+            commands::get_events,
+            commands::get_health_checks,
+            commands::set_health_checks,
+            commands::get_polling,
+            commands::get_shell_footprint,
+            commands::get_digest,
+            commands::set_digest,
+            commands::send_digest,
+            commands::set_polling,
+            #[cfg(feature = "test-hooks")]
+            test_hooks::kill_service,
+            #[cfg(feature = "test-hooks")]
+            test_hooks::corrupt_database,
+            #[cfg(feature = "test-hooks")]
+            test_hooks::blackhole_port,
+            commands::get_nwc,
+            commands::set_nwc_enabled,
+            commands::create_nwc_connection,
+            commands::set_nwc_budget,
+            commands::remove_nwc_connection,
+            commands::store_connection,
+            commands::show_store_connection,
+            commands::get_restart_policies,
+            commands::set_restart_policy,
+            commands::test_health_check,
+            commands::check_resources,
+            commands::get_startup_plan,
+            commands::repair_resource,
+            commands::run_self_test,
+            commands::create_backup,
+            commands::get_liquidity_policy,
+            commands::set_liquidity_policy,
+            commands::export_logs,
+            commands::services_stop,
+            commands::services_down,
+            commands::services_purge,
+            commands::set_quit_teardown,
+            commands::set_on_quit,
+            commands::reload_config,
+            commands::optimize_database,
+            commands::dump_database,
+            commands::restore_backup,
+            commands::list_backups,
+            commands::pause_node,
+            commands::resume_node,
+            commands::list_nodes,
+            commands::add_node,
+            commands::remove_node,
+            commands::set_active_node,
+            commands::list_extra_services,
+            commands::add_extra_service,
+            commands::remove_extra_service,
+            commands::restore_snapshot,
+            commands::get_self_test,
+            commands::background_service_status,
+            commands::install_background_service,
+            commands::uninstall_background_service,
+        ])
         .setup(|app| {
             let resource_dir = app
                 .path()
                 .resource_dir()
                 .expect("Failed to get resource directory");
 
-            let data_dir = app
-                .path()
-                .app_data_dir()
-                .expect("Failed to get app data directory");
+            let data_dir = synced_dir::resolve(
+                app.path()
+                    .app_data_dir()
+                    .expect("Failed to get app data directory"),
+            );
 
             std::fs::create_dir_all(&data_dir).expect("Failed to create data directory");
 
-            println!("📂 Resource directory: {:?}", resource_dir);
-            println!("📂 Data directory: {:?}", data_dir);
+            let config = AppConfig::load(&data_dir);
+            redact::register_known(&data_dir, &config);
+            let log_guard =
+                logs::init_tracing(&data_dir, &config.general.log_level, config.general.os_log);
+            i18n::init(&config.general.language);
+            crash::set_consent(&config.crash_reports);
+            crash::install_hook(data_dir.clone());
+            {
+                let data_dir = data_dir.clone();
+                std::thread::spawn(move || crash::upload_pending(&data_dir));
+            }
 
-            // Start services
-            println!("\n⚡ Starting Phoenixd Dashboard...");
-            let mut process_manager = ProcessManager::new(resource_dir.clone(), data_dir.clone());
-            
-            if let Err(e) = process_manager.start_all() {
-                eprintln!("❌ Failed to start services: {}", e);
-            } else {
-                println!("✅ Services started!");
+            info!("📂 Resource directory: {:?}", resource_dir);
+            info!("📂 Data directory: {:?}", data_dir);
+
+            info!("⚡ Starting Phoenixd Dashboard...");
+            let mut process_manager =
+                ProcessManager::new(resource_dir.clone(), data_dir.clone(), config.clone());
+            process_manager.set_notifier(notify::native(app.handle().clone()));
+            process_manager.set_node_events(node_events::native(app.handle().clone()));
+
+            if let Err(e) =
+                register_quick_receive_shortcut(app.handle(), &config.quick_receive.shortcut)
+            {
+                warn!("{}", e);
             }
 
-            // Store state
+            let clipboard_watcher = config.clipboard.watch_invoices.then(|| {
+                ClipboardWatcher::start(app.handle().clone(), notify::native(app.handle().clone()))
+            });
+
+            let metrics = config
+                .metrics
+                .enabled
+                .then(|| {
+                    MetricsServer::start(
+                        app.handle().clone(),
+                        &config.metrics.bind,
+                        config.metrics.port,
+                    )
+                })
+                .and_then(|result| result.map_err(|e| warn!("{}", e)).ok());
+
             app.manage(AppState {
+                data_dir: data_dir.clone(),
+                config: Mutex::new(config),
+                statuses: process_manager.status_cache(),
                 process_manager: Mutex::new(process_manager),
+                cloudflare: CloudflareManager::new(resource_dir.clone(), &data_dir),
+                clipboard_watcher: Mutex::new(clipboard_watcher),
+                metrics: Mutex::new(metrics),
+                uptime: Mutex::new(UptimeStats::load(&data_dir)),
+                _log_guard: log_guard,
             });
 
-            // Build simple tray menu
-            let open_dashboard = MenuItemBuilder::with_id("open", "Open Dashboard")
-                .build(app)?;
-            
-            let separator1 = PredefinedMenuItem::separator(app)?;
-
-            let restart = MenuItemBuilder::with_id("restart", "Restart")
-                .build(app)?;
-            
-            let quit = MenuItemBuilder::with_id("quit", "Quit")
-                .build(app)?;
+            // Payment links (lightning:, lnurl:, bitcoin:) open the pay screen,
+            // phoenixd-dashboard:// links the page they name
+            #[cfg(any(target_os = "linux", windows))]
+            if let Err(e) = app.deep_link().register_all() {
+                warn!("Could not register payment link handlers: {}", e);
+            }
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                let uris: Vec<String> = event.urls().iter().map(|u| u.to_string()).collect();
+                open_links(&handle, &uris);
+            });
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                let uris: Vec<String> = urls.iter().map(|u| u.to_string()).collect();
+                open_links(app.handle(), &uris);
+            }
+            // `--page=receive`, e.g. from an OS shortcut
+            if let Some(page) = std::env::args().find_map(|arg| deeplink::page_arg(&arg)) {
+                browser::open_page(app.handle(), &page);
+            }
 
-            let menu = MenuBuilder::new(app)
-                .item(&open_dashboard)
-                .item(&separator1)
-                .item(&restart)
-                .item(&quit)
-                .build()?;
+            // Build tray menu
+            let services = app
+                .state::<AppState>()
+                .process_manager
+                .lock()
+                .unwrap()
+                .service_statuses();
+            let menu = build_tray_menu(app.handle(), &services)?;
 
-            // Load tray icon
-            let icon_bytes = include_bytes!("../icons/32x32.png");
-            let icon = Image::from_bytes(icon_bytes)
-                .expect("Failed to load tray icon");
+            let icon = tray_icon::icon(tray_icon::Health::of(&services), tray_icon::os_theme());
 
-            let _tray = TrayIconBuilder::with_id("main")
+            let tray = TrayIconBuilder::with_id("main")
                 .icon(icon)
+                .icon_as_template(cfg!(target_os = "macos"))
                 .menu(&menu)
                 .tooltip("Phoenixd Dashboard")
-                .on_menu_event(move |app, event| {
-                    match event.id().as_ref() {
-                        "open" => {
-                            let _ = open::that("http://localhost:3000");
-                        }
-                        "restart" => {
-                            if let Some(state) = app.try_state::<AppState>() {
-                                let mut pm = state.process_manager.lock().unwrap();
-                                println!("🔄 Restarting services...");
-                                pm.stop_all();
-                                if let Err(e) = pm.start_all() {
-                                    eprintln!("❌ Failed: {}", e);
-                                } else {
-                                    println!("✅ Services restarted!");
-                                }
-                            }
-                        }
-                        "quit" => {
-                            if let Some(state) = app.try_state::<AppState>() {
-                                let mut pm = state.process_manager.lock().unwrap();
-                                println!("👋 Shutting down...");
-                                pm.stop_all();
-                            }
-                            app.exit(0);
-                        }
-                        _ => {}
-                    }
-                })
-                .build(app)?;
-
-            // Print startup banner
-            println!("\n╔════════════════════════════════════════════════╗");
-            println!("║  ⚡ Phoenixd Dashboard (Desktop Edition)       ║");
-            println!("╠════════════════════════════════════════════════╣");
-            println!("║  ✅ Lightning Node: Running                    ║");
-            println!("║  ℹ️  Tor/Tailscale/CF: Desktop version only    ║");
-            println!("╠════════════════════════════════════════════════╣");
-            println!("║  📍 Dashboard: http://localhost:3000           ║");
-            println!("╚════════════════════════════════════════════════╝\n");
+                .on_menu_event(|app, event| handle_menu_action(app, event.id().as_ref()))
+                .build(app);
+            // Without a tray the status window is the app's main window
+            #[cfg(target_os = "linux")]
+            if tray.is_ok() {
+                tray_icon::watch_host(app.handle().clone());
+            }
+            if let Err(e) = tray {
+                warn!(
+                    "⚠️ No system tray available ({}), using the status window instead",
+                    e
+                );
+                status_window::set_tray_missing();
+                if let Err(e) = status_window::show(app.handle()) {
+                    error!("{}", e);
+                }
+            }
+
+            // Keep the Services submenu in sync and alert on outages
+            watchdog::spawn(app.handle().clone(), services);
+            maintenance::spawn(app.handle().clone());
+            backup::spawn(app.handle().clone());
+            digest::spawn(app.handle().clone());
+            power::spawn(app.handle().clone());
+            network::spawn(app.handle().clone());
+            recent_payments::spawn(app.handle().clone());
+            watchtower::spawn(app.handle().clone());
+            health_checks::spawn(app.handle().clone());
+            tor_health::spawn(app.handle().clone());
+            bandwidth::spawn(app.handle().clone());
+            phoenixd_update::spawn(app.handle().clone());
+            clock::spawn(app.handle().clone());
+            battery::spawn(app.handle().clone());
+            footprint::spawn(app.handle().clone());
+            if app
+                .state::<AppState>()
+                .config
+                .lock()
+                .unwrap()
+                .general
+                .control_socket
+            {
+                control_socket::spawn(app.handle().clone());
+            }
+
+            // Services start in the background behind a progress window
+            startup::spawn(app.handle().clone());
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            // Closing the last native window must not quit the tray app;
+            // only an explicit exit (tray "Quit") carries an exit code
+            if let RunEvent::ExitRequested { api, code, .. } = event {
+                if code.is_none() {
+                    api.prevent_exit();
+                }
+            }
+        });
 }
@@ -0,0 +1,94 @@
+use crate::audit;
+use crate::config::MaintenanceConfig;
+use crate::database;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::process_manager::SERVICES;
+use crate::scheduler;
+use crate::AppState;
+use chrono::{DateTime, Local};
+use croner::Cron;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{error, info, warn};
+
+/// Delay between attempts while a payment or channel operation is in flight
+const RETRY: Duration = Duration::from_secs(60);
+
+/// Next scheduled restart, if maintenance is enabled and the schedule parses
+pub fn next_run(config: &MaintenanceConfig) -> Option<DateTime<Local>> {
+    if !config.enabled {
+        return None;
+    }
+    let cron = Cron::new(&config.schedule).parse().ok()?;
+    cron.find_next_occurrence(&Local::now(), false).ok()
+}
+
+/// Run scheduled maintenance restarts in the background
+pub fn spawn(app: AppHandle) {
+    let read_config = {
+        let app = app.clone();
+        move || {
+            app.state::<AppState>()
+                .config
+                .lock()
+                .unwrap()
+                .maintenance
+                .clone()
+        }
+    };
+    let config = read_config();
+    if config.enabled && next_run(&config).is_none() {
+        warn!("Invalid maintenance schedule {:?}", config.schedule);
+    }
+
+    scheduler::run_on_cron(read_config, next_run, move |config, next| {
+        run_window(&app, &config, next);
+        let services = app
+            .state::<AppState>()
+            .process_manager
+            .lock()
+            .unwrap()
+            .service_statuses();
+        crate::refresh_tray_menu(&app, &services);
+    });
+}
+
+/// Restart once nothing is in flight, or give up when the window closes
+fn run_window(app: &AppHandle, config: &MaintenanceConfig, scheduled: DateTime<Local>) {
+    let deadline = scheduled + Duration::from_secs(config.window_minutes * 60);
+    let state = app.state::<AppState>();
+
+    loop {
+        let client = PhoenixdClient::new(&state.data_dir);
+        match tauri::async_runtime::block_on(client.has_pending_operations()) {
+            Ok(true) => info!("⏳ Payment or channel operation in flight, postponing maintenance"),
+            // Nothing we can see is in flight (phoenixd may even be down), so go ahead
+            Ok(false) | Err(_) => {
+                info!("🛠️ Running scheduled maintenance");
+                let mut pm = state.process_manager.lock().unwrap();
+                for name in SERVICES
+                    .iter()
+                    .filter(|name| config.services.iter().any(|s| s == *name))
+                {
+                    audit::record(&state.data_dir, "service.maintenance_restart", *name);
+                    if let Err(e) = pm.restart_service(name) {
+                        error!("Maintenance restart of {} failed: {}", name, e);
+                    }
+                }
+                if config.optimize_database {
+                    audit::record(&state.data_dir, "database.optimize", "maintenance");
+                    if let Err(e) = database::optimize(&mut pm, &state.data_dir) {
+                        error!("{}", e);
+                    }
+                }
+                return;
+            }
+        }
+
+        if Local::now() + RETRY > deadline {
+            warn!("Skipped scheduled maintenance: operations still in flight at the end of the window");
+            return;
+        }
+        std::thread::sleep(RETRY);
+    }
+}
@@ -0,0 +1,47 @@
+use mdns_sd::{ServiceDaemon, ServiceInfo};
+use tracing::info;
+
+const SERVICE_TYPE: &str = "_http._tcp.local.";
+
+/// Advertises the dashboard as `<name>.local` while LAN access is enabled
+pub struct MdnsAdvertiser {
+    daemon: ServiceDaemon,
+    fullname: String,
+    hostname: String,
+}
+
+impl MdnsAdvertiser {
+    pub fn start(name: &str, port: u16) -> Result<Self, String> {
+        let ip = crate::lan::lan_ip().ok_or_else(|| "No LAN interface available".to_string())?;
+        let daemon =
+            ServiceDaemon::new().map_err(|e| format!("Failed to start mDNS responder: {}", e))?;
+
+        let host = format!("{}.local.", name);
+        let properties = [("path", "/")];
+        let info = ServiceInfo::new(SERVICE_TYPE, name, &host, ip, port, &properties[..])
+            .map_err(|e| format!("Invalid mDNS service: {}", e))?;
+        let fullname = info.get_fullname().to_string();
+
+        daemon
+            .register(info)
+            .map_err(|e| format!("Failed to advertise mDNS service: {}", e))?;
+
+        let hostname = host.trim_end_matches('.').to_string();
+        info!("📡 Advertising dashboard as http://{}:{}", hostname, port);
+        Ok(Self {
+            daemon,
+            fullname,
+            hostname,
+        })
+    }
+
+    /// Advertised host, e.g. `phoenixd-dashboard.local`
+    pub fn hostname(&self) -> &str {
+        &self.hostname
+    }
+
+    pub fn stop(self) {
+        let _ = self.daemon.unregister(&self.fullname);
+        let _ = self.daemon.shutdown();
+    }
+}
@@ -0,0 +1,355 @@
+use crate::footprint;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::process_manager::{ServiceState, ServiceStatus};
+use crate::token;
+use crate::AppState;
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{header, Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+use serde::Serialize;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+use tracing::{error, info};
+
+#[derive(Clone)]
+struct MetricsState {
+    app: AppHandle,
+    /// Kept between scrapes: CPU usage is measured since the previous refresh
+    system: Arc<Mutex<System>>,
+}
+
+/// Per-check budget, so a hung service can't stall the monitor's request
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Serialize)]
+struct Check {
+    name: &'static str,
+    ok: bool,
+    detail: String,
+}
+
+/// Per-service figures gathered under the process manager lock
+struct ServiceSample {
+    status: ServiceStatus,
+    restarts: u32,
+    cpu_percent: Option<f32>,
+    memory_bytes: Option<u64>,
+}
+
+/// Monitoring listener: Prometheus `/metrics` for Grafana and friends, and
+/// an aggregate `/healthz` for uptime monitors
+pub struct MetricsServer {
+    task: JoinHandle<()>,
+}
+
+impl MetricsServer {
+    pub fn start(app: AppHandle, bind_ip: &str, port: u16) -> Result<Self, String> {
+        let socket = std::net::TcpListener::bind((bind_ip, port))
+            .and_then(|l| l.set_nonblocking(true).map(|_| l))
+            .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+        info!(
+            "📈 Metrics on http://{}:{}/metrics and /healthz",
+            bind_ip, port
+        );
+
+        let state = MetricsState {
+            app,
+            system: Arc::new(Mutex::new(System::new())),
+        };
+        let router = Router::new()
+            .route("/metrics", get(metrics))
+            .route("/healthz", get(healthz))
+            .with_state(state);
+
+        let task = tauri::async_runtime::spawn(async move {
+            let result = match TcpListener::from_std(socket) {
+                Ok(listener) => axum::serve(listener, router).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                error!("Metrics listener failed: {}", e);
+            }
+        });
+
+        Ok(Self { task })
+    }
+
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+async fn metrics(State(state): State<MetricsState>) -> Response {
+    let app = state.app.clone();
+    // Health checks connect to ports and the manager lock is a std Mutex
+    let samples = tauri::async_runtime::spawn_blocking(move || sample_services(&state)).await;
+    let Ok(samples) = samples else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let data_dir = app.state::<AppState>().data_dir.clone();
+    let balance = PhoenixdClient::new(&data_dir).get_balance().await.ok();
+
+    let mut out = String::new();
+    per_service(
+        &mut out,
+        "phoenixd_dashboard_service_up",
+        "gauge",
+        "1 if the service process is running",
+        &samples,
+        |s| Some((s.status.state != ServiceState::Stopped) as u8 as f64),
+    );
+    per_service(
+        &mut out,
+        "phoenixd_dashboard_service_healthy",
+        "gauge",
+        "1 if the service accepts connections on its port",
+        &samples,
+        |s| Some((s.status.state == ServiceState::Running) as u8 as f64),
+    );
+    per_service(
+        &mut out,
+        "phoenixd_dashboard_service_restarts_total",
+        "counter",
+        "Restarts since the app started",
+        &samples,
+        |s| Some(s.restarts as f64),
+    );
+    per_service(
+        &mut out,
+        "phoenixd_dashboard_process_cpu_percent",
+        "gauge",
+        "CPU usage of the service process since the last scrape",
+        &samples,
+        |s| s.cpu_percent.map(f64::from),
+    );
+    per_service(
+        &mut out,
+        "phoenixd_dashboard_process_memory_bytes",
+        "gauge",
+        "Resident memory of the service process",
+        &samples,
+        |s| s.memory_bytes.map(|bytes| bytes as f64),
+    );
+    if let Some(balance) = balance {
+        family(
+            &mut out,
+            "phoenixd_balance_sat",
+            "gauge",
+            "Spendable balance reported by phoenixd",
+        );
+        let _ = writeln!(out, "phoenixd_balance_sat {}", balance.balance_sat);
+        family(
+            &mut out,
+            "phoenixd_fee_credit_sat",
+            "gauge",
+            "Fee credit reported by phoenixd",
+        );
+        let _ = writeln!(out, "phoenixd_fee_credit_sat {}", balance.fee_credit_sat);
+    }
+
+    let shell = footprint::current();
+    family(
+        &mut out,
+        "phoenixd_dashboard_shell_cpu_percent",
+        "gauge",
+        "CPU usage of the desktop app itself",
+    );
+    let _ = writeln!(
+        out,
+        "phoenixd_dashboard_shell_cpu_percent {}",
+        shell.cpu_percent
+    );
+    family(
+        &mut out,
+        "phoenixd_dashboard_shell_memory_bytes",
+        "gauge",
+        "Resident memory of the desktop app itself",
+    );
+    let _ = writeln!(
+        out,
+        "phoenixd_dashboard_shell_memory_bytes {}",
+        shell.memory_bytes
+    );
+    family(
+        &mut out,
+        "phoenixd_dashboard_poller_wakeups_total",
+        "counter",
+        "Times each background poller woke up",
+    );
+    for (poller, count) in &shell.wakeups {
+        let _ = writeln!(
+            out,
+            "phoenixd_dashboard_poller_wakeups_total{{poller=\"{}\"}} {}",
+            poller, count
+        );
+    }
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], out).into_response()
+}
+
+/// One JSON verdict over phoenixd's API, the backend's `/health`, the
+/// frontend port (unless API-only) and every supervised process. 503 if anything fails.
+async fn healthz(State(state): State<MetricsState>) -> Response {
+    let app = state.app.clone();
+    let samples = tauri::async_runtime::spawn_blocking(move || sample_services(&state)).await;
+    let Ok(samples) = samples else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let data_dir = app.state::<AppState>().data_dir.clone();
+    let (bind, frontend_enabled) = {
+        let app_state = app.state::<AppState>();
+        let config = app_state.config.lock().unwrap();
+        (config.bind.clone(), config.frontend.enabled)
+    };
+
+    let phoenixd = match timeout(CHECK_TIMEOUT, PhoenixdClient::new(&data_dir).get_info()).await {
+        Ok(Ok(_)) => Ok("API reachable".to_string()),
+        Ok(Err(e)) => Err(e),
+        Err(_) => Err("API timed out".to_string()),
+    };
+    let mut checks = vec![
+        check("phoenixd_api", phoenixd),
+        check(
+            "backend_health",
+            http_ok(
+                &format!("http://{}/health", bind.backend_addr()),
+                token::api_token(&data_dir),
+            )
+            .await,
+        ),
+    ];
+    if frontend_enabled {
+        checks.push(check("frontend_tcp", tcp_open(bind.frontend_addr()).await));
+    }
+    checks.extend(samples.iter().map(|sample| Check {
+        name: sample.status.name,
+        ok: sample.status.state == ServiceState::Running,
+        detail: format!("{:?}", sample.status.state).to_lowercase(),
+    }));
+
+    let healthy = checks.iter().all(|c| c.ok);
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = serde_json::json!({
+        "status": if healthy { "ok" } else { "degraded" },
+        "checks": checks,
+    });
+    (
+        status,
+        [(header::CONTENT_TYPE, "application/json")],
+        body.to_string(),
+    )
+        .into_response()
+}
+
+fn check(name: &'static str, result: Result<String, String>) -> Check {
+    match result {
+        Ok(detail) => Check {
+            name,
+            ok: true,
+            detail,
+        },
+        Err(detail) => Check {
+            name,
+            ok: false,
+            detail,
+        },
+    }
+}
+
+async fn http_ok(url: &str, bearer: Option<String>) -> Result<String, String> {
+    let client = Client::builder(TokioExecutor::new()).build_http::<Body>();
+    let mut request = Request::get(url);
+    if let Some(bearer) = bearer {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", bearer));
+    }
+    let request = request
+        .body(Body::empty())
+        .map_err(|e| format!("Invalid request: {}", e))?;
+
+    match timeout(CHECK_TIMEOUT, client.request(request)).await {
+        Ok(Ok(response)) if response.status().is_success() => Ok(response.status().to_string()),
+        Ok(Ok(response)) => Err(response.status().to_string()),
+        Ok(Err(e)) => Err(format!("Not reachable: {}", e)),
+        Err(_) => Err("Timed out".to_string()),
+    }
+}
+
+async fn tcp_open(addr: SocketAddr) -> Result<String, String> {
+    match timeout(CHECK_TIMEOUT, TcpStream::connect(addr)).await {
+        Ok(Ok(_)) => Ok(format!("{} open", addr)),
+        Ok(Err(e)) => Err(format!("{} closed: {}", addr, e)),
+        Err(_) => Err(format!("{} timed out", addr)),
+    }
+}
+
+fn sample_services(state: &MetricsState) -> Vec<ServiceSample> {
+    let app_state = state.app.state::<AppState>();
+    let mut pm = app_state.process_manager.lock().unwrap();
+    let statuses = pm.service_statuses();
+    let pids: Vec<Option<u32>> = statuses.iter().map(|s| pm.service_pid(s.name)).collect();
+
+    let mut system = state.system.lock().unwrap();
+    let tracked: Vec<Pid> = pids
+        .iter()
+        .flatten()
+        .map(|&pid| Pid::from_u32(pid))
+        .collect();
+    system.refresh_processes(ProcessesToUpdate::Some(&tracked), true);
+
+    statuses
+        .into_iter()
+        .zip(pids)
+        .map(|(status, pid)| {
+            let process = pid.and_then(|pid| system.process(Pid::from_u32(pid)));
+            ServiceSample {
+                restarts: pm.restart_count(status.name),
+                cpu_percent: process.map(|p| p.cpu_usage()),
+                memory_bytes: process.map(|p| p.memory()),
+                status,
+            }
+        })
+        .collect()
+}
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// One metric family labelled by service; `None` leaves the service out
+fn per_service(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: &[ServiceSample],
+    value: impl Fn(&ServiceSample) -> Option<f64>,
+) {
+    family(out, name, kind, help);
+    for sample in samples {
+        if let Some(value) = value(sample) {
+            let _ = writeln!(
+                out,
+                "{}{{service=\"{}\"}} {}",
+                name, sample.status.name, value
+            );
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use crate::token;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Form, Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::async_runtime::JoinHandle;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tracing::{error, info};
+
+/// Simulated payers settle our invoices after this long
+const SETTLE_DELAY: Duration = Duration::from_secs(5);
+/// A random "tip" comes in this often, so the dashboard has something to show
+const TIP_INTERVAL: Duration = Duration::from_secs(45);
+
+type Params = HashMap<String, String>;
+
+/// Fake wallet behind the mock API
+struct MockNode {
+    balance_sat: u64,
+    fee_credit_sat: u64,
+    incoming: Vec<Value>,
+    outgoing: Vec<Value>,
+}
+
+#[derive(Clone)]
+struct MockState {
+    node: Arc<Mutex<MockNode>>,
+    /// Websocket payment events
+    events: broadcast::Sender<String>,
+}
+
+/// Stand-in for phoenixd on its usual port, imitating its REST API and
+/// websocket with a fake balance, invoices and payments. For demos and UI
+/// work without a funded node.
+pub struct MockPhoenixd {
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockPhoenixd {
+    pub fn start(bind_ip: IpAddr, port: u16) -> Result<Self, String> {
+        let addr = SocketAddr::new(bind_ip, port);
+        let socket = std::net::TcpListener::bind(addr)
+            .and_then(|l| l.set_nonblocking(true).map(|_| l))
+            .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+        info!("🎭 Mock phoenixd on http://{}", addr);
+
+        let state = MockState {
+            node: Arc::new(Mutex::new(MockNode {
+                balance_sat: 250_000,
+                fee_credit_sat: 1_500,
+                incoming: Vec::new(),
+                outgoing: Vec::new(),
+            })),
+            events: broadcast::channel(64).0,
+        };
+        let router = Router::new()
+            .route("/getinfo", get(get_info))
+            .route("/getbalance", get(get_balance))
+            .route("/listchannels", get(list_channels))
+            .route("/estimateliquidityfees", get(estimate_liquidity_fees))
+            .route("/getlnaddress", get(|| async { "₿demo@phoenixwallet.me" }))
+            .route("/createinvoice", post(create_invoice))
+            .route("/createoffer", post(create_offer))
+            .route("/payinvoice", post(pay))
+            .route("/payoffer", post(pay))
+            .route("/paylnaddress", post(pay))
+            .route("/sendtoaddress", post(send_to_address))
+            .route("/bumpfee", post(|| async { random_hex(32) }))
+            .route("/closechannel", post(|| async { random_hex(32) }))
+            .route("/decodeinvoice", post(decode))
+            .route("/decodeoffer", post(decode))
+            .route("/export", post(|| async { "" }))
+            .route("/lnurlpay", post(pay))
+            .route(
+                "/lnurlwithdraw",
+                post(|| async { json_response(json!({ "receivedSat": 1000 })) }),
+            )
+            .route("/lnurlauth", post(|| async { "ok" }))
+            .route("/payments/incoming", get(list_incoming))
+            .route("/payments/incoming/{hash}", get(get_incoming))
+            .route("/payments/outgoing", get(list_outgoing))
+            .route("/payments/outgoing/{id}", get(get_outgoing))
+            .route("/payments/outgoingbyhash/{hash}", get(get_outgoing_by_hash))
+            .route("/websocket", get(websocket))
+            .with_state(state.clone());
+
+        let server = tauri::async_runtime::spawn(async move {
+            let result = match TcpListener::from_std(socket) {
+                Ok(listener) => axum::serve(listener, router).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                error!("Mock phoenixd failed: {}", e);
+            }
+        });
+        let tips = tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(TIP_INTERVAL).await;
+                let amount = 100 + random_u64() % 5_000;
+                receive(&state, random_hex(32), amount, "Demo tip", None);
+            }
+        });
+
+        Ok(Self {
+            tasks: vec![server, tips],
+        })
+    }
+
+    pub fn stop(self) {
+        for task in self.tasks {
+            task.abort();
+        }
+    }
+}
+
+fn json_response(value: Value) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/json")],
+        value.to_string(),
+    )
+        .into_response()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn random_hex(bytes: usize) -> String {
+    (0..bytes.div_ceil(16))
+        .filter_map(|_| token::generate().ok())
+        .collect::<String>()
+        .chars()
+        .take(bytes * 2)
+        .collect()
+}
+
+fn random_u64() -> u64 {
+    u64::from_str_radix(&random_hex(8), 16).unwrap_or_default()
+}
+
+fn amount(params: &Params, key: &str) -> u64 {
+    params
+        .get(key)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Book an incoming payment and tell websocket listeners, like phoenixd does
+fn receive(
+    state: &MockState,
+    payment_hash: String,
+    amount_sat: u64,
+    description: &str,
+    external_id: Option<String>,
+) {
+    let now = now_millis();
+    let mut node = state.node.lock().unwrap();
+    node.balance_sat += amount_sat;
+    node.incoming.push(json!({
+        "type": "incoming_payment",
+        "subType": "lightning",
+        "paymentHash": payment_hash,
+        "preimage": random_hex(32),
+        "externalId": external_id,
+        "description": description,
+        "isPaid": true,
+        "receivedSat": amount_sat,
+        "fees": 0,
+        "completedAt": now,
+        "createdAt": now,
+    }));
+    drop(node);
+
+    let event = json!({
+        "type": "payment_received",
+        "timestamp": now,
+        "amountSat": amount_sat,
+        "paymentHash": payment_hash,
+        "externalId": external_id,
+    });
+    let _ = state.events.send(event.to_string());
+}
+
+async fn get_info(State(state): State<MockState>) -> Response {
+    let balance = state.node.lock().unwrap().balance_sat;
+    json_response(json!({
+        "nodeId": "02mock00000000000000000000000000000000000000000000000000000000000000",
+        "channels": [{
+            "state": "Normal",
+            "channelId": "mock-channel",
+            "balanceSat": balance,
+            "inboundLiquiditySat": 2_000_000,
+            "capacitySat": balance + 2_000_000,
+            "fundingTxId": "mock-funding-tx",
+        }],
+        "chain": "mainnet",
+        "blockHeight": 880_000,
+        "version": "mock",
+    }))
+}
+
+async fn get_balance(State(state): State<MockState>) -> Response {
+    let node = state.node.lock().unwrap();
+    json_response(json!({
+        "balanceSat": node.balance_sat,
+        "feeCreditSat": node.fee_credit_sat,
+    }))
+}
+
+async fn list_channels(State(state): State<MockState>) -> Response {
+    let balance = state.node.lock().unwrap().balance_sat;
+    json_response(
+        json!([{ "state": "Normal", "channelId": "mock-channel", "balanceSat": balance }]),
+    )
+}
+
+async fn estimate_liquidity_fees(Query(params): Query<Params>) -> Response {
+    let amount_sat = amount(&params, "amountSat");
+    json_response(json!({
+        "miningFeeSat": 1_200,
+        "serviceFeeSat": amount_sat / 100,
+    }))
+}
+
+async fn create_invoice(State(state): State<MockState>, Form(params): Form<Params>) -> Response {
+    let amount_sat = amount(&params, "amountSat");
+    let payment_hash = random_hex(32);
+    let description = params.get("description").cloned().unwrap_or_default();
+    let external_id = params.get("externalId").cloned();
+
+    // Someone "pays" every invoice with an amount shortly after
+    if amount_sat > 0 {
+        let state = state.clone();
+        let payment_hash = payment_hash.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SETTLE_DELAY).await;
+            receive(&state, payment_hash, amount_sat, &description, external_id);
+        });
+    }
+
+    json_response(json!({
+        "amountSat": amount_sat,
+        "paymentHash": payment_hash,
+        "serialized": format!("lnbcmock{}n1{}", amount_sat, random_hex(40)),
+    }))
+}
+
+async fn create_offer() -> Response {
+    json_response(json!({ "offer": format!("lnomock1{}", random_hex(40)) }))
+}
+
+/// Every outgoing payment succeeds if the fake balance covers it
+async fn pay(State(state): State<MockState>, Form(params): Form<Params>) -> Response {
+    let amount_sat = match amount(&params, "amountSat") {
+        0 => 1_000,
+        amount_sat => amount_sat,
+    };
+    let fee_sat = 1 + amount_sat / 1_000;
+    let mut node = state.node.lock().unwrap();
+    if node.balance_sat < amount_sat + fee_sat {
+        return (StatusCode::BAD_REQUEST, "insufficient funds").into_response();
+    }
+    node.balance_sat -= amount_sat + fee_sat;
+
+    let now = now_millis();
+    let (payment_id, payment_hash, preimage) = (random_hex(16), random_hex(32), random_hex(32));
+    node.outgoing.push(json!({
+        "type": "outgoing_payment",
+        "subType": "lightning",
+        "paymentId": payment_id,
+        "paymentHash": payment_hash,
+        "preimage": preimage,
+        "isPaid": true,
+        "sent": amount_sat + fee_sat,
+        "fees": fee_sat * 1_000,
+        "completedAt": now,
+        "createdAt": now,
+    }));
+
+    json_response(json!({
+        "recipientAmountSat": amount_sat,
+        "routingFeeSat": fee_sat,
+        "paymentId": payment_id,
+        "paymentHash": payment_hash,
+        "paymentPreimage": preimage,
+    }))
+}
+
+async fn send_to_address(State(state): State<MockState>, Form(params): Form<Params>) -> Response {
+    let amount_sat = amount(&params, "amountSat");
+    let mut node = state.node.lock().unwrap();
+    if node.balance_sat < amount_sat {
+        return (StatusCode::BAD_REQUEST, "insufficient funds").into_response();
+    }
+    node.balance_sat -= amount_sat;
+    random_hex(32).into_response()
+}
+
+async fn decode(Form(params): Form<Params>) -> Response {
+    let serialized = params
+        .get("invoice")
+        .or_else(|| params.get("offer"))
+        .cloned()
+        .unwrap_or_default();
+    // Mock invoices carry their amount: lnbcmock<amount>n1...
+    let amount_sat: u64 = serialized
+        .strip_prefix("lnbcmock")
+        .and_then(|rest| rest.split('n').next())
+        .and_then(|amount| amount.parse().ok())
+        .unwrap_or(1_000);
+    json_response(json!({
+        "chain": "mainnet",
+        "amount": amount_sat * 1_000,
+        "paymentHash": random_hex(32),
+        "description": "Mock payment",
+        "minFinalCltvExpiryDelta": 18,
+        "paymentSecret": random_hex(32),
+        "timestampSeconds": now_millis() / 1_000,
+    }))
+}
+
+/// Newest first, honoring `limit` and `offset` like phoenixd
+fn page(payments: &[Value], params: &Params) -> Response {
+    let limit = params
+        .get("limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(20);
+    let offset = params
+        .get("offset")
+        .and_then(|o| o.parse().ok())
+        .unwrap_or(0);
+    let page: Vec<&Value> = payments.iter().rev().skip(offset).take(limit).collect();
+    json_response(json!(page))
+}
+
+fn find(payments: &[Value], key: &str, value: &str) -> Response {
+    match payments.iter().find(|p| p[key] == value) {
+        Some(payment) => json_response(payment.clone()),
+        None => (StatusCode::NOT_FOUND, "payment not found").into_response(),
+    }
+}
+
+async fn list_incoming(State(state): State<MockState>, Query(params): Query<Params>) -> Response {
+    page(&state.node.lock().unwrap().incoming, &params)
+}
+
+async fn get_incoming(State(state): State<MockState>, Path(hash): Path<String>) -> Response {
+    find(&state.node.lock().unwrap().incoming, "paymentHash", &hash)
+}
+
+async fn list_outgoing(State(state): State<MockState>, Query(params): Query<Params>) -> Response {
+    page(&state.node.lock().unwrap().outgoing, &params)
+}
+
+async fn get_outgoing(State(state): State<MockState>, Path(id): Path<String>) -> Response {
+    find(&state.node.lock().unwrap().outgoing, "paymentId", &id)
+}
+
+async fn get_outgoing_by_hash(
+    State(state): State<MockState>,
+    Path(hash): Path<String>,
+) -> Response {
+    find(&state.node.lock().unwrap().outgoing, "paymentHash", &hash)
+}
+
+async fn websocket(State(state): State<MockState>, upgrade: WebSocketUpgrade) -> Response {
+    let events = state.events.subscribe();
+    upgrade.on_upgrade(move |socket| forward_events(socket, events))
+}
+
+async fn forward_events(mut socket: WebSocket, mut events: broadcast::Receiver<String>) {
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                if socket.send(Message::Text(event.into())).await.is_err() {
+                    return;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
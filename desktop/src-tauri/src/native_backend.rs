@@ -0,0 +1,789 @@
+use crate::config;
+use crate::proxy;
+use axum::body::{Body, Bytes};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use data_encoding::{BASE64, HEXLOWER};
+use futures_util::{SinkExt, StreamExt};
+use hyper_util::client::legacy::{connect::HttpConnector, Client};
+use hyper_util::rt::TokioExecutor;
+use rusqlite::{Connection, OptionalExtension};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tracing::{error, info, warn};
+
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+const RECONNECT: Duration = Duration::from_secs(5);
+/// Cookie the bearer or pairing token is traded for on the pair page
+const TOKEN_COOKIE: &str = "pd_token";
+const TOKEN_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+const TICKET_TTL: Duration = Duration::from_secs(60);
+
+/// What a dashboard route forwards to and how phoenixd's answer is shaped
+struct Route {
+    method: Method,
+    path: &'static str,
+    phoenixd: &'static str,
+    reply: Reply,
+    /// Moves funds, refused in watch-only mode
+    spends: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Reply {
+    /// phoenixd's JSON as is
+    Json,
+    /// Plain-text answer wrapped as `{ <field>: text }`
+    Field(&'static str),
+    /// phoenixd answers 200 with a `reason` when a payment fails
+    Payment,
+}
+
+/// The Node backend's phoenixd routes, with the same paths and responses
+const ROUTES: &[Route] = &[
+    route(
+        Method::GET,
+        "/api/node/info",
+        "/getinfo",
+        Reply::Json,
+        false,
+    ),
+    route(
+        Method::GET,
+        "/api/node/balance",
+        "/getbalance",
+        Reply::Json,
+        false,
+    ),
+    route(
+        Method::GET,
+        "/api/node/channels",
+        "/listchannels",
+        Reply::Json,
+        false,
+    ),
+    route(
+        Method::POST,
+        "/api/node/channels/close",
+        "/closechannel",
+        Reply::Field("txId"),
+        true,
+    ),
+    route(
+        Method::GET,
+        "/api/node/estimatefees",
+        "/estimateliquidityfees",
+        Reply::Json,
+        false,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/createinvoice",
+        "/createinvoice",
+        Reply::Json,
+        false,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/createoffer",
+        "/createoffer",
+        Reply::Field("offer"),
+        false,
+    ),
+    route(
+        Method::GET,
+        "/api/phoenixd/getlnaddress",
+        "/getlnaddress",
+        Reply::Field("address"),
+        false,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/payinvoice",
+        "/payinvoice",
+        Reply::Payment,
+        true,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/payoffer",
+        "/payoffer",
+        Reply::Payment,
+        true,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/paylnaddress",
+        "/paylnaddress",
+        Reply::Payment,
+        true,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/sendtoaddress",
+        "/sendtoaddress",
+        Reply::Field("txId"),
+        true,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/bumpfee",
+        "/bumpfee",
+        Reply::Field("txId"),
+        true,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/decodeinvoice",
+        "/decodeinvoice",
+        Reply::Json,
+        false,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/decodeoffer",
+        "/decodeoffer",
+        Reply::Json,
+        false,
+    ),
+    route(
+        Method::POST,
+        "/api/phoenixd/export",
+        "/export",
+        Reply::Field("message"),
+        false,
+    ),
+    route(
+        Method::POST,
+        "/api/lnurl/pay",
+        "/lnurlpay",
+        Reply::Json,
+        true,
+    ),
+    route(
+        Method::POST,
+        "/api/lnurl/withdraw",
+        "/lnurlwithdraw",
+        Reply::Json,
+        false,
+    ),
+    route(
+        Method::POST,
+        "/api/lnurl/auth",
+        "/lnurlauth",
+        Reply::Field("message"),
+        false,
+    ),
+];
+
+const fn route(
+    method: Method,
+    path: &'static str,
+    phoenixd: &'static str,
+    reply: Reply,
+    spends: bool,
+) -> Route {
+    Route {
+        method,
+        path,
+        phoenixd,
+        reply,
+        spends,
+    }
+}
+
+/// The node the backend serves
+pub struct Upstream {
+    pub addr: SocketAddr,
+    pub password: String,
+}
+
+/// Who may call the API, as the Node backend decides from `API_TOKEN` and
+/// `PAIRING_TOKEN`: the bearer token or the cookie it is traded for, or the
+/// pairing token's cookie
+pub struct Access {
+    pub api_token: String,
+    /// Set while LAN access is on
+    pub pairing_token: Option<String>,
+    /// Origins the pair page may send a browser on to, the first by default
+    pub origins: Vec<String>,
+}
+
+impl Access {
+    fn allows(&self, headers: &HeaderMap) -> bool {
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(str::trim);
+        let cookie = proxy::cookie(headers, TOKEN_COOKIE);
+        let cookie = cookie.as_deref();
+        matches(bearer, &self.api_token)
+            || matches(cookie, &self.api_token)
+            || self
+                .pairing_token
+                .as_deref()
+                .is_some_and(|pairing| matches(cookie, pairing))
+    }
+}
+
+fn matches(given: Option<&str>, expected: &str) -> bool {
+    !expected.is_empty()
+        && given.is_some_and(|given| proxy::constant_time_eq(given.as_bytes(), expected.as_bytes()))
+}
+
+#[derive(Clone)]
+struct BackendState {
+    client: Client<HttpConnector, Body>,
+    upstream: Arc<Upstream>,
+    db_path: Arc<PathBuf>,
+    read_only: bool,
+    node_paused: bool,
+    events: broadcast::Sender<String>,
+    access: Arc<Access>,
+    /// One-time tickets for the pair page and when they expire
+    tickets: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+/// In-process replacement for the Node backend covering the node, payment
+/// and LNURL API and the `/ws` payment events. Contacts, categories,
+/// recurring payments and apps still need the Node backend.
+pub struct NativeBackend {
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl NativeBackend {
+    pub fn start(
+        bind: SocketAddr,
+        upstream: Upstream,
+        db_path: PathBuf,
+        read_only: bool,
+        node_paused: bool,
+        access: Access,
+    ) -> Result<Self, String> {
+        let socket = std::net::TcpListener::bind(bind)
+            .and_then(|l| l.set_nonblocking(true).map(|_| l))
+            .map_err(|e| format!("Failed to listen on {}: {}", bind, e))?;
+
+        let state = BackendState {
+            client: Client::builder(TokioExecutor::new()).build_http(),
+            upstream: Arc::new(upstream),
+            db_path: Arc::new(db_path),
+            read_only,
+            node_paused,
+            events: broadcast::channel(64).0,
+            access: Arc::new(access),
+            tickets: Arc::default(),
+        };
+        let app = Router::new()
+            .route("/health", get(health))
+            .route("/ws", get(websocket))
+            .route("/api/auth/status", get(auth_status))
+            .route("/api/setup/status", get(setup_status))
+            .route("/api/desktop/tickets", post(issue_ticket))
+            .route("/api/desktop/pair", get(pair))
+            .fallback(dispatch)
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_access,
+            ))
+            .with_state(state.clone());
+
+        info!("🦀 Native backend on {}", bind);
+        let tasks = vec![
+            tauri::async_runtime::spawn(serve(socket, app)),
+            tauri::async_runtime::spawn(relay_events(state)),
+        ];
+        Ok(Self { tasks })
+    }
+
+    pub fn stop(self) {
+        for task in self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// The native backend has no password login yet, so a protected dashboard
+/// keeps using the Node backend
+pub fn password_set(db_path: &Path) -> bool {
+    Connection::open(db_path)
+        .and_then(|db| {
+            db.query_row(
+                "SELECT passwordHash FROM Settings WHERE id = 'singleton'",
+                [],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()
+        })
+        .map(|hash| hash.flatten().is_some())
+        .unwrap_or(false)
+}
+
+async fn serve(socket: std::net::TcpListener, app: Router) {
+    let listener = match TcpListener::from_std(socket) {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("Native backend listener failed: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = axum::serve(listener, app).await {
+        error!("Native backend stopped: {}", e);
+    }
+}
+
+async fn health(State(state): State<BackendState>) -> Json<Value> {
+    Json(json!({
+        "status": "ok",
+        "timestamp": chrono::Utc::now().timestamp_millis(),
+        "desktopMode": true,
+        "nodePaused": state.node_paused,
+        "engine": "native",
+    }))
+}
+
+/// Everything but the health check and pairing needs the API token, or its
+/// cookie, or the pairing cookie
+async fn require_access(
+    State(state): State<BackendState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if path == "/health"
+        || path.starts_with("/api/desktop/")
+        || state.access.allows(request.headers())
+    {
+        return next.run(request).await;
+    }
+    error(
+        StatusCode::UNAUTHORIZED,
+        "API token required, open the dashboard from the desktop app",
+    )
+}
+
+/// One-time ticket for the pair page, so the API token itself never ends up
+/// in a browser's address bar or history
+async fn issue_ticket(State(state): State<BackendState>, headers: HeaderMap) -> Response {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+    if !matches(bearer, &state.access.api_token) {
+        return error(StatusCode::UNAUTHORIZED, "API token required");
+    }
+    let ticket = match crate::token::generate() {
+        Ok(ticket) => ticket,
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+    let now = Instant::now();
+    let mut tickets = state.tickets.lock().unwrap();
+    tickets.retain(|_, expires| *expires > now);
+    tickets.insert(ticket.clone(), now + TICKET_TTL);
+    Json(json!({ "ticket": ticket })).into_response()
+}
+
+/// Trade the pairing token or a ticket for the cookie and send the browser
+/// on to `next`, if that is one of the dashboard's origins
+async fn pair(
+    State(state): State<BackendState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let access = &state.access;
+    let paired = params.get("token").filter(|token| {
+        access
+            .pairing_token
+            .as_deref()
+            .is_some_and(|pairing| matches(Some(token), pairing))
+    });
+    let ticket = params
+        .get("ticket")
+        .and_then(|ticket| state.tickets.lock().unwrap().remove(ticket))
+        .is_some_and(|expires| expires > Instant::now());
+    let token = match paired {
+        Some(token) => token.clone(),
+        None if ticket => access.api_token.clone(),
+        None => return error(StatusCode::UNAUTHORIZED, "Invalid pairing token"),
+    };
+
+    let ours = |url: &String| {
+        let origin = config::origin_of(url);
+        origin.is_some()
+            && access
+                .origins
+                .iter()
+                .any(|o| config::origin_of(o) == origin)
+    };
+    let next = params
+        .get("next")
+        .filter(|next| ours(next))
+        .or(access.origins.first())
+        .cloned()
+        .unwrap_or_default();
+    let cookie = format!(
+        "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Strict",
+        TOKEN_COOKIE,
+        token,
+        TOKEN_MAX_AGE.as_secs()
+    );
+    (
+        StatusCode::FOUND,
+        [(header::LOCATION, next), (header::SET_COOKIE, cookie)],
+    )
+        .into_response()
+}
+
+fn settings(db_path: &Path) -> (bool, String) {
+    Connection::open(db_path)
+        .and_then(|db| {
+            db.query_row(
+                "SELECT setupCompleted, defaultLocale FROM Settings WHERE id = 'singleton'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+        })
+        .ok()
+        .flatten()
+        .unwrap_or((false, "en".to_string()))
+}
+
+async fn auth_status(State(state): State<BackendState>) -> Json<Value> {
+    let (setup_completed, locale) = settings(&state.db_path);
+    Json(json!({
+        "hasPassword": false,
+        "authenticated": true,
+        "autoLockMinutes": 0,
+        "lockScreenBg": "storm-clouds",
+        "setupCompleted": setup_completed,
+        "defaultLocale": locale,
+    }))
+}
+
+async fn setup_status(State(state): State<BackendState>) -> Json<Value> {
+    let (setup_completed, locale) = settings(&state.db_path);
+    Json(json!({
+        "setupCompleted": setup_completed,
+        "setupProfile": null,
+        "defaultLocale": locale,
+    }))
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(json!({ "error": message.into() }))).into_response()
+}
+
+async fn dispatch(State(state): State<BackendState>, request: Request) -> Response {
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().map(str::to_string);
+    let method = request.method().clone();
+
+    // Payment lists and lookups
+    if method == Method::GET {
+        if let Some(rest) = path.strip_prefix("/api/payments/") {
+            let phoenixd_path = match rest {
+                "incoming" | "outgoing" => {
+                    return list_payments(&state, rest, query.as_deref()).await
+                }
+                rest if rest.starts_with("incoming/")
+                    || rest.starts_with("outgoing/")
+                    || rest.starts_with("outgoingbyhash/") =>
+                {
+                    format!("/payments/{}", rest)
+                }
+                _ => return error(StatusCode::NOT_FOUND, "Not found"),
+            };
+            return match call(&state, Method::GET, &phoenixd_path, None).await {
+                Ok(body) => json_response(&body),
+                Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, e),
+            };
+        }
+    }
+
+    let Some(route) = ROUTES
+        .iter()
+        .find(|route| route.method == method && route.path == path)
+    else {
+        return error(
+            StatusCode::NOT_IMPLEMENTED,
+            "Not available with the native backend",
+        );
+    };
+    if route.spends && state.read_only {
+        return error(StatusCode::FORBIDDEN, "The dashboard is in watch-only mode");
+    }
+
+    let form = if method == Method::POST {
+        let body = match axum::body::to_bytes(request.into_body(), MAX_BODY_BYTES).await {
+            Ok(body) => body,
+            Err(_) => return error(StatusCode::PAYLOAD_TOO_LARGE, "Request too large"),
+        };
+        match to_form(&body) {
+            Ok(form) => Some(form),
+            Err(e) => return error(StatusCode::BAD_REQUEST, e),
+        }
+    } else {
+        None
+    };
+    let phoenixd_path = match query {
+        Some(query) if method == Method::GET => format!("{}?{}", route.phoenixd, query),
+        _ => route.phoenixd.to_string(),
+    };
+
+    let body = match call(&state, method, &phoenixd_path, form).await {
+        Ok(body) => body,
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+    match route.reply {
+        Reply::Json => json_response(&body),
+        Reply::Field(field) => {
+            let text = String::from_utf8_lossy(&body)
+                .trim()
+                .trim_matches('"')
+                .to_string();
+            Json(json!({ field: text })).into_response()
+        }
+        Reply::Payment => {
+            let result: Value = serde_json::from_slice(&body).unwrap_or_default();
+            match result.get("reason").and_then(Value::as_str) {
+                Some(reason) => error(StatusCode::INTERNAL_SERVER_ERROR, reason),
+                None => Json(result).into_response(),
+            }
+        }
+    }
+}
+
+/// The dashboard posts JSON; phoenixd takes form fields
+fn to_form(body: &Bytes) -> Result<String, String> {
+    if body.is_empty() {
+        return Ok(String::new());
+    }
+    let fields: serde_json::Map<String, Value> =
+        serde_json::from_slice(body).map_err(|e| format!("Invalid JSON body: {}", e))?;
+    let mut form = form_urlencoded::Serializer::new(String::new());
+    for (key, value) in fields {
+        match value {
+            Value::Null => {}
+            Value::String(text) => {
+                form.append_pair(&key, &text);
+            }
+            other => {
+                form.append_pair(&key, &other.to_string());
+            }
+        }
+    }
+    Ok(form.finish())
+}
+
+fn json_response(body: &[u8]) -> Response {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(value) => Json(value).into_response(),
+        Err(_) => error(
+            StatusCode::BAD_GATEWAY,
+            String::from_utf8_lossy(body).trim().to_string(),
+        ),
+    }
+}
+
+async fn call(
+    state: &BackendState,
+    method: Method,
+    path: &str,
+    form: Option<String>,
+) -> Result<Bytes, String> {
+    let authorization = format!(
+        "Basic {}",
+        BASE64.encode(format!(":{}", state.upstream.password).as_bytes())
+    );
+    let mut builder = axum::http::Request::builder()
+        .method(method)
+        .uri(format!("http://{}{}", state.upstream.addr, path))
+        .header(header::AUTHORIZATION, authorization);
+    if form.is_some() {
+        builder = builder.header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+    }
+    let request = builder
+        .body(Body::from(form.unwrap_or_default()))
+        .map_err(|e| format!("Invalid phoenixd request: {}", e))?;
+
+    let response = state
+        .client
+        .request(request)
+        .await
+        .map_err(|e| format!("phoenixd is not reachable: {}", e))?;
+    let status = response.status();
+    let body = axum::body::to_bytes(Body::new(response.into_body()), MAX_BODY_BYTES)
+        .await
+        .map_err(|e| format!("Failed to read phoenixd response: {}", e))?;
+    if status.is_success() {
+        Ok(body)
+    } else {
+        Err(String::from_utf8_lossy(&body).trim().to_string())
+    }
+}
+
+/// phoenixd returns the oldest payments first; the dashboard wants the
+/// newest first, paginated, with the total in `X-Total-Count`
+async fn list_payments(state: &BackendState, direction: &str, query: Option<&str>) -> Response {
+    let params: Vec<(String, String)> =
+        form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    // Fetch everything so sorting covers the requested page
+    let upstream = {
+        let mut upstream = form_urlencoded::Serializer::new(String::new());
+        upstream.append_pair("limit", "10000");
+        for name in ["from", "to", "all", "externalId"] {
+            if let Some(value) = param(name) {
+                upstream.append_pair(name, value);
+            }
+        }
+        upstream.finish()
+    };
+    let path = format!("/payments/{}?{}", direction, upstream);
+    let body = match call(state, Method::GET, &path, None).await {
+        Ok(body) => body,
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+    let mut payments: Vec<Value> = serde_json::from_slice(&body).unwrap_or_default();
+    let time = |payment: &Value| {
+        payment
+            .get("completedAt")
+            .and_then(Value::as_i64)
+            .filter(|&t| t > 0)
+            .or_else(|| payment.get("createdAt").and_then(Value::as_i64))
+            .unwrap_or_default()
+    };
+    payments.sort_by_key(|payment| std::cmp::Reverse(time(payment)));
+
+    let total = payments.len();
+    let offset: usize = param("offset").and_then(|v| v.parse().ok()).unwrap_or(0);
+    if let Some(limit) = param("limit").and_then(|v| v.parse::<usize>().ok()) {
+        payments = payments.into_iter().skip(offset).take(limit).collect();
+    }
+    let mut response = Json(payments).into_response();
+    response
+        .headers_mut()
+        .insert("X-Total-Count", HeaderValue::from(total));
+    response
+}
+
+async fn websocket(State(state): State<BackendState>, upgrade: WebSocketUpgrade) -> Response {
+    let events = state.events.subscribe();
+    upgrade.on_upgrade(move |socket| forward_events(socket, events))
+}
+
+async fn forward_events(socket: WebSocket, mut events: broadcast::Receiver<String>) {
+    let (mut sender, mut receiver) = socket.split();
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    if sender.send(Message::Text(event.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            message = receiver.next() => {
+                if !matches!(message, Some(Ok(_))) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Follow phoenixd's websocket: pass every event on to the dashboard's
+/// sockets and log received payments like the Node backend does
+async fn relay_events(state: BackendState) {
+    let url = format!("ws://{}/websocket", state.upstream.addr);
+    loop {
+        let mut request = match url.as_str().into_client_request() {
+            Ok(request) => request,
+            Err(e) => {
+                error!("Invalid phoenixd websocket URL: {}", e);
+                return;
+            }
+        };
+        if let Ok(value) = HeaderValue::from_str(&format!(
+            "Basic {}",
+            BASE64.encode(format!(":{}", state.upstream.password).as_bytes())
+        )) {
+            request.headers_mut().insert(header::AUTHORIZATION, value);
+        }
+
+        match tokio_tungstenite::connect_async(request).await {
+            Ok((mut stream, _)) => {
+                info!("Connected to phoenixd websocket");
+                while let Some(Ok(message)) = stream.next().await {
+                    let Ok(text) = message.to_text() else {
+                        continue;
+                    };
+                    if text.is_empty() {
+                        continue;
+                    }
+                    let _ = state.events.send(text.to_string());
+                    if let Ok(event) = serde_json::from_str::<Value>(text) {
+                        if event.get("type").and_then(Value::as_str) == Some("payment_received") {
+                            if let Err(e) = log_payment(&state.db_path, &event) {
+                                warn!("Failed to save payment: {}", e);
+                            }
+                        }
+                    }
+                }
+                warn!("Disconnected from phoenixd websocket");
+            }
+            Err(e) => warn!("phoenixd websocket unavailable: {}", e),
+        }
+        tokio::time::sleep(RECONNECT).await;
+    }
+}
+
+fn log_payment(db_path: &Path, event: &Value) -> Result<(), String> {
+    let mut id = [0u8; 12];
+    getrandom::getrandom(&mut id).map_err(|e| e.to_string())?;
+    let now = chrono::Utc::now().timestamp_millis();
+    Connection::open(db_path)
+        .and_then(|db| {
+            db.execute(
+                "INSERT INTO PaymentLog (id, type, paymentHash, amountSat, status, rawData, createdAt, updatedAt)
+                 VALUES (?1, 'incoming', ?2, ?3, 'completed', ?4, ?5, ?5)",
+                rusqlite::params![
+                    format!("c{}", HEXLOWER.encode(&id)),
+                    event.get("paymentHash").and_then(Value::as_str).unwrap_or("unknown"),
+                    event.get("amountSat").and_then(Value::as_i64).unwrap_or_default(),
+                    event.to_string(),
+                    now,
+                ],
+            )
+        })
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,90 @@
+use crate::audit;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::scheduler;
+use crate::token;
+use crate::AppState;
+use std::net::{SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+/// Anycast resolvers reachable from almost any network; one answering is enough
+const PROBES: [([u8; 4], u16); 3] = [
+    ([1, 1, 1, 1], 443),
+    ([8, 8, 8, 8], 443),
+    ([9, 9, 9, 9], 443),
+];
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// Give phoenixd a moment to reconnect on its own before checking on it
+const SETTLE: Duration = Duration::from_secs(20);
+
+static ONLINE: AtomicBool = AtomicBool::new(true);
+
+/// Whether the internet was reachable at the last probe
+pub fn is_online() -> bool {
+    ONLINE.load(Ordering::Relaxed)
+}
+
+fn probe() -> bool {
+    PROBES.iter().any(|&(ip, port)| {
+        TcpStream::connect_timeout(&SocketAddr::from((ip, port)), PROBE_TIMEOUT).is_ok()
+    })
+}
+
+/// Probe internet access periodically. Losing it shows the node as offline
+/// in the tray; getting it back checks that phoenixd and the backend
+/// recovered, restarting whichever didn't.
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let online = probe();
+        if online != ONLINE.swap(online, Ordering::Relaxed) {
+            let _ = app.emit("network-status", online);
+            if online {
+                info!("🌐 Back online");
+            } else {
+                warn!("🌐 No internet connection");
+            }
+            refresh_tray(&app);
+            if online {
+                std::thread::sleep(SETTLE);
+                check_recovery(&app);
+            }
+        }
+        scheduler::sleep(&app, "network");
+    });
+}
+
+fn refresh_tray(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let services = state.statuses.lock().unwrap().clone();
+    crate::refresh_tray_menu(app, &services);
+    crate::update_tray_tooltip(app, &state.process_manager.lock().unwrap());
+}
+
+fn check_recovery(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let client = PhoenixdClient::new(&state.data_dir);
+    let node_ok = tauri::async_runtime::block_on(client.get_info()).is_ok();
+    let backend = state.config.lock().unwrap().bind.backend_addr();
+    let mut request = ureq::get(&format!("http://{}/health", backend)).timeout(PROBE_TIMEOUT);
+    if let Some(bearer) = token::api_token(&state.data_dir) {
+        request = request.set("Authorization", &format!("Bearer {}", bearer));
+    }
+    let backend_ok = request.call().is_ok();
+
+    let mut pm = state.process_manager.lock().unwrap();
+    for (name, ok) in [("phoenixd", node_ok), ("backend", backend_ok)] {
+        if ok {
+            continue;
+        }
+        warn!(
+            "{} didn't recover after the network came back, restarting it",
+            name
+        );
+        audit::record(&state.data_dir, "service.reconnect_restart", name);
+        if let Err(e) = pm.restart_service(name) {
+            warn!("{}", e);
+        }
+    }
+}
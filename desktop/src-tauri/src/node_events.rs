@@ -0,0 +1,82 @@
+use crate::i18n::t;
+use crate::notify;
+use crate::AppState;
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::info;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeEventKind {
+    PeerConnected,
+    PeerDisconnected,
+    ChannelOpened,
+    LiquidityPurchased,
+    SwapInConfirmed,
+}
+
+impl NodeEventKind {
+    fn i18n_key(self) -> &'static str {
+        match self {
+            NodeEventKind::PeerConnected => "node_event.peer_connected",
+            NodeEventKind::PeerDisconnected => "node_event.peer_disconnected",
+            NodeEventKind::ChannelOpened => "node_event.channel_opened",
+            NodeEventKind::LiquidityPurchased => "node_event.liquidity_purchased",
+            NodeEventKind::SwapInConfirmed => "node_event.swap_in_confirmed",
+        }
+    }
+}
+
+/// Something notable phoenixd logged, emitted to windows as `node-event`
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeEvent {
+    pub kind: NodeEventKind,
+    pub at: String,
+    /// The log line it was recognized in
+    pub line: String,
+}
+
+/// Receives the events recognized in phoenixd's output
+pub type NodeEventSink = Arc<dyn Fn(NodeEvent) + Send + Sync>;
+
+/// Words phoenixd's log lines carry for each event, matched case-insensitively
+/// in order; every word of a rule has to appear
+const RULES: [(NodeEventKind, &[&str]); 6] = [
+    (NodeEventKind::PeerDisconnected, &["disconnected"]),
+    (NodeEventKind::PeerConnected, &["peer", "connected"]),
+    (NodeEventKind::ChannelOpened, &["channel", "opened"]),
+    (
+        NodeEventKind::LiquidityPurchased,
+        &["liquidity", "purchase"],
+    ),
+    (NodeEventKind::SwapInConfirmed, &["swap-in", "confirmed"]),
+    (NodeEventKind::SwapInConfirmed, &["swap in", "confirmed"]),
+];
+
+pub fn parse(line: &str) -> Option<NodeEvent> {
+    let lower = line.to_lowercase();
+    let (kind, _) = RULES
+        .iter()
+        .find(|(_, words)| words.iter().all(|word| lower.contains(word)))?;
+    Some(NodeEvent {
+        kind: *kind,
+        at: chrono::Local::now().to_rfc3339(),
+        line: line.trim().to_string(),
+    })
+}
+
+/// Emit events to the app's windows and, if enabled, as notifications
+pub fn native(app: AppHandle) -> NodeEventSink {
+    Arc::new(move |event: NodeEvent| {
+        info!("🔔 Node event {:?}", event.kind);
+        let _ = app.emit("node-event", &event);
+
+        let enabled = app
+            .try_state::<AppState>()
+            .is_some_and(|state| state.config.lock().unwrap().phoenixd.notify_events);
+        if enabled {
+            notify::native(app.clone())(t(event.kind.i18n_key()), &event.line);
+        }
+    })
+}
@@ -0,0 +1,134 @@
+use crate::i18n::t;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::windows::{self, escape};
+use serde::Serialize;
+use tauri::AppHandle;
+
+/// Basic facts about a node, straight from phoenixd
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    pub node_id: String,
+    pub version: String,
+    pub chain: String,
+    pub block_height: Option<u64>,
+    pub channels: usize,
+    /// Channels in the `Normal` state
+    pub active_channels: usize,
+    pub balance_sat: u64,
+    pub inbound_liquidity_sat: u64,
+    pub fee_credit_sat: u64,
+    pub channel_list: Vec<ChannelInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelInfo {
+    pub channel_id: String,
+    pub state: String,
+    pub balance_sat: u64,
+    pub inbound_liquidity_sat: u64,
+    pub capacity_sat: u64,
+}
+
+/// Ask phoenixd's `/getinfo` and `/getbalance`
+pub async fn fetch(client: &PhoenixdClient) -> Result<NodeInfo, String> {
+    let info = client.get_info().await?;
+    let balance = client.get_balance().await?;
+
+    let text = |key: &str| info[key].as_str().unwrap_or_default().to_string();
+    let channels = info["channels"].as_array().cloned().unwrap_or_default();
+    let sum = |key: &str| -> u64 { channels.iter().filter_map(|c| c[key].as_u64()).sum() };
+    Ok(NodeInfo {
+        node_id: text("nodeId"),
+        version: text("version"),
+        chain: text("chain"),
+        block_height: info["blockHeight"].as_u64(),
+        channels: channels.len(),
+        active_channels: channels
+            .iter()
+            .filter(|c| c["state"].as_str() == Some("Normal"))
+            .count(),
+        balance_sat: balance.balance_sat,
+        inbound_liquidity_sat: sum("inboundLiquiditySat"),
+        fee_credit_sat: balance.fee_credit_sat,
+        channel_list: channels
+            .iter()
+            .map(|c| ChannelInfo {
+                channel_id: c["channelId"].as_str().unwrap_or_default().to_string(),
+                state: c["state"].as_str().unwrap_or_default().to_string(),
+                balance_sat: c["balanceSat"].as_u64().unwrap_or_default(),
+                inbound_liquidity_sat: c["inboundLiquiditySat"].as_u64().unwrap_or_default(),
+                capacity_sat: c["capacitySat"].as_u64().unwrap_or_default(),
+            })
+            .collect(),
+    })
+}
+
+/// Native window with the node's details, readable while the web frontend is down
+pub fn show_window(app: &AppHandle, info: &NodeInfo) -> Result<(), String> {
+    let sats = |amount: u64| format!("{} sat", amount);
+    let rows = [
+        ("node_info.version", info.version.clone()),
+        ("node_info.chain", info.chain.clone()),
+        (
+            "node_info.block_height",
+            info.block_height
+                .map(|h| h.to_string())
+                .unwrap_or_else(|| "—".to_string()),
+        ),
+        (
+            "node_info.channels",
+            format!("{} / {}", info.active_channels, info.channels),
+        ),
+        ("node_info.balance", sats(info.balance_sat)),
+        (
+            "node_info.inbound_liquidity",
+            sats(info.inbound_liquidity_sat),
+        ),
+        ("node_info.fee_credit", sats(info.fee_credit_sat)),
+    ];
+
+    let mut body = format!(
+        "<section><h2>{}</h2>{}</section><table>",
+        escape(t("node_info.node_id")),
+        windows::copyable(&info.node_id)
+    );
+    for (label, value) in rows {
+        body.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td></tr>",
+            escape(t(label)),
+            escape(&value)
+        ));
+    }
+    body.push_str("</table>");
+    for channel in &info.channel_list {
+        body.push_str(&format!(
+            "<p>{} — {} · {} / {}</p>",
+            escape(&channel.channel_id.chars().take(16).collect::<String>()),
+            escape(&channel.state),
+            sats(channel.balance_sat),
+            sats(channel.capacity_sat)
+        ));
+    }
+
+    windows::show(
+        app,
+        "node-info",
+        t("window.node_info"),
+        &body,
+        (420.0, 480.0),
+    )
+}
+
+/// Error page for when phoenixd can't be asked
+pub fn show_error(app: &AppHandle, error: &str) -> Result<(), String> {
+    let body = format!("<p>{}</p>", escape(error));
+    windows::show(
+        app,
+        "node-info",
+        t("window.node_info"),
+        &body,
+        (420.0, 480.0),
+    )
+}
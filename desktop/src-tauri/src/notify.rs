@@ -0,0 +1,83 @@
+use crate::{journal, AppState};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessesToUpdate, System};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tracing::{debug, warn};
+
+/// Callback used by the managers to surface something to the user
+/// (title, body) without depending on Tauri themselves
+pub type Notifier = Arc<dyn Fn(&str, &str) + Send + Sync>;
+
+/// Processes that only run while the screen is being shared or recorded,
+/// lowercase and without `.exe`
+const SCREEN_SHARING_PROCESSES: &[&str] = &[
+    // Zoom's sharing helper
+    "cpthost",
+    "obs",
+    "obs64",
+    // macOS Screen Sharing, started per session
+    "screensharingd",
+    "simplescreenrecorder",
+    "gpu-screen-recorder",
+    "kazam",
+    "vokoscreenng",
+];
+/// Listing processes isn't free; reuse the answer for bursts of notifications
+const SCREEN_SHARING_TTL: Duration = Duration::from_secs(30);
+
+static SCREEN_SHARING: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
+
+fn screen_sharing() -> bool {
+    let mut cached = SCREEN_SHARING.lock().unwrap();
+    if let Some((at, sharing)) = *cached {
+        if at.elapsed() < SCREEN_SHARING_TTL {
+            return sharing;
+        }
+    }
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    let sharing = system.processes().values().any(|process| {
+        let name = process.name().to_string_lossy().to_lowercase();
+        SCREEN_SHARING_PROCESSES.contains(&name.trim_end_matches(".exe"))
+    });
+    *cached = Some((Instant::now(), sharing));
+    sharing
+}
+
+/// Why notifications are held back right now, if they are
+pub fn quiet_reason(app: &AppHandle) -> Option<&'static str> {
+    let state = app.try_state::<AppState>()?;
+    let config = state.config.lock().unwrap().quiet_hours.clone();
+    if !config.enabled {
+        None
+    } else if config.covers(chrono::Local::now().time()) {
+        Some("quiet_hours")
+    } else if config.while_screen_sharing && screen_sharing() {
+        Some("screen_sharing")
+    } else {
+        None
+    }
+}
+
+/// Notifier backed by native OS notifications. During quiet hours they are
+/// only written to the journal.
+pub fn native(app: AppHandle) -> Notifier {
+    Arc::new(move |title: &str, body: &str| {
+        if let Some(reason) = quiet_reason(&app) {
+            debug!("🔕 Holding back notification ({}): {}", reason, title);
+            let state = app.state::<AppState>();
+            journal::record(
+                &state.data_dir,
+                "notification.held",
+                None,
+                format!("{} ({}): {}", title, reason, body),
+            );
+            return;
+        }
+        if let Err(e) = app.notification().builder().title(title).body(body).show() {
+            warn!("Could not show notification: {}", e);
+        }
+    })
+}
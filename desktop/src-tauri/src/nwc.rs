@@ -0,0 +1,183 @@
+use crate::config::{NwcConfig, NwcConnection};
+use crate::i18n::t;
+use crate::{redact, secrets, tls};
+use data_encoding::HEXLOWER;
+use serde::Serialize;
+use serde_json::json;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tracing::info;
+
+/// Keychain entry of the bridge's own Nostr key
+const WALLET_KEY: &str = "nwc-wallet-key";
+const WINDOW_LABEL: &str = "nwc-pairing";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pairing {
+    pub name: String,
+    /// `nostr+walletconnect://` URI with the client's secret; not kept
+    pub uri: String,
+}
+
+/// The bundled bridge, or one on PATH
+fn find_binary(resource_dir: &Path) -> Result<PathBuf, String> {
+    let binary_name = if cfg!(target_os = "windows") {
+        "nwc-bridge.exe"
+    } else {
+        "nwc-bridge"
+    };
+    let bundled = resource_dir.join("binaries").join(binary_name);
+    if bundled.exists() {
+        return Ok(bundled);
+    }
+    which::which("nwc-bridge").map_err(|_| {
+        "nwc-bridge not found. Please install it or include it in the app bundle.".to_string()
+    })
+}
+
+/// A new Nostr secret key, hex
+fn new_secret() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate key: {}", e))?;
+    let secret = HEXLOWER.encode(&bytes);
+    redact::register(&secret);
+    Ok(secret)
+}
+
+/// The bridge's Nostr key, created on first use
+pub fn wallet_key() -> Result<String, String> {
+    if let Some(key) = secrets::get(WALLET_KEY) {
+        return Ok(key);
+    }
+    let key = new_secret()?;
+    secrets::set(WALLET_KEY, &key)?;
+    Ok(key)
+}
+
+/// Public key of a secret key, worked out by the bridge (`nwc-bridge pubkey`
+/// reads the secret from stdin)
+fn public_key(binary: &Path, secret: &str) -> Result<String, String> {
+    let mut child = Command::new(binary)
+        .arg("pubkey")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run nwc-bridge: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(secret.as_bytes())
+            .map_err(|e| format!("Failed to run nwc-bridge: {}", e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run nwc-bridge: {}", e))?;
+    let pubkey = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || pubkey.len() != 64 || HEXLOWER.decode(pubkey.as_bytes()).is_err()
+    {
+        return Err(format!(
+            "nwc-bridge could not derive a public key: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(pubkey)
+}
+
+fn pairing_uri(wallet_pubkey: &str, relay: &str, secret: &str) -> String {
+    let query = form_urlencoded::Serializer::new(String::new())
+        .append_pair("relay", relay)
+        .append_pair("secret", secret)
+        .finish();
+    format!("nostr+walletconnect://{}?{}", wallet_pubkey, query)
+}
+
+/// Add a connection to `config` and return its pairing URI, which is the
+/// only place the client's secret is kept
+pub fn create_connection(
+    resource_dir: &Path,
+    config: &mut NwcConfig,
+    name: &str,
+    budget_sat: u64,
+    budget_renewal: &str,
+) -> Result<String, String> {
+    let binary = find_binary(resource_dir)?;
+    let wallet_pubkey = public_key(&binary, &wallet_key()?)?;
+    let secret = new_secret()?;
+    config.connections.push(NwcConnection {
+        name: name.trim().to_string(),
+        pubkey: public_key(&binary, &secret)?,
+        budget_sat,
+        budget_renewal: budget_renewal.to_string(),
+        created_at: chrono::Local::now().to_rfc3339(),
+    });
+    config.validate()?;
+    info!("⚡ New NWC connection '{}'", name);
+    Ok(pairing_uri(&wallet_pubkey, &config.relay, &secret))
+}
+
+/// A name for a connection paired from the tray
+pub fn next_name(config: &NwcConfig) -> String {
+    (1..)
+        .map(|n| format!("{} {}", t("nwc.connection"), n))
+        .find(|name| config.connections.iter().all(|c| c.name != *name))
+        .unwrap()
+}
+
+fn config_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("nwc").join("bridge.json")
+}
+
+/// Write the bridge's settings, before each start. Budgets are enforced by
+/// the bridge, which keeps its spending records next to them.
+pub fn write_config(
+    data_dir: &Path,
+    config: &NwcConfig,
+    phoenixd_url: &str,
+    phoenixd_password: &str,
+    read_only: bool,
+) -> Result<(), String> {
+    let path = config_path(data_dir);
+    let dir = path.parent().unwrap();
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    let content = json!({
+        "relay": config.relay,
+        "phoenixd_url": phoenixd_url,
+        "phoenixd_password": phoenixd_password,
+        "read_only": read_only,
+        "state_dir": dir,
+        "connections": config.connections,
+    });
+    tls::write_private(&path, &content.to_string())
+}
+
+/// `nwc-bridge run` on the settings `write_config` leaves; the wallet key
+/// goes in its environment as `NWC_WALLET_KEY`
+pub fn command(resource_dir: &Path, data_dir: &Path) -> Result<Command, String> {
+    let mut command = Command::new(find_binary(resource_dir)?);
+    command
+        .arg("run")
+        .arg("--config")
+        .arg(config_path(data_dir))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    Ok(command)
+}
+
+/// Native window with the pairing URI as a QR code for a Nostr client
+pub fn show_pairing_window(app: &tauri::AppHandle, name: &str, uri: &str) -> Result<(), String> {
+    let body = format!(
+        r#"<div class="qr">{}</div><p>{}</p>{}"#,
+        crate::qr::to_svg(uri)?,
+        crate::windows::escape(&t("window.nwc_pairing_hint").replace("{name}", name)),
+        crate::windows::copyable(uri)
+    );
+    crate::windows::show(
+        app,
+        WINDOW_LABEL,
+        t("window.nwc_pairing"),
+        &body,
+        (360.0, 580.0),
+    )
+}
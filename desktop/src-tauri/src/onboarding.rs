@@ -0,0 +1,160 @@
+use crate::config::AppConfig;
+use crate::redact;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const STATE_FILE: &str = "onboarding.json";
+
+/// First-run wizard steps, in order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    #[default]
+    Network,
+    Wallet,
+    Password,
+    SeedBackup,
+    RemoteAccess,
+    Done,
+}
+
+impl Step {
+    fn next(self) -> Self {
+        match self {
+            Step::Network => Step::Wallet,
+            Step::Wallet => Step::Password,
+            Step::Password => Step::SeedBackup,
+            Step::SeedBackup => Step::RemoteAccess,
+            Step::RemoteAccess | Step::Done => Step::Done,
+        }
+    }
+}
+
+/// Wizard progress, persisted after every step so an interrupted
+/// onboarding resumes where it stopped
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Onboarding {
+    pub step: Step,
+    /// The wallet was restored from a recovery phrase rather than created
+    pub restored: bool,
+}
+
+pub fn seed_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(".phoenix").join("seed.dat")
+}
+
+impl Onboarding {
+    fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(STATE_FILE)
+    }
+
+    /// Installs that already had a wallet before the wizard existed count
+    /// as onboarded
+    pub fn load(data_dir: &Path) -> Self {
+        match std::fs::read_to_string(Self::path(data_dir)) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Invalid onboarding state, starting over: {}", e);
+                Self::default()
+            }),
+            Err(_) if seed_path(data_dir).exists() => Self {
+                step: Step::Done,
+                restored: false,
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize onboarding state: {}", e))?;
+        std::fs::write(Self::path(data_dir), content)
+            .map_err(|e| format!("Failed to write onboarding state: {}", e))
+    }
+
+    /// phoenixd creates a fresh seed on first start, so it must not run
+    /// before the user had the chance to restore one instead
+    pub fn awaits_wallet(&self, data_dir: &Path) -> bool {
+        matches!(self.step, Step::Network | Step::Wallet) && !seed_path(data_dir).exists()
+    }
+
+    /// Fail unless the wizard is at `step`
+    pub fn expect(&self, step: Step) -> Result<(), String> {
+        if self.step == step {
+            Ok(())
+        } else {
+            Err(format!(
+                "Onboarding is at step {:?}, not {:?}",
+                self.step, step
+            ))
+        }
+    }
+
+    pub fn advance(&mut self, data_dir: &Path) -> Result<(), String> {
+        self.step = self.step.next();
+        self.save(data_dir)
+    }
+}
+
+/// Write a restored recovery phrase where phoenixd expects its seed.
+/// Never overwrites an existing wallet.
+pub fn restore_seed(data_dir: &Path, mnemonic: &str) -> Result<(), String> {
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    if words.len() != 12
+        || !words
+            .iter()
+            .all(|w| w.chars().all(|c| c.is_ascii_lowercase()))
+    {
+        return Err("The recovery phrase must be 12 lowercase words".to_string());
+    }
+
+    redact::register(&words.join(" "));
+    let path = seed_path(data_dir);
+    if path.exists() {
+        return Err("A wallet already exists in this data directory".to_string());
+    }
+    std::fs::create_dir_all(path.parent().unwrap_or(data_dir))
+        .map_err(|e| format!("Failed to create phoenixd data dir: {}", e))?;
+    crate::tls::write_private(&path, &words.join(" "))
+        .map_err(|e| format!("Failed to write seed: {}", e))
+}
+
+/// Move a restored wallet that turned out to be the wrong one out of the
+/// way, so the wizard can try again. Nothing is deleted.
+pub fn set_aside_wallet(data_dir: &Path) -> Result<PathBuf, String> {
+    let current = data_dir.join(".phoenix");
+    let aside = data_dir.join(format!(
+        ".phoenix.rejected-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::rename(&current, &aside)
+        .map_err(|e| format!("Failed to move the restored wallet aside: {}", e))?;
+    Ok(aside)
+}
+
+pub fn read_seed(data_dir: &Path) -> Result<String, String> {
+    std::fs::read_to_string(seed_path(data_dir))
+        .map(|seed| seed.trim().to_string())
+        .map_err(|e| format!("Failed to read seed: {}", e))
+}
+
+/// Set the dashboard password through the backend's one-time setup endpoint
+pub fn set_dashboard_password(data_dir: &Path, password: &str) -> Result<(), String> {
+    let token = crate::token::api_token(data_dir).unwrap_or_default();
+    let backend = AppConfig::load(data_dir).bind.backend_addr();
+    ureq::post(&format!("http://{}/api/auth/setup", backend))
+        .set("Authorization", &format!("Bearer {}", token))
+        .send_json(serde_json::json!({ "password": password }))
+        .map(|_| ())
+        .map_err(|e| match e {
+            ureq::Error::Status(_, response) => {
+                let body: serde_json::Value = response.into_json().unwrap_or_default();
+                body.get("error")
+                    .and_then(|error| error.as_str())
+                    .unwrap_or("The backend rejected the password")
+                    .to_string()
+            }
+            ureq::Error::Transport(e) => format!("Backend is not reachable: {}", e),
+        })
+}
@@ -0,0 +1,173 @@
+use crate::AppState;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+/// A long-running operation as the UI sees it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    pub id: String,
+    pub kind: String,
+    pub description: String,
+    pub started_at: String,
+    pub elapsed_ms: u64,
+    pub timeout_secs: u64,
+    /// Last step the operation reported
+    pub progress: Option<String>,
+    pub cancelling: bool,
+}
+
+struct Entry {
+    operation: Operation,
+    started: Instant,
+    cancel: Arc<AtomicBool>,
+}
+
+static RUNNING: Mutex<BTreeMap<String, Entry>> = Mutex::new(BTreeMap::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Handed to the work of an operation; it calls `check` (or `sleep`) at
+/// safe points to honor cancellation and the timeout
+pub struct Handle {
+    app: AppHandle,
+    id: String,
+    cancel: Arc<AtomicBool>,
+    deadline: Instant,
+    timeout: Duration,
+}
+
+impl Handle {
+    /// Err once the operation was cancelled or ran past its timeout
+    pub fn check(&self) -> Result<(), String> {
+        if self.cancel.load(Ordering::Relaxed) {
+            Err("Cancelled".to_string())
+        } else if Instant::now() >= self.deadline {
+            Err(format!("Timed out after {}s", self.timeout.as_secs()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sleep in short steps, stopping early on cancellation or timeout
+    pub fn sleep(&self, duration: Duration) -> Result<(), String> {
+        let until = Instant::now() + duration;
+        while Instant::now() < until {
+            self.check()?;
+            std::thread::sleep((until - Instant::now()).min(Duration::from_millis(100)));
+        }
+        self.check()
+    }
+
+    /// Report the current step to the UI
+    pub fn progress(&self, step: impl Into<String>) {
+        let step = step.into();
+        let operation = {
+            let mut running = RUNNING.lock().unwrap();
+            let Some(entry) = running.get_mut(&self.id) else {
+                return;
+            };
+            entry.operation.progress = Some(step);
+            snapshot(entry)
+        };
+        emit(&self.app, &operation, "running");
+    }
+}
+
+fn snapshot(entry: &Entry) -> Operation {
+    Operation {
+        elapsed_ms: entry.started.elapsed().as_millis() as u64,
+        cancelling: entry.cancel.load(Ordering::Relaxed),
+        ..entry.operation.clone()
+    }
+}
+
+fn emit(app: &AppHandle, operation: &Operation, state: &str) {
+    let mut payload = serde_json::to_value(operation).unwrap_or_default();
+    payload["state"] = serde_json::Value::from(state);
+    let _ = app.emit("operation-progress", payload);
+}
+
+/// Run `work` as a tracked operation of `kind` on the calling thread, with
+/// the timeout configured for that kind. Progress goes out as
+/// `operation-progress` events.
+pub fn run<T>(
+    app: &AppHandle,
+    kind: &str,
+    description: impl Into<String>,
+    work: impl FnOnce(&Handle) -> Result<T, String>,
+) -> Result<T, String> {
+    let timeout = app
+        .try_state::<AppState>()
+        .map(|state| state.config.lock().unwrap().operations.timeout(kind))
+        .unwrap_or(Duration::from_secs(
+            crate::config::DEFAULT_OPERATION_TIMEOUT_SECS,
+        ));
+    let id = format!("{}-{}", kind, NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let handle = Handle {
+        app: app.clone(),
+        id: id.clone(),
+        cancel: Arc::new(AtomicBool::new(false)),
+        deadline: Instant::now() + timeout,
+        timeout,
+    };
+    let entry = Entry {
+        operation: Operation {
+            id: id.clone(),
+            kind: kind.to_string(),
+            description: description.into(),
+            started_at: chrono::Local::now().to_rfc3339(),
+            elapsed_ms: 0,
+            timeout_secs: timeout.as_secs(),
+            progress: None,
+            cancelling: false,
+        },
+        started: Instant::now(),
+        cancel: Arc::clone(&handle.cancel),
+    };
+    emit(app, &entry.operation, "running");
+    RUNNING.lock().unwrap().insert(id.clone(), entry);
+
+    let result = handle.check().and_then(|()| work(&handle));
+
+    let entry = RUNNING.lock().unwrap().remove(&id);
+    if let Some(entry) = entry {
+        let operation = snapshot(&entry);
+        let state = match &result {
+            Ok(_) => "done",
+            Err(_) if operation.cancelling => "cancelled",
+            Err(_) if Instant::now() >= handle.deadline => "timed_out",
+            Err(_) => "failed",
+        };
+        if state != "done" {
+            warn!(
+                "⏱️ {} {} after {}ms",
+                operation.description, state, operation.elapsed_ms
+            );
+        }
+        emit(app, &operation, state);
+    }
+    result
+}
+
+/// Ask a running operation to stop at its next safe point
+pub fn cancel(id: &str) -> Result<(), String> {
+    let running = RUNNING.lock().unwrap();
+    let entry = running
+        .get(id)
+        .ok_or_else(|| format!("No running operation '{}'", id))?;
+    entry.cancel.store(true, Ordering::Relaxed);
+    info!("⏹️ Cancelling {}", entry.operation.description);
+    Ok(())
+}
+
+/// Operations in flight, oldest first
+pub fn list() -> Vec<Operation> {
+    let mut operations: Vec<Operation> = RUNNING.lock().unwrap().values().map(snapshot).collect();
+    operations.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    operations
+}
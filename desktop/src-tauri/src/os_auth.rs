@@ -0,0 +1,168 @@
+use crate::audit;
+use crate::i18n::t;
+use crate::windows;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+use tauri::{AppHandle, WindowEvent};
+use tracing::{info, warn};
+
+const WINDOW_LABEL: &str = "confirm-identity";
+/// How long the typed-phrase prompt waits for an answer
+const PROMPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Ask the OS to confirm the user's identity (Windows Hello, the macOS
+/// admin prompt, polkit) before a sensitive action. Where none of that is
+/// available the user has to type a phrase instead. Blocks until answered,
+/// so never call it on the main thread.
+pub fn require(app: &AppHandle, data_dir: &Path, action: &str, reason: &str) -> Result<(), String> {
+    let granted = match os_prompt(reason) {
+        Some(granted) => granted,
+        None => {
+            info!("No OS authentication available, asking for the confirmation phrase");
+            typed_phrase(app, reason)
+        }
+    };
+
+    audit::record(
+        data_dir,
+        if granted {
+            "auth.granted"
+        } else {
+            "auth.denied"
+        },
+        action,
+    );
+    if granted {
+        Ok(())
+    } else {
+        Err("Authentication was cancelled or failed".to_string())
+    }
+}
+
+/// `Some(granted)` if the OS has a way to prompt, `None` only when it has
+/// none here. A failed or abandoned prompt is a denial, never a reason to
+/// fall back to the phrase.
+#[cfg(target_os = "linux")]
+fn os_prompt(_reason: &str) -> Option<bool> {
+    // pkexec brings up the desktop's polkit agent. It exits 127 both for a
+    // missing agent and for failed attempts, so tell them apart by what it
+    // says.
+    let output = match Command::new("pkexec").arg("/bin/true").output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => return None,
+        Err(e) => {
+            warn!("Could not run pkexec: {}", e);
+            return Some(false);
+        }
+    };
+    if output.status.success() {
+        Some(true)
+    } else if String::from_utf8_lossy(&output.stderr).contains("No authentication agent") {
+        None
+    } else {
+        Some(false)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn os_prompt(reason: &str) -> Option<bool> {
+    let script = format!(
+        r#"do shell script "true" with prompt "{}" with administrator privileges"#,
+        reason.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    let output = match Command::new("osascript").args(["-e", &script]).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => return None,
+        Err(e) => {
+            warn!("Could not run osascript: {}", e);
+            return Some(false);
+        }
+    };
+    // Cancelling is -128, a wrong password another code; both are denials
+    Some(output.status.success())
+}
+
+#[cfg(windows)]
+fn os_prompt(reason: &str) -> Option<bool> {
+    // Windows Hello through the WinRT consent API, reachable from Windows PowerShell
+    let script = format!(
+        r#"Add-Type -AssemblyName System.Runtime.WindowsRuntime
+$null = [Windows.Security.Credentials.UI.UserConsentVerifier, Windows.Security.Credentials.UI, ContentType = WindowsRuntime]
+$asTask = [System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object {{ $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1' }} | Select-Object -First 1
+$operation = [Windows.Security.Credentials.UI.UserConsentVerifier]::RequestVerificationAsync('{}')
+$task = $asTask.MakeGenericMethod([Windows.Security.Credentials.UI.UserConsentVerificationResult]).Invoke($null, @($operation))
+$task.Wait()
+Write-Output $task.Result"#,
+        reason.replace('\'', "''")
+    );
+    let output = match Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => return None,
+        Err(e) => {
+            warn!("Could not run PowerShell: {}", e);
+            return Some(false);
+        }
+    };
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "Verified" => Some(true),
+        "DeviceNotPresent" | "NotConfiguredForUser" | "DisabledByPolicy" => None,
+        // Canceled, RetriesExhausted, DeviceBusy or a script error
+        _ => Some(false),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn os_prompt(_reason: &str) -> Option<bool> {
+    None
+}
+
+/// Small window asking the user to type a fixed phrase
+fn typed_phrase(app: &AppHandle, reason: &str) -> bool {
+    let phrase = t("auth.phrase");
+    let body = format!(
+        r#"<p>{}</p><p>{}</p><form action="submit"><input name="phrase" autocomplete="off" autofocus><button type="submit">{}</button></form>"#,
+        windows::escape(reason),
+        windows::escape(&t("auth.type_phrase").replace("{phrase}", phrase)),
+        windows::escape(t("auth.confirm"))
+    );
+
+    let (tx, rx) = mpsc::channel::<bool>();
+    let submit_tx = tx.clone();
+    let expected = phrase.to_string();
+    let window = match windows::show_form(
+        app,
+        WINDOW_LABEL,
+        t("auth.title"),
+        &body,
+        (380.0, 260.0),
+        move |url| {
+            let typed = url
+                .query_pairs()
+                .find(|(key, _)| key == "phrase")
+                .map(|(_, value)| value.trim().to_string())
+                .unwrap_or_default();
+            let _ = submit_tx.send(typed.eq_ignore_ascii_case(&expected));
+        },
+    ) {
+        Ok(window) => window,
+        Err(e) => {
+            warn!("{}", e);
+            return false;
+        }
+    };
+    window.on_window_event(move |event| {
+        if matches!(event, WindowEvent::Destroyed) {
+            let _ = tx.send(false);
+        }
+    });
+
+    let granted = rx.recv_timeout(PROMPT_TIMEOUT).unwrap_or(false);
+    let _ = window.destroy();
+    granted
+}
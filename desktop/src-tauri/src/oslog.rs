@@ -0,0 +1,119 @@
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::{Layer, Registry};
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Layer forwarding the shell's lifecycle events (info and up) to the OS
+/// logging facility: journald on Linux, os_log on macOS, the Event Log on Windows
+pub fn layer() -> Option<BoxedLayer> {
+    platform_layer().map(|layer| layer.with_filter(LevelFilter::INFO).boxed())
+}
+
+#[cfg(target_os = "linux")]
+fn platform_layer() -> Option<BoxedLayer> {
+    // Not every distro runs journald; the file log still has everything
+    tracing_journald::layer().ok().map(|layer| {
+        layer
+            .with_syslog_identifier("phoenixd-dashboard".to_string())
+            .boxed()
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn platform_layer() -> Option<BoxedLayer> {
+    Some(tracing_oslog::OsLogger::new("com.phoenixd.dashboard", "default").boxed())
+}
+
+#[cfg(windows)]
+fn platform_layer() -> Option<BoxedLayer> {
+    event_log::EventLogLayer::new().map(|layer| layer.boxed())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn platform_layer() -> Option<BoxedLayer> {
+    None
+}
+
+#[cfg(windows)]
+mod event_log {
+    use std::fmt::Write;
+    use tracing::field::{Field, Visit};
+    use tracing::{Event, Level, Subscriber};
+    use tracing_subscriber::layer::Context;
+    use tracing_subscriber::Layer;
+    use windows_sys::Win32::System::EventLog::{
+        RegisterEventSourceW, ReportEventW, EVENTLOG_ERROR_TYPE, EVENTLOG_INFORMATION_TYPE,
+        EVENTLOG_WARNING_TYPE,
+    };
+
+    const SOURCE: &str = "Phoenixd Dashboard";
+
+    /// Writes events to the Application log. The source isn't registered
+    /// (that needs admin rights), so Event Viewer shows the text with a note
+    /// about the missing message file, which is fine for support purposes.
+    pub struct EventLogLayer {
+        // HANDLE kept as an integer so the layer is Send + Sync
+        handle: isize,
+    }
+
+    impl EventLogLayer {
+        pub fn new() -> Option<Self> {
+            let source = wide(SOURCE);
+            let handle = unsafe { RegisterEventSourceW(std::ptr::null(), source.as_ptr()) };
+            if handle.is_null() {
+                None
+            } else {
+                Some(Self {
+                    handle: handle as isize,
+                })
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct Message(String);
+
+    impl Visit for Message {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                let _ = write!(self.0, "{:?}", value);
+            } else {
+                let _ = write!(self.0, " {}={:?}", field.name(), value);
+            }
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for EventLogLayer {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            let mut message = Message::default();
+            event.record(&mut message);
+
+            let kind = match *event.metadata().level() {
+                Level::ERROR => EVENTLOG_ERROR_TYPE,
+                Level::WARN => EVENTLOG_WARNING_TYPE,
+                _ => EVENTLOG_INFORMATION_TYPE,
+            };
+            let message = crate::redact::scrub(&message.0);
+            let text = wide(&format!("{}: {}", event.metadata().target(), message));
+            let strings = [text.as_ptr()];
+
+            unsafe {
+                ReportEventW(
+                    self.handle as _,
+                    kind,
+                    0,
+                    0,
+                    std::ptr::null_mut(),
+                    1,
+                    0,
+                    strings.as_ptr(),
+                    std::ptr::null(),
+                );
+            }
+        }
+    }
+
+    fn wide(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+}
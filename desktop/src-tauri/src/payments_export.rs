@@ -0,0 +1,164 @@
+use crate::operations::Handle;
+use crate::phoenixd_api::PhoenixdClient;
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Payments asked from phoenixd per request
+const PAGE_SIZE: usize = 100;
+
+/// Creation times to export, in milliseconds since the epoch; open-ended
+/// where missing
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportRange {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+/// One invoice or payment, flattened for accounting
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRecord {
+    /// `incoming` or `outgoing`
+    pub direction: &'static str,
+    /// `paid`, `pending`, or `failed` for outgoing payments that gave up
+    pub status: &'static str,
+    pub payment_hash: String,
+    /// `paymentId` of outgoing payments
+    pub payment_id: String,
+    pub amount_sat: u64,
+    pub fees_msat: u64,
+    pub description: String,
+    pub created_at: String,
+    pub completed_at: String,
+    pub invoice: String,
+}
+
+impl PaymentRecord {
+    fn parse(payment: &Value, incoming: bool) -> Self {
+        let text = |key: &str| payment[key].as_str().unwrap_or_default().to_string();
+        let time = |key: &str| {
+            payment[key]
+                .as_i64()
+                .and_then(|ms| Local.timestamp_millis_opt(ms).single())
+                .map(|time| time.to_rfc3339())
+                .unwrap_or_default()
+        };
+        let paid = payment["isPaid"].as_bool().unwrap_or_default();
+        let completed = payment["completedAt"].as_i64().is_some();
+        Self {
+            direction: if incoming { "incoming" } else { "outgoing" },
+            status: match (paid, completed) {
+                (true, _) => "paid",
+                (false, true) if !incoming => "failed",
+                _ => "pending",
+            },
+            payment_hash: text("paymentHash"),
+            payment_id: text("paymentId"),
+            amount_sat: payment[if incoming { "receivedSat" } else { "sent" }]
+                .as_u64()
+                .unwrap_or_default(),
+            fees_msat: payment["fees"].as_u64().unwrap_or_default(),
+            description: text("description"),
+            created_at: time("createdAt"),
+            completed_at: time("completedAt"),
+            invoice: text("invoice"),
+        }
+    }
+}
+
+/// Every incoming and outgoing payment in `range`, oldest first
+pub fn fetch(
+    client: &PhoenixdClient,
+    range: &ExportRange,
+    op: &Handle,
+) -> Result<Vec<PaymentRecord>, String> {
+    fetch_with(client, range, |direction, offset| {
+        op.check()?;
+        op.progress(format!("{} payments: {}", direction, offset));
+        Ok(())
+    })
+}
+
+/// Same as `fetch`, calling `before_page` with the direction and offset
+/// before each request; an error from it stops the fetch
+pub fn fetch_with(
+    client: &PhoenixdClient,
+    range: &ExportRange,
+    mut before_page: impl FnMut(&str, usize) -> Result<(), String>,
+) -> Result<Vec<PaymentRecord>, String> {
+    let mut records = Vec::new();
+    for (direction, incoming) in [("incoming", true), ("outgoing", false)] {
+        let mut offset = 0;
+        loop {
+            before_page(direction, offset)?;
+            let page = tauri::async_runtime::block_on(
+                client.payments_page(direction, range.from, range.to, PAGE_SIZE, offset),
+            )?;
+            records.extend(page.iter().map(|p| PaymentRecord::parse(p, incoming)));
+            if page.len() < PAGE_SIZE {
+                break;
+            }
+            offset += PAGE_SIZE;
+        }
+    }
+    records.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(records)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_csv(records: &[PaymentRecord]) -> String {
+    let mut csv = String::from(
+        "direction,status,payment_hash,payment_id,amount_sat,fees_msat,description,created_at,completed_at,invoice\n",
+    );
+    for record in records {
+        let fields = [
+            record.direction.to_string(),
+            record.status.to_string(),
+            record.payment_hash.clone(),
+            record.payment_id.clone(),
+            record.amount_sat.to_string(),
+            record.fees_msat.to_string(),
+            record.description.clone(),
+            record.created_at.clone(),
+            record.completed_at.clone(),
+            record.invoice.clone(),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Write the payments in `range` to `dest` as `csv` or `json`. Returns how
+/// many were written.
+pub fn export(
+    client: &PhoenixdClient,
+    range: &ExportRange,
+    format: &str,
+    dest: &Path,
+    op: &Handle,
+) -> Result<usize, String> {
+    if !matches!(format, "csv" | "json") {
+        return Err(format!("Unknown export format: {}", format));
+    }
+    let records = fetch(client, range, op)?;
+    let content = if format == "csv" {
+        to_csv(&records)
+    } else {
+        serde_json::to_string_pretty(&records)
+            .map_err(|e| format!("Failed to serialize payments: {}", e))?
+    };
+    std::fs::write(dest, content).map_err(|e| format!("Failed to write {:?}: {}", dest, e))?;
+    Ok(records.len())
+}
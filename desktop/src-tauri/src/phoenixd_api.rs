@@ -0,0 +1,243 @@
+use crate::config::AppConfig;
+use crate::redact;
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use data_encoding::BASE64;
+use hyper_util::client::legacy::{connect::HttpConnector, Client};
+use hyper_util::rt::TokioExecutor;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::Path;
+
+const MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
+fn read_conf_password(phoenix_conf: &Path, key: &str) -> String {
+    let prefix = format!("{}=", key);
+    std::fs::read_to_string(phoenix_conf)
+        .ok()
+        .and_then(|content| {
+            content
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix.as_str()))
+                .map(|password| password.trim().to_string())
+        })
+        .inspect(|password| redact::register(password))
+        .unwrap_or_default()
+}
+
+/// Read `http-password` from phoenixd's `phoenix.conf` (empty if missing)
+pub fn read_password(phoenix_conf: &Path) -> String {
+    read_conf_password(phoenix_conf, "http-password")
+}
+
+/// Read `http-password-limited-access`, which can create invoices and read
+/// but not send (empty if missing)
+pub fn read_limited_password(phoenix_conf: &Path) -> String {
+    read_conf_password(phoenix_conf, "http-password-limited-access")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Invoice {
+    pub amount_sat: Option<u64>,
+    pub payment_hash: String,
+    pub serialized: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub balance_sat: u64,
+    pub fee_credit_sat: u64,
+}
+
+/// Minimal client for the few phoenixd calls the shell makes itself, so
+/// simple actions work without going through the backend or the web UI
+pub struct PhoenixdClient {
+    client: Client<HttpConnector, Body>,
+    base_url: String,
+    authorization: String,
+}
+
+impl PhoenixdClient {
+    /// Client for the main node
+    pub fn new(data_dir: &Path) -> Self {
+        Self::at(data_dir, AppConfig::load(data_dir).bind.phoenixd_addr())
+    }
+
+    /// Client for the node the dashboard currently shows
+    pub fn active(data_dir: &Path) -> Self {
+        let config = AppConfig::load(data_dir);
+        match config.nodes.active() {
+            Some(node) => Self::at(&node.home(data_dir), config.bind.node_addr(node)),
+            None => Self::at(data_dir, config.bind.phoenixd_addr()),
+        }
+    }
+
+    /// `home` is the phoenixd HOME holding `.phoenix/phoenix.conf`
+    fn at(home: &Path, addr: SocketAddr) -> Self {
+        Self::with_conf(&home.join(".phoenix").join("phoenix.conf"), addr)
+    }
+
+    /// Client for a phoenixd the app doesn't manage, authenticated with the
+    /// password in its `phoenix.conf`
+    pub fn with_conf(phoenix_conf: &Path, addr: SocketAddr) -> Self {
+        let password = read_password(phoenix_conf);
+        Self {
+            client: Client::builder(TokioExecutor::new()).build_http(),
+            base_url: format!("http://{}", addr),
+            authorization: format!(
+                "Basic {}",
+                BASE64.encode(format!(":{}", password).as_bytes())
+            ),
+        }
+    }
+
+    pub async fn get_info(&self) -> Result<serde_json::Value, String> {
+        let body = self.request(Method::GET, "/getinfo", None).await?;
+        serde_json::from_slice(&body).map_err(|e| format!("Invalid phoenixd response: {}", e))
+    }
+
+    pub async fn node_id(&self) -> Result<String, String> {
+        self.get_info()
+            .await?
+            .get("nodeId")
+            .and_then(|id| id.as_str())
+            .map(String::from)
+            .ok_or_else(|| "phoenixd did not report a node id".to_string())
+    }
+
+    pub async fn get_balance(&self) -> Result<Balance, String> {
+        let body = self.request(Method::GET, "/getbalance", None).await?;
+        serde_json::from_slice(&body).map_err(|e| format!("Invalid phoenixd response: {}", e))
+    }
+
+    /// Whether a payment or a channel open/close is in flight, which a
+    /// phoenixd restart would interrupt
+    pub async fn has_pending_operations(&self) -> Result<bool, String> {
+        let info = self.get_info().await?;
+        let channel_busy = info
+            .get("channels")
+            .and_then(|channels| channels.as_array())
+            .is_some_and(|channels| {
+                channels.iter().any(|channel| {
+                    channel
+                        .get("state")
+                        .and_then(|state| state.as_str())
+                        .is_some_and(|state| {
+                            state.starts_with("WaitFor")
+                                || matches!(state, "ShuttingDown" | "Negotiating" | "Closing")
+                        })
+                })
+            });
+        if channel_busy {
+            return Ok(true);
+        }
+
+        let body = self
+            .request(Method::GET, "/payments/outgoing?all=true&limit=50", None)
+            .await?;
+        let payments: Vec<serde_json::Value> = serde_json::from_slice(&body)
+            .map_err(|e| format!("Invalid phoenixd response: {}", e))?;
+        Ok(payments
+            .iter()
+            .any(|payment| payment.get("completedAt").is_none_or(|at| at.is_null())))
+    }
+
+    /// Create a BOLT11 invoice; no amount means the payer chooses
+    pub async fn create_invoice(
+        &self,
+        amount_sat: Option<u64>,
+        description: &str,
+    ) -> Result<Invoice, String> {
+        let form = {
+            let mut form = form_urlencoded::Serializer::new(String::new());
+            form.append_pair("description", description);
+            if let Some(amount) = amount_sat {
+                form.append_pair("amountSat", &amount.to_string());
+            }
+            form.finish()
+        };
+
+        let body = self
+            .request(Method::POST, "/createinvoice", Some(form))
+            .await?;
+        serde_json::from_slice(&body).map_err(|e| format!("Invalid phoenixd response: {}", e))
+    }
+
+    /// Latest settled payments in one direction (`incoming` or `outgoing`)
+    pub async fn list_payments(
+        &self,
+        direction: &str,
+        limit: usize,
+    ) -> Result<Vec<serde_json::Value>, String> {
+        let path = format!("/payments/{}?all=false&limit={}", direction, limit);
+        let body = self.request(Method::GET, &path, None).await?;
+        serde_json::from_slice(&body).map_err(|e| format!("Invalid phoenixd response: {}", e))
+    }
+
+    /// One page of payments in one direction, settled or not, created
+    /// between `from` and `to` (milliseconds since the epoch)
+    pub async fn payments_page(
+        &self,
+        direction: &str,
+        from: Option<i64>,
+        to: Option<i64>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<serde_json::Value>, String> {
+        let mut path = format!(
+            "/payments/{}?all=true&limit={}&offset={}",
+            direction, limit, offset
+        );
+        if let Some(from) = from {
+            path.push_str(&format!("&from={}", from));
+        }
+        if let Some(to) = to {
+            path.push_str(&format!("&to={}", to));
+        }
+        let body = self.request(Method::GET, &path, None).await?;
+        serde_json::from_slice(&body).map_err(|e| format!("Invalid phoenixd response: {}", e))
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        form: Option<String>,
+    ) -> Result<axum::body::Bytes, String> {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.base_url, path))
+            .header(header::AUTHORIZATION, &self.authorization);
+        if form.is_some() {
+            builder = builder.header(header::CONTENT_TYPE, "application/x-www-form-urlencoded");
+        }
+        let request = builder
+            .body(form.map(Body::from).unwrap_or_else(Body::empty))
+            .map_err(|e| format!("Invalid phoenixd request: {}", e))?;
+
+        let response = self
+            .client
+            .request(request)
+            .await
+            .map_err(|e| format!("phoenixd is not reachable: {}", e))?;
+        let status = response.status();
+        let body = axum::body::to_bytes(Body::new(response.into_body()), MAX_RESPONSE_BYTES)
+            .await
+            .map_err(|e| format!("Failed to read phoenixd response: {}", e))?;
+
+        if status == StatusCode::UNAUTHORIZED {
+            return Err("phoenixd rejected the API password".to_string());
+        }
+        if !status.is_success() {
+            return Err(format!(
+                "phoenixd {} failed ({}): {}",
+                path,
+                status,
+                String::from_utf8_lossy(&body).trim()
+            ));
+        }
+        Ok(body)
+    }
+}
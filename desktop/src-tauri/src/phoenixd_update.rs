@@ -0,0 +1,189 @@
+use crate::i18n::t;
+use crate::operations::Handle;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::{audit, backup, journal, network, notify, resources, scheduler, AppState};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{error, info, warn};
+
+const RELEASES_URL: &str = "https://api.github.com/repos/ACINQ/phoenixd/releases/latest";
+/// Let phoenixd come up before the first check
+const FIRST_CHECK: Duration = Duration::from_secs(60);
+/// How long an upgraded node gets to answer with the new version
+const UPGRADE_TIMEOUT: Duration = Duration::from_secs(90);
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateStatus {
+    /// As phoenixd's `/getinfo` reports it
+    pub current: Option<String>,
+    /// Newest ACINQ release
+    pub latest: Option<String>,
+    pub update_available: bool,
+    pub checked_at: Option<String>,
+}
+
+static STATUS: Mutex<Option<UpdateStatus>> = Mutex::new(None);
+
+pub fn status() -> UpdateStatus {
+    STATUS.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Newer release than the running node, for the tray
+pub fn available() -> Option<String> {
+    let status = status();
+    status.update_available.then_some(status.latest).flatten()
+}
+
+/// `0.3.4` → `[0, 3, 4]`; anything after the numbers (`-SNAPSHOT`) is ignored
+fn parse(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split(['.', '-'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+fn is_newer(latest: &str, current: &str) -> bool {
+    parse(latest) > parse(current)
+}
+
+fn running_version(client: &PhoenixdClient) -> Result<String, String> {
+    let info = tauri::async_runtime::block_on(client.get_info())?;
+    info["version"]
+        .as_str()
+        .map(|version| version.trim_start_matches('v').to_string())
+        .ok_or_else(|| "phoenixd did not report its version".to_string())
+}
+
+fn latest_release() -> Result<String, String> {
+    let release: serde_json::Value = ureq::get(RELEASES_URL)
+        .timeout(Duration::from_secs(15))
+        .call()
+        .map_err(|e| format!("Failed to check for phoenixd releases: {}", e))?
+        .into_json()
+        .map_err(|e| format!("Invalid release response: {}", e))?;
+    release["tag_name"]
+        .as_str()
+        .map(|tag| tag.trim_start_matches('v').to_string())
+        .ok_or_else(|| "The latest release has no tag".to_string())
+}
+
+/// Compare the running node with the latest release, notifying once per
+/// new version
+pub fn check(app: &AppHandle) -> Result<UpdateStatus, String> {
+    let state = app.state::<AppState>();
+    let current = running_version(&PhoenixdClient::new(&state.data_dir))?;
+    let latest = latest_release()?;
+    let update_available = is_newer(&latest, &current);
+    let status = UpdateStatus {
+        current: Some(current.clone()),
+        latest: Some(latest.clone()),
+        update_available,
+        checked_at: Some(chrono::Local::now().to_rfc3339()),
+    };
+    let previous = STATUS.lock().unwrap().replace(status.clone());
+    let announced = previous.is_some_and(|p| p.update_available && p.latest == status.latest);
+    if update_available && !announced {
+        info!("⬆️ phoenixd {} is available (running {})", latest, current);
+        let body = t("notify.phoenixd_update_body")
+            .replace("{latest}", &latest)
+            .replace("{current}", &current);
+        notify::native(app.clone())(t("notify.phoenixd_update_title"), &body);
+        let services = state.statuses.lock().unwrap().clone();
+        crate::refresh_tray_menu(app, &services);
+    }
+    Ok(status)
+}
+
+/// Check for phoenixd releases twice a day
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        std::thread::sleep(FIRST_CHECK);
+        loop {
+            let config = app
+                .state::<AppState>()
+                .config
+                .lock()
+                .unwrap()
+                .phoenixd
+                .clone();
+            if config.update_check && !config.mock && network::is_online() {
+                if let Err(e) = check(&app) {
+                    warn!("{}", e);
+                }
+            }
+            scheduler::sleep(&app, "phoenixd_update");
+        }
+    });
+}
+
+/// Back up, swap in the latest release and make sure the node comes back
+/// on it; otherwise go back to the binary it ran before
+pub fn upgrade(app: &AppHandle, op: &Handle) -> Result<String, String> {
+    let state = app.state::<AppState>();
+    let version = available().ok_or_else(|| "phoenixd is up to date".to_string())?;
+
+    op.progress("Backing up");
+    let backup_config = state.config.lock().unwrap().backup.clone();
+    backup::create(
+        app,
+        &backup::next_archive_path(&state.data_dir, &backup_config),
+    )?;
+    op.check()?;
+
+    op.progress(format!("Downloading phoenixd {}", version));
+    resources::install_phoenixd(&version, &state.data_dir)?;
+    audit::record(&state.data_dir, "phoenixd.upgrade", &version);
+
+    op.progress("Restarting phoenixd");
+    let restart = |app: &AppHandle| {
+        let state = app.state::<AppState>();
+        let mut pm = state.process_manager.lock().unwrap();
+        pm.restart_service("phoenixd")
+    };
+    restart(app)?;
+
+    op.progress("Waiting for phoenixd");
+    let client = PhoenixdClient::new(&state.data_dir);
+    let started = std::time::Instant::now();
+    let came_back = loop {
+        if running_version(&client).is_ok_and(|running| parse(&running) == parse(&version)) {
+            break true;
+        }
+        if started.elapsed() >= UPGRADE_TIMEOUT || op.sleep(Duration::from_secs(2)).is_err() {
+            break false;
+        }
+    };
+
+    if !came_back {
+        error!("phoenixd {} didn't come up, rolling back", version);
+        resources::rollback_phoenixd(&state.data_dir)?;
+        restart(app)?;
+        journal::record(
+            &state.data_dir,
+            "phoenixd.upgrade_failed",
+            Some("phoenixd"),
+            &version,
+        );
+        return Err(format!(
+            "phoenixd {} didn't come up healthy, the previous version is back",
+            version
+        ));
+    }
+
+    journal::record(
+        &state.data_dir,
+        "phoenixd.upgraded",
+        Some("phoenixd"),
+        &version,
+    );
+    info!("⬆️ phoenixd upgraded to {}", version);
+    if let Some(status) = STATUS.lock().unwrap().as_mut() {
+        status.current = Some(version.clone());
+        status.update_available = false;
+    }
+    Ok(version)
+}
@@ -0,0 +1,144 @@
+use crate::config::AppConfig;
+use crate::process_manager::ProcessManager;
+use crate::redact;
+use crate::resources::{self, Component};
+use crate::supervisor;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Print what startup would do and exit, without starting anything
+pub const DRY_RUN_ARG: &str = "--dry-run";
+/// Print the plan as JSON instead of text
+const JSON_ARG: &str = "--json";
+
+/// What the shell would run for one service
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedService {
+    pub name: String,
+    /// Where it listens, if anywhere
+    pub listen: Option<String>,
+    /// Program and arguments; empty for servers running inside the shell
+    pub command: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    /// Passwords and tokens are masked
+    pub env: BTreeMap<String, String>,
+    /// Why it runs in-process, or why it couldn't start
+    pub note: Option<String>,
+}
+
+impl PlannedService {
+    /// A service spawned as a child process, or the reason it can't be
+    pub fn process(name: &str, listen: Option<String>, command: Result<Command, String>) -> Self {
+        match command {
+            Ok(command) => Self {
+                name: name.to_string(),
+                listen,
+                command: std::iter::once(command.get_program())
+                    .chain(command.get_args())
+                    .map(|arg| redact::scrub(&arg.to_string_lossy()))
+                    .collect(),
+                cwd: command.get_current_dir().map(PathBuf::from),
+                env: command
+                    .get_envs()
+                    .filter_map(|(key, value)| {
+                        let key = key.to_string_lossy().into_owned();
+                        let value = value?.to_string_lossy().into_owned();
+                        let value = if redact::is_secret_key(&key) && !value.is_empty() {
+                            redact::REDACTED.to_string()
+                        } else {
+                            redact::scrub(&value)
+                        };
+                        Some((key, value))
+                    })
+                    .collect(),
+                note: None,
+            },
+            Err(e) => Self::in_process(name, listen, format!("would fail: {}", e)),
+        }
+    }
+
+    /// A server the shell runs itself
+    pub fn in_process(name: &str, listen: Option<String>, note: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            listen,
+            command: Vec::new(),
+            cwd: None,
+            env: BTreeMap::new(),
+            note: Some(note.into()),
+        }
+    }
+}
+
+/// Everything startup would resolve for the current config
+#[derive(Debug, Clone, Serialize)]
+pub struct Plan {
+    pub data_dir: PathBuf,
+    pub resource_dir: PathBuf,
+    pub missing: Vec<Component>,
+    pub dashboard_url: String,
+    pub services: Vec<PlannedService>,
+}
+
+impl Plan {
+    pub fn new(pm: &ProcessManager, config: &AppConfig, data_dir: PathBuf) -> Self {
+        Self {
+            missing: resources::missing(pm.resource_dir(), &data_dir, config),
+            resource_dir: pm.resource_dir().to_path_buf(),
+            data_dir,
+            dashboard_url: pm.dashboard_url(),
+            services: pm.plan(),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "Data dir:      {}\nResources:     {}\nDashboard URL: {}\n",
+            self.data_dir.display(),
+            self.resource_dir.display(),
+            self.dashboard_url
+        );
+        if !self.missing.is_empty() {
+            let missing: Vec<&str> = self.missing.iter().map(|c| c.label()).collect();
+            text.push_str(&format!("Missing:       {}\n", missing.join(", ")));
+        }
+        for service in &self.services {
+            text.push_str(&format!("\n[{}]\n", service.name));
+            if let Some(listen) = &service.listen {
+                text.push_str(&format!("  listen: {}\n", listen));
+            }
+            if let Some(note) = &service.note {
+                text.push_str(&format!("  {}\n", note));
+            }
+            if !service.command.is_empty() {
+                text.push_str(&format!("  run:    {}\n", service.command.join(" ")));
+            }
+            if let Some(cwd) = &service.cwd {
+                text.push_str(&format!("  cwd:    {}\n", cwd.display()));
+            }
+            for (key, value) in &service.env {
+                text.push_str(&format!("  env:    {}={}\n", key, value));
+            }
+        }
+        text
+    }
+}
+
+/// `--dry-run [--json] [--data-dir <path>]`: print the plan for the
+/// configured mode to stdout
+pub fn run_cli() -> Result<(), String> {
+    let data_dir = supervisor::data_dir_from_args();
+    let config = AppConfig::load(&data_dir);
+    let pm = ProcessManager::new(supervisor::resource_dir(), data_dir.clone(), config.clone());
+    let plan = Plan::new(&pm, &config, data_dir);
+    if std::env::args().any(|arg| arg == JSON_ARG) {
+        let json = serde_json::to_string_pretty(&plan)
+            .map_err(|e| format!("Failed to serialize the plan: {}", e))?;
+        println!("{}", json);
+    } else {
+        print!("{}", plan.to_text());
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::phoenixd_api::PhoenixdClient;
+use crate::AppState;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+const INTERVAL: Duration = Duration::from_secs(10);
+
+/// Keep the computer awake while phoenixd has a payment or channel
+/// operation in flight, if `general.prevent_sleep` is on
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut inhibit: Option<Inhibit> = None;
+        loop {
+            std::thread::sleep(INTERVAL);
+            let state = app.state::<AppState>();
+            let enabled = state.config.lock().unwrap().general.prevent_sleep;
+            let busy = enabled
+                && tauri::async_runtime::block_on(
+                    PhoenixdClient::new(&state.data_dir).has_pending_operations(),
+                )
+                .unwrap_or(false);
+
+            match (busy, inhibit.is_some()) {
+                (true, false) => match Inhibit::acquire() {
+                    Ok(acquired) => {
+                        info!("☕ Preventing sleep while payments settle");
+                        inhibit = Some(acquired);
+                    }
+                    Err(e) => warn!("Could not prevent sleep: {}", e),
+                },
+                (false, true) => {
+                    info!("😴 Node idle, allowing sleep again");
+                    inhibit = None;
+                }
+                _ => {}
+            }
+        }
+    });
+}
+
+/// Sleep stays blocked while this is alive
+#[cfg(not(windows))]
+struct Inhibit(std::process::Child);
+
+#[cfg(not(windows))]
+impl Inhibit {
+    fn acquire() -> Result<Self, String> {
+        #[cfg(target_os = "macos")]
+        let mut command = {
+            // -i: no idle sleep; -w: ends with us even if we're killed
+            let mut command = std::process::Command::new("caffeinate");
+            command
+                .arg("-i")
+                .arg("-w")
+                .arg(std::process::id().to_string());
+            command
+        };
+        #[cfg(not(target_os = "macos"))]
+        let mut command = {
+            // `cat` holds the lock until we close its stdin
+            let mut command = std::process::Command::new("systemd-inhibit");
+            command.args([
+                "--what=sleep:idle",
+                "--who=Phoenixd Dashboard",
+                "--why=Lightning payments are settling",
+                "--mode=block",
+                "cat",
+            ]);
+            command
+        };
+        command
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .map(Inhibit)
+            .map_err(|e| format!("Failed to start {:?}: {}", command.get_program(), e))
+    }
+}
+
+#[cfg(not(windows))]
+impl Drop for Inhibit {
+    fn drop(&mut self) {
+        drop(self.0.stdin.take());
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// The execution state belongs to a thread, so a dedicated one holds it
+/// until the sender is dropped
+#[cfg(windows)]
+struct Inhibit {
+    _release: std::sync::mpsc::Sender<()>,
+}
+
+#[cfg(windows)]
+impl Inhibit {
+    fn acquire() -> Result<Self, String> {
+        use windows_sys::Win32::System::Power::{
+            SetThreadExecutionState, ES_CONTINUOUS, ES_SYSTEM_REQUIRED,
+        };
+
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let (acquired, result) = std::sync::mpsc::channel::<bool>();
+        std::thread::spawn(move || {
+            let previous = unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED) };
+            let _ = acquired.send(previous != 0);
+            // Returns once the sender is dropped
+            let _ = released.recv();
+            unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+        });
+        match result.recv() {
+            Ok(true) => Ok(Inhibit { _release: release }),
+            _ => Err("SetThreadExecutionState failed".to_string()),
+        }
+    }
+}
@@ -1,56 +1,217 @@
+use crate::access_guard::{AccessGuard, BlockedClient};
+use crate::cloudflare::{self, CloudflareManager};
+use crate::config::{self, AppConfig, ExtraService, NodeConfig};
+use crate::database;
+use crate::health_checks;
+use crate::i18n::t;
+use crate::journal;
+use crate::lan;
+use crate::logs;
+use crate::mdns::MdnsAdvertiser;
+use crate::mock_phoenixd::MockPhoenixd;
+use crate::native_backend::{self, NativeBackend, Upstream};
+use crate::node_events::{self, NodeEventSink};
+use crate::notify::Notifier;
+use crate::nwc;
+use crate::phoenixd_api;
+use crate::plan::PlannedService;
+use crate::profile::{self, SharedProfile, StartupProfile};
+use crate::proxy::{self, Credentials, ReverseProxy};
+use crate::resources;
+use crate::static_frontend::{self, StaticFrontend};
+use crate::tailscale;
+use crate::tls::{TlsFiles, TlsTerminator};
+use crate::token;
+use crate::tor::{self, TorManager};
+use crate::tor_health;
+use serde::Serialize;
 use serde_json::json;
-use std::path::PathBuf;
-use std::process::{Child, Command, Stdio};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tracing::{info, info_span, warn};
+
+/// Every process the manager can run, in start order
+pub const SERVICES: [&str; 6] = [
+    "phoenixd",
+    "backend",
+    "frontend",
+    "tor",
+    "cloudflared",
+    "nwc",
+];
+
+/// PID of a phoenixd left running when the app quit, for the next launch
+const KEPT_PID_FILE: &str = "phoenixd.pid";
+/// Present while the node is paused, so it stays paused across restarts
+const PAUSED_FILE: &str = "node.paused";
+/// How long phoenixd gets to shut down on its own when paused
+const PAUSE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Called with a translated description of each step while `start_all` runs
+pub type Progress = Arc<dyn Fn(&str) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceState {
+    Running,
+    /// Process is alive but its port doesn't accept connections
+    Unhealthy,
+    Stopped,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServiceStatus {
+    pub name: &'static str,
+    pub state: ServiceState,
+}
+
+/// One of the phoenixd instances, the main one first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NodeStatus {
+    pub name: String,
+    pub chain: String,
+    pub port: u16,
+    pub state: ServiceState,
+    /// Shown in the dashboard
+    pub active: bool,
+}
+
+/// Last known service states, refreshed by every poll
+pub type StatusCache = Arc<Mutex<Vec<ServiceStatus>>>;
 
 pub struct ProcessManager {
     resource_dir: PathBuf,
     data_dir: PathBuf,
+    config: AppConfig,
     phoenixd: Option<Child>,
+    /// phoenixd left running by an earlier session and re-attached to
+    adopted_phoenixd: Option<u32>,
+    /// phoenixd's output goes to its log file rather than a pipe, so it can
+    /// outlive the app
+    phoenixd_detachable: bool,
+    /// Stands in for phoenixd in mock mode
+    mock_phoenixd: Option<MockPhoenixd>,
     backend: Option<Child>,
+    /// Serves the API instead of the Node backend when `backend.native` is on
+    native_backend: Option<NativeBackend>,
     frontend: Option<Child>,
+    /// Serves the exported frontend instead of Next.js when `frontend.serve_static` is on
+    static_frontend: Option<StaticFrontend>,
+    tor: Option<Child>,
+    cloudflared: Option<Child>,
+    /// Nostr Wallet Connect bridge
+    nwc: Option<Child>,
+    /// Extra phoenixd instances by name
+    extra_nodes: HashMap<String, Child>,
+    /// Slots of the user-defined services, created on first use
+    extra_services: HashMap<String, Option<Child>>,
+    quick_tunnel_url: Arc<Mutex<Option<String>>>,
+    pairing_token: Option<String>,
+    /// Bearer token the backend requires, new for every start
+    api_token: String,
+    /// `ALLOWED_ORIGINS` the running Node backend got
+    backend_origins: Option<String>,
+    mdns: Option<MdnsAdvertiser>,
+    tls: Option<TlsTerminator>,
+    proxy: Option<ReverseProxy>,
+    tailscale_url: Option<String>,
+    access_guard: Arc<AccessGuard>,
+    notifier: Option<Notifier>,
+    node_events: Option<NodeEventSink>,
+    progress: Option<Progress>,
+    /// Leave phoenixd to the onboarding wizard (it would create a new seed)
+    defer_phoenixd: bool,
+    /// The background service owns the services; only observe them
+    attached: bool,
+    profile: SharedProfile,
+    /// Restarts per service since the app started
+    restarts: HashMap<String, u32>,
+    status_cache: StatusCache,
 }
 
 impl ProcessManager {
-    pub fn new(resource_dir: PathBuf, data_dir: PathBuf) -> Self {
+    pub fn new(resource_dir: PathBuf, data_dir: PathBuf, config: AppConfig) -> Self {
         // In development, resources might be in a different location
+        let discovery_started = Instant::now();
         let actual_resource_dir = Self::find_resource_dir(&resource_dir);
-        
+        let profile = StartupProfile::new(discovery_started.elapsed());
+        let access_guard = Arc::new(AccessGuard::new(
+            config.proxy.rate_limit_per_minute,
+            config.proxy.max_failed_auth,
+            config.proxy.lockout_minutes,
+        ));
+
         Self {
             resource_dir: actual_resource_dir,
             data_dir,
+            config,
             phoenixd: None,
+            adopted_phoenixd: None,
+            phoenixd_detachable: false,
+            mock_phoenixd: None,
             backend: None,
+            native_backend: None,
             frontend: None,
+            static_frontend: None,
+            tor: None,
+            cloudflared: None,
+            nwc: None,
+            extra_nodes: HashMap::new(),
+            extra_services: HashMap::new(),
+            quick_tunnel_url: Arc::new(Mutex::new(None)),
+            pairing_token: None,
+            api_token: String::new(),
+            backend_origins: None,
+            mdns: None,
+            tls: None,
+            proxy: None,
+            tailscale_url: None,
+            access_guard,
+            notifier: None,
+            node_events: None,
+            progress: None,
+            defer_phoenixd: false,
+            attached: false,
+            profile,
+            restarts: HashMap::new(),
+            status_cache: Arc::new(Mutex::new(Vec::new())),
         }
     }
-    
+
     fn find_resource_dir(default_dir: &PathBuf) -> PathBuf {
         // Helper to check if a directory has all required resources
         fn has_all_resources(dir: &PathBuf) -> bool {
-            let has_phoenixd = dir.join("binaries").join("phoenixd").exists() 
+            let has_phoenixd = dir.join("binaries").join("phoenixd").exists()
                 || dir.join("binaries").join("phoenixd.exe").exists();
             let has_backend = dir.join("backend").join("dist").join("index.js").exists();
             let has_frontend = dir.join("frontend").join("server.js").exists();
-            
-            println!("Checking resources at {:?}: phoenixd={}, backend={}, frontend={}", 
-                dir, has_phoenixd, has_backend, has_frontend);
-            
+
+            info!(
+                "Checking resources at {:?}: phoenixd={}, backend={}, frontend={}",
+                dir, has_phoenixd, has_backend, has_frontend
+            );
+
             has_phoenixd && has_backend && has_frontend
         }
-        
+
         // Check if all resources exist in the default location
         if has_all_resources(default_dir) {
-            println!("Using bundled resources at: {:?}", default_dir);
+            info!("Using bundled resources at: {:?}", default_dir);
             return default_dir.clone();
         }
-        
+
         // Check for _up_/resources (Tauri bundles relative paths here)
         let up_resources = default_dir.join("_up_").join("resources");
         if has_all_resources(&up_resources) {
-            println!("Using bundled resources at: {:?}", up_resources);
+            info!("Using bundled resources at: {:?}", up_resources);
             return up_resources;
         }
-        
+
         // In development, check desktop/resources
         // Path: target/debug -> target -> src-tauri -> desktop/resources
         let dev_resources = default_dir
@@ -58,251 +219,1904 @@ impl ProcessManager {
             .and_then(|p| p.parent()) // src-tauri
             .and_then(|p| p.parent()) // desktop
             .map(|p| p.join("resources"));
-            
+
         if let Some(dev_path) = dev_resources {
             if has_all_resources(&dev_path) {
-                println!("Using development resources at: {:?}", dev_path);
+                info!("Using development resources at: {:?}", dev_path);
                 return dev_path;
             }
         }
-        
+
         // Fall back to default (will error later if resources not found)
-        println!("Warning: Could not find complete resources, using default: {:?}", default_dir);
+        warn!(
+            "Could not find complete resources, using default: {:?}",
+            default_dir
+        );
         default_dir.clone()
     }
 
+    pub fn resource_dir(&self) -> &Path {
+        &self.resource_dir
+    }
+
+    pub fn set_config(&mut self, config: AppConfig) {
+        self.access_guard.set_limits(
+            config.proxy.rate_limit_per_minute,
+            config.proxy.max_failed_auth,
+            config.proxy.lockout_minutes,
+        );
+        self.config = config;
+    }
+
+    pub fn set_notifier(&mut self, notifier: Notifier) {
+        self.notifier = Some(notifier);
+    }
+
+    pub fn set_node_events(&mut self, sink: NodeEventSink) {
+        self.node_events = Some(sink);
+    }
+
+    pub fn set_progress(&mut self, progress: Option<Progress>) {
+        self.progress = progress;
+    }
+
+    pub fn set_defer_phoenixd(&mut self, defer: bool) {
+        self.defer_phoenixd = defer;
+    }
+
+    pub fn set_attached(&mut self, attached: bool) {
+        self.attached = attached;
+    }
+
+    fn ensure_owned(&self) -> Result<(), String> {
+        if self.attached {
+            Err("Services are managed by the background service".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn report(&self, key: &str) {
+        if let Some(progress) = &self.progress {
+            progress(t(key));
+        }
+    }
+
     pub fn start_all(&mut self) -> Result<(), String> {
-        println!("Starting all services...");
-        
-        // Start phoenixd first
-        self.start_phoenixd()?;
-        
-        // Wait a bit for phoenixd to initialize
-        std::thread::sleep(std::time::Duration::from_secs(2));
-        
-        // Start backend
-        self.start_backend()?;
-        
-        // Wait for backend to be ready
-        std::thread::sleep(std::time::Duration::from_secs(1));
-        
-        // Start frontend
-        self.start_frontend()?;
-        
-        println!("All services started!");
+        let _span = info_span!("start_all").entered();
+        info!("Starting all services...");
+
+        self.pairing_token = if self.config.lan.enabled {
+            Some(lan::generate_pairing_token()?)
+        } else {
+            None
+        };
+        self.api_token = token::issue_api_token(&self.data_dir)?;
+
+        if let Err(e) = self.config.bind.validate() {
+            warn!("{}, using the default", e);
+        }
+        if let Err(e) = self.config.nodes.validate() {
+            warn!("{}, not starting the extra nodes", e);
+        }
+
+        self.profile.lock().unwrap().begin();
+        let ready_addrs: Vec<(&'static str, SocketAddr)> = ["phoenixd", "backend", "frontend"]
+            .into_iter()
+            .filter(|name| *name != "frontend" || self.config.frontend.enabled)
+            .filter_map(|name| Some((name, self.config.bind.service_addr(name)?)))
+            .collect();
+        profile::watch_ready(&self.profile, &ready_addrs);
+
+        if self.node_paused() {
+            info!("⏸️ The node is paused, not starting phoenixd");
+        } else if self.defer_phoenixd && !self.config.phoenixd.mock {
+            info!("⏸️ phoenixd waits for the wallet step of onboarding");
+        }
+        if !self.config.frontend.enabled {
+            info!("🔌 API-only mode, not starting the frontend");
+        }
+
+        // Up before the frontend, which is handed https URLs while it runs
+        if self.config.https.enabled {
+            if let Err(e) = self.timed("https start", Self::start_https) {
+                warn!("{}", e);
+            }
+        }
+
+        // phoenixd's connections go through tor's SOCKS port from the start
+        if self.node_over_tor() {
+            if let Err(e) = self.timed("tor start", Self::start_tor) {
+                warn!("{}", e);
+            }
+            let socks = SocketAddr::from(([127, 0, 0, 1], tor::SOCKS_PORT));
+            let started = Instant::now();
+            while !port_open(socks) && started.elapsed() < READY_GATE {
+                std::thread::sleep(Duration::from_millis(250));
+            }
+        }
+
+        // Start every service whose dependencies accept connections, then
+        // wait for the next gate to open
+        let mut pending: Vec<(&str, &[&str])> = STARTUP_GRAPH
+            .into_iter()
+            .filter(|(name, _)| self.starts_with_all(name))
+            .collect();
+        let enabled: Vec<&str> = pending.iter().map(|(name, _)| *name).collect();
+        let bind = self.config.bind.clone();
+        let ready =
+            |name: &&str| !enabled.contains(name) || bind.service_addr(name).is_some_and(port_open);
+        let mut remote_started = false;
+        let mut gate_opened = Instant::now();
+        loop {
+            let (wave, waiting): (Vec<_>, Vec<_>) = pending.into_iter().partition(|(_, deps)| {
+                deps.iter().all(ready) || gate_opened.elapsed() > READY_GATE
+            });
+            pending = waiting;
+            for (name, _) in wave {
+                self.report(&format!("startup.{}", name));
+                match name {
+                    "phoenixd" => self.timed("phoenixd start", Self::start_phoenixd),
+                    "backend" => self.timed("backend start", Self::start_backend),
+                    _ => self.timed("frontend start", Self::start_frontend),
+                }?;
+                gate_opened = Instant::now();
+            }
+
+            // Remote access doesn't wait for the core services
+            if !remote_started {
+                self.start_remote_access();
+                remote_started = true;
+            }
+            if pending.is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(250));
+        }
+
+        if !self.config.phoenixd.mock && self.config.nodes.validate().is_ok() {
+            for node in self.config.nodes.extra.clone() {
+                if let Err(e) = self.start_extra_node(&node) {
+                    warn!("{}", e);
+                }
+            }
+        }
+
+        if self.config.nwc.enabled && !self.node_paused() {
+            if let Err(e) = self.timed("nwc start", Self::start_nwc) {
+                warn!("{}", e);
+            }
+        }
+
+        match self.config.services.validate() {
+            Ok(()) => {
+                for service in self.config.services.extra.clone() {
+                    if !service.autostart {
+                        continue;
+                    }
+                    if let Err(e) = self.start_extra_service(&service) {
+                        warn!("{}", e);
+                    }
+                }
+            }
+            Err(e) => warn!("{}, not starting the extra services", e),
+        }
+
+        journal::record(&self.data_dir, "services.started", None, "all services");
+        info!("All services started!");
         Ok(())
     }
 
-    fn start_phoenixd(&mut self) -> Result<(), String> {
-        let phoenixd_binary = self.get_phoenixd_binary_path();
-        
-        if !phoenixd_binary.exists() {
-            return Err(format!(
-                "Phoenixd binary not found at: {:?}. Please ensure phoenixd is installed.",
-                phoenixd_binary
+    /// What `start_all` would run for the current config, in startup order,
+    /// without starting or writing anything
+    pub fn plan(&self) -> Vec<PlannedService> {
+        let mut plan = Vec::new();
+        let tor_socks = format!("127.0.0.1:{}", tor::SOCKS_PORT);
+        if self.runs_tor() {
+            let torrc = TorManager::new(&self.data_dir).torrc_path();
+            let command = self
+                .find_tor_binary()
+                .map(|binary| Self::tor_command(&binary, &torrc));
+            plan.push(PlannedService::process("tor", Some(tor_socks), command));
+        }
+
+        let listen = |name: &str| {
+            self.config
+                .bind
+                .service_addr(name)
+                .map(|addr| addr.to_string())
+        };
+        if self.starts_with_all("phoenixd") {
+            plan.push(if self.config.phoenixd.mock {
+                PlannedService::in_process(
+                    "phoenixd",
+                    listen("phoenixd"),
+                    "mock node inside the shell",
+                )
+            } else {
+                let command = self.phoenixd_command(&self.data_dir, &self.config.phoenixd.chain);
+                PlannedService::process("phoenixd", listen("phoenixd"), command)
+            });
+        } else if self.node_paused() {
+            plan.push(PlannedService::in_process("phoenixd", None, "paused"));
+        } else {
+            plan.push(PlannedService::in_process(
+                "phoenixd",
+                None,
+                "waits for onboarding",
+            ));
+        }
+        if !self.config.phoenixd.mock && self.config.nodes.validate().is_ok() {
+            for node in &self.config.nodes.extra {
+                plan.push(PlannedService::process(
+                    &format!("phoenixd-{}", node.name),
+                    Some(self.config.bind.node_addr(node).to_string()),
+                    self.extra_node_command(node),
+                ));
+            }
+        }
+
+        let backend_listen = Some(format!("{}:4000", self.backend_bind()));
+        let db_path = self.backend_node().0.join(database::DB_FILE);
+        plan.push(
+            if self.config.backend.native && !native_backend::password_set(&db_path) {
+                PlannedService::in_process(
+                    "backend",
+                    backend_listen,
+                    "native backend inside the shell",
+                )
+            } else {
+                PlannedService::process("backend", backend_listen, self.backend_command())
+            },
+        );
+
+        if self.config.frontend.enabled {
+            let frontend_listen = Some(format!("{}:3000", self.frontend_bind()));
+            plan.push(
+                if self.config.frontend.serve_static
+                    && static_frontend::available(&self.resource_dir)
+                {
+                    PlannedService::in_process(
+                        "frontend",
+                        frontend_listen,
+                        "static build served by the shell",
+                    )
+                } else {
+                    PlannedService::process("frontend", frontend_listen, self.frontend_command())
+                },
+            );
+        }
+
+        if self.config.nwc.enabled {
+            plan.push(PlannedService::process(
+                "nwc",
+                None,
+                nwc::command(&self.resource_dir, &self.data_dir),
+            ));
+        }
+
+        for service in self
+            .config
+            .services
+            .extra
+            .iter()
+            .filter(|service| service.autostart)
+        {
+            let command = Ok(self.extra_service_command(service));
+            plan.push(PlannedService::process(&service.name, None, command));
+        }
+
+        let public_ip = if self.config.lan.enabled {
+            "0.0.0.0"
+        } else {
+            "127.0.0.1"
+        };
+        if self.config.proxy.enabled {
+            plan.push(PlannedService::in_process(
+                "proxy",
+                Some(self.exposed_addr().to_string()),
+                "authenticating reverse proxy inside the shell",
+            ));
+        }
+        if self.config.cloudflare.enabled {
+            let manager = CloudflareManager::new(self.resource_dir.clone(), &self.data_dir);
+            let command = if self.config.cloudflare.quick {
+                manager.quick_tunnel_command(self.tunnel_addr())
+            } else {
+                manager.tunnel_command(&self.config.cloudflare.tunnel_name)
+            };
+            plan.push(PlannedService::process("cloudflared", None, command));
+        }
+        if self.config.tailscale.enabled {
+            plan.push(PlannedService::in_process(
+                "tailscale",
+                None,
+                format!("tailscale serve for {}", self.exposed_addr()),
+            ));
+        }
+        if self.config.https.enabled {
+            plan.push(PlannedService::in_process(
+                "https",
+                Some(format!("{}:{}", public_ip, self.config.https.port)),
+                format!(
+                    "TLS terminator inside the shell, API on port {}",
+                    self.config.https.api_port
+                ),
             ));
         }
+        plan
+    }
+
+    /// Whether `start_all` brings up this core service
+    fn starts_with_all(&self, name: &str) -> bool {
+        match name {
+            "phoenixd" => {
+                !self.node_paused() && (!self.defer_phoenixd || self.config.phoenixd.mock)
+            }
+            "frontend" => self.config.frontend.enabled,
+            _ => true,
+        }
+    }
+
+    fn start_remote_access(&mut self) {
+        // Remote exposures below point at the proxy, so it has to be up first
+        self.report("startup.remote_access");
+        if self.config.proxy.enabled {
+            if let Err(e) = self.timed("proxy start", Self::start_proxy) {
+                warn!("{}", e);
+            }
+        }
+
+        // Tor is optional, a failure here shouldn't take the dashboard down
+        if self.config.tor.enabled && self.tor.is_none() {
+            if let Err(e) = self.timed("tor start", Self::start_tor) {
+                warn!("{}", e);
+            }
+        }
+
+        if self.config.cloudflare.enabled {
+            if let Err(e) = self.timed("cloudflared start", Self::start_cloudflared) {
+                warn!("{}", e);
+            }
+        }
+
+        if self.config.tailscale.enabled {
+            if let Err(e) = self.timed("tailscale start", Self::start_tailscale) {
+                warn!("{}", e);
+            }
+        }
 
+        self.update_mdns();
+    }
+
+    fn start_phoenixd(&mut self) -> Result<(), String> {
+        let _span = info_span!("start", service = "phoenixd").entered();
+        if self.config.phoenixd.mock {
+            self.mock_phoenixd = Some(MockPhoenixd::start(self.config.bind.phoenixd_ip(), 9740)?);
+            return Ok(());
+        }
         // Phoenixd stores data in ~/.phoenix by default
         // We set HOME to our data_dir so it uses data_dir/.phoenix
         let phoenixd_home = self.data_dir.clone();
-        std::fs::create_dir_all(&phoenixd_home)
-            .map_err(|e| format!("Failed to create phoenixd home dir: {}", e))?;
+        tor::set_node_private(false);
+        let node_events = self.node_events.clone();
+        let on_line = move |line: &str| {
+            if let (Some(sink), Some(event)) = (&node_events, node_events::parse(line)) {
+                sink(event);
+            }
+        };
 
-        println!("Starting phoenixd from: {:?}", phoenixd_binary);
-        println!("Phoenixd HOME: {:?}", phoenixd_home);
+        if let Some(pid) = self.kept_phoenixd() {
+            info!(
+                "🔗 Re-attaching to phoenixd left running by the last session (pid {})",
+                pid
+            );
+            journal::record(
+                &self.data_dir,
+                "service.reattached",
+                Some("phoenixd"),
+                format!("pid {}", pid),
+            );
+            logs::follow(&self.data_dir, "phoenixd", move || pid_alive(pid), on_line);
+            self.adopted_phoenixd = Some(pid);
+            self.phoenixd_detachable = true;
+            return Ok(());
+        }
 
-        let child = Command::new(&phoenixd_binary)
-            .arg("--agree-to-terms-of-service")
-            .arg("--http-bind-ip")
-            .arg("127.0.0.1")
-            .env("HOME", &phoenixd_home)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+        self.prepare_phoenixd(&phoenixd_home)?;
+        let mut command = self.phoenixd_command(&phoenixd_home, &self.config.phoenixd.chain)?;
+        let detachable = self.config.general.on_quit != "stop";
+        if detachable {
+            let (stdout, stderr) = logs::detached_output(&self.data_dir, "phoenixd")?;
+            command.stdout(stdout).stderr(stderr);
+        }
+        if self.node_over_tor() {
+            tor::spawn_circuit_check();
+        }
+        let mut child = command
             .spawn()
             .map_err(|e| format!("Failed to start phoenixd: {}", e))?;
 
+        if detachable {
+            let pid = child.id();
+            logs::follow(&self.data_dir, "phoenixd", move || pid_alive(pid), on_line);
+        } else {
+            logs::capture_with(&self.data_dir, "phoenixd", &mut child, on_line);
+        }
         self.phoenixd = Some(child);
-        println!("Phoenixd started successfully");
+        self.phoenixd_detachable = detachable;
+        info!("Phoenixd started successfully");
         Ok(())
     }
 
-    fn start_backend(&mut self) -> Result<(), String> {
-        let backend_dir = self.resource_dir.join("backend");
-        let node_path = self.find_node_binary()?;
-        
-        let backend_entry = backend_dir.join("dist").join("index.js");
-        
-        if !backend_entry.exists() {
-            return Err(format!(
-                "Backend entry point not found at: {:?}",
-                backend_entry
-            ));
+    /// The phoenixd an earlier session left running, if it still serves its port
+    fn kept_phoenixd(&self) -> Option<u32> {
+        let path = self.data_dir.join(KEPT_PID_FILE);
+        let pid = std::fs::read_to_string(&path).ok()?.trim().parse().ok();
+        let serving = self
+            .config
+            .bind
+            .service_addr("phoenixd")
+            .is_some_and(port_open);
+        match pid.filter(|pid| pid_alive(*pid) && serving) {
+            Some(pid) => Some(pid),
+            None => {
+                let _ = std::fs::remove_file(&path);
+                None
+            }
         }
+    }
 
-        // Setup environment for backend
-        // Phoenixd stores data in $HOME/.phoenix
-        let phoenix_conf = self.data_dir.join(".phoenix").join("phoenix.conf");
-        
-        // Read phoenixd password from config if available
-        let phoenixd_password = self.read_phoenixd_password(&phoenix_conf);
-        
-        // SQLite database path
-        let db_path = self.data_dir.join("dashboard.db");
-        let database_url = format!("file:{}", db_path.display());
-
-        println!("Starting backend from: {:?}", backend_entry);
-        println!("Database URL: {}", database_url);
-        
-        // Copy template database if it doesn't exist
-        if !db_path.exists() {
-            let template_db = self.resource_dir.join("template.db");
-            if template_db.exists() {
-                println!("Initializing database from template...");
-                if let Err(e) = std::fs::copy(&template_db, &db_path) {
-                    eprintln!("Warning: Could not copy template database: {}", e);
-                } else {
-                    println!("Database initialized successfully");
-                }
-            } else {
-                eprintln!("Warning: Template database not found at {:?}", template_db);
-            }
+    /// Let phoenixd outlive the app: forget its process without stopping it
+    /// and note its PID for the next launch to re-attach. False when it isn't
+    /// running or its output still goes through a pipe that quitting breaks.
+    pub fn keep_phoenixd(&mut self) -> bool {
+        if !self.phoenixd_detachable || !self.is_service_running("phoenixd") {
+            return false;
         }
+        let Some(pid) = self
+            .phoenixd
+            .take()
+            .map(|child| child.id())
+            .or(self.adopted_phoenixd.take())
+        else {
+            return false;
+        };
+        if let Err(e) = std::fs::write(self.data_dir.join(KEPT_PID_FILE), pid.to_string()) {
+            warn!("Failed to write {}: {}", KEPT_PID_FILE, e);
+        }
+        journal::record(
+            &self.data_dir,
+            "service.kept_running",
+            Some("phoenixd"),
+            format!("pid {}", pid),
+        );
+        info!("⚡ Leaving phoenixd running (pid {})", pid);
+        true
+    }
 
-        let child = Command::new(&node_path)
-            .arg(&backend_entry)
-            .current_dir(&backend_dir)
-            .env("NODE_ENV", "production")
-            .env("PORT", "4000")
-            .env("DESKTOP_MODE", "true")
-            .env("DATABASE_URL", &database_url)
-            .env("PHOENIXD_URL", "http://127.0.0.1:9740")
-            .env("PHOENIXD_PASSWORD", &phoenixd_password)
-            .env("FRONTEND_URL", "http://localhost:3000")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to start backend: {}", e))?;
+    fn stop_adopted_phoenixd(&mut self) {
+        if let Some(pid) = self.adopted_phoenixd.take() {
+            info!("Stopping phoenixd (pid {})...", pid);
+            kill_pid(pid);
+            let _ = std::fs::remove_file(self.data_dir.join(KEPT_PID_FILE));
+        }
+    }
 
-        self.backend = Some(child);
-        println!("Backend started successfully");
+    /// Create phoenixd's HOME and the torsocks config its command refers to
+    fn prepare_phoenixd(&self, home: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(home)
+            .map_err(|e| format!("Failed to create phoenixd home dir: {}", e))?;
+        if self.node_over_tor() {
+            TorManager::new(&self.data_dir).write_torsocks_conf()?;
+            info!("🧅 Routing phoenixd's traffic through Tor");
+        }
         Ok(())
     }
 
-    fn start_frontend(&mut self) -> Result<(), String> {
-        let frontend_dir = self.resource_dir.join("frontend");
-        let node_path = self.find_node_binary()?;
-        
-        // For standalone Next.js build
-        let server_js = frontend_dir.join("server.js");
-        
-        if !server_js.exists() {
+    /// phoenixd with `HOME` and `--chain` set, through torsocks when the
+    /// node's traffic goes over Tor
+    fn phoenixd_command(&self, home: &Path, chain: &str) -> Result<Command, String> {
+        let phoenixd_binary = self.get_phoenixd_binary_path();
+
+        if !phoenixd_binary.exists() {
             return Err(format!(
-                "Frontend server not found at: {:?}. Make sure to build with 'next build' (standalone output).",
-                server_js
+                "Phoenixd binary not found at: {:?}. Please ensure phoenixd is installed.",
+                phoenixd_binary
             ));
         }
 
-        println!("Starting frontend from: {:?}", server_js);
+        info!("Starting phoenixd from: {:?}", phoenixd_binary);
+        info!("Phoenixd HOME: {:?}", home);
 
-        let child = Command::new(&node_path)
-            .arg(&server_js)
-            .current_dir(&frontend_dir)
-            .env("NODE_ENV", "production")
-            .env("PORT", "3000")
-            .env("HOSTNAME", "localhost")
-            .env("NEXT_PUBLIC_API_URL", "http://localhost:4000")
-            .env("NEXT_PUBLIC_WS_URL", "ws://localhost:4000")
+        let mut command = if self.node_over_tor() {
+            let torsocks = self.find_torsocks_binary()?;
+            let conf = TorManager::new(&self.data_dir).torsocks_conf_path();
+            let mut command = Command::new(torsocks);
+            command
+                .env("TORSOCKS_CONF_FILE", conf)
+                .arg(&phoenixd_binary);
+            command
+        } else {
+            Command::new(&phoenixd_binary)
+        };
+        if chain != "mainnet" {
+            command.arg("--chain").arg(chain);
+        }
+        command
+            .arg("--agree-to-terms-of-service")
+            .arg("--http-bind-ip")
+            .arg(self.config.bind.phoenixd_ip().to_string())
+            .env("HOME", home)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .stderr(Stdio::piped());
+        Ok(command)
+    }
+
+    fn start_extra_node(&mut self, node: &NodeConfig) -> Result<(), String> {
+        let _span = info_span!("start", service = "phoenixd", node = %node.name).entered();
+        if self.extra_node_running(&node.name) {
+            return Ok(());
+        }
+        let home = node.home(&self.data_dir);
+        self.prepare_phoenixd(&home)?;
+        let mut child = self
+            .extra_node_command(node)?
             .spawn()
-            .map_err(|e| format!("Failed to start frontend: {}", e))?;
+            .map_err(|e| format!("Failed to start node {}: {}", node.name, e))?;
+        logs::capture(
+            &self.data_dir,
+            &format!("phoenixd-{}", node.name),
+            &mut child,
+        );
+        self.extra_nodes.insert(node.name.clone(), child);
+        info!("⚡ Node {} started on port {}", node.name, node.port);
+        Ok(())
+    }
 
-        self.frontend = Some(child);
-        println!("Frontend started successfully");
+    fn extra_node_command(&self, node: &NodeConfig) -> Result<Command, String> {
+        let mut command = self.phoenixd_command(&node.home(&self.data_dir), &node.chain)?;
+        command.arg("--http-bind-port").arg(node.port.to_string());
+        Ok(command)
+    }
+
+    fn extra_service_command(&self, service: &ExtraService) -> Command {
+        let mut command = Command::new(&service.command);
+        command
+            .args(&service.args)
+            .envs(&service.env)
+            .current_dir(if service.cwd.is_empty() {
+                self.data_dir.clone()
+            } else {
+                PathBuf::from(&service.cwd)
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        command
+    }
+
+    fn start_extra_service(&mut self, service: &ExtraService) -> Result<(), String> {
+        let _span = info_span!("start", service = %service.name).entered();
+        let mut child = self
+            .extra_service_command(service)
+            .spawn()
+            .map_err(|e| format!("Failed to start {}: {}", service.name, e))?;
+        logs::capture(&self.data_dir, &service.name, &mut child);
+        self.extra_services
+            .insert(service.name.clone(), Some(child));
+        info!("🧩 {} started", service.name);
         Ok(())
     }
 
-    fn get_phoenixd_binary_path(&self) -> PathBuf {
-        let binary_name = if cfg!(target_os = "windows") {
-            "phoenixd.exe"
-        } else {
-            "phoenixd"
-        };
-        
-        // First check in resources/binaries
-        let bundled = self.resource_dir.join("binaries").join(binary_name);
-        if bundled.exists() {
-            return bundled;
+    fn stop_extra_services(&mut self) {
+        for (name, child) in self.extra_services.iter_mut() {
+            if let Some(mut child) = child.take() {
+                info!("Stopping {}...", name);
+                let _ = child.kill();
+                let _ = child.wait();
+            }
         }
-        
-        // Then check system PATH
-        if let Ok(path) = which::which("phoenixd") {
-            return path;
+    }
+
+    fn extra_node_running(&mut self, name: &str) -> bool {
+        self.extra_nodes
+            .get_mut(name)
+            .is_some_and(|child| matches!(child.try_wait(), Ok(None)))
+    }
+
+    fn stop_extra_nodes(&mut self) {
+        for (name, mut child) in self.extra_nodes.drain() {
+            info!("Stopping node {}...", name);
+            let _ = child.kill();
+            let _ = child.wait();
         }
-        
-        // Default to bundled path (will error later if not found)
-        bundled
     }
 
-    fn find_node_binary(&self) -> Result<PathBuf, String> {
-        // First check in resources (bundled Node.js)
-        let bundled_node = if cfg!(target_os = "windows") {
-            self.resource_dir.join("node").join("node.exe")
+    /// Every phoenixd instance, the main one first
+    pub fn node_statuses(&mut self) -> Vec<NodeStatus> {
+        let active = self.config.nodes.active_name().to_string();
+        let main_state = if !self.is_service_running("phoenixd") {
+            ServiceState::Stopped
+        } else if !port_open(self.config.bind.phoenixd_addr()) {
+            ServiceState::Unhealthy
         } else {
-            self.resource_dir.join("node").join("bin").join("node")
+            ServiceState::Running
         };
-        
-        if bundled_node.exists() {
-            return Ok(bundled_node);
+        let mut nodes = vec![NodeStatus {
+            name: config::MAIN_NODE.to_string(),
+            chain: self.config.phoenixd.chain.clone(),
+            port: self.config.bind.phoenixd_addr().port(),
+            state: main_state,
+            active: active == config::MAIN_NODE,
+        }];
+        for node in self.config.nodes.extra.clone() {
+            let state = if !self.extra_node_running(&node.name) {
+                ServiceState::Stopped
+            } else if !port_open(self.config.bind.node_addr(&node)) {
+                ServiceState::Unhealthy
+            } else {
+                ServiceState::Running
+            };
+            nodes.push(NodeStatus {
+                active: active == node.name,
+                name: node.name,
+                chain: node.chain,
+                port: node.port,
+                state,
+            });
         }
-        
-        // Fall back to system Node.js
-        which::which("node")
-            .map_err(|_| "Node.js not found. Please install Node.js or include it in the app bundle.".to_string())
+        nodes
     }
 
-    fn read_phoenixd_password(&self, config_path: &PathBuf) -> String {
-        if !config_path.exists() {
-            return String::new();
-        }
-        
-        match std::fs::read_to_string(config_path) {
-            Ok(content) => {
-                for line in content.lines() {
-                    if line.starts_with("http-password=") {
-                        return line.replace("http-password=", "").trim().to_string();
-                    }
-                }
-                String::new()
-            }
-            Err(_) => String::new(),
-        }
+    /// Start an extra node that stopped or was added since `start_all`
+    pub fn start_node(&mut self, name: &str) -> Result<(), String> {
+        self.ensure_owned()?;
+        let node = self
+            .config
+            .nodes
+            .extra
+            .iter()
+            .find(|node| node.name == name)
+            .cloned()
+            .ok_or_else(|| format!("No node named '{}'", name))?;
+        self.start_extra_node(&node)
     }
 
-    pub fn stop_all(&mut self) {
-        println!("Stopping all services...");
-        
-        // Stop in reverse order
-        if let Some(mut child) = self.frontend.take() {
-            println!("Stopping frontend...");
+    pub fn stop_node(&mut self, name: &str) -> Result<(), String> {
+        self.ensure_owned()?;
+        if let Some(mut child) = self.extra_nodes.remove(name) {
+            info!("Stopping node {}...", name);
             let _ = child.kill();
             let _ = child.wait();
         }
-        
-        if let Some(mut child) = self.backend.take() {
-            println!("Stopping backend...");
-            let _ = child.kill();
-            let _ = child.wait();
+        Ok(())
+    }
+
+    /// phoenixd HOME and API address of the node the backend serves
+    fn backend_node(&self) -> (PathBuf, SocketAddr) {
+        // The backend serves the active node; extra nodes keep their
+        // dashboard data next to their own phoenixd data
+        match self.config.nodes.active() {
+            Some(node) => (node.home(&self.data_dir), self.config.bind.node_addr(node)),
+            None => (self.data_dir.clone(), self.config.bind.phoenixd_addr()),
+        }
+    }
+
+    fn start_backend(&mut self) -> Result<(), String> {
+        let _span = info_span!("start", service = "backend").entered();
+        let (phoenixd_home, phoenixd_addr) = self.backend_node();
+        info!("Backend serves node {}", self.config.nodes.active_name());
+        self.backend_origins = None;
+
+        // Setup environment for backend
+        // Phoenixd stores data in $HOME/.phoenix
+        let phoenix_conf = phoenixd_home.join(".phoenix").join("phoenix.conf");
+
+        // Read phoenixd password from config if available
+        let phoenixd_password = phoenixd_api::read_password(&phoenix_conf);
+
+        // SQLite database path
+        let db_path = phoenixd_home.join(database::DB_FILE);
+        info!("Database URL: file:{}", db_path.display());
+
+        // Copy template database if it doesn't exist
+        if !db_path.exists() {
+            let template_db = self.resource_dir.join("template.db");
+            if template_db.exists() {
+                info!("Initializing database from template...");
+                if let Err(e) = std::fs::copy(&template_db, &db_path) {
+                    warn!("Could not copy template database: {}", e);
+                } else {
+                    info!("Database initialized successfully");
+                }
+            } else {
+                warn!("Template database not found at {:?}", template_db);
+            }
+        }
+
+        if self.config.backend.native {
+            if native_backend::password_set(&db_path) {
+                warn!("The native backend has no password login yet, using the Node backend");
+            } else {
+                let ip = self
+                    .backend_bind()
+                    .parse()
+                    .unwrap_or(self.config.bind.backend_ip());
+                let upstream = Upstream {
+                    addr: phoenixd_addr,
+                    password: phoenixd_password,
+                };
+                let access = native_backend::Access {
+                    api_token: self.api_token.clone(),
+                    pairing_token: self.pairing_token.clone(),
+                    origins: self.allowed_origins(),
+                };
+                self.native_backend = Some(NativeBackend::start(
+                    SocketAddr::new(ip, 4000),
+                    upstream,
+                    db_path,
+                    self.config.general.watch_only,
+                    self.node_paused(),
+                    access,
+                )?);
+                return Ok(());
+            }
+        }
+
+        let mut child = self
+            .backend_command()?
+            .spawn()
+            .map_err(|e| format!("Failed to start backend: {}", e))?;
+
+        logs::capture(&self.data_dir, "backend", &mut child);
+        self.backend = Some(child);
+        self.backend_origins = Some(self.allowed_origins().join(","));
+        info!("Backend started successfully");
+        Ok(())
+    }
+
+    /// The Node backend with its environment
+    fn backend_command(&self) -> Result<Command, String> {
+        let (phoenixd_home, phoenixd_addr) = self.backend_node();
+        let phoenix_conf = phoenixd_home.join(".phoenix").join("phoenix.conf");
+        let phoenixd_password = phoenixd_api::read_password(&phoenix_conf);
+        let database_url = format!("file:{}", phoenixd_home.join(database::DB_FILE).display());
+
+        let backend_dir = self.resource_dir.join("backend");
+        let node_path = self.find_node_binary()?;
+
+        let backend_entry = backend_dir.join("dist").join("index.js");
+
+        if !backend_entry.exists() {
+            return Err(format!(
+                "Backend entry point not found at: {:?}",
+                backend_entry
+            ));
+        }
+        info!("Starting backend from: {:?}", backend_entry);
+
+        let mut command = Command::new(&node_path);
+        command
+            .arg(&backend_entry)
+            .current_dir(&backend_dir)
+            .env("NODE_ENV", "production")
+            .env("PORT", "4000")
+            .env("HOST", self.backend_bind())
+            .env("DESKTOP_MODE", "true")
+            .env("DATABASE_URL", &database_url)
+            .env("PHOENIXD_URL", format!("http://{}", phoenixd_addr))
+            .env("PHOENIXD_PASSWORD", &phoenixd_password)
+            .env("FRONTEND_URL", self.frontend_origin())
+            .env("ALLOWED_ORIGINS", self.allowed_origins().join(","))
+            .env(
+                "PAIRING_TOKEN",
+                self.pairing_token.as_deref().unwrap_or_default(),
+            )
+            .env("API_TOKEN", &self.api_token)
+            .env("READ_ONLY", self.config.general.watch_only.to_string())
+            .env("NODE_PAUSED", self.node_paused().to_string())
+            .envs(self.config.services.backend.env())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        Ok(command)
+    }
+
+    fn start_frontend(&mut self) -> Result<(), String> {
+        if !self.config.frontend.enabled {
+            return Err("The frontend is disabled (API-only mode)".to_string());
+        }
+        let _span = info_span!("start", service = "frontend").entered();
+        if self.config.frontend.serve_static {
+            if static_frontend::available(&self.resource_dir) {
+                let ip = self
+                    .frontend_bind()
+                    .parse()
+                    .unwrap_or(Ipv4Addr::LOCALHOST.into());
+                self.static_frontend = Some(StaticFrontend::start(
+                    SocketAddr::new(ip, 3000),
+                    static_frontend::static_dir(&self.resource_dir),
+                )?);
+                return Ok(());
+            }
+            warn!("No static frontend build in the bundle, starting the Next.js server");
+        }
+        let mut child = self
+            .frontend_command()?
+            .spawn()
+            .map_err(|e| format!("Failed to start frontend: {}", e))?;
+
+        logs::capture(&self.data_dir, "frontend", &mut child);
+        self.frontend = Some(child);
+        info!("Frontend started successfully");
+        Ok(())
+    }
+
+    /// The Next.js standalone server with its environment
+    fn frontend_command(&self) -> Result<Command, String> {
+        let frontend_dir = self.resource_dir.join("frontend");
+        let node_path = self.find_node_binary()?;
+
+        // For standalone Next.js build
+        let server_js = frontend_dir.join("server.js");
+
+        if !server_js.exists() {
+            return Err(format!(
+                "Frontend server not found at: {:?}. Make sure to build with 'next build' (standalone output).",
+                server_js
+            ));
+        }
+
+        let hostname = self.frontend_bind();
+        let api_url = self.backend_base_url(&config::browser_host(self.config.bind.backend_ip()));
+        info!("Starting frontend from: {:?}", server_js);
+
+        let mut command = Command::new(&node_path);
+        command
+            .arg(&server_js)
+            .current_dir(&frontend_dir)
+            .env("NODE_ENV", "production")
+            .env("PORT", "3000")
+            .env("HOSTNAME", hostname)
+            .env("NEXT_PUBLIC_WS_URL", api_url.replacen("http", "ws", 1))
+            .env("NEXT_PUBLIC_API_URL", api_url)
+            .envs(self.config.services.frontend.env())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        Ok(command)
+    }
+
+    fn start_tor(&mut self) -> Result<(), String> {
+        let _span = info_span!("start", service = "tor").entered();
+        let tor_binary = self.find_tor_binary()?;
+        let dashboard = self.config.tor.enabled.then(|| self.exposed_addr());
+        let node_api = self
+            .config
+            .tor
+            .node_api
+            .then(|| self.config.bind.phoenixd_addr());
+        let torrc = TorManager::new(&self.data_dir).prepare(dashboard, node_api)?;
+
+        info!("Starting tor from: {:?}", tor_binary);
+
+        let mut child = Self::tor_command(&tor_binary, &torrc)
+            .spawn()
+            .map_err(|e| format!("Failed to start tor: {}", e))?;
+
+        logs::capture(&self.data_dir, "tor", &mut child);
+        self.tor = Some(child);
+        info!("Tor started successfully");
+        Ok(())
+    }
+
+    fn tor_command(tor_binary: &Path, torrc: &Path) -> Command {
+        let mut command = Command::new(tor_binary);
+        command
+            .arg("-f")
+            .arg(torrc)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        command
+    }
+
+    /// Restart tor so it picks up changes to the hidden service (e.g. client authorization)
+    pub fn restart_tor(&mut self) -> Result<(), String> {
+        if let Some(mut child) = self.tor.take() {
+            info!("Stopping tor...");
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+
+        if self.runs_tor() {
+            self.start_tor()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn runs_tor(&self) -> bool {
+        self.config.tor.enabled || self.node_over_tor()
+    }
+
+    fn node_over_tor(&self) -> bool {
+        self.config.tor.node_traffic && !self.config.phoenixd.mock
+    }
+
+    /// The dashboard as devices on the same network reach it
+    fn lan_dashboard_url(&self) -> Option<String> {
+        let ip = lan::lan_ip()?;
+        if self.proxy.is_some() {
+            Some(format!("https://{}:{}", ip, self.config.proxy.port))
+        } else {
+            Some(self.dashboard_base_url(&ip.to_string()))
+        }
+    }
+
+    /// URL a phone on the same network can open to pair with the dashboard.
+    /// The backend trades the token for a cookie and sends the phone on to
+    /// the dashboard; behind the proxy, its login takes the token's place.
+    pub fn lan_access_url(&self) -> Option<String> {
+        let token = self.pairing_token.as_ref()?;
+        let dashboard = self.lan_dashboard_url()?;
+        if self.proxy.is_some() {
+            return Some(dashboard);
+        }
+        let backend = self.backend_base_url(&lan::lan_ip()?.to_string());
+        let mut url = tauri::Url::parse(&format!("{}/api/desktop/pair", backend)).ok()?;
+        url.query_pairs_mut()
+            .append_pair("token", token)
+            .append_pair("next", &dashboard);
+        Some(url.to_string())
+    }
+
+    /// Local address tor and tailscale forward to: the proxy when it's
+    /// enabled, so nothing remote ever reaches the services unauthenticated
+    pub fn exposed_addr(&self) -> SocketAddr {
+        if self.config.proxy.enabled {
+            SocketAddr::from(([127, 0, 0, 1], self.config.proxy.plain_port))
+        } else if self.config.frontend.enabled {
+            self.config.bind.frontend_addr()
+        } else {
+            self.config.bind.backend_addr()
+        }
+    }
+
+    /// Local address cloudflared forwards to, the proxy's own listener for it
+    pub fn tunnel_addr(&self) -> SocketAddr {
+        if self.config.proxy.enabled {
+            SocketAddr::from(([127, 0, 0, 1], self.config.proxy.tunnel_port))
+        } else {
+            self.exposed_addr()
+        }
+    }
+
+    /// Address the frontend listens on. Behind the proxy it never needs to
+    /// be reachable directly.
+    fn frontend_bind(&self) -> String {
+        match self.config.bind.frontend_ip() {
+            Some(ip) => ip.to_string(),
+            None if self.lan_direct() => "0.0.0.0".to_string(),
+            None => "localhost".to_string(),
+        }
+    }
+
+    fn backend_bind(&self) -> String {
+        if self.config.bind.backend.is_empty() && self.lan_direct() {
+            "0.0.0.0".to_string()
+        } else {
+            self.config.bind.backend_ip().to_string()
+        }
+    }
+
+    /// LAN devices talk to the services themselves rather than the proxy
+    fn lan_direct(&self) -> bool {
+        self.config.lan.enabled && !self.config.proxy.enabled
+    }
+
+    /// The local web UI, or the backend API when there is none
+    fn ui_port(&self) -> u16 {
+        if self.config.frontend.enabled {
+            3000
+        } else {
+            4000
+        }
+    }
+
+    /// Origin the backend accepts browser requests from
+    fn frontend_origin(&self) -> String {
+        if self.config.frontend.enabled || self.config.frontend.external_url.is_empty() {
+            format!("http://{}:3000", self.frontend_host())
+        } else {
+            self.config
+                .frontend
+                .external_url
+                .trim_end_matches('/')
+                .to_string()
+        }
+    }
+
+    /// Every origin the dashboard is exposed under: local, LAN, the onion,
+    /// the tunnel and the tailnet, plus `backend.allowed_origins`
+    pub fn allowed_origins(&self) -> Vec<String> {
+        let mut urls = vec![self.frontend_origin(), self.dashboard_url()];
+        if self.config.lan.enabled {
+            urls.extend(self.lan_dashboard_url());
+        }
+        if self.config.tor.enabled {
+            if let Some(onion) = TorManager::new(&self.data_dir).onion_address() {
+                urls.push(format!("http://{}", onion));
+            }
+        }
+        if self.config.cloudflare.enabled {
+            if self.config.cloudflare.quick {
+                urls.extend(self.quick_tunnel_url.lock().unwrap().clone());
+            } else if !self.config.cloudflare.hostname.is_empty() {
+                urls.push(format!("https://{}", self.config.cloudflare.hostname));
+            }
+        }
+        urls.extend(self.tailscale_url());
+        urls.extend(self.config.backend.allowed_origins.iter().cloned());
+
+        let mut origins: Vec<String> = Vec::new();
+        for origin in urls.iter().filter_map(|url| config::origin_of(url)) {
+            if !origins.contains(&origin) {
+                origins.push(origin);
+            }
+        }
+        origins
+    }
+
+    /// Restart the Node backend when an exposure came or went since it
+    /// started, so its CORS check lets the new origin in
+    pub fn sync_backend_origins(&mut self) -> Result<(), String> {
+        let Some(current) = self.backend_origins.clone() else {
+            return Ok(());
+        };
+        let origins = self.allowed_origins().join(",");
+        if current == origins || !self.is_service_running("backend") {
+            return Ok(());
+        }
+        info!(
+            "🌐 Allowed origins are now {}, restarting the backend",
+            origins
+        );
+        self.restart_service("backend")
+    }
+
+    pub fn start_proxy(&mut self) -> Result<(), String> {
+        let _span = info_span!("start", service = "proxy").entered();
+        self.stop_proxy();
+
+        if self.config.proxy.password.is_empty() {
+            return Err("Reverse proxy password is not set".to_string());
+        }
+
+        // The https entry point reuses the local CA from the https setting
+        let mut hosts = vec!["localhost".to_string(), "127.0.0.1".to_string()];
+        if let Some(ip) = lan::lan_ip() {
+            hosts.push(ip.to_string());
+        }
+        if let Some(hostname) = self.mdns_hostname() {
+            hosts.push(hostname);
+        }
+        let files = TlsFiles::new(&self.data_dir);
+        files.ensure(&hosts)?;
+
+        let bind_ip = if self.config.lan.enabled {
+            "0.0.0.0"
+        } else {
+            "127.0.0.1"
+        };
+        let upstreams = proxy::Upstreams {
+            frontend: self.config.bind.frontend_addr(),
+            backend: self.config.bind.backend_addr(),
+        };
+        let listeners = vec![
+            proxy::Listener {
+                bind_ip: bind_ip.to_string(),
+                port: self.config.proxy.port,
+                tls: Some(files.server_config()?),
+                cloudflare: false,
+            },
+            proxy::Listener {
+                bind_ip: "127.0.0.1".to_string(),
+                port: self.config.proxy.plain_port,
+                tls: None,
+                cloudflare: false,
+            },
+            proxy::Listener {
+                bind_ip: "127.0.0.1".to_string(),
+                port: self.config.proxy.tunnel_port,
+                tls: None,
+                cloudflare: true,
+            },
+        ];
+
+        let credentials = Credentials {
+            username: self.config.proxy.username.clone(),
+            password: self.config.proxy.password.clone(),
+            read_only_password: self.config.proxy.read_only_password.clone(),
+        };
+        self.proxy = Some(ReverseProxy::start(
+            credentials,
+            self.api_token.clone(),
+            listeners,
+            upstreams,
+            Arc::clone(&self.access_guard),
+            self.notifier.clone(),
+            &self.data_dir,
+        )?);
+        Ok(())
+    }
+
+    pub fn blocked_clients(&self) -> Vec<BlockedClient> {
+        self.access_guard.blocked_clients()
+    }
+
+    /// Lift the lockout for one client, or for everyone when `ip` is None
+    pub fn unblock_client(&self, ip: Option<IpAddr>) -> bool {
+        match ip {
+            Some(ip) => self.access_guard.unblock(ip),
+            None => {
+                self.access_guard.unblock_all();
+                true
+            }
+        }
+    }
+
+    pub fn stop_proxy(&mut self) {
+        if let Some(proxy) = self.proxy.take() {
+            proxy.stop();
+        }
+    }
+
+    /// Local URL the tray and browser should open; the external UI or the
+    /// API itself in API-only mode
+    pub fn dashboard_url(&self) -> String {
+        if self.config.frontend.enabled {
+            self.dashboard_base_url(&self.frontend_host())
+        } else if !self.config.frontend.external_url.is_empty() {
+            self.config.frontend.external_url.clone()
+        } else {
+            self.backend_url()
+        }
+    }
+
+    fn dashboard_base_url(&self, host: &str) -> String {
+        if !self.config.frontend.enabled {
+            return self.backend_base_url(host);
+        }
+        if self.tls.is_some() {
+            format!("https://{}:{}", host, self.config.https.port)
+        } else {
+            format!("http://{}:3000", host)
+        }
+    }
+
+    /// Local URL of the backend API, for external UIs
+    pub fn backend_url(&self) -> String {
+        self.backend_base_url(&config::browser_host(self.config.bind.backend_ip()))
+    }
+
+    fn frontend_host(&self) -> String {
+        self.config
+            .bind
+            .frontend_ip()
+            .map(config::browser_host)
+            .unwrap_or_else(|| "localhost".to_string())
+    }
+
+    fn backend_base_url(&self, host: &str) -> String {
+        if self.tls.is_some() {
+            format!("https://{}:{}", host, self.config.https.api_port)
+        } else {
+            format!("http://{}:4000", host)
+        }
+    }
+
+    /// Issue a certificate for every name the dashboard is reachable under
+    /// and start terminating TLS in front of the frontend and backend
+    pub fn start_https(&mut self) -> Result<(), String> {
+        let _span = info_span!("start", service = "https").entered();
+        self.stop_https();
+
+        let mut hosts = vec![
+            "localhost".to_string(),
+            "127.0.0.1".to_string(),
+            "::1".to_string(),
+        ];
+        if self.config.lan.enabled {
+            if let Some(ip) = lan::lan_ip() {
+                hosts.push(ip.to_string());
+            }
+        }
+        // From the config, as the name is advertised only after this starts
+        if self.config.lan.enabled && self.config.lan.mdns {
+            hosts.push(format!("{}.local", self.config.lan.mdns_name));
+        }
+
+        let files = TlsFiles::new(&self.data_dir);
+        files.ensure(&hosts)?;
+
+        let bind_ip = if self.config.lan.enabled {
+            "0.0.0.0"
+        } else {
+            "127.0.0.1"
+        };
+        let mut routes = vec![(self.config.https.api_port, self.config.bind.backend_addr())];
+        if self.config.frontend.enabled {
+            routes.push((self.config.https.port, self.config.bind.frontend_addr()));
+        }
+        self.tls = Some(TlsTerminator::start(
+            files.server_config()?,
+            bind_ip,
+            &routes,
+        )?);
+        Ok(())
+    }
+
+    pub fn stop_https(&mut self) {
+        if let Some(tls) = self.tls.take() {
+            tls.stop();
+        }
+    }
+
+    /// Start or stop the mDNS advertisement to match the LAN settings
+    pub fn update_mdns(&mut self) {
+        if let Some(advertiser) = self.mdns.take() {
+            advertiser.stop();
+        }
+
+        if self.config.lan.enabled && self.config.lan.mdns {
+            match MdnsAdvertiser::start(&self.config.lan.mdns_name, self.ui_port()) {
+                Ok(advertiser) => self.mdns = Some(advertiser),
+                Err(e) => warn!("{}", e),
+            }
+        }
+    }
+
+    pub fn mdns_hostname(&self) -> Option<String> {
+        self.mdns.as_ref().map(|m| m.hostname().to_string())
+    }
+
+    /// Issue a fresh pairing token, invalidating the previous one
+    pub fn regenerate_pairing_token(&mut self) -> Result<(), String> {
+        if !self.config.lan.enabled {
+            return Err("LAN access is disabled".to_string());
+        }
+
+        self.pairing_token = Some(lan::generate_pairing_token()?);
+        self.restart_backend()
+    }
+
+    fn restart_backend(&mut self) -> Result<(), String> {
+        if let Some(mut child) = self.backend.take() {
+            info!("Stopping backend...");
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        self.stop_native_backend();
+        self.start_backend()
+    }
+
+    fn stop_native_backend(&mut self) {
+        if let Some(native) = self.native_backend.take() {
+            info!("Stopping native backend...");
+            native.stop();
+        }
+    }
+
+    pub fn start_cloudflared(&mut self) -> Result<(), String> {
+        let _span = info_span!("start", service = "cloudflared").entered();
+        if self.is_cloudflared_running() {
+            return Ok(());
+        }
+
+        let manager = CloudflareManager::new(self.resource_dir.clone(), &self.data_dir);
+        let mut command = if self.config.cloudflare.quick {
+            info!("Starting Cloudflare quick tunnel");
+            manager.quick_tunnel_command(self.tunnel_addr())?
+        } else {
+            let cloudflare = &self.config.cloudflare;
+            info!("Starting Cloudflare tunnel '{}'", cloudflare.tunnel_name);
+            if !cloudflare.tunnel_id.is_empty() {
+                manager.write_config(
+                    &cloudflare.tunnel_id,
+                    &cloudflare.hostname,
+                    self.tunnel_addr(),
+                )?;
+            }
+            manager.tunnel_command(&cloudflare.tunnel_name)?
+        };
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("Failed to start cloudflared: {}", e))?;
+
+        // Log cloudflared's output, picking up the quick tunnel URL on the way
+        *self.quick_tunnel_url.lock().unwrap() = None;
+        let quick_tunnel_url = Arc::clone(&self.quick_tunnel_url);
+        logs::capture_with(&self.data_dir, "cloudflared", &mut child, move |line| {
+            if let Some(url) = cloudflare::parse_quick_tunnel_url(line) {
+                info!("☁️ Public URL: {}", url);
+                *quick_tunnel_url.lock().unwrap() = Some(url);
+            }
+        });
+
+        self.cloudflared = Some(child);
+        info!("Cloudflare tunnel started successfully");
+        Ok(())
+    }
+
+    pub fn stop_cloudflared(&mut self) {
+        if let Some(mut child) = self.cloudflared.take() {
+            info!("Stopping cloudflared...");
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        *self.quick_tunnel_url.lock().unwrap() = None;
+    }
+
+    pub fn is_cloudflared_running(&mut self) -> bool {
+        match self.cloudflared.as_mut() {
+            Some(child) => matches!(child.try_wait(), Ok(None)),
+            None => false,
+        }
+    }
+
+    /// Public https URL of the running tunnel, if there is one yet
+    pub fn get_public_url(&mut self) -> Option<String> {
+        if !self.is_cloudflared_running() {
+            return None;
+        }
+
+        if self.config.cloudflare.quick {
+            self.quick_tunnel_url.lock().unwrap().clone()
+        } else if self.config.cloudflare.hostname.is_empty() {
+            None
+        } else {
+            Some(format!("https://{}", self.config.cloudflare.hostname))
+        }
+    }
+
+    fn start_nwc(&mut self) -> Result<(), String> {
+        let _span = info_span!("start", service = "nwc").entered();
+        if self.is_service_running("nwc") {
+            return Ok(());
+        }
+        self.config.nwc.validate()?;
+        // The bridge serves the same node as the backend
+        let (phoenixd_home, phoenixd_addr) = self.backend_node();
+        let phoenix_conf = phoenixd_home.join(".phoenix").join("phoenix.conf");
+        nwc::write_config(
+            &self.data_dir,
+            &self.config.nwc,
+            &format!("http://{}", phoenixd_addr),
+            &phoenixd_api::read_password(&phoenix_conf),
+            self.config.general.watch_only,
+        )?;
+        let mut child = nwc::command(&self.resource_dir, &self.data_dir)?
+            .env("NWC_WALLET_KEY", nwc::wallet_key()?)
+            .spawn()
+            .map_err(|e| format!("Failed to start the NWC bridge: {}", e))?;
+        logs::capture(&self.data_dir, "nwc", &mut child);
+        self.nwc = Some(child);
+        info!("⚡ NWC bridge started on {}", self.config.nwc.relay);
+        Ok(())
+    }
+
+    pub fn start_tailscale(&mut self) -> Result<(), String> {
+        let _span = info_span!("start", service = "tailscale").entered();
+        let url = tailscale::serve(self.exposed_addr())?;
+        info!("🔗 Shared on Tailscale: {}", url);
+        self.tailscale_url = Some(url);
+        Ok(())
+    }
+
+    pub fn stop_tailscale(&mut self) {
+        if self.tailscale_url.take().is_some() {
+            info!("Stopping tailscale serve...");
+            if let Err(e) = tailscale::unserve() {
+                warn!("{}", e);
+            }
         }
-        
+    }
+
+    /// Every URL the dashboard can currently be opened under, with an i18n label key
+    pub fn access_urls(&mut self) -> Vec<(&'static str, String)> {
+        let mut urls = vec![("access.local", self.dashboard_url())];
+
+        if let Some(url) = self.lan_access_url() {
+            urls.push(("access.lan", url));
+        }
+        if self.is_service_running("tor") {
+            if let Some(onion) = TorManager::new(&self.data_dir).onion_address() {
+                urls.push(("access.tor", format!("http://{}", onion)));
+            }
+        }
+        if let Some(url) = self.get_public_url() {
+            urls.push(("access.cloudflare", url));
+        }
+        if let Some(url) = self.tailscale_url() {
+            urls.push(("access.tailscale", url));
+        }
+        urls
+    }
+
+    /// Run a startup step and record how long it took
+    fn timed<T>(&mut self, name: &str, step: impl FnOnce(&mut Self) -> T) -> T {
+        let profile = Arc::clone(&self.profile);
+        profile::timed(&profile, name, || step(self))
+    }
+
+    pub fn startup_profile(&self) -> StartupProfile {
+        self.profile.lock().unwrap().clone()
+    }
+
+    /// MagicDNS URL the dashboard is shared under on the tailnet
+    pub fn tailscale_url(&self) -> Option<String> {
+        self.tailscale_url.clone()
+    }
+
+    fn find_tor_binary(&self) -> Result<PathBuf, String> {
+        let binary_name = if cfg!(target_os = "windows") {
+            "tor.exe"
+        } else {
+            "tor"
+        };
+
+        let bundled = self.resource_dir.join("binaries").join(binary_name);
+        if bundled.exists() {
+            return Ok(bundled);
+        }
+
+        which::which("tor").map_err(|_| {
+            "Tor not found. Please install tor or include it in the app bundle.".to_string()
+        })
+    }
+
+    fn find_torsocks_binary(&self) -> Result<PathBuf, String> {
+        if cfg!(target_os = "windows") {
+            return Err(
+                "Routing phoenixd over Tor needs torsocks, which isn't available on Windows"
+                    .to_string(),
+            );
+        }
+
+        let bundled = self.resource_dir.join("binaries").join("torsocks");
+        if bundled.exists() {
+            return Ok(bundled);
+        }
+
+        which::which("torsocks").map_err(|_| {
+            "torsocks not found. Please install it to route phoenixd over Tor.".to_string()
+        })
+    }
+
+    fn get_phoenixd_binary_path(&self) -> PathBuf {
+        // An upgrade installed from the tray wins over the bundled release
+        let upgraded = resources::phoenixd_path(&resources::upgraded_dir(&self.data_dir));
+        if upgraded.exists() {
+            return upgraded;
+        }
+
+        // Then check in resources/binaries
+        let bundled = resources::phoenixd_path(&self.resource_dir);
+        if bundled.exists() {
+            return bundled;
+        }
+
+        // Then a copy downloaded by the resource repair
+        let repaired = resources::phoenixd_path(&resources::repaired_dir(&self.data_dir));
+        if repaired.exists() {
+            return repaired;
+        }
+
+        // Then check system PATH
+        if let Ok(path) = which::which("phoenixd") {
+            return path;
+        }
+
+        // Default to bundled path (will error later if not found)
+        bundled
+    }
+
+    fn find_node_binary(&self) -> Result<PathBuf, String> {
+        // First check in resources (bundled Node.js), then a repaired copy
+        for dir in [
+            self.resource_dir.clone(),
+            resources::repaired_dir(&self.data_dir),
+        ] {
+            let node = resources::node_path(&dir);
+            if node.exists() {
+                return Ok(node);
+            }
+        }
+
+        // Fall back to system Node.js
+        which::which("node").map_err(|_| {
+            "Node.js not found. Please install Node.js or include it in the app bundle.".to_string()
+        })
+    }
+
+    fn child_slot(&mut self, name: &str) -> Result<&mut Option<Child>, String> {
+        match name {
+            "phoenixd" => Ok(&mut self.phoenixd),
+            "backend" => Ok(&mut self.backend),
+            "frontend" => Ok(&mut self.frontend),
+            "tor" => Ok(&mut self.tor),
+            "cloudflared" => Ok(&mut self.cloudflared),
+            "nwc" => Ok(&mut self.nwc),
+            _ if self.config.services.get(name).is_some() => {
+                Ok(self.extra_services.entry(name.to_string()).or_default())
+            }
+            _ => Err(format!("Unknown service: {}", name)),
+        }
+    }
+
+    fn is_service_running(&mut self, name: &str) -> bool {
+        if self.attached {
+            return self.config.bind.service_addr(name).is_some_and(port_open);
+        }
+        if name == "phoenixd" && self.mock_phoenixd.is_some() {
+            return true;
+        }
+        if let Some(pid) = self.adopted_phoenixd.filter(|_| name == "phoenixd") {
+            return pid_alive(pid);
+        }
+        if name == "backend" && self.native_backend.is_some() {
+            return true;
+        }
+        if name == "frontend" && self.static_frontend.is_some() {
+            return true;
+        }
+        match self.child_slot(name) {
+            Ok(Some(child)) => matches!(child.try_wait(), Ok(None)),
+            _ => false,
+        }
+    }
+
+    /// State of every service that is enabled or currently running
+    pub fn service_statuses(&mut self) -> Vec<ServiceStatus> {
+        let extra: Vec<&'static str> = self
+            .config
+            .services
+            .extra
+            .iter()
+            .map(|service| intern(&service.name))
+            .collect();
+        let shown: Vec<&'static str> = SERVICES
+            .into_iter()
+            .filter(|&name| match name {
+                "tor" => self.runs_tor() || self.tor.is_some(),
+                "cloudflared" => self.config.cloudflare.enabled || self.cloudflared.is_some(),
+                "nwc" => self.config.nwc.enabled || self.nwc.is_some(),
+                "frontend" => self.config.frontend.enabled || self.frontend.is_some(),
+                _ => true,
+            })
+            .chain(extra)
+            .collect();
+
+        let statuses: Vec<ServiceStatus> = shown
+            .into_iter()
+            .map(|name| {
+                let state = if !self.is_service_running(name) {
+                    ServiceState::Stopped
+                } else if name == "tor" && tor_health::degraded() {
+                    ServiceState::Unhealthy
+                } else if let Some(healthy) = health_checks::verdict(name) {
+                    if healthy {
+                        ServiceState::Running
+                    } else {
+                        ServiceState::Unhealthy
+                    }
+                } else if self
+                    .config
+                    .bind
+                    .service_addr(name)
+                    .is_some_and(|addr| !port_open(addr))
+                {
+                    ServiceState::Unhealthy
+                } else {
+                    ServiceState::Running
+                };
+                ServiceStatus { name, state }
+            })
+            .collect();
+        *self.status_cache.lock().unwrap() = statuses.clone();
+        statuses
+    }
+
+    /// Result of the latest `service_statuses` call, readable without
+    /// waiting for the manager (it's held for the whole of a restart)
+    pub fn status_cache(&self) -> StatusCache {
+        Arc::clone(&self.status_cache)
+    }
+
+    pub fn start_service(&mut self, name: &str) -> Result<(), String> {
+        self.ensure_owned()?;
+        if self.is_service_running(name) {
+            return Ok(());
+        }
+
+        match name {
+            "phoenixd" => self.start_phoenixd(),
+            "backend" => self.start_backend(),
+            "frontend" => self.start_frontend(),
+            "tor" => self.start_tor(),
+            "cloudflared" => self.start_cloudflared(),
+            "nwc" => self.start_nwc(),
+            _ => match self.config.services.get(name).cloned() {
+                Some(service) => self.start_extra_service(&service),
+                None => Err(format!("Unknown service: {}", name)),
+            },
+        }
+    }
+
+    /// Whether phoenixd was paused on purpose; the backend and frontend keep
+    /// serving what they have
+    pub fn node_paused(&self) -> bool {
+        self.data_dir.join(PAUSED_FILE).exists()
+    }
+
+    /// Shut phoenixd down cleanly and keep it down, e.g. before an OS
+    /// upgrade. The NWC bridge stops with it; the backend restarts so it
+    /// knows the node is paused.
+    pub fn pause_node(&mut self) -> Result<(), String> {
+        self.ensure_owned()?;
+        std::fs::write(
+            self.data_dir.join(PAUSED_FILE),
+            chrono::Local::now().to_rfc3339(),
+        )
+        .map_err(|e| format!("Failed to write {}: {}", PAUSED_FILE, e))?;
+        info!("⏸️ Pausing the node");
+        self.stop_service("nwc")?;
+        self.terminate_phoenixd();
+        self.stop_service("phoenixd")?;
+        journal::record(&self.data_dir, "node.paused", Some("phoenixd"), "");
+        self.restart_service("backend")
+    }
+
+    pub fn resume_node(&mut self) -> Result<(), String> {
+        self.ensure_owned()?;
+        let _ = std::fs::remove_file(self.data_dir.join(PAUSED_FILE));
+        info!("▶️ Resuming the node");
+        self.start_service("phoenixd")?;
+        let addr = self.config.bind.phoenixd_addr();
+        let started = Instant::now();
+        while !port_open(addr) && started.elapsed() < READY_GATE {
+            std::thread::sleep(Duration::from_millis(250));
+        }
+        journal::record(&self.data_dir, "node.resumed", Some("phoenixd"), "");
+        self.restart_service("backend")?;
+        if self.config.nwc.enabled {
+            self.start_service("nwc")?;
+        }
+        Ok(())
+    }
+
+    /// Ask phoenixd to exit (SIGTERM) and give it `PAUSE_TIMEOUT` to close
+    /// its databases; `stop_service` kills whatever is left
+    fn terminate_phoenixd(&mut self) {
+        let pid = match self.phoenixd.as_ref() {
+            Some(child) => Some(child.id()),
+            None => self.adopted_phoenixd,
+        };
+        let Some(pid) = pid else {
+            return;
+        };
+        let mut system = System::new();
+        let sysinfo_pid = Pid::from_u32(pid);
+        system.refresh_processes(ProcessesToUpdate::Some(&[sysinfo_pid]), true);
+        let asked = system
+            .process(sysinfo_pid)
+            .and_then(|process| process.kill_with(sysinfo::Signal::Term))
+            .unwrap_or(false);
+        if !asked {
+            return;
+        }
+        let started = Instant::now();
+        while started.elapsed() < PAUSE_TIMEOUT {
+            let exited = match self.phoenixd.as_mut() {
+                Some(child) => !matches!(child.try_wait(), Ok(None)),
+                None => !pid_alive(pid),
+            };
+            if exited {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(250));
+        }
+        warn!(
+            "phoenixd did not exit within {:?}, killing it",
+            PAUSE_TIMEOUT
+        );
+    }
+
+    pub fn stop_service(&mut self, name: &str) -> Result<(), String> {
+        self.ensure_owned()?;
+        if name == "cloudflared" {
+            self.stop_cloudflared();
+            return Ok(());
+        }
+        if name == "phoenixd" {
+            tor::set_node_private(false);
+            if let Some(mock) = self.mock_phoenixd.take() {
+                mock.stop();
+            }
+            self.stop_adopted_phoenixd();
+        }
+        if name == "backend" {
+            self.stop_native_backend();
+        }
+        if name == "frontend" {
+            if let Some(server) = self.static_frontend.take() {
+                info!("Stopping static frontend...");
+                server.stop();
+            }
+        }
+
+        if let Some(mut child) = self.child_slot(name)?.take() {
+            info!("Stopping {}...", name);
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        Ok(())
+    }
+
+    pub fn restart_service(&mut self, name: &str) -> Result<(), String> {
+        self.stop_service(name)?;
+        *self.restarts.entry(name.to_string()).or_default() += 1;
+        self.start_service(name)
+    }
+
+    pub fn restart_count(&self, name: &str) -> u32 {
+        self.restarts.get(name).copied().unwrap_or_default()
+    }
+
+    /// How a service's process ended if it exited on its own; `None` while
+    /// it runs or after it was stopped on purpose
+    pub fn exit_status(&mut self, name: &str) -> Option<ExitStatus> {
+        match self.child_slot(name) {
+            Ok(Some(child)) => child.try_wait().ok().flatten(),
+            _ => None,
+        }
+    }
+
+    /// PID of a service's process while it is alive
+    pub fn service_pid(&mut self, name: &str) -> Option<u32> {
+        if let Some(pid) = self.adopted_phoenixd.filter(|_| name == "phoenixd") {
+            return pid_alive(pid).then_some(pid);
+        }
+        if self.is_service_running(name) {
+            self.child_slot(name).ok()?.as_ref().map(|child| child.id())
+        } else {
+            None
+        }
+    }
+
+    pub fn log_path(&self, name: &str) -> PathBuf {
+        logs::log_path(&self.data_dir, name)
+    }
+
+    pub fn stop_all(&mut self) {
+        info!("Stopping all services...");
+
+        // Stop in reverse order
+        self.stop_https();
+        self.stop_proxy();
+
+        if let Some(advertiser) = self.mdns.take() {
+            advertiser.stop();
+        }
+
+        self.stop_cloudflared();
+        self.stop_tailscale();
+
+        if let Some(mut child) = self.nwc.take() {
+            info!("Stopping the NWC bridge...");
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+
+        if let Some(mut child) = self.tor.take() {
+            info!("Stopping tor...");
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+
+        if let Some(mut child) = self.frontend.take() {
+            info!("Stopping frontend...");
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        if let Some(server) = self.static_frontend.take() {
+            server.stop();
+        }
+
+        if let Some(mut child) = self.backend.take() {
+            info!("Stopping backend...");
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        self.stop_native_backend();
+
+        self.stop_extra_nodes();
+        self.stop_extra_services();
+
         if let Some(mut child) = self.phoenixd.take() {
-            println!("Stopping phoenixd...");
+            info!("Stopping phoenixd...");
             let _ = child.kill();
             let _ = child.wait();
         }
-        
-        println!("All services stopped");
+        self.stop_adopted_phoenixd();
+        tor::set_node_private(false);
+        if let Some(mock) = self.mock_phoenixd.take() {
+            mock.stop();
+        }
+
+        journal::record(&self.data_dir, "services.stopped", None, "all services");
+        info!("All services stopped");
+    }
+
+    /// Quit the app's way: `teardown(mode)`, but with phoenixd left running
+    /// when `keep_node` is set and it can be
+    pub fn quit(&mut self, mode: &str, keep_node: bool) -> Result<(), String> {
+        if keep_node && !self.keep_phoenixd() {
+            warn!("phoenixd can't be left running, stopping it with the rest");
+        }
+        self.teardown(mode)
+    }
+
+    /// Stop everything, then clear what the mode throws away: `down` the
+    /// service logs, `purge` the dashboard databases as well
+    pub fn teardown(&mut self, mode: &str) -> Result<(), String> {
+        if !matches!(mode, "stop" | "down" | "purge") {
+            return Err(format!("Unknown teardown mode: {}", mode));
+        }
+        self.stop_all();
+        if mode == "stop" {
+            return Ok(());
+        }
+
+        // The shell's own log lives in a subdirectory and is kept, and so is
+        // the log of a phoenixd left running
+        let kept = logs::log_path(&self.data_dir, "phoenixd");
+        let keep_node_log = self.data_dir.join(KEPT_PID_FILE).exists();
+        if let Ok(entries) = std::fs::read_dir(logs::log_dir(&self.data_dir)) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if keep_node_log && path == kept {
+                    continue;
+                }
+                if path.extension().is_some_and(|ext| ext == "log") {
+                    if let Err(e) = std::fs::remove_file(&path) {
+                        warn!("Failed to remove {:?}: {}", path, e);
+                    }
+                }
+            }
+        }
+        info!("🧹 Service logs cleared");
+        if mode == "down" {
+            return Ok(());
+        }
+
+        let homes = std::iter::once(self.data_dir.clone()).chain(
+            self.config
+                .nodes
+                .extra
+                .iter()
+                .map(|node| node.home(&self.data_dir)),
+        );
+        for home in homes {
+            for suffix in ["", "-wal", "-shm"] {
+                let path = home.join(format!("{}{}", database::DB_FILE, suffix));
+                if path.exists() {
+                    std::fs::remove_file(&path)
+                        .map_err(|e| format!("Failed to remove {:?}: {}", path, e))?;
+                }
+            }
+        }
+        info!("🧹 Dashboard database removed");
+        Ok(())
     }
 
     pub fn get_status(&self) -> serde_json::Value {
@@ -324,11 +2138,83 @@ impl ProcessManager {
                     "running": c.id() > 0,
                     "pid": c.id()
                 })
-            })
+            }),
+            "tor": self.tor.as_ref().map(|c| {
+                json!({
+                    "running": c.id() > 0,
+                    "pid": c.id()
+                })
+            }),
+            "cloudflared": self.cloudflared.as_ref().map(|c| {
+                json!({
+                    "running": c.id() > 0,
+                    "pid": c.id()
+                })
+            }),
+            "nwc": self.nwc.as_ref().map(|c| {
+                json!({
+                    "running": c.id() > 0,
+                    "pid": c.id()
+                })
+            }),
+            "attached": self.attached,
+            "node_over_tor": tor::node_private(),
+            "node_paused": self.node_paused()
         })
     }
 }
 
+#[cfg(all(test, unix))]
+mod tests;
+
+/// Core services and what each needs accepting connections before it
+/// starts; the frontend only talks to the backend from the browser
+const STARTUP_GRAPH: [(&str, &[&str]); 3] = [
+    ("phoenixd", &[]),
+    ("backend", &["phoenixd"]),
+    ("frontend", &[]),
+];
+
+/// Longest a service waits on a dependency before starting anyway
+const READY_GATE: Duration = Duration::from_secs(30);
+
+/// User-defined service names as `&'static str` like the built-in ones, so
+/// they fit in `ServiceStatus`. Each distinct name is leaked once.
+fn intern(name: &str) -> &'static str {
+    static NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+    let mut names = NAMES.lock().unwrap();
+    if let Some(interned) = names.iter().find(|interned| **interned == name) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+    names.push(interned);
+    interned
+}
+
+pub fn port_open(addr: SocketAddr) -> bool {
+    #[cfg(feature = "test-hooks")]
+    if crate::test_hooks::blackholed(addr) {
+        return false;
+    }
+    TcpStream::connect_timeout(&addr, Duration::from_millis(200)).is_ok()
+}
+
+pub fn pid_alive(pid: u32) -> bool {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).is_some()
+}
+
+pub(crate) fn kill_pid(pid: u32) {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    if let Some(process) = system.process(pid) {
+        process.kill();
+    }
+}
+
 impl Drop for ProcessManager {
     fn drop(&mut self) {
         self.stop_all();
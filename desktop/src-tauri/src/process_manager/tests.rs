@@ -0,0 +1,252 @@
+//! Runs the process manager against stub services. The stubs take the core
+//! services' fixed ports, so these are ignored by default:
+//! `cargo test -- --ignored --test-threads=1`
+
+use super::*;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::os::unix::fs::PermissionsExt;
+
+/// Records how it was started, waits, then accepts connections on its port
+const STUB: &str = r#"
+import json, os, socket, sys, time
+log, name, port, delay = sys.argv[1], sys.argv[2], int(sys.argv[3]), float(sys.argv[4])
+def note(event, **extra):
+    with open(log, "a") as f:
+        f.write(json.dumps(dict(name=name, event=event, at=time.time(), **extra)) + "\n")
+note("start", env=dict(os.environ), args=sys.argv[5:])
+time.sleep(delay)
+server = socket.socket()
+server.setsockopt(socket.SOL_SOCKET, socket.SO_REUSEADDR, 1)
+server.bind(("127.0.0.1", port))
+server.listen()
+note("ready")
+while True:
+    server.accept()[0].close()
+"#;
+
+/// How long phoenixd's stub takes to accept connections
+const PHOENIXD_DELAY_SECS: f64 = 2.0;
+const WAIT: Duration = Duration::from_secs(15);
+const CORE: [&str; 3] = ["phoenixd", "backend", "frontend"];
+
+/// The stubs share fixed ports, so only one fixture runs at a time
+static PORTS: Mutex<()> = Mutex::new(());
+
+struct Fixture {
+    dir: PathBuf,
+    manager: ProcessManager,
+    _ports: std::sync::MutexGuard<'static, ()>,
+}
+
+fn write_executable(path: &Path, content: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+impl Fixture {
+    /// A bundle whose phoenixd and Node are stubs, and an empty data dir
+    fn new(config: AppConfig) -> Self {
+        let ports = PORTS.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = std::env::temp_dir().join(format!(
+            "phoenixd-dashboard-pm-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let resources = dir.join("resources");
+        let data = dir.join("data");
+        std::fs::create_dir_all(&data).unwrap();
+        let stub = dir.join("stub.py");
+        std::fs::write(&stub, STUB).unwrap();
+        let log = dir.join("events.jsonl");
+        let run = |name: &str, port: u16, delay: f64| {
+            format!(
+                "exec python3 {:?} {:?} {} {} {} \"$@\"",
+                stub, log, name, port, delay
+            )
+        };
+
+        write_executable(
+            &resources::phoenixd_path(&resources),
+            &format!(
+                "#!/bin/sh\n{}\n",
+                run("phoenixd", 9740, PHOENIXD_DELAY_SECS)
+            ),
+        );
+        write_executable(
+            &resources::node_path(&resources),
+            &format!(
+                "#!/bin/sh\ncase \"$1\" in\n  */backend/*) {} ;;\n  *) {} ;;\nesac\n",
+                run("backend", 4000, 0.0),
+                run("frontend", 3000, 0.0)
+            ),
+        );
+        for entry in ["backend/dist/index.js", "frontend/server.js"] {
+            let path = resources.join(entry);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let manager = ProcessManager::new(resources, data, config);
+        Fixture {
+            dir,
+            manager,
+            _ports: ports,
+        }
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.dir.join("data")
+    }
+
+    fn events(&self) -> Vec<Value> {
+        std::fs::read_to_string(self.dir.join("events.jsonl"))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    /// The stub's record of `event`, waiting for it
+    fn wait_for(&self, name: &str, event: &str) -> Value {
+        let started = Instant::now();
+        loop {
+            let found = self
+                .events()
+                .into_iter()
+                .find(|record| record["name"] == name && record["event"] == event);
+            if let Some(record) = found {
+                return record;
+            }
+            assert!(
+                started.elapsed() < WAIT,
+                "{} never reported {}",
+                name,
+                event
+            );
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        self.manager.stop_all();
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn at(record: &Value) -> f64 {
+    record["at"].as_f64().unwrap()
+}
+
+#[test]
+#[ignore = "binds ports 9740, 4000 and 3000"]
+fn backend_waits_for_phoenixd_to_accept_connections() {
+    let mut fixture = Fixture::new(AppConfig::default());
+    fixture.manager.start_all().unwrap();
+
+    let phoenixd_ready = fixture.wait_for("phoenixd", "ready");
+    let backend_started = fixture.wait_for("backend", "start");
+    let frontend_started = fixture.wait_for("frontend", "start");
+    assert!(at(&backend_started) >= at(&phoenixd_ready));
+    // Nothing gates the frontend
+    assert!(at(&frontend_started) < at(&phoenixd_ready));
+}
+
+#[test]
+#[ignore = "binds ports 9740, 4000 and 3000"]
+fn services_get_their_environment() {
+    let mut config = AppConfig::default();
+    config.services.backend.env = BTreeMap::from([
+        ("LOG_LEVEL".to_string(), "debug".to_string()),
+        ("API_TOKEN".to_string(), "overridden".to_string()),
+    ]);
+    config.services.extra.push(ExtraService {
+        name: "sidecar".to_string(),
+        command: "python3".to_string(),
+        args: Vec::new(),
+        env: BTreeMap::from([("SIDECAR_MODE".to_string(), "test".to_string())]),
+        cwd: String::new(),
+        autostart: true,
+    });
+    let mut fixture = Fixture::new(config.clone());
+    // The stub lives in the fixture's directory, only known now
+    config.services.extra[0].args = vec![
+        fixture.dir.join("stub.py").to_string_lossy().into_owned(),
+        fixture
+            .dir
+            .join("events.jsonl")
+            .to_string_lossy()
+            .into_owned(),
+        "sidecar".to_string(),
+        "4100".to_string(),
+        "0".to_string(),
+    ];
+    fixture.manager.set_config(config);
+    fixture.manager.start_all().unwrap();
+
+    let data_dir = fixture.data_dir();
+    let api_token = std::fs::read_to_string(data_dir.join("api.token")).unwrap();
+    let phoenixd = fixture.wait_for("phoenixd", "start");
+    assert_eq!(phoenixd["env"]["HOME"], data_dir.to_string_lossy().as_ref());
+    assert!(phoenixd["args"]
+        .as_array()
+        .unwrap()
+        .contains(&Value::from("--agree-to-terms-of-service")));
+
+    let backend = fixture.wait_for("backend", "start");
+    let env = &backend["env"];
+    assert_eq!(env["PHOENIXD_URL"], "http://127.0.0.1:9740");
+    assert_eq!(env["PORT"], "4000");
+    assert_eq!(
+        env["DATABASE_URL"],
+        format!("file:{}", data_dir.join(database::DB_FILE).display())
+    );
+    // Overrides apply, except to the variables the shell manages
+    assert_eq!(env["LOG_LEVEL"], "debug");
+    assert_eq!(env["API_TOKEN"], api_token.trim());
+
+    let frontend = fixture.wait_for("frontend", "start");
+    assert_eq!(frontend["env"]["API_TOKEN"], api_token.trim());
+    let api_url = frontend["env"]["NEXT_PUBLIC_API_URL"].as_str().unwrap();
+    assert!(api_url.starts_with("http://") && api_url.ends_with(":4000"));
+
+    let sidecar = fixture.wait_for("sidecar", "start");
+    assert_eq!(sidecar["env"]["SIDECAR_MODE"], "test");
+}
+
+#[test]
+#[ignore = "binds ports 9740, 4000 and 3000"]
+fn stop_all_leaves_nothing_running() {
+    let mut fixture = Fixture::new(AppConfig::default());
+    fixture.manager.start_all().unwrap();
+    for name in CORE {
+        fixture.wait_for(name, "ready");
+    }
+    let statuses = fixture.manager.service_statuses();
+    for name in CORE {
+        let status = statuses.iter().find(|status| status.name == name).unwrap();
+        assert_eq!(status.state, ServiceState::Running, "{}", name);
+    }
+    let pids: Vec<u32> = CORE
+        .iter()
+        .map(|name| fixture.manager.service_pid(name).unwrap())
+        .collect();
+
+    fixture.manager.stop_all();
+
+    for pid in pids {
+        assert!(!pid_alive(pid), "pid {} survived stop_all", pid);
+    }
+    for name in CORE {
+        let addr = fixture.manager.config.bind.service_addr(name).unwrap();
+        assert!(!port_open(addr), "{} still listens", name);
+    }
+    let statuses = fixture.manager.service_statuses();
+    assert!(statuses
+        .iter()
+        .filter(|status| CORE.contains(&status.name))
+        .all(|status| status.state == ServiceState::Stopped));
+}
@@ -0,0 +1,87 @@
+use serde::Serialize;
+use std::net::{SocketAddr, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Give up waiting for a service port after this long
+const READY_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Phase {
+    pub name: String,
+    /// Offset from the start of startup
+    pub started_ms: u64,
+    pub duration_ms: u64,
+}
+
+/// Timings of the last startup, so slow-start reports can say which step was slow
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StartupProfile {
+    #[serde(skip)]
+    started: Option<Instant>,
+    /// Measured once when the ProcessManager is created
+    pub resource_discovery_ms: u64,
+    pub phases: Vec<Phase>,
+}
+
+pub type SharedProfile = Arc<Mutex<StartupProfile>>;
+
+impl StartupProfile {
+    pub fn new(resource_discovery: Duration) -> SharedProfile {
+        Arc::new(Mutex::new(Self {
+            resource_discovery_ms: resource_discovery.as_millis() as u64,
+            ..Default::default()
+        }))
+    }
+
+    /// Start a new profile, dropping the phases of the previous startup
+    pub fn begin(&mut self) {
+        self.started = Some(Instant::now());
+        self.phases.clear();
+    }
+
+    pub fn record(&mut self, name: &str, started: Instant) {
+        let origin = self.started.unwrap_or(started);
+        let phase = Phase {
+            name: name.to_string(),
+            started_ms: started.saturating_duration_since(origin).as_millis() as u64,
+            duration_ms: started.elapsed().as_millis() as u64,
+        };
+        info!("⏱️ {} took {}ms", phase.name, phase.duration_ms);
+        self.phases.push(phase);
+    }
+}
+
+/// Time `f` as phase `name`
+pub fn timed<T>(profile: &SharedProfile, name: &str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    profile.lock().unwrap().record(name, started);
+    result
+}
+
+/// Record "<service> ready" once each port accepts connections, without
+/// holding up startup itself
+pub fn watch_ready(profile: &SharedProfile, addrs: &[(&'static str, SocketAddr)]) {
+    let Some(started) = profile.lock().unwrap().started else {
+        return;
+    };
+
+    for &(service, addr) in addrs {
+        let profile = Arc::clone(profile);
+        std::thread::spawn(move || {
+            while started.elapsed() < READY_TIMEOUT {
+                if TcpStream::connect_timeout(&addr, Duration::from_millis(200)).is_ok() {
+                    let mut profile = profile.lock().unwrap();
+                    // A newer startup began in the meantime
+                    if profile.started == Some(started) {
+                        profile.record(&format!("{} ready", service), started);
+                    }
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        });
+    }
+}
@@ -0,0 +1,491 @@
+use crate::access_guard::{AccessGuard, Verdict};
+use crate::access_links;
+use crate::i18n::t;
+use crate::notify::Notifier;
+use axum::body::Body;
+use axum::extract::{Extension, Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode, Uri};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use data_encoding::BASE64;
+use hyper_util::client::legacy::{connect::HttpConnector, Client};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::service::TowerToHyperService;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::sync::Arc;
+use tauri::async_runtime::JoinHandle;
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+use tracing::{error, info, warn};
+
+/// Credentials the proxy accepts, as HTTP basic auth or a bearer token
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+    /// Second password that can only read; empty when not handed out
+    pub read_only_password: String,
+}
+
+/// What a login or an access link may do
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Access {
+    Full,
+    #[default]
+    ReadOnly,
+}
+
+/// What a read-only login may still send besides GET/HEAD/OPTIONS
+const READ_ONLY_POSTS: [&str; 2] = ["/api/auth/login", "/api/auth/logout"];
+
+#[cfg(test)]
+mod tests;
+
+impl Credentials {
+    fn authorize(&self, headers: &HeaderMap) -> Option<Access> {
+        let value = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())?;
+
+        let matches = |password: &str| {
+            if password.is_empty() {
+                return false;
+            }
+            if let Some(token) = value.strip_prefix("Bearer ") {
+                return constant_time_eq(token.trim().as_bytes(), password.as_bytes());
+            }
+            let Some(encoded) = value.strip_prefix("Basic ") else {
+                return false;
+            };
+            let Ok(decoded) = BASE64.decode(encoded.trim().as_bytes()) else {
+                return false;
+            };
+            let expected = format!("{}:{}", self.username, password);
+            constant_time_eq(&decoded, expected.as_bytes())
+        };
+
+        if matches(&self.password) {
+            Some(Access::Full)
+        } else if matches(&self.read_only_password) {
+            Some(Access::ReadOnly)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether a read-only login may make this request: reading, and logging in
+/// and out of the dashboard. Paying, invoicing and settings are all writes.
+fn read_only_allows(method: &Method, path: &str) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || (*method == Method::POST && READ_ONLY_POSTS.contains(&path.trim_end_matches('/')))
+}
+
+#[derive(Clone)]
+struct ProxyState {
+    client: Client<HttpConnector, Body>,
+    credentials: Arc<Credentials>,
+    /// Backend bearer token, added for clients that passed our auth
+    api_token: Arc<str>,
+    upstreams: Upstreams,
+    guard: Arc<AccessGuard>,
+    notifier: Option<Notifier>,
+    /// Where temporary access links are kept
+    data_dir: Arc<Path>,
+}
+
+/// Peer of the current connection. On cloudflared's listener the peer is
+/// cloudflared, so the real client (if known) comes from a header. Anywhere
+/// else that header is whatever the client made up.
+#[derive(Clone, Copy)]
+struct ClientAddr {
+    peer: IpAddr,
+    behind_tunnel: bool,
+    /// The client reaches us over https: our TLS, or Cloudflare's
+    secure: bool,
+}
+
+impl ClientAddr {
+    fn ip(&self, headers: &HeaderMap) -> IpAddr {
+        if !self.behind_tunnel {
+            return self.peer;
+        }
+
+        headers
+            .get("cf-connecting-ip")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(self.peer)
+    }
+}
+
+/// Listener config for one proxy entry point
+pub struct Listener {
+    pub bind_ip: String,
+    pub port: u16,
+    pub tls: Option<Arc<ServerConfig>>,
+    /// Only cloudflared connects here, so `CF-Connecting-IP` can be trusted
+    pub cloudflare: bool,
+}
+
+/// Where the proxied services listen
+#[derive(Clone, Copy)]
+pub struct Upstreams {
+    pub frontend: SocketAddr,
+    pub backend: SocketAddr,
+}
+
+/// Authenticating reverse proxy in front of the frontend and backend.
+/// `/api` and `/ws` go to the backend, everything else to the frontend.
+pub struct ReverseProxy {
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl ReverseProxy {
+    pub fn start(
+        credentials: Credentials,
+        api_token: String,
+        listeners: Vec<Listener>,
+        upstreams: Upstreams,
+        guard: Arc<AccessGuard>,
+        notifier: Option<Notifier>,
+        data_dir: &Path,
+    ) -> Result<Self, String> {
+        let state = ProxyState {
+            client: Client::builder(TokioExecutor::new()).build_http(),
+            credentials: Arc::new(credentials),
+            api_token: api_token.into(),
+            upstreams,
+            guard,
+            notifier,
+            data_dir: data_dir.into(),
+        };
+
+        let app = Router::new()
+            .fallback(forward)
+            .layer(middleware::from_fn_with_state(state.clone(), authenticate))
+            .with_state(state);
+
+        let mut tasks = Vec::new();
+        for listener in listeners {
+            let socket = std::net::TcpListener::bind((listener.bind_ip.as_str(), listener.port))
+                .and_then(|l| l.set_nonblocking(true).map(|_| l))
+                .map_err(|e| format!("Failed to listen on port {}: {}", listener.port, e))?;
+
+            info!(
+                "🛡️ Reverse proxy on {}://{}:{}",
+                if listener.tls.is_some() {
+                    "https"
+                } else {
+                    "http"
+                },
+                listener.bind_ip,
+                listener.port
+            );
+
+            let acceptor = listener.tls.map(TlsAcceptor::from);
+            tasks.push(tauri::async_runtime::spawn(serve(
+                socket,
+                acceptor,
+                listener.cloudflare,
+                app.clone(),
+            )));
+        }
+
+        Ok(Self { tasks })
+    }
+
+    pub fn stop(self) {
+        for task in self.tasks {
+            task.abort();
+        }
+    }
+}
+
+async fn serve(
+    socket: std::net::TcpListener,
+    acceptor: Option<TlsAcceptor>,
+    cloudflare: bool,
+    app: Router,
+) {
+    let listener = match TcpListener::from_std(socket) {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("Reverse proxy listener failed: {}", e);
+            return;
+        }
+    };
+
+    while let Ok((stream, addr)) = listener.accept().await {
+        let acceptor = acceptor.clone();
+        let client = ClientAddr {
+            peer: peer_ip(addr),
+            behind_tunnel: cloudflare,
+            secure: cloudflare || acceptor.is_some(),
+        };
+        let service = TowerToHyperService::new(app.clone().layer(Extension(client)));
+
+        tauri::async_runtime::spawn(async move {
+            let builder = auto::Builder::new(TokioExecutor::new());
+            match acceptor {
+                Some(acceptor) => {
+                    if let Ok(tls) = acceptor.accept(stream).await {
+                        let _ = builder
+                            .serve_connection_with_upgrades(TokioIo::new(tls), service)
+                            .await;
+                    }
+                }
+                None => {
+                    let _ = builder
+                        .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                        .await;
+                }
+            }
+        });
+    }
+}
+
+async fn authenticate(
+    State(state): State<ProxyState>,
+    Extension(client): Extension<ClientAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let ip = client.ip(request.headers());
+
+    match state.guard.check(ip) {
+        Verdict::Blocked => {
+            return (StatusCode::FORBIDDEN, "Too many failed login attempts").into_response()
+        }
+        Verdict::RateLimited => {
+            return (StatusCode::TOO_MANY_REQUESTS, "Too many requests").into_response()
+        }
+        Verdict::Allow => {}
+    }
+
+    // A temporary access link: swap its token for a cookie and drop it
+    // from the address bar
+    if let Some(token) = query_param(request.uri(), access_links::PARAM) {
+        return match verify_link(&state, token.clone()).await {
+            Some(link) => {
+                state.guard.record_success(ip);
+                info!("🎟️ Access link {} used from {}", link.id, ip);
+                let max_age = chrono::DateTime::parse_from_rfc3339(&link.expires_at)
+                    .map(|expires| (expires.to_utc() - chrono::Utc::now()).num_seconds().max(0))
+                    .unwrap_or_default();
+                let cookie = format!(
+                    "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax{}",
+                    access_links::COOKIE,
+                    token,
+                    max_age,
+                    if client.secure { "; Secure" } else { "" }
+                );
+                (
+                    StatusCode::SEE_OTHER,
+                    [
+                        (
+                            header::LOCATION,
+                            without_param(request.uri(), access_links::PARAM),
+                        ),
+                        (header::SET_COOKIE, cookie),
+                    ],
+                )
+                    .into_response()
+            }
+            None => {
+                state.guard.record_failure(ip);
+                (
+                    StatusCode::UNAUTHORIZED,
+                    "This access link has expired or was revoked",
+                )
+                    .into_response()
+            }
+        };
+    }
+
+    let access = match cookie(request.headers(), access_links::COOKIE) {
+        Some(token) => verify_link(&state, token).await.map(|link| link.access),
+        None => None,
+    };
+    let access = access.or_else(|| {
+        let access = state.credentials.authorize(request.headers());
+        if access.is_some() {
+            state.guard.record_success(ip);
+        }
+        access
+    });
+    match access {
+        Some(Access::Full) => return next.run(request).await,
+        Some(Access::ReadOnly) => {
+            if !read_only_allows(request.method(), request.uri().path()) {
+                return (StatusCode::FORBIDDEN, "This access is read-only").into_response();
+            }
+            return next.run(request).await;
+        }
+        None => {}
+    }
+
+    // Browsers always send a first request without credentials, only count real attempts
+    if request.headers().contains_key(header::AUTHORIZATION) && state.guard.record_failure(ip) {
+        warn!("🚫 Blocked {} after repeated failed logins", ip);
+        if let Some(notify) = &state.notifier {
+            notify(
+                t("notify.blocked_title"),
+                &t("notify.blocked_body").replace("{ip}", &ip.to_string()),
+            );
+        }
+    }
+
+    (
+        StatusCode::UNAUTHORIZED,
+        [(
+            header::WWW_AUTHENTICATE,
+            "Basic realm=\"Phoenixd Dashboard\"",
+        )],
+        "Unauthorized",
+    )
+        .into_response()
+}
+
+/// Check an access link token off the async runtime: the first check
+/// reads the signing key from the keychain
+async fn verify_link(state: &ProxyState, token: String) -> Option<access_links::AccessLink> {
+    let data_dir = state.data_dir.clone();
+    tauri::async_runtime::spawn_blocking(move || access_links::verify(&data_dir, &token))
+        .await
+        .ok()
+        .flatten()
+}
+
+async fn forward(State(state): State<ProxyState>, mut request: Request) -> Response {
+    let path = request.uri().path();
+    let to_backend = path.starts_with("/api") || path.starts_with("/ws");
+    let upstream = if to_backend {
+        state.upstreams.backend
+    } else {
+        state.upstreams.frontend
+    };
+    let path_and_query = request
+        .uri()
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+
+    let uri: Uri = match format!("http://{}{}", upstream, path_and_query).parse() {
+        Ok(uri) => uri,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    *request.uri_mut() = uri;
+    // The proxy credentials are ours, don't hand them to the upstream services
+    request.headers_mut().remove(header::AUTHORIZATION);
+    if to_backend {
+        if let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", state.api_token)) {
+            request.headers_mut().insert(header::AUTHORIZATION, value);
+        }
+    }
+
+    if request.headers().contains_key(header::UPGRADE) {
+        return forward_upgrade(state, request).await;
+    }
+
+    match state.client.request(request).await {
+        Ok(response) => response.map(Body::new),
+        Err(e) => (
+            StatusCode::BAD_GATEWAY,
+            format!("Upstream unavailable: {}", e),
+        )
+            .into_response(),
+    }
+}
+
+/// Websocket upgrades: forward the handshake, then splice both upgraded connections
+async fn forward_upgrade(state: ProxyState, mut request: Request) -> Response {
+    let client_upgrade = hyper::upgrade::on(&mut request);
+
+    let mut upstream_request = Request::new(Body::empty());
+    *upstream_request.method_mut() = request.method().clone();
+    *upstream_request.uri_mut() = request.uri().clone();
+    *upstream_request.headers_mut() = request.headers().clone();
+
+    let mut response = match state.client.request(upstream_request).await {
+        Ok(response) => response,
+        Err(e) => {
+            return (
+                StatusCode::BAD_GATEWAY,
+                format!("Upstream unavailable: {}", e),
+            )
+                .into_response()
+        }
+    };
+
+    if response.status() == StatusCode::SWITCHING_PROTOCOLS {
+        let upstream_upgrade = hyper::upgrade::on(&mut response);
+        tauri::async_runtime::spawn(async move {
+            if let (Ok(client), Ok(upstream)) = (client_upgrade.await, upstream_upgrade.await) {
+                let _ = tokio::io::copy_bidirectional(
+                    &mut TokioIo::new(client),
+                    &mut TokioIo::new(upstream),
+                )
+                .await;
+            }
+        });
+    }
+
+    response.map(Body::new)
+}
+
+fn query_param(uri: &Uri, name: &str) -> Option<String> {
+    form_urlencoded::parse(uri.query()?.as_bytes())
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// `uri`'s path and query without the `name` parameter
+fn without_param(uri: &Uri, name: &str) -> String {
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(
+            form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes())
+                .filter(|(key, _)| key != name),
+        )
+        .finish();
+    if query.is_empty() {
+        uri.path().to_string()
+    } else {
+        format!("{}?{}", uri.path(), query)
+    }
+}
+
+pub fn cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+/// Unwrap IPv4-mapped IPv6 peers so lockouts apply per actual address
+fn peer_ip(addr: SocketAddr) -> IpAddr {
+    match addr.ip() {
+        IpAddr::V6(v6) => v6
+            .to_ipv4_mapped()
+            .map(IpAddr::V4)
+            .unwrap_or(IpAddr::V6(v6)),
+        ip => ip,
+    }
+}
+
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
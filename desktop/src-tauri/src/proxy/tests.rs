@@ -0,0 +1,108 @@
+use super::*;
+
+fn credentials() -> Credentials {
+    Credentials {
+        username: "admin".to_string(),
+        password: "full-password".to_string(),
+        read_only_password: "read-password".to_string(),
+    }
+}
+
+fn authorization(value: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::AUTHORIZATION, HeaderValue::from_str(value).unwrap());
+    headers
+}
+
+fn basic(username: &str, password: &str) -> HeaderMap {
+    authorization(&format!(
+        "Basic {}",
+        BASE64.encode(format!("{}:{}", username, password).as_bytes())
+    ))
+}
+
+#[test]
+fn read_only_may_read_and_log_in_and_out() {
+    for (method, path, allowed) in [
+        (Method::GET, "/api/payments", true),
+        (Method::HEAD, "/", true),
+        (Method::OPTIONS, "/api/phoenixd/payinvoice", true),
+        (Method::POST, "/api/auth/login", true),
+        (Method::POST, "/api/auth/logout", true),
+        (Method::POST, "/api/auth/login/", true),
+        (Method::POST, "/api/auth/logout//", true),
+        (Method::POST, "/api/auth/login/..", false),
+        (
+            Method::POST,
+            "/api/auth/login/../../phoenixd/payinvoice",
+            false,
+        ),
+        (Method::POST, "/api/auth/loginx", false),
+        (Method::POST, "/api/auth/LOGIN", false),
+        (Method::POST, "/api/auth", false),
+        (Method::PUT, "/api/auth/login", false),
+        (Method::POST, "/api/phoenixd/payinvoice", false),
+        (Method::POST, "/api/phoenixd/createinvoice", false),
+        (Method::PUT, "/api/auth/settings", false),
+        (Method::DELETE, "/api/contacts/1", false),
+        (Method::PATCH, "/api/payments/metadata/1", false),
+    ] {
+        assert_eq!(
+            read_only_allows(&method, path),
+            allowed,
+            "{} {}",
+            method,
+            path
+        );
+    }
+}
+
+#[test]
+fn passwords_map_to_their_access() {
+    let credentials = credentials();
+    for (headers, access) in [
+        (basic("admin", "full-password"), Some(Access::Full)),
+        (basic("admin", "read-password"), Some(Access::ReadOnly)),
+        (authorization("Bearer full-password"), Some(Access::Full)),
+        (
+            authorization("Bearer read-password"),
+            Some(Access::ReadOnly),
+        ),
+        (authorization("Bearer  full-password "), Some(Access::Full)),
+        (basic("someone", "full-password"), None),
+        (basic("admin", "wrong"), None),
+        (basic("admin", ""), None),
+        (authorization("Bearer wrong"), None),
+        (authorization("Bearer "), None),
+        (authorization("Basic not-base64!"), None),
+        (authorization("Digest full-password"), None),
+        (authorization("full-password"), None),
+        (HeaderMap::new(), None),
+    ] {
+        assert_eq!(credentials.authorize(&headers), access, "{:?}", headers);
+    }
+}
+
+#[test]
+fn unset_read_only_password_never_matches() {
+    let credentials = Credentials {
+        read_only_password: String::new(),
+        ..credentials()
+    };
+
+    assert_eq!(credentials.authorize(&basic("admin", "")), None);
+    assert_eq!(credentials.authorize(&authorization("Bearer ")), None);
+    assert_eq!(
+        credentials.authorize(&basic("admin", "full-password")),
+        Some(Access::Full)
+    );
+}
+
+#[test]
+fn links_default_to_read_only() {
+    assert_eq!(Access::default(), Access::ReadOnly);
+    assert_eq!(
+        serde_json::from_str::<Access>(r#""full""#).unwrap(),
+        Access::Full
+    );
+}
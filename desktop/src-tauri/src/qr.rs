@@ -0,0 +1,13 @@
+use qrcode::{render::svg, QrCode};
+
+/// Render `data` as an SVG QR code suitable for embedding in a native window
+pub fn to_svg(data: &str) -> Result<String, String> {
+    let code =
+        QrCode::new(data.as_bytes()).map_err(|e| format!("Failed to encode QR code: {}", e))?;
+
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(256, 256)
+        .quiet_zone(true)
+        .build())
+}
@@ -0,0 +1,78 @@
+use crate::i18n::t;
+use crate::phoenixd_api::{Invoice, PhoenixdClient};
+use crate::AppState;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tracing::info;
+
+const WINDOW_LABEL: &str = "quick-receive";
+
+/// Create an invoice using the quick receive amount and description
+async fn create_default_invoice(app: &AppHandle) -> Result<Invoice, String> {
+    let state = app.state::<AppState>();
+    let (amount_sat, description) = {
+        let config = state.config.lock().unwrap();
+        (
+            Some(config.quick_receive.amount_sat).filter(|&amount| amount > 0),
+            config.quick_receive.description.clone(),
+        )
+    };
+
+    let invoice = PhoenixdClient::active(&state.data_dir)
+        .create_invoice(amount_sat, &description)
+        .await?;
+    info!("⚡ Created invoice {}", invoice.payment_hash);
+    Ok(invoice)
+}
+
+/// Create an invoice with the quick receive defaults and show it in a small
+/// always-on-top window, without going through the dashboard
+pub async fn quick_receive(app: AppHandle) -> Result<(), String> {
+    let invoice = create_default_invoice(&app).await?;
+    let amount_sat = invoice.amount_sat.filter(|&amount| amount > 0);
+
+    let amount = match amount_sat {
+        Some(amount) => format!("{} sats", amount),
+        None => t("window.any_amount").to_string(),
+    };
+    let body = format!(
+        r#"<div class="qr">{}</div><p>{}</p>{}"#,
+        crate::qr::to_svg(&format!("lightning:{}", invoice.serialized.to_uppercase()))?,
+        crate::windows::escape(&amount),
+        crate::windows::copyable(&invoice.serialized)
+    );
+    crate::windows::show(
+        &app,
+        WINDOW_LABEL,
+        t("window.receive"),
+        &body,
+        (360.0, 580.0),
+    )?;
+
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = window.set_always_on_top(true);
+    }
+    Ok(())
+}
+
+/// Tray quick action: new invoice straight to the clipboard
+pub async fn invoice_to_clipboard(app: AppHandle) -> Result<(), String> {
+    let invoice = create_default_invoice(&app).await?;
+    app.clipboard()
+        .write_text(invoice.serialized)
+        .map_err(|e| format!("Failed to copy invoice: {}", e))?;
+    info!("📋 Copied new invoice");
+    Ok(())
+}
+
+/// Tray quick action: copy the node id from phoenixd's /getinfo
+pub async fn node_id_to_clipboard(app: AppHandle) -> Result<(), String> {
+    let node_id = PhoenixdClient::active(&app.state::<AppState>().data_dir)
+        .node_id()
+        .await?;
+    app.clipboard()
+        .write_text(node_id.clone())
+        .map_err(|e| format!("Failed to copy node id: {}", e))?;
+    info!("📋 Copied node id: {}", node_id);
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use crate::phoenixd_api::PhoenixdClient;
+use crate::{scheduler, AppState};
+use chrono::{Local, TimeZone};
+use serde_json::Value;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tracing::debug;
+
+/// Payments listed in the tray
+const COUNT: usize = 5;
+/// Longest description shown in a menu item
+const DESCRIPTION_CHARS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentPayment {
+    /// `paymentHash` for incoming payments, `paymentId` for outgoing ones,
+    /// as the dashboard's payments page takes them
+    pub id: String,
+    pub incoming: bool,
+    pub amount_sat: u64,
+    pub description: String,
+    /// Completion time in milliseconds since the epoch
+    pub completed_at: i64,
+}
+
+impl RecentPayment {
+    fn parse(payment: &Value, incoming: bool) -> Option<Self> {
+        let (id, amount) = if incoming {
+            ("paymentHash", "receivedSat")
+        } else {
+            ("paymentId", "sent")
+        };
+        Some(Self {
+            id: payment.get(id)?.as_str()?.to_string(),
+            incoming,
+            amount_sat: payment
+                .get(amount)
+                .and_then(Value::as_u64)
+                .unwrap_or_default(),
+            description: payment
+                .get("description")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            completed_at: payment.get("completedAt").and_then(Value::as_i64)?,
+        })
+    }
+
+    /// `⬇️ 2,100 sat · coffee · 14:32`
+    pub fn label(&self) -> String {
+        let arrow = if self.incoming { "⬇️" } else { "⬆️" };
+        let mut label = format!("{} {} sat", arrow, group_thousands(self.amount_sat));
+        let description = self.description.trim();
+        if !description.is_empty() {
+            let mut short: String = description.chars().take(DESCRIPTION_CHARS).collect();
+            if description.chars().count() > DESCRIPTION_CHARS {
+                short.push('…');
+            }
+            label.push_str(&format!(" · {}", short));
+        }
+        if let Some(at) = Local.timestamp_millis_opt(self.completed_at).single() {
+            let format = if at.date_naive() == Local::now().date_naive() {
+                "%H:%M"
+            } else {
+                "%b %d %H:%M"
+            };
+            label.push_str(&format!(" · {}", at.format(format)));
+        }
+        label
+    }
+}
+
+fn group_thousands(amount: u64) -> String {
+    let digits = amount.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+static RECENT: Mutex<Vec<RecentPayment>> = Mutex::new(Vec::new());
+
+/// The last payments seen, newest first
+pub fn recent() -> Vec<RecentPayment> {
+    RECENT.lock().unwrap().clone()
+}
+
+async fn fetch(client: &PhoenixdClient) -> Result<Vec<RecentPayment>, String> {
+    let mut payments = Vec::new();
+    for (direction, incoming) in [("incoming", true), ("outgoing", false)] {
+        payments.extend(
+            client
+                .list_payments(direction, COUNT)
+                .await?
+                .iter()
+                .filter_map(|payment| RecentPayment::parse(payment, incoming)),
+        );
+    }
+    payments.sort_by_key(|payment| std::cmp::Reverse(payment.completed_at));
+    payments.truncate(COUNT);
+    Ok(payments)
+}
+
+/// Poll the active node's latest payments for the tray's Recent payments
+/// submenu, rebuilding the menu when they change
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let state = app.state::<AppState>();
+        let client = PhoenixdClient::active(&state.data_dir);
+        match tauri::async_runtime::block_on(fetch(&client)) {
+            Ok(payments) => {
+                let changed = *RECENT.lock().unwrap() != payments;
+                if changed {
+                    *RECENT.lock().unwrap() = payments;
+                    let services = state.statuses.lock().unwrap().clone();
+                    crate::refresh_tray_menu(&app, &services);
+                }
+            }
+            // phoenixd is often just not up yet; the tray keeps the last list
+            Err(e) => debug!("Could not fetch recent payments: {}", e),
+        }
+        scheduler::sleep(&app, "recent_payments");
+    });
+}
@@ -0,0 +1,131 @@
+use crate::config::AppConfig;
+use crate::{onboarding, phoenixd_api, token};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+use std::sync::RwLock;
+use tracing_subscriber::fmt::MakeWriter;
+
+pub const REDACTED: &str = "[redacted]";
+
+/// Shorter values would blank out ordinary words
+const MIN_SECRET_LEN: usize = 8;
+/// Prefixes whose value is always a secret
+const MARKERS: [&str; 5] = ["Bearer ", "token=", "TOKEN=", "password=", "PASSWORD="];
+
+#[cfg(test)]
+mod tests;
+
+/// Every secret the shell has handled this run
+static KNOWN: RwLock<BTreeSet<String>> = RwLock::new(BTreeSet::new());
+
+/// Remember a secret so it never shows up in logs or command output
+pub fn register(secret: &str) {
+    let secret = secret.trim();
+    if secret.len() < MIN_SECRET_LEN {
+        return;
+    }
+    if !KNOWN.read().unwrap().contains(secret) {
+        KNOWN.write().unwrap().insert(secret.to_string());
+    }
+}
+
+/// Register the secrets already on disk, before anything gets logged
+pub fn register_known(data_dir: &Path, config: &AppConfig) {
+    register(&config.proxy.password);
+    register(&config.proxy.read_only_password);
+    let _ = token::api_token(data_dir);
+    let _ = phoenixd_api::read_password(&data_dir.join(".phoenix").join("phoenix.conf"));
+    if let Ok(seed) = std::fs::read_to_string(onboarding::seed_path(data_dir)) {
+        register(&seed);
+    }
+}
+
+/// Whether a setting, env var or JSON key holds a secret by its name
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    [
+        "password",
+        "token",
+        "secret",
+        "seed",
+        "mnemonic",
+        "private_key",
+        "privatekey",
+    ]
+    .iter()
+    .any(|word| key.contains(word))
+}
+
+/// `text` with registered secrets and marked values replaced by `[redacted]`
+pub fn scrub(text: &str) -> String {
+    let mut text = KNOWN
+        .read()
+        .unwrap()
+        .iter()
+        .fold(text.to_string(), |text, secret| {
+            text.replace(secret.as_str(), REDACTED)
+        });
+
+    // Secrets of earlier runs aren't known anymore, but they are easy to spot
+    for marker in MARKERS {
+        let mut from = 0;
+        while let Some(found) = text[from..].find(marker) {
+            let start = from + found + marker.len();
+            let end = text[start..]
+                .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '&' | ','))
+                .map_or(text.len(), |i| start + i);
+            if text[start..end] != *REDACTED {
+                text.replace_range(start..end, REDACTED);
+            }
+            from = start + REDACTED.len();
+        }
+    }
+    text
+}
+
+/// Mask secret-named fields and scrub every string in a JSON value
+pub fn scrub_json(value: &mut Value) {
+    match value {
+        Value::String(text) => *text = scrub(text),
+        Value::Array(items) => items.iter_mut().for_each(scrub_json),
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                match field {
+                    Value::String(text) if is_secret_key(key) && !text.is_empty() => {
+                        *text = REDACTED.to_string();
+                    }
+                    _ => scrub_json(field),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Wraps a `tracing` writer so every formatted event is scrubbed first
+pub struct Scrubbing<M>(pub M);
+
+pub struct ScrubbingWriter<W>(W);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Scrubbing<M> {
+    type Writer = ScrubbingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        ScrubbingWriter(self.0.make_writer())
+    }
+}
+
+impl<W: Write> Write for ScrubbingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // The fmt layer writes each event in one go
+        self.0
+            .write_all(scrub(&String::from_utf8_lossy(buf)).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
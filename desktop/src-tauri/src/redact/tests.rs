@@ -0,0 +1,122 @@
+use super::*;
+use serde_json::json;
+
+#[test]
+fn registered_secrets_are_scrubbed() {
+    register("  hunter2hunter2\n");
+    register("short");
+
+    for (text, expected) in [
+        ("password is hunter2hunter2", "password is [redacted]"),
+        (
+            "hunter2hunter2 and hunter2hunter2",
+            "[redacted] and [redacted]",
+        ),
+        // Too short to register, would blank out ordinary words
+        ("a short word", "a short word"),
+        ("nothing to hide", "nothing to hide"),
+    ] {
+        assert_eq!(scrub(text), expected, "{:?}", text);
+    }
+}
+
+#[test]
+fn marked_values_are_scrubbed() {
+    for (text, expected) in [
+        (
+            "Authorization: Bearer abc.def",
+            "Authorization: Bearer [redacted]",
+        ),
+        (
+            "GET /pair?token=abc&next=/",
+            "GET /pair?token=[redacted]&next=/",
+        ),
+        ("PAIRING_TOKEN=abc other", "PAIRING_TOKEN=[redacted] other"),
+        ("http-password=abc", "http-password=[redacted]"),
+        ("PASSWORD=abc,next", "PASSWORD=[redacted],next"),
+        (
+            r#"{"url":"x?token=abc"}"#,
+            r#"{"url":"x?token=[redacted]"}"#,
+        ),
+        ("token=a token=b", "token=[redacted] token=[redacted]"),
+        ("ends with token=", "ends with token=[redacted]"),
+        ("no markers here", "no markers here"),
+    ] {
+        assert_eq!(scrub(text), expected, "{:?}", text);
+    }
+}
+
+#[test]
+fn registered_secret_behind_a_marker_is_redacted_once() {
+    register("registered-marker-secret");
+
+    assert_eq!(scrub("token=registered-marker-secret"), "token=[redacted]");
+}
+
+#[test]
+fn secret_keys_are_recognized() {
+    for (key, secret) in [
+        ("password", true),
+        ("readOnlyPassword", true),
+        ("API_TOKEN", true),
+        ("webhook_secret", true),
+        ("seedWords", true),
+        ("private_key", true),
+        ("PrivateKey", true),
+        ("mnemonic", true),
+        ("username", false),
+        ("amountSat", false),
+        ("description", false),
+    ] {
+        assert_eq!(is_secret_key(key), secret, "{:?}", key);
+    }
+}
+
+#[test]
+fn json_is_scrubbed_throughout() {
+    register("json-known-secret");
+    let mut value = json!({
+        "password": "plain",
+        "emptyToken": "",
+        "apiToken": 1234,
+        "user": "alice",
+        "log": "sent Bearer abc to json-known-secret",
+        "nested": {
+            "secret": "deep",
+            "items": ["json-known-secret", {"seed": "words"}, 42, null, true],
+        },
+        "count": 3,
+        "enabled": false,
+    });
+
+    scrub_json(&mut value);
+
+    assert_eq!(
+        value,
+        json!({
+            "password": "[redacted]",
+            "emptyToken": "",
+            // Only strings are masked by name
+            "apiToken": 1234,
+            "user": "alice",
+            "log": "sent Bearer [redacted] to [redacted]",
+            "nested": {
+                "secret": "[redacted]",
+                "items": ["[redacted]", {"seed": "[redacted]"}, 42, null, true],
+            },
+            "count": 3,
+            "enabled": false,
+        })
+    );
+}
+
+#[test]
+fn json_leaves_stay_as_they_are() {
+    for leaf in [json!(null), json!(true), json!(7), json!(1.5)] {
+        let mut value = leaf.clone();
+
+        scrub_json(&mut value);
+
+        assert_eq!(value, leaf);
+    }
+}
@@ -0,0 +1,310 @@
+use crate::config::AppConfig;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::info;
+
+/// Same release the build scripts bundle
+const PHOENIXD_VERSION: &str = "0.3.4";
+const NODE_VERSION: &str = "22.12.0";
+const MAX_DOWNLOAD_BYTES: u64 = 256 * 1024 * 1024;
+
+/// A piece of the app bundle the services need to run
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Component {
+    Phoenixd,
+    NodeRuntime,
+    BackendDist,
+    FrontendStandalone,
+    TemplateDb,
+}
+
+impl Component {
+    pub fn label(self) -> &'static str {
+        match self {
+            Component::Phoenixd => "phoenixd",
+            Component::NodeRuntime => "Node.js runtime",
+            Component::BackendDist => "backend",
+            Component::FrontendStandalone => "frontend",
+            Component::TemplateDb => "template database",
+        }
+    }
+
+    /// Whether the repair can download it; the rest is built with the app
+    /// and only comes back by reinstalling
+    pub fn downloadable(self) -> bool {
+        matches!(self, Component::Phoenixd | Component::NodeRuntime)
+    }
+}
+
+/// Where downloaded components go, since the bundle itself is often read-only
+pub fn repaired_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("repaired")
+}
+
+/// Where a phoenixd upgrade goes; it takes precedence over the bundled one
+pub fn upgraded_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("upgraded")
+}
+
+/// phoenixd's binary under a resources-like directory
+pub fn phoenixd_path(dir: &Path) -> PathBuf {
+    let binary_name = if cfg!(target_os = "windows") {
+        "phoenixd.exe"
+    } else {
+        "phoenixd"
+    };
+    dir.join("binaries").join(binary_name)
+}
+
+/// Node's binary under a resources-like directory
+pub fn node_path(dir: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        dir.join("node").join("node.exe")
+    } else {
+        dir.join("node").join("bin").join("node")
+    }
+}
+
+/// Components neither the bundle, an earlier repair nor the system provide
+pub fn missing(resource_dir: &Path, data_dir: &Path, config: &AppConfig) -> Vec<Component> {
+    let repaired = repaired_dir(data_dir);
+    let frontend_enabled = config.frontend.enabled;
+    let static_frontend =
+        config.frontend.serve_static && crate::static_frontend::available(resource_dir);
+    // Without the Node backend and the Next.js server nothing needs Node.js
+    let node_backend = !config.backend.native;
+    let next_frontend = frontend_enabled && !static_frontend;
+    let mut missing = Vec::new();
+    if !phoenixd_path(resource_dir).exists()
+        && !phoenixd_path(&repaired).exists()
+        && which::which("phoenixd").is_err()
+    {
+        missing.push(Component::Phoenixd);
+    }
+    if (node_backend || next_frontend)
+        && !node_path(resource_dir).exists()
+        && !node_path(&repaired).exists()
+        && which::which("node").is_err()
+    {
+        missing.push(Component::NodeRuntime);
+    }
+    if node_backend
+        && !resource_dir
+            .join("backend")
+            .join("dist")
+            .join("index.js")
+            .exists()
+    {
+        missing.push(Component::BackendDist);
+    }
+    if next_frontend && !resource_dir.join("frontend").join("server.js").exists() {
+        missing.push(Component::FrontendStandalone);
+    }
+    // Only copied to create the database on first run
+    if !resource_dir.join("template.db").exists()
+        && !data_dir.join(crate::database::DB_FILE).exists()
+    {
+        missing.push(Component::TemplateDb);
+    }
+    missing
+}
+
+/// Download a missing component into the repaired dir, checking it against
+/// the SHA-256 sums its project publishes
+pub fn repair(component: Component, data_dir: &Path) -> Result<(), String> {
+    let repaired = repaired_dir(data_dir);
+    std::fs::create_dir_all(&repaired)
+        .map_err(|e| format!("Failed to create {:?}: {}", repaired, e))?;
+    match component {
+        Component::Phoenixd => repair_phoenixd(&repaired),
+        Component::NodeRuntime => repair_node(&repaired),
+        other => Err(format!(
+            "The {} comes with the app; reinstall Phoenixd Dashboard to restore it",
+            other.label()
+        )),
+    }
+}
+
+fn repair_phoenixd(repaired: &Path) -> Result<(), String> {
+    let path = phoenixd_path(repaired);
+    write_executable(&path, &download_phoenixd(PHOENIXD_VERSION)?)?;
+    info!("🔧 Restored phoenixd at {:?}", path);
+    Ok(())
+}
+
+/// Download a phoenixd release into the upgraded dir, keeping the binary it
+/// replaces as `phoenixd.previous` for a rollback
+pub fn install_phoenixd(version: &str, data_dir: &Path) -> Result<(), String> {
+    let binary = download_phoenixd(version)?;
+    let path = phoenixd_path(&upgraded_dir(data_dir));
+    if path.exists() {
+        std::fs::rename(&path, path.with_extension("previous"))
+            .map_err(|e| format!("Failed to keep the previous phoenixd: {}", e))?;
+    }
+    write_executable(&path, &binary)?;
+    info!("⬆️ Installed phoenixd {} at {:?}", version, path);
+    Ok(())
+}
+
+/// Undo `install_phoenixd`: back to the previous upgrade, or the bundled
+/// phoenixd if there was none
+pub fn rollback_phoenixd(data_dir: &Path) -> Result<(), String> {
+    let path = phoenixd_path(&upgraded_dir(data_dir));
+    let previous = path.with_extension("previous");
+    if previous.exists() {
+        std::fs::rename(&previous, &path)
+    } else {
+        std::fs::remove_file(&path)
+    }
+    .map_err(|e| format!("Failed to roll back phoenixd: {}", e))
+}
+
+/// The release binary for this platform, checked against its published sum
+fn download_phoenixd(version: &str) -> Result<Vec<u8>, String> {
+    let platform = match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => "macos-arm64",
+        ("macos", "x86_64") => "macos-x64",
+        ("linux", "x86_64") => "linux-x64",
+        ("windows", "x86_64") => "windows-x64",
+        (os, arch) => return Err(format!("No phoenixd release for {} {}", os, arch)),
+    };
+    let base = format!(
+        "https://github.com/ACINQ/phoenixd/releases/download/v{}",
+        version
+    );
+    let archive_name = format!("phoenix-{}-{}.zip", version, platform);
+    info!("🔧 Downloading {}", archive_name);
+    let archive = download(&format!("{}/{}", base, archive_name))?;
+    verify(
+        &archive,
+        &archive_name,
+        &download(&format!("{}/SHA256SUMS.asc", base))?,
+    )?;
+
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(archive))
+        .map_err(|e| format!("Invalid phoenixd archive: {}", e))?;
+    let binary_name = if cfg!(target_os = "windows") {
+        "phoenixd.exe"
+    } else {
+        "phoenixd"
+    };
+    let entry_name = zip
+        .file_names()
+        .find(|name| name.rsplit('/').next() == Some(binary_name))
+        .map(str::to_string)
+        .ok_or_else(|| "phoenixd not found in the release archive".to_string())?;
+    let mut binary = Vec::new();
+    zip.by_name(&entry_name)
+        .and_then(|mut entry| entry.read_to_end(&mut binary).map_err(Into::into))
+        .map_err(|e| format!("Failed to extract phoenixd: {}", e))?;
+    Ok(binary)
+}
+
+fn repair_node(repaired: &Path) -> Result<(), String> {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        "windows" => "win",
+        os => os,
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        arch => return Err(format!("No Node.js build for {}", arch)),
+    };
+    let ext = if cfg!(target_os = "windows") {
+        "zip"
+    } else {
+        "tar.gz"
+    };
+    let base = format!("https://nodejs.org/dist/v{}", NODE_VERSION);
+    let dir_name = format!("node-v{}-{}-{}", NODE_VERSION, os, arch);
+    let archive_name = format!("{}.{}", dir_name, ext);
+    info!("🔧 Downloading {}", archive_name);
+    let archive = download(&format!("{}/{}", base, archive_name))?;
+    verify(
+        &archive,
+        &archive_name,
+        &download(&format!("{}/SHASUMS256.txt", base))?,
+    )?;
+
+    let staging = repaired.join(".node-download");
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging)
+        .map_err(|e| format!("Failed to create {:?}: {}", staging, e))?;
+    if ext == "zip" {
+        zip::ZipArchive::new(std::io::Cursor::new(archive))
+            .and_then(|mut zip| zip.extract(&staging))
+            .map_err(|e| format!("Failed to extract Node.js: {}", e))?;
+    } else {
+        let archive_path = staging.join(&archive_name);
+        std::fs::write(&archive_path, archive)
+            .map_err(|e| format!("Failed to save Node.js archive: {}", e))?;
+        let status = Command::new("tar")
+            .arg("-xzf")
+            .arg(&archive_path)
+            .arg("-C")
+            .arg(&staging)
+            .status()
+            .map_err(|e| format!("Failed to run tar: {}", e))?;
+        if !status.success() {
+            return Err(format!("tar failed to extract Node.js ({})", status));
+        }
+    }
+
+    let target = repaired.join("node");
+    let _ = std::fs::remove_dir_all(&target);
+    std::fs::rename(staging.join(&dir_name), &target)
+        .map_err(|e| format!("Failed to install Node.js: {}", e))?;
+    let _ = std::fs::remove_dir_all(&staging);
+    info!("🔧 Restored Node.js at {:?}", target);
+    Ok(())
+}
+
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_DOWNLOAD_BYTES)
+        .read_to_end(&mut body)
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    Ok(body)
+}
+
+/// Compare against the `<sha256>  <file>` line for `name` in a sums file
+fn verify(data: &[u8], name: &str, sums: &[u8]) -> Result<(), String> {
+    let expected = String::from_utf8_lossy(sums)
+        .lines()
+        .find_map(|line| {
+            let (hash, file) = line.split_once(char::is_whitespace)?;
+            (file.trim().trim_start_matches('*') == name).then(|| hash.to_lowercase())
+        })
+        .ok_or_else(|| format!("No published checksum for {}", name))?;
+    let actual =
+        data_encoding::HEXLOWER.encode(ring::digest::digest(&ring::digest::SHA256, data).as_ref());
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!("Checksum mismatch for {}", name))
+    }
+}
+
+fn write_executable(path: &Path, data: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    std::fs::write(path, data).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to set permissions on {:?}: {}", path, e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use crate::config::AppConfig;
+use crate::i18n::t;
+use crate::windows::{self, escape};
+use crate::{audit, backup, commands, journal, notify, operations, os_auth, AppState};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tracing::{error, info, warn};
+
+const MARKER_FILE: &str = "safe_mode.json";
+const WINDOW_LABEL: &str = "safe-mode";
+
+/// Why the app is in safe mode; persisted so it stays there across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafeMode {
+    pub service: String,
+    pub crashes: u32,
+    pub entered_at: String,
+}
+
+/// Recent crash times per service
+static CRASHES: Mutex<Option<HashMap<String, Vec<Instant>>>> = Mutex::new(None);
+
+fn marker_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(MARKER_FILE)
+}
+
+pub fn current(data_dir: &Path) -> Option<SafeMode> {
+    let content = std::fs::read_to_string(marker_path(data_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn is_active(data_dir: &Path) -> bool {
+    marker_path(data_dir).exists()
+}
+
+/// Count a crash; too many within the configured window stop everything
+/// and put the app in safe mode
+pub fn record_crash(app: &AppHandle, service: &str) {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().unwrap().auto_heal.clone();
+    if config.crash_loop_crashes == 0 || is_active(&state.data_dir) {
+        return;
+    }
+    let window = Duration::from_secs(config.crash_loop_window_secs);
+    let crashes = {
+        let mut all = CRASHES.lock().unwrap();
+        let times = all
+            .get_or_insert_with(HashMap::new)
+            .entry(service.to_string())
+            .or_default();
+        times.retain(|at| at.elapsed() < window);
+        times.push(Instant::now());
+        times.len() as u32
+    };
+    if crashes >= config.crash_loop_crashes {
+        enter(app, service, crashes);
+    }
+}
+
+fn enter(app: &AppHandle, service: &str, crashes: u32) {
+    let state = app.state::<AppState>();
+    error!(
+        "🛟 {} crashed {} times in a row, stopping services and entering safe mode",
+        service, crashes
+    );
+    let safe_mode = SafeMode {
+        service: service.to_string(),
+        crashes,
+        entered_at: chrono::Local::now().to_rfc3339(),
+    };
+    if let Err(e) = serde_json::to_string_pretty(&safe_mode)
+        .map_err(|e| e.to_string())
+        .and_then(|content| {
+            std::fs::write(marker_path(&state.data_dir), content).map_err(|e| e.to_string())
+        })
+    {
+        warn!("Failed to persist safe mode: {}", e);
+    }
+    CRASHES.lock().unwrap().take();
+    journal::record(
+        &state.data_dir,
+        "safe_mode.entered",
+        Some(service),
+        format!("{} crashes", crashes),
+    );
+
+    let services = {
+        let mut pm = state.process_manager.lock().unwrap();
+        pm.stop_all();
+        pm.service_statuses()
+    };
+    crate::refresh_tray_menu(app, &services);
+    let body = t("notify.safe_mode_body").replace("{service}", service);
+    notify::native(app.clone())(t("notify.safe_mode_title"), &body);
+    show_window(app);
+}
+
+/// Forget the crash loop without touching the services
+fn clear(data_dir: &Path) {
+    let _ = std::fs::remove_file(marker_path(data_dir));
+    CRASHES.lock().unwrap().take();
+    journal::record(data_dir, "safe_mode.exited", None, "");
+    info!("🛟 Leaving safe mode");
+}
+
+/// Leave safe mode and start the services again
+pub fn exit(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    clear(&state.data_dir);
+    let services = {
+        let mut pm = state.process_manager.lock().unwrap();
+        pm.start_all()?;
+        pm.service_statuses()
+    };
+    crate::refresh_tray_menu(app, &services);
+    Ok(())
+}
+
+/// Move config.toml aside and go back to the defaults
+pub fn reset_config(app: &AppHandle) -> Result<PathBuf, String> {
+    let state = app.state::<AppState>();
+    let path = AppConfig::path(&state.data_dir);
+    let aside = path.with_extension(format!(
+        "toml.{}.bak",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    if path.exists() {
+        std::fs::rename(&path, &aside)
+            .map_err(|e| format!("Failed to move the config aside: {}", e))?;
+    }
+    let config = AppConfig::default();
+    config.save(&state.data_dir)?;
+    *state.config.lock().unwrap() = config.clone();
+    state.process_manager.lock().unwrap().set_config(config);
+    audit::record(&state.data_dir, "config.reset", aside.to_string_lossy());
+    info!("🛟 Reset the config, the old one is at {:?}", aside);
+    Ok(aside)
+}
+
+/// Restore the newest backup archive, which starts the services again
+fn restore_latest(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().unwrap().backup.clone();
+    let newest = backup::list(&state.data_dir, &config)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "There is no backup to restore".to_string())?;
+    os_auth::require(
+        app,
+        &state.data_dir,
+        "backup.restore",
+        t("auth.restore_backup"),
+    )?;
+    operations::run(app, "restore", "Restore backup", |_| {
+        backup::restore(app, &newest.path)
+    })?;
+    clear(&state.data_dir);
+    let services = state.process_manager.lock().unwrap().service_statuses();
+    crate::refresh_tray_menu(app, &services);
+    Ok(())
+}
+
+/// Native window with the recovery actions, usable with every service down
+pub fn show_window(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let reason = current(&state.data_dir)
+        .map(|mode| {
+            t("safe_mode.reason")
+                .replace("{service}", &mode.service)
+                .replace("{crashes}", &mode.crashes.to_string())
+        })
+        .unwrap_or_default();
+    let buttons: String = [
+        ("logs", "safe_mode.view_logs"),
+        ("restore", "safe_mode.restore_backup"),
+        ("reset_config", "safe_mode.reset_config"),
+        ("resume", "safe_mode.resume"),
+    ]
+    .iter()
+    .map(|(action, label)| {
+        format!(
+            r#"<button type="submit" name="action" value="{}">{}</button> "#,
+            action,
+            escape(t(label))
+        )
+    })
+    .collect();
+    let body = format!(
+        r#"<p>{}</p><p>{}</p><form action="submit">{}</form>"#,
+        escape(&reason),
+        escape(t("safe_mode.hint")),
+        buttons
+    );
+
+    let handle = app.clone();
+    let result = windows::show_form(
+        app,
+        WINDOW_LABEL,
+        t("safe_mode.title"),
+        &body,
+        (420.0, 300.0),
+        move |url| {
+            let action = url
+                .query_pairs()
+                .find(|(key, _)| key == "action")
+                .map(|(_, value)| value.into_owned())
+                .unwrap_or_default();
+            let app = handle.clone();
+            // Restores and restarts block, keep them off the webview's thread
+            std::thread::spawn(move || run_action(&app, &action));
+        },
+    );
+    if let Err(e) = result {
+        error!("{}", e);
+    }
+}
+
+fn run_action(app: &AppHandle, action: &str) {
+    let state = app.state::<AppState>();
+    let result = match action {
+        "logs" => {
+            let config = state.config.lock().unwrap().clone();
+            commands::open_data_path(&state.data_dir, &config, "logs")
+        }
+        "restore" => restore_latest(app),
+        "reset_config" => reset_config(app).map(|_| ()),
+        "resume" => exit(app),
+        _ => Ok(()),
+    };
+    match result {
+        Ok(()) if matches!(action, "restore" | "resume") => {
+            if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+                let _ = window.destroy();
+            }
+        }
+        Ok(()) => {}
+        Err(e) => {
+            error!("Safe mode action {} failed: {}", action, e);
+            notify::native(app.clone())(t("safe_mode.title"), &e);
+        }
+    }
+}
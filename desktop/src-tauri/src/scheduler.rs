@@ -0,0 +1,75 @@
+use crate::config::{PollingConfig, POLL_INTERVALS};
+use crate::footprint;
+use crate::AppState;
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// How often a cron schedule is re-read while waiting for the next run
+const TICK: Duration = Duration::from_secs(30);
+
+/// `interval` made up to `percent` shorter or longer at random
+pub fn jittered(interval: Duration, percent: u32) -> Duration {
+    let mut bytes = [0u8; 4];
+    if percent == 0 || getrandom::getrandom(&mut bytes).is_err() {
+        return interval;
+    }
+    // Uniform in [-1, 1]
+    let unit = u32::from_le_bytes(bytes) as f64 / u32::MAX as f64 * 2.0 - 1.0;
+    interval.mul_f64(1.0 + unit * percent as f64 / 100.0)
+}
+
+/// Wait before `poller` runs again, as configured under `[polling]` and
+/// stretched while the footprint sampler throttles
+pub fn sleep(app: &AppHandle, poller: &str) {
+    let config = app
+        .state::<AppState>()
+        .config
+        .lock()
+        .unwrap()
+        .polling
+        .clone();
+    let interval = config.interval(poller).mul_f64(footprint::slowdown());
+    std::thread::sleep(jittered(interval, config.jitter_percent));
+    footprint::woke(poller);
+}
+
+/// Every poller's interval in seconds, defaults included
+pub fn intervals(config: &PollingConfig) -> BTreeMap<String, u64> {
+    POLL_INTERVALS
+        .iter()
+        .map(|(name, _)| (name.to_string(), config.interval(name).as_secs()))
+        .collect()
+}
+
+/// On a thread of its own, run `job` with the config it was scheduled from
+/// at each time `next_run` gives for what `read_config` returns. A schedule
+/// that changes or gets turned off meanwhile applies right away.
+pub fn run_on_cron<C, R, N, J>(read_config: R, next_run: N, mut job: J)
+where
+    C: Send + 'static,
+    R: Fn() -> C + Send + 'static,
+    N: Fn(&C) -> Option<DateTime<Local>> + Send + 'static,
+    J: FnMut(C, DateTime<Local>) + Send + 'static,
+{
+    std::thread::spawn(move || 'schedule: loop {
+        let config = read_config();
+        let Some(next) = next_run(&config) else {
+            std::thread::sleep(TICK);
+            continue;
+        };
+
+        // Sleep in short steps so a changed schedule is picked up
+        while Local::now() < next {
+            let remaining = (next - Local::now()).to_std().unwrap_or_default();
+            std::thread::sleep(remaining.min(TICK));
+            let current = next_run(&read_config());
+            if current.is_none() || (Local::now() < next && current != Some(next)) {
+                continue 'schedule;
+            }
+        }
+
+        job(config, next);
+    });
+}
@@ -0,0 +1,28 @@
+use crate::redact;
+use keyring::Entry;
+
+/// Keychain service name; entries show up under it in Keychain Access,
+/// Credential Manager or the kernel keyring
+const SERVICE: &str = "phoenixd-dashboard";
+
+/// Secrets kept in the OS keychain instead of config.toml
+pub const TELEGRAM_BOT_TOKEN: &str = "telegram-bot-token";
+
+pub fn get(key: &str) -> Option<String> {
+    let secret = Entry::new(SERVICE, key).ok()?.get_password().ok()?;
+    redact::register(&secret);
+    Some(secret)
+}
+
+pub fn set(key: &str, value: &str) -> Result<(), String> {
+    Entry::new(SERVICE, key)
+        .and_then(|entry| entry.set_password(value))
+        .map_err(|e| format!("Failed to store {} in the keychain: {}", key, e))
+}
+
+pub fn delete(key: &str) -> Result<(), String> {
+    match Entry::new(SERVICE, key).and_then(|entry| entry.delete_credential()) {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove {} from the keychain: {}", key, e)),
+    }
+}
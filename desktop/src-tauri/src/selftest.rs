@@ -0,0 +1,258 @@
+use crate::clock;
+use crate::i18n::t;
+use crate::notify;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::redact;
+use crate::token;
+use crate::tor::{self, TorManager};
+use crate::{network, AppState};
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+/// Budget for local checks
+const LOCAL_TIMEOUT: Duration = Duration::from_secs(5);
+/// Onion circuits take a while to build
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub at: String,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+impl SelfTestReport {
+    pub fn failed(&self) -> impl Iterator<Item = &SelfTestCheck> {
+        self.checks.iter().filter(|check| !check.ok)
+    }
+
+    /// One line for the tray menu
+    pub fn summary(&self) -> String {
+        match self.failed().count() {
+            0 => t("selftest.passed").to_string(),
+            failed => t("selftest.failed").replace("{count}", &failed.to_string()),
+        }
+    }
+}
+
+/// Result of the last run, shown in the tray
+static LAST: Mutex<Option<SelfTestReport>> = Mutex::new(None);
+
+pub fn last() -> Option<SelfTestReport> {
+    LAST.lock().unwrap().clone()
+}
+
+/// What the self-test needs to know, copied out so no lock is held while it runs
+struct Targets {
+    backend: SocketAddr,
+    frontend: Option<SocketAddr>,
+    public_url: Option<String>,
+    onion: Option<String>,
+}
+
+/// End-to-end check of the running stack: phoenixd's API, the backend's
+/// health and websocket, the frontend, and the tunnel and onion service as
+/// seen from outside. Blocks for up to a minute, so run it off the main thread.
+pub fn run(app: &AppHandle) -> SelfTestReport {
+    let state = app.state::<AppState>();
+    let (backend, frontend, tor, clock_skew_secs) = {
+        let config = state.config.lock().unwrap();
+        let frontend = config.frontend.enabled.then(|| config.bind.frontend_addr());
+        (
+            config.bind.backend_addr(),
+            frontend,
+            config.tor.enabled,
+            config.alerts.clock_skew_secs,
+        )
+    };
+    let targets = Targets {
+        backend,
+        frontend,
+        public_url: state.process_manager.lock().unwrap().get_public_url(),
+        onion: if tor {
+            TorManager::new(&state.data_dir).onion_address()
+        } else {
+            None
+        },
+    };
+    let bearer = token::api_token(&state.data_dir);
+
+    let mut checks = vec![
+        check("phoenixd", phoenixd(&state.data_dir)),
+        check(
+            "backend",
+            http_get(
+                &format!("http://{}/health", targets.backend),
+                bearer.as_deref(),
+            ),
+        ),
+        check("websocket", websocket(targets.backend, bearer.as_deref())),
+    ];
+    if let Some(frontend) = targets.frontend {
+        checks.push(check(
+            "frontend",
+            http_get(&format!("http://{}/", frontend), None),
+        ));
+    }
+    if let Some(url) = &targets.public_url {
+        checks.push(check("tunnel", reachable(url, None)));
+    }
+    if let Some(onion) = &targets.onion {
+        let proxy = format!("socks5://127.0.0.1:{}", tor::SOCKS_PORT);
+        checks.push(check(
+            "onion",
+            reachable(&format!("http://{}/", onion), Some(&proxy)),
+        ));
+    }
+
+    if clock_skew_secs > 0 && network::is_online() {
+        checks.push(check("clock", clock_offset(clock_skew_secs)));
+    }
+
+    let report = SelfTestReport {
+        at: chrono::Local::now().to_rfc3339(),
+        checks,
+    };
+    *LAST.lock().unwrap() = Some(report.clone());
+    report
+}
+
+/// Run, log, refresh the tray and notify about failures
+pub fn run_and_report(app: &AppHandle) -> SelfTestReport {
+    info!("🩺 Running self-test...");
+    let report = run(app);
+    for check in &report.checks {
+        if check.ok {
+            info!("✅ {}: {}", check.name, check.detail);
+        } else {
+            warn!("❌ {}: {}", check.name, check.detail);
+        }
+    }
+
+    let failures: Vec<String> = report
+        .failed()
+        .map(|check| format!("{}: {}", check.name, check.detail))
+        .collect();
+    if !failures.is_empty() {
+        notify::native(app.clone())(t("selftest.notify_title"), &failures.join("\n"));
+    }
+
+    if let Some(state) = app.try_state::<AppState>() {
+        let services = state.process_manager.lock().unwrap().service_statuses();
+        crate::refresh_tray_menu(app, &services);
+    }
+    report
+}
+
+fn check(name: &'static str, result: Result<String, String>) -> SelfTestCheck {
+    match result {
+        Ok(detail) => SelfTestCheck {
+            name,
+            ok: true,
+            detail: redact::scrub(&detail),
+        },
+        Err(detail) => SelfTestCheck {
+            name,
+            ok: false,
+            detail: redact::scrub(&detail),
+        },
+    }
+}
+
+fn phoenixd(data_dir: &Path) -> Result<String, String> {
+    let client = PhoenixdClient::new(data_dir);
+    let info = tauri::async_runtime::block_on(async {
+        tokio::time::timeout(LOCAL_TIMEOUT, client.get_info()).await
+    })
+    .map_err(|_| "getinfo timed out".to_string())?
+    .map_err(|e| format!("getinfo failed: {}", e))?;
+    let channels = info["channels"].as_array().map_or(0, Vec::len);
+    Ok(format!("getinfo answered, {} channel(s)", channels))
+}
+
+fn clock_offset(threshold_secs: u64) -> Result<String, String> {
+    let skew = clock::measure()?;
+    let detail = format!("{} ms off {}", skew.offset_ms, skew.server);
+    if skew.exceeds(threshold_secs) {
+        Err(detail)
+    } else {
+        Ok(detail)
+    }
+}
+
+fn http_get(url: &str, bearer: Option<&str>) -> Result<String, String> {
+    let mut request = ureq::get(url).timeout(LOCAL_TIMEOUT);
+    if let Some(bearer) = bearer {
+        request = request.set("Authorization", &format!("Bearer {}", bearer));
+    }
+    match request.call() {
+        Ok(response) => Ok(format!("HTTP {}", response.status())),
+        Err(ureq::Error::Status(code, _)) => Err(format!("HTTP {}", code)),
+        Err(ureq::Error::Transport(e)) => Err(format!("Not reachable: {}", e)),
+    }
+}
+
+/// From outside, any answer short of a server error counts: the proxy
+/// rightly asks for credentials
+fn reachable(url: &str, proxy: Option<&str>) -> Result<String, String> {
+    let mut agent = ureq::AgentBuilder::new().timeout(REMOTE_TIMEOUT);
+    if let Some(proxy) = proxy {
+        let proxy =
+            ureq::Proxy::new(proxy).map_err(|e| format!("Invalid proxy {}: {}", proxy, e))?;
+        agent = agent.proxy(proxy);
+    }
+    match agent.build().get(url).call() {
+        Ok(response) => Ok(format!("HTTP {}", response.status())),
+        Err(ureq::Error::Status(code, _)) if code < 500 => Ok(format!("HTTP {}", code)),
+        Err(ureq::Error::Status(code, _)) => Err(format!("HTTP {}", code)),
+        Err(ureq::Error::Transport(e)) => Err(format!("Not reachable: {}", e)),
+    }
+}
+
+/// Open the backend's payment websocket and expect `101 Switching Protocols`
+fn websocket(backend: SocketAddr, bearer: Option<&str>) -> Result<String, String> {
+    let mut stream = TcpStream::connect_timeout(&backend, LOCAL_TIMEOUT)
+        .map_err(|e| format!("Not reachable: {}", e))?;
+    let _ = stream.set_read_timeout(Some(LOCAL_TIMEOUT));
+
+    let key = token::generate()?;
+    let mut request = format!(
+        "GET /ws HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Key: {}\r\n",
+        backend,
+        data_encoding::BASE64.encode(&key.as_bytes()[..16])
+    );
+    if let Some(bearer) = bearer {
+        request.push_str(&format!("Authorization: Bearer {}\r\n", bearer));
+    }
+    request.push_str("\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| format!("Handshake failed: {}", e))?;
+
+    let mut response = [0u8; 64];
+    let read = stream
+        .read(&mut response)
+        .map_err(|e| format!("No handshake response: {}", e))?;
+    let status_line = String::from_utf8_lossy(&response[..read])
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    if status_line.contains(" 101 ") {
+        Ok("Upgrade accepted".to_string())
+    } else {
+        Err(format!("Upgrade refused: {}", status_line))
+    }
+}
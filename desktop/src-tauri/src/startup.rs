@@ -0,0 +1,232 @@
+use crate::audit;
+use crate::config::AppConfig;
+use crate::dialogs;
+use crate::i18n::t;
+use crate::lease;
+use crate::logs;
+use crate::onboarding::Onboarding;
+use crate::process_manager::{self, Progress};
+use crate::resources;
+use crate::safe_mode;
+use crate::selftest;
+use crate::supervisor;
+use crate::synced_dir;
+use crate::AppState;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{error, info, warn};
+
+const WINDOW_LABEL: &str = "startup";
+/// Give up waiting for the backend and frontend ports after this long
+const READY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Start the services in the background behind a small progress window,
+/// then close it and open the dashboard once the backend and frontend answer
+pub fn spawn(app: AppHandle) {
+    let body = format!(
+        r#"<p id="step">{}</p>"#,
+        crate::windows::escape(t("startup.phoenixd"))
+    );
+    if let Err(e) = crate::windows::show(
+        &app,
+        WINDOW_LABEL,
+        t("startup.title"),
+        &body,
+        (340.0, 160.0),
+    ) {
+        warn!("{}", e);
+    }
+
+    std::thread::spawn(move || {
+        let state = app.state::<AppState>();
+        if !supervisor::is_running(&state.data_dir) {
+            repair_resources(&app);
+        }
+        // A broken hand edit would otherwise silently run on the defaults
+        if let Err(e) = AppConfig::check(&state.data_dir) {
+            error!("{}", e);
+            let path = AppConfig::path(&state.data_dir);
+            let aside = path.with_extension("toml.invalid");
+            match std::fs::copy(&path, &aside) {
+                Ok(_) => warn!(
+                    "Running on the defaults, the edited config is kept as {:?}",
+                    aside
+                ),
+                Err(e) => warn!("Failed to keep a copy of the invalid config: {}", e),
+            }
+            dialogs::invalid_config(&app, &e, state.data_dir.clone());
+        }
+        if safe_mode::is_active(&state.data_dir) {
+            warn!("🛟 Safe mode is on, not starting the services");
+            close(&app);
+            crate::refresh_tray_menu(
+                &app,
+                &state.process_manager.lock().unwrap().service_statuses(),
+            );
+            safe_mode::show_window(&app);
+            return;
+        }
+        // Moving restarts the app, before anything runs from the synced copy
+        synced_dir::offer_relocation(&app);
+        if !supervisor::is_running(&state.data_dir) && !claim_lease(&app) {
+            close(&app);
+            crate::refresh_tray_menu(
+                &app,
+                &state.process_manager.lock().unwrap().service_statuses(),
+            );
+            return;
+        }
+        let result = {
+            let mut pm = state.process_manager.lock().unwrap();
+            if supervisor::is_running(&state.data_dir) {
+                info!("🔗 Attaching to the running background service");
+                pm.set_attached(true);
+                Ok(())
+            } else {
+                pm.set_defer_phoenixd(
+                    Onboarding::load(&state.data_dir).awaits_wallet(&state.data_dir),
+                );
+                pm.set_progress(Some(progress(app.clone())));
+                let result = pm.start_all();
+                pm.set_progress(None);
+                result
+            }
+        };
+
+        if let Err(e) = result {
+            error!("Failed to start services: {}", e);
+            close(&app);
+            dialogs::startup_error(&app, &e, logs::log_dir(&state.data_dir));
+            return;
+        }
+        info!("✅ Services started!");
+
+        let frontend = state.config.lock().unwrap().frontend.clone();
+        let mut waits = vec![("startup.waiting_backend", "backend")];
+        if frontend.enabled {
+            waits.push(("startup.waiting_frontend", "frontend"));
+        }
+        for (key, service) in waits {
+            step(&app, t(key));
+            let addr = state.config.lock().unwrap().bind.service_addr(service);
+            if let Some(addr) = addr.filter(|addr| !wait_for_port(*addr)) {
+                warn!("{} still closed after {:?}", addr, READY_TIMEOUT);
+            }
+        }
+
+        let (dashboard_url, open_dashboard) = {
+            let pm = state.process_manager.lock().unwrap();
+            crate::update_tray_tooltip(&app, &pm);
+            (
+                pm.dashboard_url(),
+                state.config.lock().unwrap().general.open_dashboard_on_start && frontend.has_ui(),
+            )
+        };
+        close(&app);
+        // A page asked for on launch opens on its own once the dashboard answers
+        if open_dashboard && !crate::browser::opening() {
+            crate::browser::open(&app, &dashboard_url);
+        }
+        let _ = app.emit("startup-complete", &dashboard_url);
+
+        // Print startup banner
+        println!("\n╔════════════════════════════════════════════════╗");
+        println!("║  ⚡ Phoenixd Dashboard (Desktop Edition)       ║");
+        println!("╠════════════════════════════════════════════════╣");
+        println!("║  ✅ Lightning Node: Running                    ║");
+        println!("║  ℹ️  Tor/Tailscale/CF: Desktop version only    ║");
+        println!("╠════════════════════════════════════════════════╣");
+        println!("║  📍 Dashboard: {:<32}║", dashboard_url);
+        println!("╚════════════════════════════════════════════════╝\n");
+
+        selftest::run_and_report(&app);
+    });
+}
+
+/// Take the data dir's lease and keep it renewed, unless another machine
+/// runs the wallet and the user would rather not start it here too
+fn claim_lease(app: &AppHandle) -> bool {
+    let state = app.state::<AppState>();
+    if let Some(lease) = lease::held_elsewhere(&state.data_dir) {
+        warn!(
+            "🔒 {} renewed the data dir lease at {}",
+            lease.hostname, lease.heartbeat
+        );
+        if !dialogs::confirm_lease_override(app, &lease) {
+            warn!(
+                "🔒 Not starting the services, the wallet runs on {}",
+                lease.hostname
+            );
+            return false;
+        }
+        audit::record(&state.data_dir, "lease.override", &lease.hostname);
+    }
+    if let Err(e) = lease::acquire(&state.data_dir) {
+        warn!("{}", e);
+    }
+    lease::spawn(app.clone(), state.data_dir.clone());
+    true
+}
+
+fn progress(app: AppHandle) -> Progress {
+    Arc::new(move |text: &str| step(&app, text))
+}
+
+/// Offer to download whatever the bundle is missing before anything fails
+/// on it halfway through startup
+fn repair_resources(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().unwrap().clone();
+    let resource_dir = state
+        .process_manager
+        .lock()
+        .unwrap()
+        .resource_dir()
+        .to_path_buf();
+    let missing = resources::missing(&resource_dir, &state.data_dir, &config);
+    if missing.is_empty() {
+        return;
+    }
+    warn!("🔧 Missing components: {:?}", missing);
+    if !dialogs::confirm_repair(app, &missing) {
+        return;
+    }
+
+    step(app, t("startup.repairing"));
+    for component in missing.into_iter().filter(|c| c.downloadable()) {
+        if let Err(e) = resources::repair(component, &state.data_dir) {
+            error!("{}", e);
+        }
+    }
+}
+
+/// Show `text` in the progress window and tell any listening window
+fn step(app: &AppHandle, text: &str) {
+    let _ = app.emit("startup-progress", text);
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let text = serde_json::to_string(text).unwrap_or_default();
+        let _ = window.eval(format!(
+            "document.getElementById('step').textContent = {}",
+            text
+        ));
+    }
+}
+
+fn close(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = window.close();
+    }
+}
+
+fn wait_for_port(addr: SocketAddr) -> bool {
+    let started = Instant::now();
+    while started.elapsed() < READY_TIMEOUT {
+        if process_manager::port_open(addr) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+    false
+}
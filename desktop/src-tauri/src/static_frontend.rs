@@ -0,0 +1,144 @@
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use std::net::SocketAddr;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tauri::async_runtime::JoinHandle;
+use tokio::net::TcpListener;
+use tracing::{error, info};
+
+/// Where the build scripts put `next build` with `output: 'export'`
+pub const STATIC_DIR: &str = "frontend-static";
+
+pub fn static_dir(resource_dir: &Path) -> PathBuf {
+    resource_dir.join(STATIC_DIR)
+}
+
+/// Whether an exported build is bundled
+pub fn available(resource_dir: &Path) -> bool {
+    static_dir(resource_dir).join("index.html").exists()
+}
+
+/// Serves the exported frontend in place of the Next.js server
+pub struct StaticFrontend {
+    task: JoinHandle<()>,
+}
+
+impl StaticFrontend {
+    pub fn start(bind: SocketAddr, root: PathBuf) -> Result<Self, String> {
+        let socket = std::net::TcpListener::bind(bind)
+            .and_then(|l| l.set_nonblocking(true).map(|_| l))
+            .map_err(|e| format!("Failed to listen on {}: {}", bind, e))?;
+        let app = Router::new()
+            .fallback(serve_file)
+            .with_state(Arc::new(root));
+
+        info!("📄 Serving the static frontend on {}", bind);
+        let task = tauri::async_runtime::spawn(async move {
+            let listener = match TcpListener::from_std(socket) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    error!("Static frontend listener failed: {}", e);
+                    return;
+                }
+            };
+            if let Err(e) = axum::serve(listener, app).await {
+                error!("Static frontend stopped: {}", e);
+            }
+        });
+        Ok(Self { task })
+    }
+
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+async fn serve_file(State(root): State<Arc<PathBuf>>, request: Request) -> Response {
+    if request.method() != Method::GET && request.method() != Method::HEAD {
+        return StatusCode::METHOD_NOT_ALLOWED.into_response();
+    }
+    let path = request.uri().path().trim_start_matches('/');
+    let Some(file) = resolve(&root, path) else {
+        return match tokio::fs::read(root.join("404.html")).await {
+            Ok(page) => (StatusCode::NOT_FOUND, html(page)).into_response(),
+            Err(_) => StatusCode::NOT_FOUND.into_response(),
+        };
+    };
+
+    let content = match tokio::fs::read(&file).await {
+        Ok(content) => content,
+        Err(_) => return StatusCode::NOT_FOUND.into_response(),
+    };
+    let mut response = Response::new(Body::from(content));
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(content_type(&file)),
+    );
+    // Next fingerprints everything under _next/static
+    let cache = if path.starts_with("_next/static/") {
+        "public, max-age=31536000, immutable"
+    } else {
+        "no-cache"
+    };
+    response
+        .headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static(cache));
+    response
+}
+
+fn html(page: Vec<u8>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+        Body::from(page),
+    )
+        .into_response()
+}
+
+/// The exported file for a URL path the way `next export` lays them out:
+/// `/send` is `send.html` or `send/index.html`
+fn resolve(root: &Path, path: &str) -> Option<PathBuf> {
+    let relative = Path::new(path);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    if path.is_empty() {
+        return Some(root.join("index.html"));
+    }
+    let base = root.join(relative);
+    let candidates = [
+        base.clone(),
+        base.with_extension("html"),
+        base.join("index.html"),
+    ];
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
+
+fn content_type(file: &Path) -> &'static str {
+    match file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+    {
+        "html" => "text/html; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "json" | "webmanifest" => "application/json",
+        "txt" => "text/plain; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "wasm" => "application/wasm",
+        _ => "application/octet-stream",
+    }
+}
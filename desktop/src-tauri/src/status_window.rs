@@ -0,0 +1,177 @@
+use crate::i18n::t;
+use crate::process_manager::ServiceState;
+use crate::windows::{self, escape};
+use crate::{logs, redact, AppState};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::menu::MenuItemKind;
+use tauri::{AppHandle, Manager, Wry};
+use tracing::error;
+
+const STATUS_LABEL: &str = "status";
+/// Lines of a log the viewer shows
+const LOG_LINES: usize = 400;
+
+/// Set when the system tray couldn't be created, so this window is the
+/// only way to control the app
+static TRAY_MISSING: AtomicBool = AtomicBool::new(false);
+
+pub fn set_tray_missing() {
+    TRAY_MISSING.store(true, Ordering::Relaxed);
+}
+
+pub fn tray_missing() -> bool {
+    TRAY_MISSING.load(Ordering::Relaxed)
+}
+
+fn services_table(app: &AppHandle) -> String {
+    let state = app.state::<AppState>();
+    let services = state.statuses.lock().unwrap().clone();
+    let uptime = state.uptime.lock().unwrap().clone();
+    let mut body = format!("<table><caption>{}</caption>", escape(t("tray.services")));
+    for service in &services {
+        let (icon, label) = match service.state {
+            ServiceState::Running => ("🟢", t("service.running")),
+            ServiceState::Unhealthy => ("🟠", t("service.unhealthy")),
+            ServiceState::Stopped => ("🔴", t("service.stopped")),
+        };
+        let stats = uptime.services.get(service.name);
+        let availability = stats
+            .filter(|stats| stats.up_seconds + stats.down_seconds > 0)
+            .map(|stats| {
+                format!(
+                    "{:.1}%",
+                    stats.up_seconds as f64 * 100.0
+                        / (stats.up_seconds + stats.down_seconds) as f64
+                )
+            })
+            .unwrap_or_default();
+        let crashes = stats.map(|stats| stats.crashes).unwrap_or_default();
+        body.push_str(&format!(
+            r#"<tr><th scope="row"><span aria-hidden="true">{}</span> {}</th><td>{}</td><td>{}</td><td>{} {}</td></tr>"#,
+            icon,
+            escape(service.name),
+            escape(label),
+            availability,
+            crashes,
+            escape(t("window.crashes"))
+        ));
+    }
+    body.push_str("</table>");
+    body
+}
+
+/// The tray menu as buttons, submenus as groups
+fn menu_items(items: Vec<MenuItemKind<Wry>>, html: &mut String) {
+    for item in items {
+        match item {
+            MenuItemKind::MenuItem(item) => {
+                let disabled = if item.is_enabled().unwrap_or(true) {
+                    ""
+                } else {
+                    " disabled"
+                };
+                html.push_str(&format!(
+                    r#"<button type="submit" name="action" value="{}"{}>{}</button> "#,
+                    escape(&item.id().0),
+                    disabled,
+                    escape(&item.text().unwrap_or_default())
+                ));
+            }
+            MenuItemKind::Check(item) => {
+                let pressed = item.is_checked().unwrap_or_default();
+                html.push_str(&format!(
+                    r#"<button type="submit" name="action" value="{}" aria-pressed="{}">{}{}</button> "#,
+                    escape(&item.id().0),
+                    pressed,
+                    if pressed { "✓ " } else { "" },
+                    escape(&item.text().unwrap_or_default())
+                ));
+            }
+            MenuItemKind::Submenu(submenu) => {
+                html.push_str(&format!(
+                    "<fieldset><legend>{}</legend>",
+                    escape(&submenu.text().unwrap_or_default())
+                ));
+                menu_items(submenu.items().unwrap_or_default(), html);
+                html.push_str("</fieldset>");
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Live panel with every service's state, availability and crashes, and
+/// the tray's actions for when the tray is hard to reach or missing
+pub fn show(app: &AppHandle) -> Result<(), String> {
+    let handle = app.clone();
+    windows::show_live_form(
+        app,
+        STATUS_LABEL,
+        t("window.status"),
+        (480.0, 640.0),
+        10,
+        |app, _| {
+            let services = app.state::<AppState>().statuses.lock().unwrap().clone();
+            let mut actions = String::new();
+            match crate::build_tray_menu(app, &services).and_then(|menu| menu.items()) {
+                Ok(items) => menu_items(items, &mut actions),
+                Err(e) => error!("Failed to list the tray actions: {}", e),
+            }
+            let notice = if tray_missing() {
+                format!(r#"<p role="status">{}</p>"#, escape(t("window.no_tray")))
+            } else {
+                String::new()
+            };
+            format!(
+                r#"{}{}<form action="submit" aria-label="{}">{}</form>"#,
+                notice,
+                services_table(app),
+                escape(t("window.actions")),
+                actions
+            )
+        },
+        move |url| {
+            let Some(action) = url
+                .query_pairs()
+                .find(|(key, _)| key == "action")
+                .map(|(_, value)| value.into_owned())
+            else {
+                return;
+            };
+            let app = handle.clone();
+            let _ = handle.run_on_main_thread(move || {
+                crate::handle_menu_action(&app, &action);
+                if let Some(window) = app.get_webview_window(STATUS_LABEL) {
+                    let _ = window.eval("setTimeout(() => location.reload(), 500)");
+                }
+            });
+        },
+    )
+}
+
+/// One window per service, so several logs can be followed side by side
+pub fn show_logs(app: &AppHandle, service: &str) -> Result<(), String> {
+    let label: String = format!("logs-{}", service)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let title = t("window.logs").replace("{service}", service);
+    let service = service.to_string();
+    windows::show_live(app, &label, &title, (720.0, 480.0), 3, move |app, _| {
+        let path = logs::log_path(&app.state::<AppState>().data_dir, &service);
+        let lines = logs::tail(&path, LOG_LINES);
+        if lines.is_empty() {
+            return format!("<p>{}</p>", escape(t("window.no_log")));
+        }
+        format!(
+            "<pre>{}</pre><script>window.scrollTo(0, document.body.scrollHeight)</script>",
+            escape(&redact::scrub(&lines.join("\n")))
+        )
+    })
+}
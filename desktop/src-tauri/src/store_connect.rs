@@ -0,0 +1,198 @@
+use crate::i18n::t;
+use crate::phoenixd_api;
+use crate::tor::TorManager;
+use crate::{audit, lan, os_auth, tls, token, AppState};
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tracing::info;
+
+/// How the store server reaches phoenixd: from this computer, the local
+/// network or over Tor
+pub const EXPOSURES: [&str; 3] = ["local", "lan", "tor"];
+const LIMITED_PASSWORD_KEY: &str = "http-password-limited-access";
+
+/// What a BTCPay Server or LNbits instance needs to use the main node
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreConnection {
+    pub exposure: String,
+    /// phoenixd's API as the store server reaches it
+    pub url: String,
+    /// Receive-only password: invoices and reads, no sending
+    pub restricted: bool,
+    /// BTCPay's lightning connection string for phoenixd
+    pub btcpay: String,
+    /// LNbits' funding source settings, as `.env` lines
+    pub lnbits: String,
+}
+
+/// Give phoenix.conf a limited-access password if it has none. True when
+/// one was added, which phoenixd only picks up on a restart.
+fn provision_limited_password(phoenix_conf: &Path) -> Result<bool, String> {
+    if !phoenixd_api::read_limited_password(phoenix_conf).is_empty() {
+        return Ok(false);
+    }
+    let mut content = std::fs::read_to_string(phoenix_conf)
+        .map_err(|e| format!("Failed to read {:?}: {}", phoenix_conf, e))?;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&format!(
+        "{}={}\n",
+        LIMITED_PASSWORD_KEY,
+        token::generate()?
+    ));
+    tls::write_private(phoenix_conf, &content)?;
+    Ok(true)
+}
+
+/// phoenixd's API URL through `exposure`. Tor publishes the API on the
+/// onion first if it didn't yet.
+fn node_url(app: &AppHandle, exposure: &str) -> Result<String, String> {
+    let state = app.state::<AppState>();
+    let bind = state.config.lock().unwrap().bind.clone();
+    let port = bind.phoenixd_addr().port();
+    match exposure {
+        "local" => Ok(format!("http://{}", bind.phoenixd_addr())),
+        "lan" => {
+            if bind.phoenixd_ip().is_loopback() {
+                return Err(
+                    "phoenixd only listens on this computer; set bind.phoenixd to 0.0.0.0 to reach it from the network"
+                        .to_string(),
+                );
+            }
+            let ip = lan::lan_ip().ok_or("This computer has no local network address")?;
+            Ok(format!("http://{}:{}", ip, port))
+        }
+        _ => {
+            let published = {
+                let mut config = state.config.lock().unwrap();
+                if !config.tor.enabled {
+                    return Err(
+                        "Enable Tor first, the store reaches the node through its onion"
+                            .to_string(),
+                    );
+                }
+                if config.tor.node_api {
+                    None
+                } else {
+                    config.tor.node_api = true;
+                    config.save(&state.data_dir)?;
+                    Some(config.clone())
+                }
+            };
+            if let Some(config) = published {
+                audit::record(&state.data_dir, "mode.tor_node_api", "on");
+                let mut pm = state.process_manager.lock().unwrap();
+                pm.set_config(config);
+                pm.restart_tor()?;
+            }
+            let onion = TorManager::new(&state.data_dir)
+                .onion_address()
+                .ok_or("Tor hasn't published the onion address yet, try again in a minute")?;
+            Ok(format!("http://{}:{}", onion, port))
+        }
+    }
+}
+
+/// The most private exposure that reaches beyond this computer, if any
+pub fn default_exposure(app: &AppHandle) -> &'static str {
+    let config = app.state::<AppState>().config.lock().unwrap().clone();
+    if config.tor.enabled {
+        "tor"
+    } else if !config.bind.phoenixd_ip().is_loopback() {
+        "lan"
+    } else {
+        "local"
+    }
+}
+
+/// Connection details for the main node through `exposure`. `restricted`
+/// hands out the limited-access password, added to phoenix.conf if needed;
+/// the full password, which can send, needs the OS login.
+pub fn connection(
+    app: &AppHandle,
+    exposure: &str,
+    restricted: bool,
+) -> Result<StoreConnection, String> {
+    if !EXPOSURES.contains(&exposure) {
+        return Err(format!(
+            "Unknown exposure '{}': use {}",
+            exposure,
+            EXPOSURES.join(", ")
+        ));
+    }
+    let state = app.state::<AppState>();
+    let phoenix_conf = state.data_dir.join(".phoenix").join("phoenix.conf");
+    let password = if restricted {
+        if provision_limited_password(&phoenix_conf)? {
+            info!("🔑 Added a limited-access password to phoenix.conf, restarting phoenixd");
+            state
+                .process_manager
+                .lock()
+                .unwrap()
+                .restart_service("phoenixd")?;
+        }
+        phoenixd_api::read_limited_password(&phoenix_conf)
+    } else {
+        os_auth::require(
+            app,
+            &state.data_dir,
+            "store.full_access",
+            t("auth.store_full_access"),
+        )?;
+        phoenixd_api::read_password(&phoenix_conf)
+    };
+    if password.is_empty() {
+        return Err(format!("No phoenixd password in {:?}", phoenix_conf));
+    }
+    let url = node_url(app, exposure)?;
+    audit::record(
+        &state.data_dir,
+        "store.connect",
+        format!(
+            "{} {}",
+            exposure,
+            if restricted { "receive-only" } else { "full" }
+        ),
+    );
+    Ok(StoreConnection {
+        exposure: exposure.to_string(),
+        btcpay: format!("type=phoenixd;server={};password={}", url, password),
+        lnbits: format!(
+            "LNBITS_BACKEND_WALLET_CLASS=PhoenixdWallet\nPHOENIXD_API_ENDPOINT={}\nPHOENIXD_API_PASSWORD={}",
+            url, password
+        ),
+        url,
+        restricted,
+    })
+}
+
+/// Native window with both forms as copyable text and QR codes
+pub fn show_window(app: &AppHandle, connection: &StoreConnection) -> Result<(), String> {
+    let limits = if connection.restricted {
+        t("store.receive_only")
+    } else {
+        t("store.full_access")
+    };
+    let mut body = format!("<p>{}</p>", crate::windows::escape(limits));
+    for (label, text) in [
+        ("store.btcpay", &connection.btcpay),
+        ("store.lnbits", &connection.lnbits),
+    ] {
+        body.push_str(&format!(
+            r#"<section><h2>{}</h2><div class="qr">{}</div>{}</section>"#,
+            crate::windows::escape(t(label)),
+            crate::qr::to_svg(text)?,
+            crate::windows::copyable(text)
+        ));
+    }
+    crate::windows::show(
+        app,
+        "store-connect",
+        t("window.store_connect"),
+        &body,
+        (380.0, 720.0),
+    )
+}
@@ -0,0 +1,118 @@
+use crate::config::AppConfig;
+use crate::lease;
+use crate::logs;
+use crate::process_manager::{self, ProcessManager};
+use crate::synced_dir;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+/// Written while a headless supervisor owns the services
+const PID_FILE: &str = "supervisor.pid";
+/// Services the supervisor brings back when they exit on their own
+#[cfg_attr(not(any(windows, target_os = "macos")), allow(dead_code))]
+const CORE_SERVICES: [&str; 3] = ["phoenixd", "backend", "frontend"];
+
+/// Same directory Tauri resolves for the GUI (`app_data_dir`), so both
+/// modes share config, wallet and logs
+pub fn default_data_dir() -> PathBuf {
+    synced_dir::resolve(
+        dirs::data_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("com.phoenixd.dashboard"),
+    )
+}
+
+/// `--data-dir <path>` from the command line, or the default
+pub fn data_dir_from_args() -> PathBuf {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|arg| arg == "--data-dir")
+        .and_then(|i| args.get(i + 1))
+        .map(PathBuf::from)
+        .unwrap_or_else(default_data_dir)
+}
+
+/// Bundled resources relative to the executable, as Tauri lays them out
+pub fn resource_dir() -> PathBuf {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    if cfg!(target_os = "macos") {
+        exe_dir.join("../Resources")
+    } else {
+        exe_dir
+    }
+}
+
+/// Run the services without the GUI until `stop` is set. Used by the
+/// Windows service and the macOS launch agent; the tray app attaches to it
+/// instead of starting its own.
+#[cfg_attr(not(any(windows, target_os = "macos")), allow(dead_code))]
+pub fn run(data_dir: PathBuf, stop: Arc<AtomicBool>) -> Result<(), String> {
+    std::fs::create_dir_all(&data_dir)
+        .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    let config = AppConfig::load(&data_dir);
+    let _log_guard =
+        logs::init_tracing(&data_dir, &config.general.log_level, config.general.os_log);
+
+    info!("⚡ Starting headless supervisor in {:?}", data_dir);
+    let auto_heal = config.auto_heal.clone();
+    // Nobody is there to confirm a takeover
+    if let Some(lease) = lease::held_elsewhere(&data_dir) {
+        return Err(format!(
+            "The wallet in {:?} runs on {} (lease renewed at {})",
+            data_dir, lease.hostname, lease.heartbeat
+        ));
+    }
+    lease::acquire(&data_dir)?;
+    let mut pm = ProcessManager::new(resource_dir(), data_dir.clone(), config);
+    pm.start_all()?;
+    std::fs::write(data_dir.join(PID_FILE), std::process::id().to_string())
+        .map_err(|e| format!("Failed to write {}: {}", PID_FILE, e))?;
+
+    let mut renewed = Instant::now();
+    while !stop.load(Ordering::SeqCst) {
+        std::thread::sleep(Duration::from_secs(1));
+        if renewed.elapsed() >= lease::HEARTBEAT {
+            if let Err(e) = lease::renew(&data_dir) {
+                error!("🔒 {}, stopping", e);
+                break;
+            }
+            renewed = Instant::now();
+        }
+        for name in CORE_SERVICES {
+            let Some(status) = pm.exit_status(name) else {
+                continue;
+            };
+            if !auto_heal.restarts_after_exit(name, status.success()) {
+                continue;
+            }
+            warn!("{} stopped ({}), restarting", name, status);
+            if let Err(e) = pm.restart_service(name) {
+                error!("Failed to restart {}: {}", name, e);
+            }
+        }
+    }
+
+    info!("Stopping headless supervisor");
+    pm.stop_all();
+    lease::release(&data_dir);
+    let _ = std::fs::remove_file(data_dir.join(PID_FILE));
+    Ok(())
+}
+
+/// Whether a headless supervisor is running for `data_dir`
+pub fn is_running(data_dir: &Path) -> bool {
+    let Some(pid) = std::fs::read_to_string(data_dir.join(PID_FILE))
+        .ok()
+        .and_then(|pid| pid.trim().parse::<u32>().ok())
+    else {
+        return false;
+    };
+
+    process_manager::pid_alive(pid)
+}
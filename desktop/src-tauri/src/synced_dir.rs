@@ -0,0 +1,172 @@
+use crate::i18n::t;
+use crate::{audit, dialogs, docker_import, supervisor, AppState};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+use tracing::{info, warn};
+
+/// Folder names sync clients create, matched against every ancestor
+const FOLDER_NAMES: [(&str, &str); 9] = [
+    ("Dropbox", "Dropbox"),
+    ("OneDrive", "OneDrive"),
+    ("iCloud Drive", "iCloud"),
+    ("Mobile Documents", "iCloud"),
+    ("Google Drive", "Google Drive"),
+    ("GoogleDrive", "Google Drive"),
+    ("pCloudDrive", "pCloud"),
+    ("Box Sync", "Box"),
+    ("MEGAsync", "MEGA"),
+];
+/// Files or folders sync clients leave at the root of what they sync
+const MARKERS: [(&str, &str); 5] = [
+    (".dropbox", "Dropbox"),
+    (".dropbox.cache", "Dropbox"),
+    (".stfolder", "Syncthing"),
+    (".sync", "Resilio Sync"),
+    (".nextcloudsync.log", "Nextcloud"),
+];
+
+/// The sync client `dir` lives under, if any. Symlinks are followed, so a
+/// data dir linked into Dropbox counts.
+pub fn detect(dir: &Path) -> Option<&'static str> {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    for ancestor in dir.ancestors() {
+        let name = ancestor
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        // OneDrive folders are often "OneDrive - Company"
+        let by_name = FOLDER_NAMES
+            .iter()
+            .find(|(folder, _)| name == *folder || name.starts_with(&format!("{} - ", folder)));
+        if let Some((_, provider)) = by_name {
+            return Some(provider);
+        }
+        // macOS file providers mount under ~/Library/CloudStorage/<Provider>-<account>
+        let parent = ancestor.parent().and_then(Path::file_name);
+        if parent.is_some_and(|parent| parent == "CloudStorage") {
+            let provider = FOLDER_NAMES
+                .iter()
+                .find(|(folder, _)| name.starts_with(folder));
+            return Some(provider.map_or("a cloud storage provider", |(_, provider)| provider));
+        }
+        let marked = MARKERS
+            .iter()
+            .find(|(marker, _)| ancestor.join(marker).exists());
+        if let Some((_, provider)) = marked {
+            return Some(provider);
+        }
+    }
+    None
+}
+
+/// Next to the default data dir, so it's found before knowing where the
+/// data lives
+fn location_file(default: &Path) -> PathBuf {
+    let name = default.file_name().unwrap_or_default().to_string_lossy();
+    default.with_file_name(format!("{}.location", name))
+}
+
+/// Where the data dir was moved to, or `default`
+pub fn resolve(default: PathBuf) -> PathBuf {
+    std::fs::read_to_string(location_file(&default))
+        .ok()
+        .map(|location| PathBuf::from(location.trim()))
+        .filter(|location| location.is_dir())
+        .unwrap_or(default)
+}
+
+/// A local folder to move the data dir to: the default one if it isn't
+/// synced, otherwise one the user picks. Blocks on the folder picker.
+fn choose_target(app: &AppHandle, current: &Path) -> Option<PathBuf> {
+    let default = app.path().app_data_dir().ok()?;
+    if default != current && detect(&default).is_none() && !has_entries(&default) {
+        return Some(default);
+    }
+    let picked = app
+        .dialog()
+        .file()
+        .set_title(t("dialog.relocate_pick"))
+        .blocking_pick_folder()
+        .and_then(|path| path.into_path().ok())?;
+    Some(picked.join("phoenixd-dashboard"))
+}
+
+fn has_entries(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some())
+}
+
+/// Warn about a synced data dir and, if the user agrees, move it to a local
+/// folder and restart from there. Blocks on dialogs, so never call it on
+/// the main thread.
+pub fn offer_relocation(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let current = state.data_dir.clone();
+    let Some(provider) = detect(&current) else {
+        return;
+    };
+    warn!("☁️ The data dir {:?} is synced by {}", current, provider);
+    if !dialogs::confirm_relocation(app, provider, &current) {
+        return;
+    }
+    if supervisor::is_running(&current) {
+        dialogs::relocation_failed(app, t("dialog.relocate_background"));
+        return;
+    }
+    let Some(target) = choose_target(app, &current) else {
+        return;
+    };
+    if let Some(provider) = detect(&target) {
+        dialogs::relocation_failed(
+            app,
+            &t("dialog.relocate_target_synced").replace("{provider}", provider),
+        );
+        return;
+    }
+    if let Err(e) = relocate(app, &current, &target) {
+        dialogs::relocation_failed(app, &e);
+        return;
+    }
+    app.restart();
+}
+
+/// Stop the services, copy the data dir to `target` and point the app
+/// there. The synced copy is renamed, not deleted, in case something went
+/// missing.
+fn relocate(app: &AppHandle, current: &Path, target: &Path) -> Result<(), String> {
+    if has_entries(target) {
+        return Err(format!("{:?} is not empty", target));
+    }
+    let default = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let state = app.state::<AppState>();
+    info!("📦 Moving the data dir from {:?} to {:?}", current, target);
+    audit::record(current, "data_dir.relocate", target.to_string_lossy());
+    // Nothing may write to the data dir while it's copied
+    let mut pm = state.process_manager.lock().unwrap();
+    pm.stop_all();
+    if let Err(e) = docker_import::copy_dir(current, target) {
+        let _ = std::fs::remove_dir_all(target);
+        let _ = pm.start_all();
+        return Err(e);
+    }
+
+    let pointer = location_file(&default);
+    if let Err(e) = std::fs::write(&pointer, target.to_string_lossy().as_bytes()) {
+        let _ = pm.start_all();
+        return Err(format!("Failed to write {:?}: {}", pointer, e));
+    }
+
+    let aside = current.with_file_name(format!(
+        "{}.moved-{}",
+        current.file_name().unwrap_or_default().to_string_lossy(),
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    if let Err(e) = std::fs::rename(current, &aside) {
+        warn!("Failed to rename the synced data dir to {:?}: {}", aside, e);
+    }
+    info!("📦 Data dir moved, restarting");
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// What the local tailscale daemon reports about this machine
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TailscaleStatus {
+    pub installed: bool,
+    pub running: bool,
+    pub ip: Option<String>,
+    /// MagicDNS name without the trailing dot, e.g. `laptop.tail1234.ts.net`
+    pub dns_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StatusJson {
+    backend_state: String,
+    #[serde(rename = "Self")]
+    this: Option<PeerJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PeerJson {
+    #[serde(rename = "DNSName", default)]
+    dns_name: String,
+    #[serde(rename = "TailscaleIPs", default)]
+    tailscale_ips: Vec<String>,
+}
+
+fn find_tailscale_binary() -> Result<PathBuf, String> {
+    if let Ok(path) = which::which("tailscale") {
+        return Ok(path);
+    }
+
+    // The Mac App Store build doesn't put the CLI on PATH
+    let app_cli = PathBuf::from("/Applications/Tailscale.app/Contents/MacOS/Tailscale");
+    if cfg!(target_os = "macos") && app_cli.exists() {
+        return Ok(app_cli);
+    }
+
+    Err("Tailscale is not installed".to_string())
+}
+
+/// Query `tailscale status`; a missing install or stopped daemon is not an error
+pub fn status() -> TailscaleStatus {
+    let Ok(binary) = find_tailscale_binary() else {
+        return TailscaleStatus::default();
+    };
+    let installed = TailscaleStatus {
+        installed: true,
+        ..Default::default()
+    };
+    let Ok(output) = Command::new(binary).args(["status", "--json"]).output() else {
+        return installed;
+    };
+    let Ok(parsed) = serde_json::from_slice::<StatusJson>(&output.stdout) else {
+        return installed;
+    };
+
+    let this = parsed.this;
+    TailscaleStatus {
+        installed: true,
+        running: parsed.backend_state == "Running",
+        ip: this
+            .as_ref()
+            .and_then(|p| p.tailscale_ips.iter().find(|ip| !ip.contains(':')).cloned()),
+        dns_name: this
+            .map(|p| p.dns_name.trim_end_matches('.').to_string())
+            .filter(|name| !name.is_empty()),
+    }
+}
+
+/// Share a local address on the tailnet over https via `tailscale serve`.
+/// Only devices on the tailnet can reach it; tailscale provides the certificate.
+pub fn serve(addr: SocketAddr) -> Result<String, String> {
+    let status = status();
+    if !status.running {
+        return Err("Tailscale is not running".to_string());
+    }
+    let dns_name = status
+        .dns_name
+        .ok_or_else(|| "MagicDNS is not enabled on this tailnet".to_string())?;
+
+    let output = Command::new(find_tailscale_binary()?)
+        .args(["serve", "--bg", "--https=443"])
+        .arg(format!("http://{}", addr))
+        .output()
+        .map_err(|e| format!("Failed to run tailscale serve: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "tailscale serve failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(format!("https://{}", dns_name))
+}
+
+pub fn unserve() -> Result<(), String> {
+    let output = Command::new(find_tailscale_binary()?)
+        .args(["serve", "--https=443", "off"])
+        .output()
+        .map_err(|e| format!("Failed to run tailscale serve: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to stop tailscale serve: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Failure injection for exercising the watchdog, auto-heal and safe mode
+//! end to end. Only built with the `test-hooks` feature.
+
+use crate::database::DB_FILE;
+use crate::{journal, process_manager, AppState};
+use std::io::{Seek, SeekFrom, Write};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::State;
+use tracing::warn;
+
+/// Ports that read as closed, until when
+static BLACKHOLED: Mutex<Vec<(u16, Instant)>> = Mutex::new(Vec::new());
+
+/// Whether probes of `addr` should fail as if nothing listened there
+pub fn blackholed(addr: SocketAddr) -> bool {
+    let mut ports = BLACKHOLED.lock().unwrap();
+    ports.retain(|(_, until)| Instant::now() < *until);
+    ports.iter().any(|(port, _)| *port == addr.port())
+}
+
+/// SIGKILL a service's process behind the process manager's back, as a
+/// crash would
+#[tauri::command]
+pub fn kill_service(state: State<'_, AppState>, service: String) -> Result<u32, String> {
+    let pid = state
+        .process_manager
+        .lock()
+        .unwrap()
+        .service_pid(&service)
+        .ok_or_else(|| format!("{} is not running", service))?;
+    warn!("🧪 Killing {} (pid {})", service, pid);
+    journal::record(
+        &state.data_dir,
+        "test_hooks.kill",
+        Some(&service),
+        pid.to_string(),
+    );
+    process_manager::kill_pid(pid);
+    Ok(pid)
+}
+
+/// Overwrite the backend database's header, keeping an intact copy next
+/// to it. Returns where the copy went.
+#[tauri::command]
+pub fn corrupt_database(state: State<'_, AppState>) -> Result<PathBuf, String> {
+    let path = state.data_dir.join(DB_FILE);
+    let copy = state
+        .data_dir
+        .join(format!("{}.before-corruption", DB_FILE));
+    std::fs::copy(&path, &copy).map_err(|e| format!("Failed to copy {:?}: {}", path, e))?;
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    file.seek(SeekFrom::Start(0))
+        .and_then(|_| file.write_all(&[0xde; 100]))
+        .map_err(|e| format!("Failed to corrupt {:?}: {}", path, e))?;
+    warn!("🧪 Corrupted {:?}, intact copy in {:?}", path, copy);
+    journal::record(
+        &state.data_dir,
+        "test_hooks.corrupt_database",
+        Some("backend"),
+        copy.to_string_lossy(),
+    );
+    Ok(copy)
+}
+
+/// Make a service's port read as closed to every probe for `secs`
+/// seconds, without touching the service itself
+#[tauri::command]
+pub fn blackhole_port(
+    state: State<'_, AppState>,
+    service: String,
+    secs: u64,
+) -> Result<u16, String> {
+    let addr = state
+        .config
+        .lock()
+        .unwrap()
+        .bind
+        .service_addr(&service)
+        .ok_or_else(|| format!("{} has no port", service))?;
+    let until = Instant::now() + Duration::from_secs(secs);
+    let mut ports = BLACKHOLED.lock().unwrap();
+    ports.retain(|(port, _)| *port != addr.port());
+    ports.push((addr.port(), until));
+    warn!("🧪 Blackholing {} ({}) for {}s", service, addr, secs);
+    journal::record(
+        &state.data_dir,
+        "test_hooks.blackhole",
+        Some(&service),
+        format!("{}s", secs),
+    );
+    Ok(addr.port())
+}
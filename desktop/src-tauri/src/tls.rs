@@ -0,0 +1,282 @@
+use rcgen::{
+    BasicConstraints, CertificateParams, CidrSubnet, DnType, ExtendedKeyUsagePurpose,
+    GeneralSubtree, IsCa, KeyPair, KeyUsagePurpose, NameConstraints,
+};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::async_runtime::JoinHandle;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::{crypto::ring, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use tracing::{error, info, warn};
+
+/// Local CA + leaf certificate used to serve the dashboard over https
+pub struct TlsFiles {
+    dir: PathBuf,
+}
+
+impl TlsFiles {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join("tls"),
+        }
+    }
+
+    pub fn ca_cert_path(&self) -> PathBuf {
+        self.dir.join("ca.pem")
+    }
+
+    fn ca_key_path(&self) -> PathBuf {
+        self.dir.join("ca-key.pem")
+    }
+
+    pub fn cert_path(&self) -> PathBuf {
+        self.dir.join("cert.pem")
+    }
+
+    pub fn key_path(&self) -> PathBuf {
+        self.dir.join("key.pem")
+    }
+
+    /// Create the CA once, then (re)issue the leaf certificate for `hosts`.
+    /// The leaf is cheap and re-issued on every start so a changed LAN IP or
+    /// mDNS name is always covered, while the trusted CA stays the same.
+    pub fn ensure(&self, hosts: &[String]) -> Result<(), String> {
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create TLS dir: {}", e))?;
+
+        if !self.ca_cert_path().exists() || !self.ca_key_path().exists() {
+            self.generate_ca()?;
+        } else if !self.ca_is_constrained() {
+            warn!("🔐 Replacing the unconstrained local CA, install the new one to trust it");
+            self.generate_ca()?;
+        }
+
+        let ca_key = KeyPair::from_pem(&read(&self.ca_key_path())?)
+            .map_err(|e| format!("Invalid CA key: {}", e))?;
+        let ca_cert = CertificateParams::from_ca_cert_pem(&read(&self.ca_cert_path())?)
+            .and_then(|params| params.self_signed(&ca_key))
+            .map_err(|e| format!("Invalid CA certificate: {}", e))?;
+
+        let mut params = CertificateParams::new(hosts.to_vec())
+            .map_err(|e| format!("Invalid certificate hosts: {}", e))?;
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Phoenixd Dashboard");
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+
+        let key = KeyPair::generate().map_err(|e| format!("Failed to generate key: {}", e))?;
+        let cert = params
+            .signed_by(&key, &ca_cert, &ca_key)
+            .map_err(|e| format!("Failed to issue certificate: {}", e))?;
+
+        write_private(&self.key_path(), &key.serialize_pem())?;
+        std::fs::write(self.cert_path(), cert.pem())
+            .map_err(|e| format!("Failed to write certificate: {}", e))?;
+        Ok(())
+    }
+
+    fn generate_ca(&self) -> Result<(), String> {
+        info!("🔐 Generating local certificate authority...");
+
+        let mut params = CertificateParams::new(Vec::<String>::new())
+            .map_err(|e| format!("Invalid CA parameters: {}", e))?;
+        params.is_ca = IsCa::Ca(BasicConstraints::Constrained(0));
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Phoenixd Dashboard Local CA");
+        params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        params.name_constraints = Some(local_names());
+
+        let key = KeyPair::generate().map_err(|e| format!("Failed to generate CA key: {}", e))?;
+        let cert = params
+            .self_signed(&key)
+            .map_err(|e| format!("Failed to create CA: {}", e))?;
+
+        write_private(&self.ca_key_path(), &key.serialize_pem())?;
+        std::fs::write(self.ca_cert_path(), cert.pem())
+            .map_err(|e| format!("Failed to write CA certificate: {}", e))
+    }
+
+    /// Whether the CA can only sign for local names. Older ones could sign
+    /// for any site once trusted.
+    fn ca_is_constrained(&self) -> bool {
+        read(&self.ca_cert_path())
+            .ok()
+            .and_then(|pem| CertificateParams::from_ca_cert_pem(&pem).ok())
+            .is_some_and(|params| params.name_constraints.is_some())
+    }
+
+    pub fn server_config(&self) -> Result<Arc<ServerConfig>, String> {
+        let certs = CertificateDer::pem_slice_iter(read(&self.cert_path())?.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid certificate: {}", e))?;
+        let key = PrivateKeyDer::from_pem_slice(read(&self.key_path())?.as_bytes())
+            .map_err(|e| format!("Invalid private key: {}", e))?;
+
+        let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| format!("Invalid TLS configuration: {}", e))?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| format!("Invalid TLS certificate: {}", e))?;
+        Ok(Arc::new(config))
+    }
+
+    /// Add the local CA to the OS trust store so browsers accept the
+    /// certificate. Each platform asks the user for confirmation.
+    pub fn install_ca(&self) -> Result<(), String> {
+        let ca = self.ca_cert_path();
+        if !ca.exists() {
+            return Err("No local certificate authority has been generated yet".to_string());
+        }
+
+        let status = if cfg!(target_os = "macos") {
+            let keychain = dirs::home_dir()
+                .ok_or_else(|| "Could not find home directory".to_string())?
+                .join("Library/Keychains/login.keychain-db");
+            std::process::Command::new("security")
+                .args(["add-trusted-cert", "-r", "trustRoot", "-k"])
+                .arg(keychain)
+                .arg(&ca)
+                .status()
+        } else if cfg!(target_os = "windows") {
+            std::process::Command::new("certutil")
+                .args(["-user", "-addstore", "Root"])
+                .arg(&ca)
+                .status()
+        } else {
+            std::process::Command::new("pkexec")
+                .args(["sh", "-c"])
+                .arg(
+                    "cp \"$1\" /usr/local/share/ca-certificates/phoenixd-dashboard.crt && update-ca-certificates",
+                )
+                .arg("sh")
+                .arg(&ca)
+                .status()
+        };
+
+        match status {
+            Ok(status) if status.success() => {
+                info!("🔐 Installed local CA into the system trust store");
+                Ok(())
+            }
+            Ok(status) => Err(format!("Installing the CA failed ({})", status)),
+            Err(e) => Err(format!("Failed to install the CA: {}", e)),
+        }
+    }
+}
+
+/// Terminates TLS on each listen port and pipes the plain connection to a
+/// local upstream port, so websockets keep working untouched
+pub struct TlsTerminator {
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl TlsTerminator {
+    pub fn start(
+        config: Arc<ServerConfig>,
+        bind_ip: &str,
+        routes: &[(u16, SocketAddr)],
+    ) -> Result<Self, String> {
+        let mut tasks = Vec::new();
+
+        for &(listen_port, upstream) in routes {
+            let listener = std::net::TcpListener::bind((bind_ip, listen_port))
+                .and_then(|l| l.set_nonblocking(true).map(|_| l))
+                .map_err(|e| format!("Failed to listen on port {}: {}", listen_port, e))?;
+            let acceptor = TlsAcceptor::from(Arc::clone(&config));
+
+            tasks.push(tauri::async_runtime::spawn(async move {
+                let listener = match TcpListener::from_std(listener) {
+                    Ok(listener) => listener,
+                    Err(e) => {
+                        error!("TLS listener on {} failed: {}", listen_port, e);
+                        return;
+                    }
+                };
+
+                while let Ok((stream, _)) = listener.accept().await {
+                    let acceptor = acceptor.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let Ok(mut tls) = acceptor.accept(stream).await else {
+                            return;
+                        };
+                        let Ok(mut upstream) = TcpStream::connect(upstream).await else {
+                            return;
+                        };
+                        let _ = tokio::io::copy_bidirectional(&mut tls, &mut upstream).await;
+                    });
+                }
+            }));
+
+            info!("🔐 https on port {} -> {}", listen_port, upstream);
+        }
+
+        Ok(Self { tasks })
+    }
+
+    pub fn stop(self) {
+        for task in self.tasks {
+            task.abort();
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))
+}
+
+/// What the local CA may sign for: localhost, mDNS names and loopback and
+/// private addresses. The CA outlives the LAN IP, so the whole private ranges
+/// rather than the current address.
+fn local_names() -> NameConstraints {
+    let subnet = |addr: &str, prefix| {
+        GeneralSubtree::IpAddress(CidrSubnet::from_addr_prefix(
+            addr.parse::<IpAddr>().expect("valid address"),
+            prefix,
+        ))
+    };
+    NameConstraints {
+        permitted_subtrees: vec![
+            GeneralSubtree::DnsName("localhost".to_string()),
+            GeneralSubtree::DnsName("local".to_string()),
+            subnet("127.0.0.0", 8),
+            subnet("10.0.0.0", 8),
+            subnet("172.16.0.0", 12),
+            subnet("192.168.0.0", 16),
+            subnet("169.254.0.0", 16),
+            subnet("::1", 128),
+            subnet("fc00::", 7),
+            subnet("fe80::", 10),
+        ],
+        excluded_subtrees: Vec::new(),
+    }
+}
+
+pub fn write_private(path: &Path, content: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // Created owner-only, so the content is never readable by others
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+    // The mode only applies to new files
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to set permissions on {:?}: {}", path, e))?;
+    }
+
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
@@ -0,0 +1,66 @@
+use crate::redact;
+use crate::tls;
+use data_encoding::HEXLOWER;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+
+/// Bearer token of the current backend run, shared with other shell processes
+const API_TOKEN_FILE: &str = "api.token";
+
+/// Random 128-bit hex token for passwords, pairing and session secrets
+pub fn generate() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate token: {}", e))?;
+    let token = HEXLOWER.encode(&bytes);
+    redact::register(&token);
+    Ok(token)
+}
+
+/// Fresh bearer token the backend requires on its API, saved so an attached
+/// app (or the headless supervisor) can authenticate too
+pub fn issue_api_token(data_dir: &Path) -> Result<String, String> {
+    let token = generate()?;
+    tls::write_private(&data_dir.join(API_TOKEN_FILE), &token)?;
+    Ok(token)
+}
+
+/// Token of the running backend, if one was issued
+pub fn api_token(data_dir: &Path) -> Option<String> {
+    std::fs::read_to_string(data_dir.join(API_TOKEN_FILE))
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .inspect(|token| redact::register(token))
+}
+
+/// `next` behind the backend's pair page with a one-time ticket, so the
+/// browser opening it gets the cookie the backend accepts in place of the
+/// bearer token. `backend` is where the backend listens, `backend_url` the
+/// backend as the browser reaches it.
+pub fn pair_url(
+    data_dir: &Path,
+    backend: SocketAddr,
+    backend_url: &str,
+    next: &str,
+) -> Result<String, String> {
+    let token = api_token(data_dir).ok_or_else(|| "No API token issued".to_string())?;
+    let response: serde_json::Value =
+        ureq::post(&format!("http://{}/api/desktop/tickets", backend))
+            .timeout(Duration::from_secs(5))
+            .set("Authorization", &format!("Bearer {}", token))
+            .call()
+            .map_err(|e| format!("Failed to get a sign-in ticket from the backend: {}", e))?
+            .into_json()
+            .map_err(|e| format!("Invalid sign-in ticket from the backend: {}", e))?;
+    let ticket = response["ticket"]
+        .as_str()
+        .ok_or_else(|| "The backend sent no sign-in ticket".to_string())?;
+
+    let mut url = tauri::Url::parse(&format!("{}/api/desktop/pair", backend_url))
+        .map_err(|e| format!("Invalid backend URL {}: {}", backend_url, e))?;
+    url.query_pairs_mut()
+        .append_pair("ticket", ticket)
+        .append_pair("next", next);
+    Ok(url.to_string())
+}
@@ -0,0 +1,381 @@
+use data_encoding::{BASE32_NOPAD, HEXUPPER};
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tracing::{info, warn};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// Client credentials for a v3 onion service with client authorization enabled
+#[derive(Debug, Clone, Serialize)]
+pub struct TorClientAuth {
+    pub name: String,
+    pub onion_address: String,
+    /// Contents of the client's `<name>.auth_private` file
+    pub private_key: String,
+    pub qr_svg: String,
+}
+
+/// Local SOCKS port, used to check the onion service from the outside and
+/// to route phoenixd's traffic
+pub const SOCKS_PORT: u16 = 9063;
+/// Control port, cookie-authenticated, used to request new circuits
+pub const CONTROL_PORT: u16 = 9064;
+
+/// Answers whether the request came out of a tor exit
+const CHECK_URL: &str = "https://check.torproject.org/api/ip";
+/// tor may still be bootstrapping when phoenixd starts
+const CIRCUIT_CHECKS: u32 = 12;
+const CIRCUIT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Set once phoenixd runs behind torsocks and a circuit was seen working
+static NODE_PRIVATE: AtomicBool = AtomicBool::new(false);
+
+/// Whether phoenixd's traffic is known to go over Tor
+pub fn node_private() -> bool {
+    NODE_PRIVATE.load(Ordering::Relaxed)
+}
+
+pub fn set_node_private(private: bool) {
+    NODE_PRIVATE.store(private, Ordering::Relaxed);
+}
+
+/// Ask the Tor Project whether requests through our SOCKS port leave from a
+/// tor exit, returning the exit's address
+pub fn verify_circuit() -> Result<String, String> {
+    let proxy = ureq::Proxy::new(format!("socks5://127.0.0.1:{}", SOCKS_PORT))
+        .map_err(|e| format!("Invalid SOCKS proxy: {}", e))?;
+    let body: serde_json::Value = ureq::AgentBuilder::new()
+        .proxy(proxy)
+        .timeout(Duration::from_secs(30))
+        .build()
+        .get(CHECK_URL)
+        .call()
+        .map_err(|e| format!("Tor check failed: {}", e))?
+        .into_json()
+        .map_err(|e| format!("Invalid Tor check response: {}", e))?;
+
+    if body["IsTor"].as_bool() == Some(true) {
+        Ok(body["IP"].as_str().unwrap_or_default().to_string())
+    } else {
+        Err("Traffic through the SOCKS port doesn't leave via Tor".to_string())
+    }
+}
+
+/// Verify the circuit in the background, marking the node private once it works
+pub fn spawn_circuit_check() {
+    std::thread::spawn(|| {
+        let mut last_error = String::new();
+        for _ in 0..CIRCUIT_CHECKS {
+            std::thread::sleep(CIRCUIT_CHECK_INTERVAL);
+            match verify_circuit() {
+                Ok(exit) => {
+                    info!("🧅 phoenixd's traffic goes over Tor (exit {})", exit);
+                    set_node_private(true);
+                    return;
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        warn!("🧅 Could not verify the Tor circuit: {}", last_error);
+    });
+}
+
+pub struct TorManager {
+    tor_dir: PathBuf,
+}
+
+impl TorManager {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            tor_dir: data_dir.join("tor"),
+        }
+    }
+
+    pub fn torrc_path(&self) -> PathBuf {
+        self.tor_dir.join("torrc")
+    }
+
+    pub fn torsocks_conf_path(&self) -> PathBuf {
+        self.tor_dir.join("torsocks.conf")
+    }
+
+    pub fn hidden_service_dir(&self) -> PathBuf {
+        self.tor_dir.join("hidden_service")
+    }
+
+    fn authorized_clients_dir(&self) -> PathBuf {
+        self.hidden_service_dir().join("authorized_clients")
+    }
+
+    /// Write the torrc and create the directories tor expects, returning the
+    /// torrc path. Without a dashboard address tor only serves the SOCKS port;
+    /// `node_api` also goes on the onion, on its own port.
+    pub fn prepare(
+        &self,
+        dashboard: Option<SocketAddr>,
+        node_api: Option<SocketAddr>,
+    ) -> Result<PathBuf, String> {
+        secure_dir(&self.tor_dir)?;
+        secure_dir(&self.tor_dir.join("data"))?;
+
+        let mut torrc = format!(
+            "DataDirectory {}\nSocksPort 127.0.0.1:{}\nControlPort 127.0.0.1:{}\nCookieAuthentication 1\n",
+            self.tor_dir.join("data").display(),
+            SOCKS_PORT,
+            CONTROL_PORT
+        );
+        if let Some(dashboard) = dashboard {
+            secure_dir(&self.hidden_service_dir())?;
+            secure_dir(&self.authorized_clients_dir())?;
+            torrc.push_str(&format!(
+                "HiddenServiceDir {}\nHiddenServicePort 80 {}\n",
+                self.hidden_service_dir().display(),
+                dashboard
+            ));
+            if let Some(node_api) = node_api {
+                torrc.push_str(&format!(
+                    "HiddenServicePort {} {}\n",
+                    node_api.port(),
+                    node_api
+                ));
+            }
+        }
+
+        let path = self.torrc_path();
+        std::fs::write(&path, torrc).map_err(|e| format!("Failed to write torrc: {}", e))?;
+        Ok(path)
+    }
+
+    /// torsocks config sending phoenixd's connections to our SOCKS port.
+    /// Inbound is allowed so phoenixd's API keeps its bind address.
+    pub fn write_torsocks_conf(&self) -> Result<PathBuf, String> {
+        secure_dir(&self.tor_dir)?;
+        let path = self.torsocks_conf_path();
+        std::fs::write(
+            &path,
+            format!(
+                "TorAddress 127.0.0.1\nTorPort {}\nAllowInbound 1\n",
+                SOCKS_PORT
+            ),
+        )
+        .map_err(|e| format!("Failed to write torsocks.conf: {}", e))?;
+        Ok(path)
+    }
+
+    /// Onion hostname published by tor after the hidden service was first started
+    pub fn onion_address(&self) -> Option<String> {
+        std::fs::read_to_string(self.hidden_service_dir().join("hostname"))
+            .ok()
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty())
+    }
+
+    /// Whether only authorized clients can reach the onion service
+    pub fn client_auth_enabled(&self) -> bool {
+        self.list_clients().is_ok_and(|clients| !clients.is_empty())
+    }
+
+    /// Ask tor for fresh circuits (`SIGNAL NEWNYM`) over the control port
+    pub fn new_identity(&self) -> Result<(), String> {
+        self.control(&["SIGNAL NEWNYM"])?;
+        info!("🧅 Requested a new Tor identity");
+        Ok(())
+    }
+
+    /// Bytes tor has read and written since it started
+    pub fn traffic(&self) -> Result<(u64, u64), String> {
+        let replies = self.control(&["GETINFO traffic/read traffic/written"])?;
+        let value = |key: &str| {
+            replies[0]
+                .iter()
+                .find_map(|line| line.strip_prefix(&format!("{}=", key)))
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .ok_or_else(|| format!("tor did not report {}", key))
+        };
+        Ok((value("traffic/read")?, value("traffic/written")?))
+    }
+
+    /// Run commands over a cookie-authenticated control connection,
+    /// returning each command's reply lines without the status codes
+    fn control(&self, commands: &[&str]) -> Result<Vec<Vec<String>>, String> {
+        let cookie = std::fs::read(self.tor_dir.join("data").join("control_auth_cookie"))
+            .map_err(|e| format!("Failed to read the tor control cookie: {}", e))?;
+        let mut stream = TcpStream::connect_timeout(
+            &SocketAddr::from(([127, 0, 0, 1], CONTROL_PORT)),
+            Duration::from_secs(5),
+        )
+        .map_err(|e| format!("Failed to connect to the tor control port: {}", e))?;
+        let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+        let mut reader = BufReader::new(
+            stream
+                .try_clone()
+                .map_err(|e| format!("Failed to connect to the tor control port: {}", e))?,
+        );
+
+        let authenticate = format!("AUTHENTICATE {}", HEXUPPER.encode(&cookie));
+        let mut replies = Vec::new();
+        for command in std::iter::once(authenticate.as_str()).chain(commands.iter().copied()) {
+            stream
+                .write_all(format!("{}\r\n", command).as_bytes())
+                .map_err(|e| format!("Failed to talk to tor: {}", e))?;
+            // Replies are `250-key=value` lines ending with `250 OK`
+            let mut lines = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader
+                    .read_line(&mut line)
+                    .map_err(|e| format!("Failed to talk to tor: {}", e))?;
+                if !line.starts_with("250") {
+                    return Err(format!("tor refused the request: {}", line.trim()));
+                }
+                lines.push(line.get(4..).unwrap_or_default().trim_end().to_string());
+                if line.as_bytes().get(3) == Some(&b' ') {
+                    break;
+                }
+            }
+            replies.push(lines);
+        }
+        let _ = stream.write_all(b"QUIT\r\n");
+        // Drop the AUTHENTICATE reply
+        replies.remove(0);
+        Ok(replies)
+    }
+
+    pub fn list_clients(&self) -> Result<Vec<String>, String> {
+        let dir = self.authorized_clients_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read authorized clients: {}", e))?;
+
+        let mut clients: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) == Some("auth") {
+                    path.file_stem().and_then(|s| s.to_str()).map(String::from)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        clients.sort();
+        Ok(clients)
+    }
+
+    /// Generate a keypair, install the public half for the hidden service and
+    /// return the private half. The private key is never written to data_dir.
+    pub fn add_client(&self, name: &str) -> Result<TorClientAuth, String> {
+        validate_client_name(name)?;
+
+        let onion_address = self.onion_address().ok_or_else(|| {
+            "Onion address not available yet. Start Tor once before adding clients.".to_string()
+        })?;
+
+        let auth_file = self.authorized_clients_dir().join(format!("{}.auth", name));
+        if auth_file.exists() {
+            return Err(format!("A client named '{}' already exists", name));
+        }
+
+        let mut secret_bytes = [0u8; 32];
+        getrandom::getrandom(&mut secret_bytes)
+            .map_err(|e| format!("Failed to generate client key: {}", e))?;
+        let secret = StaticSecret::from(secret_bytes);
+        let public = PublicKey::from(&secret);
+
+        secure_dir(&self.authorized_clients_dir())?;
+        std::fs::write(
+            &auth_file,
+            format!(
+                "descriptor:x25519:{}\n",
+                BASE32_NOPAD.encode(public.as_bytes())
+            ),
+        )
+        .map_err(|e| format!("Failed to install client key: {}", e))?;
+
+        let private_key = format!(
+            "{}:descriptor:x25519:{}",
+            onion_address.trim_end_matches(".onion"),
+            BASE32_NOPAD.encode(&secret.to_bytes())
+        );
+        let qr_svg = crate::qr::to_svg(&private_key)?;
+
+        info!("🧅 Authorized Tor client '{}'", name);
+        Ok(TorClientAuth {
+            name: name.to_string(),
+            onion_address,
+            private_key,
+            qr_svg,
+        })
+    }
+
+    pub fn remove_client(&self, name: &str) -> Result<(), String> {
+        validate_client_name(name)?;
+
+        let auth_file = self.authorized_clients_dir().join(format!("{}.auth", name));
+        if !auth_file.exists() {
+            return Err(format!("No client named '{}'", name));
+        }
+
+        std::fs::remove_file(&auth_file)
+            .map_err(|e| format!("Failed to remove client key: {}", e))?;
+        info!("🧅 Revoked Tor client '{}'", name);
+        Ok(())
+    }
+}
+
+/// Fetch our own onion service through the SOCKS port, like a remote
+/// client would
+pub fn check_onion(onion: &str, timeout: Duration) -> Result<(), String> {
+    let proxy = ureq::Proxy::new(format!("socks5://127.0.0.1:{}", SOCKS_PORT))
+        .map_err(|e| format!("Invalid SOCKS proxy: {}", e))?;
+    let agent = ureq::AgentBuilder::new()
+        .proxy(proxy)
+        .timeout(timeout)
+        .build();
+    match agent.get(&format!("http://{}/", onion)).call() {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, _)) if code < 500 => Ok(()),
+        Err(ureq::Error::Status(code, _)) => Err(format!("Onion service answered HTTP {}", code)),
+        Err(ureq::Error::Transport(e)) => Err(format!("Onion service not reachable: {}", e)),
+    }
+}
+
+/// Write the private key in the `.auth_private` format expected by Tor Browser / tor's ClientOnionAuthDir
+pub fn export_private_key(auth: &TorClientAuth, path: &Path) -> Result<(), String> {
+    crate::tls::write_private(path, &format!("{}\n", auth.private_key))
+        .map_err(|e| format!("Failed to export client key: {}", e))
+}
+
+fn validate_client_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err("Client name must be 1-64 characters of letters, digits, '-' or '_'".to_string())
+    }
+}
+
+/// Tor refuses to use hidden service directories readable by other users
+fn secure_dir(path: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(path).map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o700))
+            .map_err(|e| format!("Failed to set permissions on {:?}: {}", path, e))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use crate::process_manager::ServiceState;
+use crate::tor::{self, TorManager};
+use crate::{journal, scheduler, AppState};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{debug, error, info, warn};
+
+/// tor needs a while to bootstrap and publish the descriptor
+const GRACE: Duration = Duration::from_secs(2 * 60);
+/// Onion connections are slow to set up, even when healthy
+const PROBE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Failed checks in a row before tor counts as degraded
+const DEGRADED_AFTER: u32 = 2;
+/// Failed checks in a row before tor gets restarted
+const RESTART_AFTER: u32 = 3;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TorHealth {
+    /// None until checked, or when client authorization keeps us out
+    pub onion_reachable: Option<bool>,
+    pub consecutive_failures: u32,
+    pub last_checked: Option<String>,
+    pub last_error: Option<String>,
+    pub restarts: u32,
+}
+
+static HEALTH: Mutex<Option<TorHealth>> = Mutex::new(None);
+
+pub fn status() -> TorHealth {
+    HEALTH.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Whether the onion service failed enough checks to show tor as unhealthy
+pub fn degraded() -> bool {
+    status().consecutive_failures >= DEGRADED_AFTER
+}
+
+/// Periodically reach our own onion service through tor's SOCKS port and
+/// restart tor when it keeps failing
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        std::thread::sleep(GRACE);
+        loop {
+            if check(&app) {
+                // Give the restarted tor the same head start
+                std::thread::sleep(GRACE);
+            } else {
+                scheduler::sleep(&app, "tor_health");
+            }
+        }
+    });
+}
+
+/// Run one check, returning whether tor was restarted
+fn check(app: &AppHandle) -> bool {
+    let state = app.state::<AppState>();
+    let (enabled, auto_restart) = {
+        let config = state.config.lock().unwrap();
+        (config.tor.enabled, config.tor.auto_restart)
+    };
+    let running = state
+        .statuses
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|status| status.name == "tor" && status.state != ServiceState::Stopped);
+    let manager = TorManager::new(&state.data_dir);
+    let onion = manager.onion_address().filter(|_| enabled && running);
+    let Some(onion) = onion else {
+        *HEALTH.lock().unwrap() = None;
+        return false;
+    };
+    // Without a client key of our own the descriptor can't be read
+    if manager.client_auth_enabled() {
+        debug!("🧅 Skipping the onion check, client authorization is on");
+        *HEALTH.lock().unwrap() = None;
+        return false;
+    }
+
+    let result = tor::check_onion(&onion, PROBE_TIMEOUT);
+    let mut health = status();
+    health.last_checked = Some(chrono::Utc::now().to_rfc3339());
+    match &result {
+        Ok(()) => {
+            if health.onion_reachable == Some(false) {
+                info!("🧅 Onion service reachable again");
+            }
+            health.onion_reachable = Some(true);
+            health.consecutive_failures = 0;
+            health.last_error = None;
+        }
+        Err(e) => {
+            health.onion_reachable = Some(false);
+            health.consecutive_failures += 1;
+            health.last_error = Some(e.clone());
+            warn!("🧅 Onion service check failed: {}", e);
+        }
+    }
+    let restart = auto_restart && health.consecutive_failures >= RESTART_AFTER;
+    if restart {
+        health.consecutive_failures = 0;
+        health.restarts += 1;
+    }
+    *HEALTH.lock().unwrap() = Some(health);
+
+    if restart {
+        warn!("🧅 Onion service unreachable, restarting tor");
+        journal::record(
+            &state.data_dir,
+            "service.restarted",
+            Some("tor"),
+            result.err().unwrap_or_default(),
+        );
+        let mut pm = state.process_manager.lock().unwrap();
+        if let Err(e) = pm.restart_tor() {
+            error!("Failed to restart tor: {}", e);
+        }
+        let services = pm.service_statuses();
+        crate::refresh_tray_menu(app, &services);
+    }
+    // The watchdog picks up the degraded state on its next pass
+    restart
+}
@@ -0,0 +1,243 @@
+use crate::process_manager::{ServiceState, ServiceStatus};
+use std::sync::Mutex;
+use tauri::image::Image;
+use tauri::AppHandle;
+use tracing::warn;
+
+/// Aggregate state the tray icon shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Health {
+    Healthy,
+    /// phoenixd is fine but another service isn't
+    Degraded,
+    /// phoenixd isn't running
+    Down,
+    /// phoenixd runs but doesn't answer yet (starting or catching up)
+    Syncing,
+    /// phoenixd runs but the internet is unreachable
+    Offline,
+}
+
+impl Health {
+    pub fn of(services: &[ServiceStatus]) -> Self {
+        let node = services
+            .iter()
+            .find(|s| s.name == "phoenixd")
+            .map_or(ServiceState::Stopped, |s| s.state);
+        match node {
+            ServiceState::Stopped => Health::Down,
+            ServiceState::Unhealthy => Health::Syncing,
+            ServiceState::Running if services.iter().all(|s| s.state == ServiceState::Running) => {
+                Health::Healthy
+            }
+            ServiceState::Running => Health::Degraded,
+        }
+    }
+
+    /// An extra node that is down degrades an otherwise healthy setup
+    pub fn with_nodes(self, all_up: bool) -> Self {
+        if self == Health::Healthy && !all_up {
+            Health::Degraded
+        } else {
+            self
+        }
+    }
+
+    /// Without internet a running node can't be healthy, whatever its processes say
+    pub fn with_network(self, online: bool) -> Self {
+        if online || self == Health::Down {
+            self
+        } else {
+            Health::Offline
+        }
+    }
+}
+
+/// Taskbar/panel theme; macOS recolors template icons itself
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+/// What the tray currently shows, so unchanged icons aren't reset every poll
+static SHOWN: Mutex<Option<(Health, Theme)>> = Mutex::new(None);
+
+pub fn icon(health: Health, theme: Theme) -> Image<'static> {
+    let bytes: &[u8] = if cfg!(target_os = "macos") {
+        match health {
+            Health::Healthy => include_bytes!("../icons/tray/healthy-template.png"),
+            Health::Degraded => include_bytes!("../icons/tray/degraded-template.png"),
+            Health::Down => include_bytes!("../icons/tray/down-template.png"),
+            Health::Syncing => include_bytes!("../icons/tray/syncing-template.png"),
+            Health::Offline => include_bytes!("../icons/tray/offline-template.png"),
+        }
+    } else {
+        match (health, theme) {
+            (Health::Healthy, Theme::Light) => include_bytes!("../icons/tray/healthy-light.png"),
+            (Health::Healthy, Theme::Dark) => include_bytes!("../icons/tray/healthy-dark.png"),
+            (Health::Degraded, Theme::Light) => include_bytes!("../icons/tray/degraded-light.png"),
+            (Health::Degraded, Theme::Dark) => include_bytes!("../icons/tray/degraded-dark.png"),
+            (Health::Down, Theme::Light) => include_bytes!("../icons/tray/down-light.png"),
+            (Health::Down, Theme::Dark) => include_bytes!("../icons/tray/down-dark.png"),
+            (Health::Syncing, Theme::Light) => include_bytes!("../icons/tray/syncing-light.png"),
+            (Health::Syncing, Theme::Dark) => include_bytes!("../icons/tray/syncing-dark.png"),
+            (Health::Offline, Theme::Light) => include_bytes!("../icons/tray/offline-light.png"),
+            (Health::Offline, Theme::Dark) => include_bytes!("../icons/tray/offline-dark.png"),
+        }
+    };
+    Image::from_bytes(bytes).expect("Failed to load tray icon")
+}
+
+/// Switch the tray icon if health or theme changed since the last call
+pub fn update(app: &AppHandle, health: Health, theme: Theme) {
+    let mut shown = SHOWN.lock().unwrap();
+    if *shown == Some((health, theme)) {
+        return;
+    }
+    let Some(tray) = app.tray_by_id("main") else {
+        return;
+    };
+
+    if let Err(e) = tray.set_icon(Some(icon(health, theme))) {
+        warn!("Could not update tray icon: {}", e);
+        return;
+    }
+    let _ = tray.set_icon_as_template(cfg!(target_os = "macos"));
+    *shown = Some((health, theme));
+}
+
+/// Theme of the taskbar or panel the tray icon sits on
+#[cfg(windows)]
+pub fn os_theme() -> Theme {
+    use windows_sys::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+    let wide = |text: &str| text.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
+    let key = wide(r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize");
+    let value = wide("SystemUsesLightTheme");
+    let mut light: u32 = 0;
+    let mut size = std::mem::size_of::<u32>() as u32;
+
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            key.as_ptr(),
+            value.as_ptr(),
+            RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            &mut light as *mut u32 as *mut _,
+            &mut size,
+        )
+    };
+    if status == 0 && light == 1 {
+        Theme::Light
+    } else {
+        Theme::Dark
+    }
+}
+
+/// Theme of the taskbar or panel the tray icon sits on
+#[cfg(target_os = "linux")]
+pub fn os_theme() -> Theme {
+    // Most panels are dark; only switch when the desktop asks for light
+    let scheme = std::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "color-scheme"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        .unwrap_or_default();
+    if scheme.contains("prefer-light") {
+        Theme::Light
+    } else {
+        Theme::Dark
+    }
+}
+
+/// Theme of the taskbar or panel the tray icon sits on
+#[cfg(not(any(windows, target_os = "linux")))]
+pub fn os_theme() -> Theme {
+    Theme::Dark
+}
+
+/// How long a StatusNotifier host gets to show up, e.g. a panel that
+/// starts after the app at login
+#[cfg(target_os = "linux")]
+const HOST_GRACE: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Whether a StatusNotifier host is there to show the tray icon; `None`
+/// when neither gdbus nor dbus-send can ask the session bus
+#[cfg(target_os = "linux")]
+fn status_notifier_host() -> Option<bool> {
+    use std::process::Command;
+    const WATCHER: &str = "org.kde.StatusNotifierWatcher";
+    let gdbus = || {
+        Command::new("gdbus")
+            .args([
+                "call",
+                "--session",
+                "--dest",
+                "org.freedesktop.DBus",
+                "--object-path",
+                "/org/freedesktop/DBus",
+                "--method",
+                "org.freedesktop.DBus.NameHasOwner",
+                WATCHER,
+            ])
+            .output()
+    };
+    let dbus_send = || {
+        Command::new("dbus-send")
+            .args([
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.DBus",
+                "/org/freedesktop/DBus",
+                "org.freedesktop.DBus.NameHasOwner",
+                &format!("string:{}", WATCHER),
+            ])
+            .output()
+    };
+    let output = gdbus()
+        .ok()
+        .filter(|output| output.status.success())
+        .or_else(|| dbus_send().ok().filter(|output| output.status.success()))?;
+    Some(String::from_utf8_lossy(&output.stdout).contains("true"))
+}
+
+/// What to do about a missing tray on this desktop
+#[cfg(target_os = "linux")]
+fn tray_hint() -> &'static str {
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .to_lowercase();
+    if desktop.contains("gnome") || desktop.contains("ubuntu") {
+        "install and enable the \"AppIndicator and KStatusNotifierItem Support\" GNOME extension (gnome-shell-extension-appindicator), then log out and back in"
+    } else {
+        "add a system tray / StatusNotifier applet to your panel, or run snixembed if the panel only supports the legacy XEmbed tray"
+    }
+}
+
+/// libappindicator creates the icon even when nothing can show it. Watch
+/// for a StatusNotifier host and fall back to the status window without one.
+#[cfg(target_os = "linux")]
+pub fn watch_host(app: AppHandle) {
+    std::thread::spawn(move || {
+        let started = std::time::Instant::now();
+        loop {
+            match status_notifier_host() {
+                Some(true) | None => return,
+                Some(false) if started.elapsed() < HOST_GRACE => {
+                    std::thread::sleep(std::time::Duration::from_secs(5));
+                }
+                Some(false) => break,
+            }
+        }
+        warn!(
+            "⚠️ No StatusNotifier host on the session bus, the tray icon won't show. To get it back, {}. Using the status window meanwhile.",
+            tray_hint()
+        );
+        crate::status_window::set_tray_missing();
+        if let Err(e) = crate::status_window::show(&app) {
+            warn!("{}", e);
+        }
+    });
+}
@@ -0,0 +1,71 @@
+use crate::i18n::t;
+use crate::notify;
+use crate::operations;
+use crate::AppState;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tracing::error;
+
+/// Menu ids of tray actions still running
+static RUNNING: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+pub fn is_running(id: &str) -> bool {
+    RUNNING.lock().unwrap().contains(id)
+}
+
+/// Run a slow tray action on a worker thread so the tray stays responsive.
+/// Its menu item shows as busy meanwhile, and a notification reports the
+/// outcome, with the detail the action returns. Clicking again while it
+/// runs does nothing.
+pub fn spawn(
+    app: &AppHandle,
+    id: impl Into<String>,
+    description: String,
+    work: impl FnOnce(&AppHandle) -> Result<Option<String>, String> + Send + 'static,
+) {
+    spawn_with(app, id, description, |app, _| work(app));
+}
+
+/// Like `spawn`, for work that reports progress or honors cancellation
+/// through its operation handle
+pub fn spawn_with(
+    app: &AppHandle,
+    id: impl Into<String>,
+    description: String,
+    work: impl FnOnce(&AppHandle, &operations::Handle) -> Result<Option<String>, String>
+        + Send
+        + 'static,
+) {
+    let id = id.into();
+    if !RUNNING.lock().unwrap().insert(id.clone()) {
+        return;
+    }
+    refresh_menu(app);
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let kind = id.split(':').next().unwrap_or_default();
+        let result = operations::run(&app, kind, description.clone(), |op| work(&app, op));
+        RUNNING.lock().unwrap().remove(&id);
+        refresh_menu(&app);
+
+        let notify = notify::native(app.clone());
+        match result {
+            Ok(None) => notify(t("tray.job_done"), &description),
+            Ok(Some(detail)) => notify(t("tray.job_done"), &format!("{}: {}", description, detail)),
+            Err(e) => {
+                error!("{}", e);
+                notify(t("tray.job_failed"), &format!("{}: {}", description, e));
+            }
+        }
+    });
+}
+
+/// Rebuild the menu from cached states; the worker may hold the manager
+fn refresh_menu(app: &AppHandle) {
+    if let Some(state) = app.try_state::<AppState>() {
+        let services = state.statuses.lock().unwrap().clone();
+        crate::refresh_tray_menu(app, &services);
+    }
+}
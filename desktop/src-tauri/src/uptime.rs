@@ -0,0 +1,104 @@
+use crate::process_manager::{ServiceState, ServiceStatus};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+const STATS_FILE: &str = "uptime.json";
+/// Days of per-day availability kept for the history panel
+const HISTORY_DAYS: usize = 30;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayUptime {
+    pub up_seconds: u64,
+    pub down_seconds: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Failure {
+    /// Unix seconds
+    pub at: u64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceUptime {
+    /// Seconds the service was running and answering on its port
+    pub up_seconds: u64,
+    /// Seconds it was enabled but stopped or unhealthy
+    pub down_seconds: u64,
+    /// Times the process exited without being asked to
+    pub crashes: u32,
+    pub last_failure: Option<Failure>,
+    /// Local date (YYYY-MM-DD) to availability that day
+    pub days: BTreeMap<String, DayUptime>,
+}
+
+/// Availability per service, persisted as `uptime.json` across app restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UptimeStats {
+    pub services: BTreeMap<String, ServiceUptime>,
+}
+
+impl UptimeStats {
+    fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(STATS_FILE)
+    }
+
+    pub fn load(data_dir: &Path) -> Self {
+        let path = Self::path(data_dir);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Invalid uptime stats at {:?}, starting over: {}", path, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize uptime stats: {}", e))?;
+        std::fs::write(Self::path(data_dir), content)
+            .map_err(|e| format!("Failed to write uptime stats: {}", e))
+    }
+
+    /// Account `elapsed` to each service as up or down
+    pub fn record(&mut self, services: &[ServiceStatus], elapsed: Duration) {
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        let seconds = elapsed.as_secs();
+
+        for status in services {
+            let service = self.services.entry(status.name.to_string()).or_default();
+            let day = service.days.entry(today.clone()).or_default();
+            if status.state == ServiceState::Running {
+                service.up_seconds += seconds;
+                day.up_seconds += seconds;
+            } else {
+                service.down_seconds += seconds;
+                day.down_seconds += seconds;
+            }
+
+            while service.days.len() > HISTORY_DAYS {
+                service.days.pop_first();
+            }
+        }
+    }
+
+    pub fn record_crash(&mut self, name: &str, reason: String) {
+        let service = self.services.entry(name.to_string()).or_default();
+        service.crashes += 1;
+        service.last_failure = Some(Failure {
+            at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            reason,
+        });
+    }
+}
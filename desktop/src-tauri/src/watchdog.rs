@@ -0,0 +1,322 @@
+use crate::alerts::{self, Alert, AlertKind};
+use crate::autoheal::AutoHeal;
+use crate::battery;
+use crate::footprint;
+use crate::journal;
+use crate::network;
+use crate::process_manager::{NodeStatus, ServiceState, ServiceStatus};
+use crate::safe_mode;
+use crate::tray_icon::{self, Health};
+use crate::AppState;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+use sysinfo::Disks;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::warn;
+
+const INTERVAL: Duration = Duration::from_secs(5);
+/// Used instead while the battery saver has services paused
+const BATTERY_INTERVAL: Duration = Duration::from_secs(30);
+/// How often uptime stats are written to disk (crashes are saved right away)
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// How often the OS theme is checked for the tray icon
+const THEME_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Poll the services every few seconds: keep the tray's Services submenu and
+/// icon in sync, track uptime, restart failing services, raise alerts on state
+/// changes and low disk space, and tell the webview about every transition
+pub fn spawn(app: AppHandle, services: Vec<ServiceStatus>) {
+    std::thread::spawn(move || {
+        let mut last = services;
+        let mut last_nodes: Vec<NodeStatus> = Vec::new();
+        let mut restarts: HashMap<&'static str, u32> = HashMap::new();
+        let mut disk_low = false;
+        // When phoenixd stopped answering, and whether node_offline went out
+        let mut node_down_since: Option<Instant> = None;
+        let mut node_offline_sent = false;
+        let mut last_tick = Instant::now();
+        let mut last_saved = Instant::now();
+        let mut auto_heal = AutoHeal::default();
+        let mut theme = tray_icon::os_theme();
+        let mut theme_checked = Instant::now();
+
+        loop {
+            std::thread::sleep(if battery::saving() {
+                BATTERY_INTERVAL
+            } else {
+                INTERVAL.mul_f64(footprint::slowdown())
+            });
+            footprint::woke("watchdog");
+            let state = app.state::<AppState>();
+            let (current, nodes, counts, exits, node_paused) = {
+                let mut pm = state.process_manager.lock().unwrap();
+                // A quick tunnel's URL or a new onion only shows up later
+                if let Err(e) = pm.sync_backend_origins() {
+                    warn!("{}", e);
+                }
+                let current = pm.service_statuses();
+                let nodes = pm.node_statuses();
+                let counts: HashMap<&'static str, u32> = current
+                    .iter()
+                    .map(|s| (s.name, pm.restart_count(s.name)))
+                    .collect();
+                let exits: Vec<(&'static str, ExitStatus)> = current
+                    .iter()
+                    .filter_map(|s| pm.exit_status(s.name).map(|status| (s.name, status)))
+                    .collect();
+                (current, nodes, counts, exits, pm.node_paused())
+            };
+            let (config, heal_config) = {
+                let config = state.config.lock().unwrap();
+                (config.alerts.clone(), config.auto_heal.clone())
+            };
+            // Exits the services' restart policies want auto-heal to undo
+            let crashed: Vec<&'static str> = exits
+                .iter()
+                .filter(|(name, status)| heal_config.restarts_after_exit(name, status.success()))
+                .map(|(name, _)| *name)
+                .collect();
+            let mut went_down = Vec::new();
+
+            {
+                let mut uptime = state.uptime.lock().unwrap();
+                uptime.record(&current, last_tick.elapsed());
+                last_tick = Instant::now();
+
+                // An exited child stays in its slot, so only count the tick it went down
+                let mut crashed = false;
+                for (name, status) in exits {
+                    let reason = format!("Exited ({})", status);
+                    let was_up = last
+                        .iter()
+                        .any(|s| s.name == name && s.state != ServiceState::Stopped);
+                    if was_up {
+                        journal::record(&state.data_dir, "service.crashed", Some(name), &reason);
+                        uptime.record_crash(name, reason);
+                        went_down.push(name);
+                        crashed = true;
+                    }
+                }
+
+                if crashed || last_saved.elapsed() >= SAVE_INTERVAL {
+                    if let Err(e) = uptime.save(&state.data_dir) {
+                        warn!("{}", e);
+                    }
+                    last_saved = Instant::now();
+                }
+            }
+
+            emit_changes(&app, &last, &current, &last_nodes, &nodes);
+            for alert in transitions(&last, &current, &restarts, &counts) {
+                alerts::dispatch(&config, alert);
+            }
+            // The main node is covered by phoenixd's service status
+            for node in nodes.iter().skip(1) {
+                let was_up = last_nodes
+                    .iter()
+                    .any(|n| n.name == node.name && n.state != ServiceState::Stopped);
+                if was_up && node.state == ServiceState::Stopped {
+                    alerts::dispatch(
+                        &config,
+                        Alert::new(
+                            AlertKind::ServiceDown,
+                            Some(&format!("phoenixd-{}", node.name)),
+                            format!("Node {} is no longer running", node.name),
+                        ),
+                    );
+                }
+            }
+
+            // A paused node is down on purpose
+            let node_up = node_paused
+                || current
+                    .iter()
+                    .any(|s| s.name == "phoenixd" && s.state == ServiceState::Running);
+            if node_up {
+                node_down_since = None;
+                node_offline_sent = false;
+            } else {
+                let since = *node_down_since.get_or_insert_with(Instant::now);
+                let limit = Duration::from_secs(config.node_offline_minutes * 60);
+                if !node_offline_sent && since.elapsed() >= limit {
+                    alerts::dispatch(
+                        &config,
+                        Alert::new(
+                            AlertKind::NodeOffline,
+                            Some("phoenixd"),
+                            format!(
+                                "phoenixd has been offline for more than {} minutes",
+                                config.node_offline_minutes
+                            ),
+                        ),
+                    );
+                    node_offline_sent = true;
+                }
+            }
+
+            if config.low_disk_mb > 0 {
+                let available_mb = available_space(&state.data_dir) / 1024 / 1024;
+                let low = available_mb < config.low_disk_mb;
+                if low && !disk_low {
+                    alerts::dispatch(
+                        &config,
+                        Alert::new(
+                            AlertKind::LowDisk,
+                            None,
+                            format!("Only {} MB free on the data directory's disk", available_mb),
+                        ),
+                    );
+                }
+                disk_low = low;
+            }
+
+            for name in went_down {
+                safe_mode::record_crash(&app, name);
+            }
+            // Safe mode stopped everything on purpose, leave it that way
+            if !safe_mode::is_active(&state.data_dir) {
+                auto_heal.tick(&app, &heal_config, &current, &crashed);
+            }
+
+            if theme_checked.elapsed() >= THEME_INTERVAL {
+                theme = tray_icon::os_theme();
+                theme_checked = Instant::now();
+            }
+            let extra_nodes_up = nodes
+                .iter()
+                .skip(1)
+                .all(|n| n.state == ServiceState::Running);
+            let health = Health::of(&current)
+                .with_nodes(extra_nodes_up)
+                .with_network(network::is_online());
+            tray_icon::update(&app, health, theme);
+
+            if current != last {
+                crate::refresh_tray_menu(&app, &current);
+                last = current;
+            }
+            restarts = counts;
+            last_nodes = nodes;
+        }
+    });
+}
+
+/// `service-state` for each service or node whose state changed, then the full
+/// lists as `services-changed` so listeners don't have to query. Transitions
+/// also go to the event journal.
+fn emit_changes(
+    app: &AppHandle,
+    last: &[ServiceStatus],
+    current: &[ServiceStatus],
+    last_nodes: &[NodeStatus],
+    nodes: &[NodeStatus],
+) {
+    let mut changed = false;
+    for status in current {
+        let previous = last.iter().find(|s| s.name == status.name).map(|s| s.state);
+        if let Some(from) = previous.filter(|state| *state != status.state) {
+            journal_transition(app, status.name, from, status.state);
+            let _ = app.emit(
+                "service-state",
+                json!({ "name": status.name, "from": previous, "to": status.state }),
+            );
+            changed = true;
+        }
+    }
+    for node in nodes.iter().skip(1) {
+        let previous = last_nodes
+            .iter()
+            .find(|n| n.name == node.name)
+            .map(|n| n.state);
+        if previous != Some(node.state) {
+            let name = format!("phoenixd-{}", node.name);
+            if let Some(from) = previous {
+                journal_transition(app, &name, from, node.state);
+            }
+            let _ = app.emit(
+                "service-state",
+                json!({ "name": name, "from": previous, "to": node.state }),
+            );
+            changed = true;
+        }
+    }
+    if changed || nodes.len() != last_nodes.len() {
+        let _ = app.emit(
+            "services-changed",
+            json!({ "services": current, "nodes": nodes }),
+        );
+    }
+}
+
+fn journal_transition(app: &AppHandle, name: &str, from: ServiceState, to: ServiceState) {
+    let kind = match (from, to) {
+        (ServiceState::Stopped, ServiceState::Running) => "service.started",
+        (_, ServiceState::Running) => "service.recovered",
+        (_, ServiceState::Unhealthy) => "service.unhealthy",
+        (_, ServiceState::Stopped) => "service.stopped",
+    };
+    let data_dir = &app.state::<AppState>().data_dir;
+    journal::record(data_dir, kind, Some(name), format!("{:?} → {:?}", from, to));
+}
+
+fn transitions(
+    last: &[ServiceStatus],
+    current: &[ServiceStatus],
+    last_restarts: &HashMap<&'static str, u32>,
+    restarts: &HashMap<&'static str, u32>,
+) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+    for status in current {
+        let previous = last.iter().find(|s| s.name == status.name).map(|s| s.state);
+
+        let restarted = restarts.get(status.name).copied().unwrap_or_default()
+            > last_restarts.get(status.name).copied().unwrap_or_default();
+        if restarted {
+            alerts.push(Alert::new(
+                AlertKind::ServiceRestarted,
+                Some(status.name),
+                format!("{} was restarted", status.name),
+            ));
+        } else if status.state == ServiceState::Stopped
+            && previous.is_some_and(|state| state != ServiceState::Stopped)
+        {
+            alerts.push(Alert::new(
+                AlertKind::ServiceDown,
+                Some(status.name),
+                format!("{} is no longer running", status.name),
+            ));
+        }
+
+        if status.state == ServiceState::Unhealthy && previous == Some(ServiceState::Running) {
+            alerts.push(if status.name == "phoenixd" {
+                Alert::new(
+                    AlertKind::NodeUnreachable,
+                    Some(status.name),
+                    "phoenixd is running but its API port stopped answering",
+                )
+            } else {
+                Alert::new(
+                    AlertKind::ServiceUnhealthy,
+                    Some(status.name),
+                    format!("{} is running but not accepting connections", status.name),
+                )
+            });
+        }
+    }
+    alerts
+}
+
+/// Free space on the disk holding `path` (the longest matching mount point)
+fn available_space(path: &Path) -> u64 {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+        .unwrap_or(u64::MAX)
+}
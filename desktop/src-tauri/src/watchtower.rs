@@ -0,0 +1,212 @@
+use crate::alerts::{self, Alert, AlertKind};
+use crate::i18n::t;
+use crate::phoenixd_api::PhoenixdClient;
+use crate::{notify, scheduler, AppState};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tracing::debug;
+
+/// How far back pending payments and open invoices are looked for
+const LOOKBACK_MS: i64 = 2 * 24 * 60 * 60 * 1000;
+const PAGE_SIZE: usize = 100;
+/// bech32's alphabet; a character's index is its 5-bit value
+const BECH32: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+/// BOLT11 invoices expire after an hour unless they say otherwise
+const DEFAULT_EXPIRY_SECS: i64 = 3600;
+
+/// A payment that needs a look, listed in the tray
+#[derive(Debug, Clone, PartialEq)]
+pub struct Flagged {
+    /// `paymentHash` for invoices, `paymentId` for outgoing payments, as the
+    /// dashboard's payments page takes them
+    pub id: String,
+    pub label: String,
+}
+
+static FLAGGED: Mutex<Vec<Flagged>> = Mutex::new(Vec::new());
+
+pub fn flagged() -> Vec<Flagged> {
+    FLAGGED.lock().unwrap().clone()
+}
+
+/// Amount in sat (if the invoice names one) and expiry in Unix seconds,
+/// read from a BOLT11 invoice
+fn invoice_terms(invoice: &str) -> Option<(Option<u64>, i64)> {
+    let invoice = invoice.trim().to_ascii_lowercase();
+    let separator = invoice.rfind('1')?;
+    let (hrp, data) = (&invoice[..separator], &invoice[separator + 1..]);
+    let words: Vec<i64> = data
+        .chars()
+        .map(|c| BECH32.find(c).map(|i| i as i64))
+        .collect::<Option<_>>()?;
+    // Timestamp first; a 104-word signature and 6-word checksum at the end
+    let tagged_end = words.len().checked_sub(104 + 6)?;
+    if tagged_end < 7 {
+        return None;
+    }
+    let number = |words: &[i64]| words.iter().fold(0i64, |acc, word| acc << 5 | word);
+    let timestamp = number(&words[..7]);
+
+    let mut expiry = DEFAULT_EXPIRY_SECS;
+    let mut rest = &words[7..tagged_end];
+    while rest.len() >= 3 {
+        let len = (rest[1] * 32 + rest[2]) as usize;
+        let value = rest.get(3..3 + len)?;
+        // Tag `x`
+        if rest[0] == 6 {
+            expiry = number(value);
+        }
+        rest = &rest[3 + len..];
+    }
+
+    let amount = hrp
+        .strip_prefix("ln")?
+        .trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    let amount_sat = if amount.is_empty() {
+        None
+    } else {
+        let (digits, multiplier) = match amount.char_indices().last()? {
+            (i, c) if c.is_ascii_alphabetic() => (&amount[..i], Some(c)),
+            _ => (amount, None),
+        };
+        let value: u64 = digits.parse().ok()?;
+        // Millisatoshi per unit of the multiplier
+        let msat = match multiplier {
+            None => value.checked_mul(100_000_000_000)?,
+            Some('m') => value.checked_mul(100_000_000)?,
+            Some('u') => value.checked_mul(100_000)?,
+            Some('n') => value.checked_mul(100)?,
+            Some('p') => value / 10,
+            Some(_) => return None,
+        };
+        Some(msat / 1000)
+    };
+    Some((amount_sat, timestamp + expiry))
+}
+
+async fn pending(client: &PhoenixdClient, direction: &str) -> Result<Vec<Value>, String> {
+    let from = chrono::Utc::now().timestamp_millis() - LOOKBACK_MS;
+    Ok(client
+        .payments_page(direction, Some(from), None, PAGE_SIZE, 0)
+        .await?
+        .into_iter()
+        .filter(|payment| !payment["isPaid"].as_bool().unwrap_or_default())
+        .filter(|payment| payment["completedAt"].is_null())
+        .collect())
+}
+
+/// Watch the active node for outgoing payments pending longer than
+/// `stuck_payment_minutes` and large invoices about to expire unpaid.
+/// Each is notified once and listed in the tray until it resolves.
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut notified: HashSet<String> = HashSet::new();
+        loop {
+            scheduler::sleep(&app, "watchtower");
+            let state = app.state::<AppState>();
+            let config = state.config.lock().unwrap().alerts.clone();
+            if config.stuck_payment_minutes == 0 && config.expiring_invoice_sat == 0 {
+                continue;
+            }
+            let client = PhoenixdClient::active(&state.data_dir);
+            let (outgoing, incoming) = match tauri::async_runtime::block_on(async {
+                Ok::<_, String>((
+                    pending(&client, "outgoing").await?,
+                    pending(&client, "incoming").await?,
+                ))
+            }) {
+                Ok(found) => found,
+                Err(e) => {
+                    debug!("Watchtower could not list payments: {}", e);
+                    continue;
+                }
+            };
+
+            let now_ms = chrono::Utc::now().timestamp_millis();
+            let mut flagged = Vec::new();
+            let mut raised = Vec::new();
+            if config.stuck_payment_minutes > 0 {
+                let limit_ms = config.stuck_payment_minutes as i64 * 60 * 1000;
+                for payment in &outgoing {
+                    let Some(id) = payment["paymentId"].as_str() else {
+                        continue;
+                    };
+                    let created = payment["createdAt"].as_i64().unwrap_or(now_ms);
+                    if now_ms - created < limit_ms {
+                        continue;
+                    }
+                    let minutes = ((now_ms - created) / 60_000).to_string();
+                    flagged.push(Flagged {
+                        id: id.to_string(),
+                        label: t("watchtower.stuck").replace("{minutes}", &minutes),
+                    });
+                    let body = t("notify.payment_stuck_body").replace("{minutes}", &minutes);
+                    raised.push((
+                        id.to_string(),
+                        AlertKind::PaymentStuck,
+                        t("notify.payment_stuck_title"),
+                        body,
+                    ));
+                }
+            }
+            if config.expiring_invoice_sat > 0 {
+                let window_secs = config.expiring_invoice_minutes as i64 * 60;
+                for invoice in &incoming {
+                    let Some(id) = invoice["paymentHash"].as_str() else {
+                        continue;
+                    };
+                    let Some((Some(amount), expires_at)) =
+                        invoice["invoice"].as_str().and_then(invoice_terms)
+                    else {
+                        continue;
+                    };
+                    let remaining = expires_at - now_ms / 1000;
+                    if amount < config.expiring_invoice_sat
+                        || remaining <= 0
+                        || remaining > window_secs
+                    {
+                        continue;
+                    }
+                    let minutes = (remaining / 60).max(1).to_string();
+                    let amount = amount.to_string();
+                    flagged.push(Flagged {
+                        id: id.to_string(),
+                        label: t("watchtower.expiring")
+                            .replace("{amount}", &amount)
+                            .replace("{minutes}", &minutes),
+                    });
+                    let body = t("notify.invoice_expiring_body")
+                        .replace("{amount}", &amount)
+                        .replace("{minutes}", &minutes);
+                    raised.push((
+                        id.to_string(),
+                        AlertKind::InvoiceExpiring,
+                        t("notify.invoice_expiring_title"),
+                        body,
+                    ));
+                }
+            }
+
+            for (id, kind, title, body) in raised {
+                if notified.insert(id.clone()) {
+                    notify::native(app.clone())(title, &body);
+                    alerts::dispatch(
+                        &config,
+                        Alert::new(kind, Some("phoenixd"), format!("{} ({})", body, id)),
+                    );
+                }
+            }
+            // Forget what resolved, so a new problem with it is told again
+            notified.retain(|id| flagged.iter().any(|flag| flag.id == *id));
+
+            let changed = *FLAGGED.lock().unwrap() != flagged;
+            if changed {
+                *FLAGGED.lock().unwrap() = flagged;
+                let services = state.statuses.lock().unwrap().clone();
+                crate::refresh_tray_menu(&app, &services);
+            }
+        }
+    });
+}
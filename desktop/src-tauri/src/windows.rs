@@ -0,0 +1,359 @@
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::http::{header::CONTENT_TYPE, Request, Response, StatusCode};
+use tauri::{
+    AppHandle, Manager, UriSchemeContext, Url, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+    WindowEvent,
+};
+use tracing::warn;
+
+/// Custom protocol the shell's own windows are served from, so they keep
+/// working when the Next.js frontend is down
+pub const PROTOCOL: &str = "shell";
+/// Where each window was last placed, in the data directory
+const GEOMETRY_FILE: &str = "windows.json";
+
+/// Builds a live window's body on every load, from the page's query string
+pub type Render = Arc<dyn Fn(&AppHandle, &str) -> String + Send + Sync>;
+type OnSubmit = Box<dyn Fn(&Url) + Send>;
+
+#[derive(Clone)]
+enum Page {
+    Static(String),
+    Live {
+        title: String,
+        refresh_secs: u32,
+        render: Render,
+    },
+}
+
+/// HTML for each native window, keyed by window label
+#[derive(Default)]
+pub struct WindowPages(Mutex<HashMap<String, Page>>);
+
+/// Logical position and inner size
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Geometry {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+static GEOMETRY: Mutex<Option<HashMap<String, Geometry>>> = Mutex::new(None);
+
+fn saved_geometry(app: &AppHandle, label: &str) -> Option<Geometry> {
+    let mut geometry = GEOMETRY.lock().unwrap();
+    geometry
+        .get_or_insert_with(|| {
+            app.try_state::<AppState>()
+                .and_then(|state| std::fs::read_to_string(state.data_dir.join(GEOMETRY_FILE)).ok())
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default()
+        })
+        .get(label)
+        .copied()
+}
+
+fn remember_geometry(window: &WebviewWindow) {
+    let scale = window.scale_factor().unwrap_or(1.0);
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+        return;
+    };
+    let (position, size) = (
+        position.to_logical::<f64>(scale),
+        size.to_logical::<f64>(scale),
+    );
+    GEOMETRY
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(
+            window.label().to_string(),
+            Geometry {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+            },
+        );
+}
+
+fn save_geometry(app: &AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let Some(content) = GEOMETRY
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|geometry| serde_json::to_string_pretty(geometry).ok())
+    else {
+        return;
+    };
+    if let Err(e) = std::fs::write(state.data_dir.join(GEOMETRY_FILE), content) {
+        warn!("Failed to save window positions: {}", e);
+    }
+}
+
+pub fn handle_protocol(
+    ctx: UriSchemeContext<'_, tauri::Wry>,
+    request: Request<Vec<u8>>,
+) -> Response<Cow<'static, [u8]>> {
+    let app = ctx.app_handle();
+    let label = request.uri().path().trim_start_matches('/');
+    let page = app
+        .try_state::<WindowPages>()
+        .and_then(|pages| pages.0.lock().unwrap().get(label).cloned())
+        .map(|page| match page {
+            Page::Static(html) => html,
+            Page::Live {
+                title,
+                refresh_secs,
+                render,
+            } => {
+                let body = render(app, request.uri().query().unwrap_or_default());
+                // Not while a control has focus, so keyboard and screen
+                // reader users don't lose their place
+                let reload = format!(
+                    "<script>setTimeout(function reload() {{ if (document.activeElement && document.activeElement !== document.body) {{ setTimeout(reload, {0}) }} else {{ location.reload() }} }}, {0})</script>",
+                    refresh_secs * 1000
+                );
+                render_page(&title, &format!("{}{}", body, reload))
+            }
+        });
+
+    match page {
+        Some(html) => Response::builder()
+            .header(CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(Cow::Owned(html.into_bytes()))
+            .unwrap(),
+        None => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Cow::Borrowed(&b"Not found"[..]))
+            .unwrap(),
+    }
+}
+
+/// Open (or refresh) a small native window showing `body`
+pub fn show(
+    app: &AppHandle,
+    label: &str,
+    title: &str,
+    body: &str,
+    size: (f64, f64),
+) -> Result<(), String> {
+    open(
+        app,
+        label,
+        title,
+        Page::Static(render_page(title, body)),
+        size,
+        None,
+    )
+}
+
+/// Open a window whose body `render` rebuilds every `refresh_secs`
+pub fn show_live(
+    app: &AppHandle,
+    label: &str,
+    title: &str,
+    size: (f64, f64),
+    refresh_secs: u32,
+    render: impl Fn(&AppHandle, &str) -> String + Send + Sync + 'static,
+) -> Result<(), String> {
+    let page = Page::Live {
+        title: title.to_string(),
+        refresh_secs,
+        render: Arc::new(render),
+    };
+    open(app, label, title, page, size, None)
+}
+
+/// Like `show_live`, with forms submitting to `submit` handed to `on_submit`
+pub fn show_live_form<F>(
+    app: &AppHandle,
+    label: &str,
+    title: &str,
+    size: (f64, f64),
+    refresh_secs: u32,
+    render: impl Fn(&AppHandle, &str) -> String + Send + Sync + 'static,
+    on_submit: F,
+) -> Result<(), String>
+where
+    F: Fn(&Url) + Send + 'static,
+{
+    let page = Page::Live {
+        title: title.to_string(),
+        refresh_secs,
+        render: Arc::new(render),
+    };
+    open(app, label, title, page, size, Some(Box::new(on_submit)))
+}
+
+/// Independent, resizable window that reopens where it was last left
+fn open(
+    app: &AppHandle,
+    label: &str,
+    title: &str,
+    page: Page,
+    size: (f64, f64),
+    on_submit: Option<OnSubmit>,
+) -> Result<(), String> {
+    app.state::<WindowPages>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(label.to_string(), page);
+
+    if let Some(window) = app.get_webview_window(label) {
+        let _ = window.eval("location.reload()");
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+
+    let mut builder =
+        WebviewWindowBuilder::new(app, label, WebviewUrl::CustomProtocol(page_url(label)?))
+            .title(title)
+            .inner_size(size.0, size.1)
+            .resizable(true);
+    if let Some(geometry) = saved_geometry(app, label) {
+        builder = builder
+            .inner_size(geometry.width, geometry.height)
+            .position(geometry.x, geometry.y);
+    }
+    if let Some(on_submit) = on_submit {
+        builder = builder.on_navigation(move |url| {
+            if url.path().ends_with("/submit") {
+                on_submit(url);
+                false
+            } else {
+                true
+            }
+        });
+    }
+    let window = builder
+        .build()
+        .map_err(|e| format!("Failed to open {} window: {}", label, e))?;
+
+    let handle = window.clone();
+    window.on_window_event(move |event| match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => remember_geometry(&handle),
+        WindowEvent::CloseRequested { .. } => {
+            remember_geometry(&handle);
+            save_geometry(handle.app_handle());
+        }
+        _ => {}
+    });
+    Ok(())
+}
+
+/// Open a fresh window whose form submits to `submit` (a GET relative to
+/// the page) are handed to `on_submit` instead of being loaded
+pub fn show_form<F>(
+    app: &AppHandle,
+    label: &str,
+    title: &str,
+    body: &str,
+    size: (f64, f64),
+    on_submit: F,
+) -> Result<WebviewWindow, String>
+where
+    F: Fn(&Url) + Send + 'static,
+{
+    if let Some(window) = app.get_webview_window(label) {
+        let _ = window.destroy();
+    }
+    app.state::<WindowPages>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(label.to_string(), Page::Static(render_page(title, body)));
+
+    WebviewWindowBuilder::new(app, label, WebviewUrl::CustomProtocol(page_url(label)?))
+        .title(title)
+        .inner_size(size.0, size.1)
+        .resizable(false)
+        .focused(true)
+        .always_on_top(true)
+        .on_navigation(move |url| {
+            if url.path().ends_with("/submit") {
+                on_submit(url);
+                false
+            } else {
+                true
+            }
+        })
+        .build()
+        .map_err(|e| format!("Failed to open {} window: {}", label, e))
+}
+
+fn page_url(label: &str) -> Result<Url, String> {
+    // Windows and Android webviews only allow custom protocols as http://<scheme>.localhost
+    let url = if cfg!(any(windows, target_os = "android")) {
+        format!("http://{}.localhost/{}", PROTOCOL, label)
+    } else {
+        format!("{}://localhost/{}", PROTOCOL, label)
+    };
+    url.parse()
+        .map_err(|e| format!("Invalid window URL {}: {}", url, e))
+}
+
+fn render_page(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+  body {{ font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; background: #0f1115; color: #e6e6e6; margin: 0; padding: 20px; text-align: center; }}
+  h1 {{ font-size: 18px; margin: 0 0 16px; }}
+  h2 {{ font-size: 14px; margin: 0 0 10px; }}
+  section {{ margin-bottom: 28px; }}
+  .qr svg {{ background: #fff; border-radius: 8px; max-width: 100%; height: auto; }}
+  code {{ display: block; word-break: break-all; background: #1b1e25; padding: 8px; border-radius: 6px; font-size: 12px; user-select: all; }}
+  p {{ font-size: 13px; color: #a0a4ad; }}
+  input {{ width: 100%; box-sizing: border-box; background: #1b1e25; color: #e6e6e6; border: 1px solid #2c313c; border-radius: 6px; padding: 8px; font-size: 13px; }}
+  table {{ width: 100%; border-collapse: collapse; font-size: 13px; text-align: left; }}
+  td {{ padding: 4px 0; border-bottom: 1px solid #1b1e25; }}
+  td:last-child {{ text-align: right; }}
+  pre {{ text-align: left; font-size: 11px; white-space: pre-wrap; word-break: break-all; background: #1b1e25; padding: 8px; border-radius: 6px; }}
+  fieldset {{ border: 1px solid #2c313c; border-radius: 6px; margin: 10px 0; text-align: left; }}
+  legend, caption {{ font-size: 13px; color: #a0a4ad; }}
+  button:disabled {{ background: #2c313c; color: #a0a4ad; cursor: default; }}
+  button {{ margin-top: 10px; background: #2b6cff; color: #fff; border: 0; border-radius: 6px; padding: 6px 14px; font-size: 13px; cursor: pointer; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{body}
+</body>
+</html>"#,
+        title = escape(title),
+        body = body
+    )
+}
+
+/// `<code>` block with a button that copies its text. Uses execCommand since
+/// the custom protocol isn't a secure context for the async clipboard API.
+pub fn copyable(text: &str) -> String {
+    format!(
+        r#"<code>{}</code><button onclick="const r=document.createRange();r.selectNodeContents(this.previousElementSibling);const s=getSelection();s.removeAllRanges();s.addRange(r);document.execCommand('copy');this.textContent='{}'">{}</button>"#,
+        escape(text),
+        escape(crate::i18n::t("window.copied")).replace('\'', "&#39;"),
+        escape(crate::i18n::t("window.copy"))
+    )
+}
+
+/// Escape text for inclusion in window HTML
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -21,7 +21,8 @@ const withSerwist = withSerwistInit({
 });
 
 const nextConfig: NextConfig = {
-  output: 'standalone',
+  // NEXT_OUTPUT=export produces the static build the desktop shell can serve
+  output: process.env.NEXT_OUTPUT === 'export' ? 'export' : 'standalone',
   reactStrictMode: true,
   outputFileTracingRoot: path.join(__dirname, './'),
 };
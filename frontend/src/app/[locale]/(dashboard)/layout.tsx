@@ -8,6 +8,7 @@ import { AuthProvider } from '@/components/auth-provider';
 import { CurrencyProvider, useCurrencyContext } from '@/components/currency-provider';
 import { AnimationProvider, useAnimationContext } from '@/components/animation-provider';
 import { PWAInstallPrompt } from '@/components/pwa-install-prompt';
+import { NodePausedBanner } from '@/components/node-paused-banner';
 import { useWebSocket } from '@/hooks/use-websocket';
 import { useToast } from '@/hooks/use-toast';
 import {
@@ -228,7 +229,10 @@ function DashboardContent({ children }: { children: React.ReactNode }) {
 
         {/* Page Content - Extra padding bottom for mobile nav */}
         <main className="flex-1 px-4 md:px-8 pb-24 md:pb-8">
-          <div className="relative z-10 max-w-[1440px] mx-auto w-full">{children}</div>
+          <div className="relative z-10 max-w-[1440px] mx-auto w-full">
+            <NodePausedBanner />
+            {children}
+          </div>
         </main>
       </div>
 
@@ -1,6 +1,7 @@
 'use client';
 
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
+import { useSearchParams } from 'next/navigation';
 import {
   Link2,
   ArrowDownToLine,
@@ -38,6 +39,16 @@ export default function LnurlPage() {
   // LNURL-Auth
   const [authUrl, setAuthUrl] = useState('');
 
+  // Pre-fill from an opened lnurl: link
+  const searchParams = useSearchParams();
+  useEffect(() => {
+    const lnurlParam = searchParams.get('lnurl');
+    if (lnurlParam) {
+      setPayUrl(lnurlParam);
+      setActiveTab('pay');
+    }
+  }, [searchParams]);
+
   const handleLnurlPay = async (e: React.FormEvent) => {
     e.preventDefault();
     if (!payUrl || !payAmount) {
@@ -1,6 +1,7 @@
 'use client';
 
 import { useEffect, useState, useCallback, useRef } from 'react';
+import { useSearchParams } from 'next/navigation';
 import {
   ArrowDownToLine,
   ArrowUpFromLine,
@@ -65,6 +66,7 @@ export default function PaymentsPage() {
   const tl = useTranslations('paymentLabels');
   const tcat = useTranslations('categories');
   const { formatValue } = useCurrencyContext();
+  const searchParams = useSearchParams();
   const [activeTab, setActiveTab] = useState<'incoming' | 'outgoing'>('incoming');
   const [incomingPayments, setIncomingPayments] = useState<IncomingPayment[]>([]);
   const [outgoingPayments, setOutgoingPayments] = useState<OutgoingPayment[]>([]);
@@ -144,6 +146,23 @@ export default function PaymentsPage() {
     fetchData();
   }, [fetchData]);
 
+  // Open the payment linked as ?payment=<paymentHash or paymentId>, e.g. from the desktop tray
+  const openedLinkRef = useRef<string | null>(null);
+  useEffect(() => {
+    const linked = searchParams.get('payment');
+    if (!linked || loading || openedLinkRef.current === linked) return;
+    const incoming = incomingPayments.find((p) => p.paymentHash === linked);
+    const outgoing = outgoingPayments.find((p) => p.paymentId === linked);
+    if (incoming || outgoing) openedLinkRef.current = linked;
+    if (incoming) {
+      setActiveTab('incoming');
+      setSelectedPayment(incoming);
+    } else if (outgoing) {
+      setActiveTab('outgoing');
+      setSelectedPayment(outgoing);
+    }
+  }, [searchParams, loading, incomingPayments, outgoingPayments]);
+
   // Listen for phoenixd connection changes
   useEffect(() => {
     const handleConnectionChange = () => {
@@ -0,0 +1,53 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { useTranslations } from 'next-intl';
+import { PauseCircle } from 'lucide-react';
+
+const API_URL = process.env.NEXT_PUBLIC_API_URL || 'http://localhost:4000';
+
+/**
+ * Shown while the desktop app keeps phoenixd paused. The backend restarts
+ * on pause and resume, so its health check says which one it is.
+ */
+export function NodePausedBanner() {
+  const t = useTranslations('node');
+  const [paused, setPaused] = useState(false);
+
+  useEffect(() => {
+    const fetchHealth = async () => {
+      try {
+        const response = await fetch(`${API_URL}/health`, {
+          credentials: 'include',
+        });
+        if (response.ok) {
+          const data = await response.json();
+          setPaused(data.nodePaused === true);
+        }
+      } catch {
+        // Backend restarting; keep what we last knew
+      }
+    };
+
+    fetchHealth();
+    const interval = setInterval(fetchHealth, 10000);
+    return () => clearInterval(interval);
+  }, []);
+
+  if (!paused) {
+    return null;
+  }
+
+  return (
+    <div
+      role="status"
+      className="mb-4 flex items-start gap-3 p-3 rounded-lg bg-warning/10 border border-warning/20"
+    >
+      <PauseCircle className="h-5 w-5 text-warning shrink-0 mt-0.5" />
+      <div>
+        <p className="text-sm font-medium text-warning">{t('pausedTitle')}</p>
+        <p className="text-sm text-muted-foreground">{t('pausedDescription')}</p>
+      </div>
+    </div>
+  );
+}